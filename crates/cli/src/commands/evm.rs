@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use zkm_sdk::{
+    evm::{program_vkey, write_solidity_verifiers, EvmFixture},
+    ZKMProofKind, ZKMProofWithPublicValues,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "evm",
+    about = "Write the Solidity verifiers of a Groth16 or Plonk proof, along with a fixture."
+)]
+pub struct EvmCmd {
+    /// The path to the proof
+    #[arg(long)]
+    proof: PathBuf,
+
+    /// The directory to write the contracts and the fixture to
+    #[arg(long, short)]
+    output: PathBuf,
+}
+
+impl EvmCmd {
+    pub fn run(&self) -> Result<()> {
+        let proof = ZKMProofWithPublicValues::load(&self.proof)?;
+        let fixture = EvmFixture::new(&proof)?;
+        let program_vkey = program_vkey(&proof.proof).context("the proof has no program vkey")?;
+
+        write_solidity_verifiers(ZKMProofKind::from(&proof.proof), program_vkey, &self.output)
+            .context("failed to write the verifier contracts")?;
+        fixture.save(&self.output)?;
+
+        println!("Verification Key Hash:\n{}", fixture.vkey);
+        println!("Public values:\n{}", fixture.public_values);
+        println!("Contracts and fixture written to {}", self.output.display());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use clap::Parser;
+use zkm_sdk::ProverClient;
+
+use super::input::{Elf, Input};
+
+#[derive(Parser)]
+#[command(name = "execute", about = "Execute a guest without generating a proof.")]
+pub struct ExecuteCmd {
+    #[command(flatten)]
+    elf: Elf,
+
+    #[command(flatten)]
+    input: Input,
+
+    /// The maximum number of cycles to execute
+    #[arg(long)]
+    max_cycles: Option<u64>,
+
+    /// Write the execution report to this path, as CSV if it ends in `.csv` and as JSON otherwise
+    #[arg(long)]
+    report: Option<PathBuf>,
+}
+
+impl ExecuteCmd {
+    pub fn run(&self) -> Result<()> {
+        let elf = self.elf.read()?;
+        let stdin = self.input.read()?;
+
+        let client = ProverClient::new();
+        let mut execute = client.execute(&elf, stdin);
+        if let Some(max_cycles) = self.max_cycles {
+            execute = execute.max_cycles(max_cycles);
+        }
+        let (public_values, report) = execute.run()?;
+
+        println!("Public values:\n{}", public_values.raw());
+        println!("{report}");
+
+        if let Some(path) = &self.report {
+            let contents = if path.extension().is_some_and(|extension| extension == "csv") {
+                report.to_csv()
+            } else {
+                report.to_json()?
+            };
+            fs::write(path, contents)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use clap::Parser;
+    use zkm_sdk::ExecutionReport;
+
+    use super::ExecuteCmd;
+
+    #[test]
+    fn test_execute_elf() {
+        let dir = tempfile::tempdir().unwrap();
+        let elf = dir.path().join("fibonacci.elf");
+        let report = dir.path().join("report.json");
+        fs::write(&elf, test_artifacts::FIBONACCI_ELF).unwrap();
+
+        let cmd = ExecuteCmd::try_parse_from([
+            "execute",
+            "--elf",
+            elf.to_str().unwrap(),
+            "--report",
+            report.to_str().unwrap(),
+        ])
+        .unwrap();
+        cmd.run().unwrap();
+
+        let report = ExecutionReport::from_json(&fs::read_to_string(report).unwrap()).unwrap();
+        assert!(report.total_instruction_count() > 0);
+    }
+}
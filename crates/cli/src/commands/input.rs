@@ -0,0 +1,88 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{ensure, Context, Result};
+use clap::Args;
+use zkm_build::{generate_elf_paths, BuildArgs};
+use zkm_sdk::ZKMStdin;
+
+#[derive(Debug, Clone, Args)]
+#[group(required = true, multiple = false)]
+pub struct Elf {
+    /// The path to the ELF file
+    #[arg(long = "elf")]
+    path: Option<String>,
+    /// The crate used to generate the ELF file
+    #[arg(long)]
+    program: Option<String>,
+}
+
+impl Elf {
+    /// Get the paths of the ELF files, together with the targets they were built for.
+    pub fn paths(&self) -> Result<Vec<(Option<String>, String)>> {
+        if let Some(path) = &self.path {
+            Ok(vec![(None, path.clone())])
+        } else if let Some(program) = &self.program {
+            let metadata_cmd = cargo_metadata::MetadataCommand::new();
+            let metadata = metadata_cmd.exec()?;
+            let build_args = BuildArgs { packages: vec![program.clone()], ..Default::default() };
+
+            Ok(generate_elf_paths(&metadata, Some(&build_args))?
+                .into_iter()
+                .map(|(target, path)| (Some(target), path.to_string()))
+                .collect())
+        } else {
+            unreachable!()
+        }
+    }
+
+    /// Read the ELF file, which must be unique.
+    pub fn read(&self) -> Result<Vec<u8>> {
+        let paths = self.paths()?;
+        ensure!(paths.len() == 1, "expected a single ELF file, found {}", paths.len());
+        let path = &paths[0].1;
+        fs::read(path).with_context(|| format!("failed to read the ELF file {path}"))
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+#[group(multiple = false)]
+pub struct Input {
+    /// The path to a bincode serialized `ZKMStdin`
+    #[arg(long)]
+    stdin: Option<PathBuf>,
+    /// A hex encoded buffer to write to the stdin, may be repeated
+    #[arg(long = "input")]
+    hex: Vec<String>,
+    /// The path to a JSON file containing an array of hex encoded buffers to write to the stdin
+    #[arg(long = "input-json")]
+    json: Option<PathBuf>,
+}
+
+impl Input {
+    /// Read the stdin of the program.
+    pub fn read(&self) -> Result<ZKMStdin> {
+        if let Some(path) = &self.stdin {
+            let bytes = fs::read(path)
+                .with_context(|| format!("failed to read the stdin {}", path.display()))?;
+            return bincode::deserialize(&bytes).context("failed to deserialize the stdin");
+        }
+
+        let buffers = match &self.json {
+            Some(path) => {
+                let json = fs::read_to_string(path)
+                    .with_context(|| format!("failed to read the input {}", path.display()))?;
+                serde_json::from_str::<Vec<String>>(&json)
+                    .context("the input must be a JSON array of hex encoded buffers")?
+            }
+            None => self.hex.clone(),
+        };
+
+        let mut stdin = ZKMStdin::new();
+        for buffer in buffers {
+            let bytes = hex::decode(buffer.trim_start_matches("0x"))
+                .with_context(|| format!("invalid hex encoded input {buffer}"))?;
+            stdin.write_vec(bytes);
+        }
+        Ok(stdin)
+    }
+}
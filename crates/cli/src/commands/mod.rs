@@ -1,3 +1,8 @@
 pub mod build;
+pub mod evm;
+pub mod execute;
+pub mod input;
 pub mod new;
+pub mod prove;
+pub mod verify;
 pub mod vkey;
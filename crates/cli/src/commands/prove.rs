@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use zkm_sdk::{HashableKey, ProverClient};
+
+use super::input::{Elf, Input};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ProofMode {
+    Core,
+    Compressed,
+    Groth16,
+    Plonk,
+}
+
+#[derive(Parser)]
+#[command(name = "prove", about = "Generate a proof of the execution of a guest.")]
+pub struct ProveCmd {
+    #[command(flatten)]
+    elf: Elf,
+
+    #[command(flatten)]
+    input: Input,
+
+    /// The kind of proof to generate
+    #[arg(long, value_enum, default_value_t = ProofMode::Core)]
+    mode: ProofMode,
+
+    /// The path to write the proof to
+    #[arg(long, short)]
+    output: PathBuf,
+}
+
+impl ProveCmd {
+    pub fn run(&self) -> Result<()> {
+        let elf = self.elf.read()?;
+        let stdin = self.input.read()?;
+
+        let client = ProverClient::new();
+        let (pk, vk) = client.setup(&elf);
+        let prove = client.prove(&pk, stdin);
+        let prove = match self.mode {
+            ProofMode::Core => prove.core(),
+            ProofMode::Compressed => prove.compressed(),
+            ProofMode::Groth16 => prove.groth16(),
+            ProofMode::Plonk => prove.plonk(),
+        };
+        let proof = prove.run()?;
+        proof.save(&self.output)?;
+
+        println!("Verification Key Hash:\n{}", vk.vk.bytes32());
+        println!("Public values:\n{}", proof.public_values.raw());
+        println!("Proof written to {}", self.output.display());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use zkm_sdk::{HashableKey, ProverClient, ZKMProofWithPublicValues};
+
+use super::input::Elf;
+
+#[derive(Parser)]
+#[command(name = "verify", about = "Verify a proof of the execution of a guest.")]
+pub struct VerifyCmd {
+    #[command(flatten)]
+    elf: Elf,
+
+    /// The path to the proof
+    #[arg(long)]
+    proof: PathBuf,
+}
+
+impl VerifyCmd {
+    pub fn run(&self) -> Result<()> {
+        let elf = self.elf.read()?;
+        let proof = ZKMProofWithPublicValues::load(&self.proof)?;
+
+        let client = ProverClient::new();
+        let (_, vk) = client.setup(&elf);
+        client.verify(&proof, &vk)?;
+
+        println!("Verification Key Hash:\n{}", vk.vk.bytes32());
+        println!("Public values:\n{}", proof.public_values.raw());
+        println!("Proof verified");
+
+        Ok(())
+    }
+}
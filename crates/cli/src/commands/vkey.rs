@@ -1,10 +1,11 @@
 use std::{fs::File, io::Read};
 
 use anyhow::Result;
-use clap::{Args, Parser};
-use zkm_build::{generate_elf_paths, BuildArgs};
+use clap::Parser;
 use zkm_sdk::{HashableKey, ProverClient};
 
+use super::input::Elf;
+
 #[derive(Parser)]
 #[command(name = "vkey", about = "View the verification key hash for a guest.")]
 pub struct VkeyCmd {
@@ -13,33 +14,9 @@ pub struct VkeyCmd {
     elf: Elf,
 }
 
-#[derive(Debug, Clone, Args)]
-#[group(required = true, multiple = false)]
-pub struct Elf {
-    /// The path to the ELF file
-    #[arg(long = "elf")]
-    path: Option<String>,
-    /// The crate used to generate the ELF file
-    #[arg(long)]
-    program: Option<String>,
-}
-
 impl VkeyCmd {
     pub fn run(&self) -> Result<()> {
-        let elf_paths = if let Some(path) = &self.elf.path {
-            vec![(None, path.clone())]
-        } else if let Some(program) = &self.elf.program {
-            let metadata_cmd = cargo_metadata::MetadataCommand::new();
-            let metadata = metadata_cmd.exec()?;
-            let build_args = BuildArgs { packages: vec![program.clone()], ..Default::default() };
-
-            generate_elf_paths(&metadata, Some(&build_args))?
-                .into_iter()
-                .map(|(target, path)| (Some(target), path.to_string()))
-                .collect()
-        } else {
-            unreachable!()
-        };
+        let elf_paths = self.elf.paths()?;
 
         for (target, elf_path) in elf_paths {
             // Read the elf file contents
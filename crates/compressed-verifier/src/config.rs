@@ -0,0 +1,78 @@
+use alloc::vec::Vec;
+use p3_challenger::DuplexChallenger;
+use p3_commit::ExtensionMmcs;
+use p3_dft::Radix2DitParallel;
+use p3_field::{extension::BinomialExtensionField, Field};
+use p3_fri::{FriConfig, TwoAdicFriPcs};
+use p3_koala_bear::{KoalaBear, Poseidon2KoalaBear};
+use p3_merkle_tree::MerkleTreeMmcs;
+use p3_poseidon2::ExternalLayerConstants;
+use p3_symmetric::{CryptographicHasher, PaddingFreeSponge, TruncatedPermutation};
+
+use crate::machine::CompressMachine;
+
+// The configuration of the compress prover, mirroring `KoalaBearPoseidon2` of `zkm-stark`.
+pub(crate) type Val = KoalaBear;
+pub(crate) type Challenge = BinomialExtensionField<Val, 4>;
+
+type Perm = Poseidon2KoalaBear<16>;
+type Hash = PaddingFreeSponge<Perm, 16, 8, 8>;
+type Compress = TruncatedPermutation<Perm, 2, 8, 16>;
+type ValMmcs = MerkleTreeMmcs<<Val as Field>::Packing, <Val as Field>::Packing, Hash, Compress, 8>;
+type ChallengeMmcs = ExtensionMmcs<Val, Challenge, ValMmcs>;
+type Dft = Radix2DitParallel<Val>;
+
+pub(crate) type Challenger = DuplexChallenger<Val, Perm, 16, 8>;
+pub(crate) type Pcs = TwoAdicFriPcs<Val, Dft, ValMmcs, ChallengeMmcs>;
+pub(crate) type Com = <Pcs as p3_commit::Pcs<Challenge, Challenger>>::Commitment;
+pub(crate) type Domain = <Pcs as p3_commit::Pcs<Challenge, Challenger>>::Domain;
+pub(crate) type PcsProof = <Pcs as p3_commit::Pcs<Challenge, Challenger>>::Proof;
+
+const ROUNDS_F: usize = 8;
+const ROUNDS_P: usize = 13;
+pub(crate) const POSEIDON2_NUM_ROUNDS: usize = ROUNDS_F + ROUNDS_P;
+
+pub(crate) struct CompressConfig {
+    perm: Perm,
+    pcs: Pcs,
+}
+
+impl CompressConfig {
+    pub(crate) fn new(machine: &CompressMachine) -> Self {
+        let mut round_constants = machine.poseidon2_round_constants.clone();
+        let internal_round_constants = round_constants
+            .drain(ROUNDS_F / 2..ROUNDS_F / 2 + ROUNDS_P)
+            .map(|constants| constants[0])
+            .collect::<Vec<_>>();
+        let external_round_constants = ExternalLayerConstants::new(
+            round_constants[..ROUNDS_F / 2].to_vec(),
+            round_constants[ROUNDS_F / 2..ROUNDS_F].to_vec(),
+        );
+        let perm = Perm::new(external_round_constants, internal_round_constants);
+
+        let hash = Hash::new(perm.clone());
+        let compress = Compress::new(perm.clone());
+        let val_mmcs = ValMmcs::new(hash, compress);
+        let fri_config = FriConfig {
+            log_blowup: machine.fri.log_blowup,
+            num_queries: machine.fri.num_queries,
+            proof_of_work_bits: machine.fri.proof_of_work_bits,
+            mmcs: ChallengeMmcs::new(val_mmcs.clone()),
+        };
+        let pcs = Pcs::new(Dft::default(), val_mmcs, fri_config);
+        Self { perm, pcs }
+    }
+
+    pub(crate) const fn pcs(&self) -> &Pcs {
+        &self.pcs
+    }
+
+    pub(crate) fn challenger(&self) -> Challenger {
+        Challenger::new(self.perm.clone())
+    }
+
+    /// Hashes the input with the Poseidon2 sponge used for the recursion digests.
+    pub(crate) fn hash(&self, input: &[Val]) -> [Val; 8] {
+        Hash::new(self.perm.clone()).hash_slice(input)
+    }
+}
@@ -0,0 +1,36 @@
+use alloc::string::String;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CompressedError {
+    #[error("Invalid compress machine description")]
+    InvalidMachine,
+    #[error("The compress machine description does not pin the allowed verifying keys")]
+    MissingAllowedVkDigests,
+    #[error("Failed to deserialize the proof")]
+    InvalidProofEncoding,
+    #[error("Chip opening length mismatch")]
+    ChipOpeningLengthMismatch,
+    #[error("Preprocessed chip {0} is missing from the shard")]
+    MissingPreprocessedChip(String),
+    #[error("Invalid log degree for chip {0}")]
+    InvalidLogDegree(String),
+    #[error("Byte multiplicities overflow")]
+    ByteMultiplicitiesOverflow,
+    #[error("Cumulative sums error: {0}")]
+    CumulativeSumsError(&'static str),
+    #[error("Invalid opening argument")]
+    InvalidOpeningArgument,
+    #[error("Invalid opening shape for chip {0}")]
+    OpeningShapeMismatch(String),
+    #[error("Out-of-domain evaluation mismatch on chip {0}")]
+    OodEvaluationMismatch(String),
+    #[error("Invalid compress verifying key")]
+    InvalidVerificationKey,
+    #[error("Invalid public values: {0}")]
+    InvalidPublicValues(&'static str),
+    #[error("Ziren vkey hash mismatch")]
+    VkeyHashMismatch,
+    #[error("Committed value digest mismatch")]
+    CommittedValueDigestMismatch,
+}
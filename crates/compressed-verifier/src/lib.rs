@@ -0,0 +1,159 @@
+//! This crate provides a verifier for Ziren compressed proofs in a no-std environment.
+//!
+//! It only depends on the Plonky3 verifier, so light clients and Ziren programs can verify
+//! compressed proofs without the prover stack. It is re-exported by `zkm-verifier` with the
+//! `compressed` feature.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+/// The description of the compress machine for this Ziren version.
+pub static COMPRESS_MACHINE_BYTES: &[u8] =
+    include_bytes!("../compress-machine/compress_machine.bin");
+
+mod config;
+pub mod error;
+mod machine;
+pub(crate) mod proof;
+pub(crate) mod public_values;
+mod verify;
+
+pub use error::CompressedError;
+pub use machine::*;
+
+#[cfg(test)]
+mod tests;
+
+use bincode::config::legacy;
+use p3_challenger::CanObserve;
+use p3_field::{FieldAlgebra, PrimeField32, TwoAdicField};
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+
+use config::{CompressConfig, Val};
+use proof::CompressedProof;
+use public_values::{
+    COMMITTED_VALUE_DIGEST, DIGEST, IS_COMPLETE, RECURSION_NUM_PV_ELTS, VK_ROOT, ZKM_VK_DIGEST,
+};
+use verify::{observe_vk, verify_shard};
+
+/// A verifier for compressed proofs, i.e. the STARK proofs of the compress machine over
+/// KoalaBear.
+#[derive(Debug)]
+pub struct CompressedVerifier;
+
+impl CompressedVerifier {
+    /// Verifies a Ziren compressed proof, as generated by the Ziren SDK.
+    ///
+    /// # Arguments
+    ///
+    /// * `proof` - The proof bytes, as returned by `ZKMProofWithPublicValues::bytes` for a
+    ///   compressed proof.
+    /// * `public_inputs` - The Ziren public inputs.
+    /// * `zkm_vkey_hash` - The Ziren vkey hash.
+    ///   This is generated in the following manner:
+    ///
+    /// ```ignore
+    /// use zkm_sdk::{HashableKey, ProverClient};
+    /// let client = ProverClient::new();
+    /// let (pk, vk) = client.setup(ELF);
+    /// let zkm_vkey_hash = vk.hash_bytes();
+    /// ```
+    /// * `compress_machine` - The description of the compress machine.
+    ///   Usually this will be the [`static@crate::COMPRESS_MACHINE_BYTES`] constant, which is the
+    ///   compress machine for the current Ziren version.
+    ///
+    /// # Returns
+    ///
+    /// A success [`Result`] if verification succeeds, or a [`CompressedError`] if verification
+    /// fails.
+    pub fn verify(
+        proof: &[u8],
+        zkm_public_inputs: &[u8],
+        zkm_vkey_hash: &[u8; 32],
+        compress_machine: &[u8],
+    ) -> Result<(), CompressedError> {
+        let machine: CompressMachine =
+            decode(compress_machine).ok_or(CompressedError::InvalidMachine)?;
+        if !machine.is_valid() {
+            return Err(CompressedError::InvalidMachine);
+        }
+        // Without the allowed verifying keys, any compress program proving the same public values
+        // would be accepted.
+        if machine.allowed_vk_digests.is_empty() {
+            return Err(CompressedError::MissingAllowedVkDigests);
+        }
+        let CompressedProof { vk, proof } =
+            decode(proof).ok_or(CompressedError::InvalidProofEncoding)?;
+
+        // The compress machine has no global lookups, so the global cumulative sum of the proof is
+        // only zero if the program starts from the zero sum.
+        if !vk.initial_global_cumulative_sum.is_zero()
+            || vk.chip_information.iter().any(|(_, domain, _)| domain.log_n > Val::TWO_ADICITY)
+        {
+            return Err(CompressedError::InvalidVerificationKey);
+        }
+        if proof.public_values.len() != machine.num_pv_elts {
+            return Err(CompressedError::InvalidPublicValues("wrong number of public values"));
+        }
+
+        // Verify the shard proof.
+        let config = CompressConfig::new(&machine);
+        let mut challenger = config.challenger();
+        observe_vk(&vk, &mut challenger);
+        challenger.observe_slice(&proof.public_values[0..machine.num_pv_elts]);
+        verify_shard(&config, &machine, &vk, &mut challenger, &proof)?;
+
+        // Validate the public values.
+        let public_values = &proof.public_values[..RECURSION_NUM_PV_ELTS];
+        if config.hash(&public_values[..DIGEST.start]) != public_values[DIGEST] {
+            return Err(CompressedError::InvalidPublicValues(
+                "recursion public values are invalid",
+            ));
+        }
+
+        if public_values[VK_ROOT] != machine.vk_root {
+            return Err(CompressedError::InvalidPublicValues("vk_root mismatch"));
+        }
+
+        let vk_digest = vk.hash(|input| config.hash(input));
+        if !machine.allowed_vk_digests.contains(&vk_digest) {
+            return Err(CompressedError::InvalidVerificationKey);
+        }
+
+        // `is_complete` should be 1. In the reduce program, this ensures that the proof is fully
+        // reduced.
+        if public_values[IS_COMPLETE] != Val::ONE {
+            return Err(CompressedError::InvalidPublicValues("is_complete is not 1"));
+        }
+
+        // Verify that the proof is for the Ziren vkey we are expecting.
+        let vkey_hash_matches = public_values[ZKM_VK_DIGEST]
+            .iter()
+            .zip(zkm_vkey_hash.chunks_exact(4))
+            .all(|(x, word)| x.as_canonical_u32() == u32::from_be_bytes(word.try_into().unwrap()));
+        if !vkey_hash_matches {
+            return Err(CompressedError::VkeyHashMismatch);
+        }
+
+        // Verify that the committed value digest matches the public inputs.
+        let committed_value_digest = Sha256::digest(zkm_public_inputs);
+        if public_values[COMMITTED_VALUE_DIGEST]
+            .iter()
+            .zip(committed_value_digest.iter())
+            .any(|(x, byte)| x.as_canonical_u32() != *byte as u32)
+        {
+            return Err(CompressedError::CommittedValueDigestMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+/// Decodes bincode encoded bytes, in the format of `bincode::serialize` of bincode 1.
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Option<T> {
+    match bincode::serde::decode_from_slice(bytes, legacy()) {
+        Ok((value, len)) if len == bytes.len() => Some(value),
+        _ => None,
+    }
+}
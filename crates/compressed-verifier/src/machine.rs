@@ -0,0 +1,170 @@
+use alloc::{string::String, vec::Vec};
+use p3_field::FieldExtensionAlgebra;
+use p3_koala_bear::KoalaBear;
+use serde::{Deserialize, Serialize};
+
+use crate::{config::Challenge, public_values::RECURSION_NUM_PV_ELTS};
+
+/// The description of the compress machine, which the compressed proof verifier checks proofs
+/// against.
+///
+/// It is generated from the compress machine of `zkm-prover` by the `build_compress_machine`
+/// script, and is embedded as [`static@crate::COMPRESS_MACHINE_BYTES`] for the current Ziren
+/// version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompressMachine {
+    /// The round constants of the width 16 Poseidon2 permutation.
+    pub poseidon2_round_constants: Vec<[KoalaBear; 16]>,
+    /// The FRI parameters of the compress prover.
+    pub fri: FriParameters,
+    /// The number of public values of a compressed proof.
+    pub num_pv_elts: usize,
+    /// The root of the allowed recursion verifying keys.
+    pub vk_root: [KoalaBear; 8],
+    /// The digests of the allowed recursion verifying keys, which the verifying key of a
+    /// compressed proof must be one of.
+    pub allowed_vk_digests: Vec<[KoalaBear; 8]>,
+    /// The chips of the machine.
+    pub chips: Vec<ChipDescription>,
+}
+
+/// The parameters of the FRI opening proofs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FriParameters {
+    pub log_blowup: usize,
+    pub num_queries: usize,
+    pub proof_of_work_bits: usize,
+}
+
+/// The description of a chip of the compress machine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChipDescription {
+    pub name: String,
+    pub preprocessed_width: usize,
+    pub width: usize,
+    /// The relative log degree of the quotient polynomial.
+    pub log_quotient_degree: usize,
+    /// Whether the chip is only opened at the current row.
+    pub local_only: bool,
+    pub num_sent_byte_lookups: usize,
+    /// The local lookups the chip sends.
+    pub sends: Vec<LookupDescription>,
+    /// The local lookups the chip receives.
+    pub receives: Vec<LookupDescription>,
+    /// The nodes of the constraint expressions, each of which only refers to earlier nodes.
+    pub nodes: Vec<ConstraintNode>,
+    /// The nodes which are constrained to be zero, in the order they are folded.
+    pub constraints: Vec<usize>,
+}
+
+/// A local lookup of a chip.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LookupDescription {
+    /// The index of the argument in the lookup table.
+    pub argument_index: usize,
+    pub values: Vec<LinearColumn>,
+    pub multiplicity: LinearColumn,
+}
+
+/// An affine combination of the columns of the current row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinearColumn {
+    pub preprocessed: Vec<(usize, KoalaBear)>,
+    pub main: Vec<(usize, KoalaBear)>,
+    pub constant: KoalaBear,
+}
+
+/// A node of the constraint expressions of a chip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConstraintNode {
+    /// A preprocessed column, in the current (`offset = 0`) or next (`offset = 1`) row.
+    Preprocessed {
+        offset: usize,
+        index: usize,
+    },
+    /// A main column, in the current (`offset = 0`) or next (`offset = 1`) row.
+    Main {
+        offset: usize,
+        index: usize,
+    },
+    Public(usize),
+    IsFirstRow,
+    IsLastRow,
+    IsTransition,
+    Constant(KoalaBear),
+    Add(usize, usize),
+    Sub(usize, usize),
+    Neg(usize),
+    Mul(usize, usize),
+}
+
+impl CompressMachine {
+    /// Checks that the description is well formed, so that evaluating it cannot go out of bounds.
+    pub(crate) fn is_valid(&self) -> bool {
+        self.poseidon2_round_constants.len() >= crate::config::POSEIDON2_NUM_ROUNDS
+            && self.num_pv_elts == RECURSION_NUM_PV_ELTS
+            && self.chips.iter().all(|chip| chip.is_valid(self.num_pv_elts))
+    }
+}
+
+impl ChipDescription {
+    /// The number of extension field columns of the permutation trace.
+    pub(crate) fn permutation_width(&self) -> usize {
+        let num_lookups = self.sends.len() + self.receives.len();
+        if num_lookups == 0 {
+            return 0;
+        }
+        num_lookups.div_ceil(self.logup_batch_size()) + 1
+    }
+
+    pub(crate) const fn quotient_width(&self) -> usize {
+        1 << self.log_quotient_degree
+    }
+
+    pub(crate) const fn logup_batch_size(&self) -> usize {
+        1 << self.log_quotient_degree
+    }
+
+    fn is_valid(&self, num_pv_elts: usize) -> bool {
+        let columns_valid = |column: &LinearColumn| {
+            column.preprocessed.iter().all(|(i, _)| *i < self.preprocessed_width)
+                && column.main.iter().all(|(i, _)| *i < self.width)
+        };
+        let lookups_valid = self.sends.iter().chain(self.receives.iter()).all(|lookup| {
+            lookup.values.iter().all(columns_valid) && columns_valid(&lookup.multiplicity)
+        });
+        let nodes_valid = self.nodes.iter().enumerate().all(|(i, node)| match *node {
+            ConstraintNode::Preprocessed { offset, index } => {
+                offset <= 1 && index < self.preprocessed_width
+            }
+            ConstraintNode::Main { offset, index } => offset <= 1 && index < self.width,
+            ConstraintNode::Public(index) => index < num_pv_elts,
+            ConstraintNode::IsFirstRow
+            | ConstraintNode::IsLastRow
+            | ConstraintNode::IsTransition
+            | ConstraintNode::Constant(_) => true,
+            ConstraintNode::Add(x, y) | ConstraintNode::Sub(x, y) | ConstraintNode::Mul(x, y) => {
+                x < i && y < i
+            }
+            ConstraintNode::Neg(x) => x < i,
+        });
+        self.log_quotient_degree < usize::BITS as usize
+            && lookups_valid
+            && nodes_valid
+            && self.constraints.iter().all(|i| *i < self.nodes.len())
+    }
+}
+
+impl LinearColumn {
+    /// Evaluates the column at the given row.
+    pub(crate) fn apply(&self, preprocessed: &[Challenge], main: &[Challenge]) -> Challenge {
+        let mut result = Challenge::from_base(self.constant);
+        for (i, weight) in self.preprocessed.iter() {
+            result += preprocessed[*i] * *weight;
+        }
+        for (i, weight) in self.main.iter() {
+            result += main[*i] * *weight;
+        }
+        result
+    }
+}
@@ -0,0 +1,108 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use p3_field::{FieldAlgebra, PrimeField32, TwoAdicField};
+use p3_matrix::Dimensions;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Challenge, Com, Domain, PcsProof, Val};
+
+// Mirrors of the `zkm-stark` proof types which share their serialized layout, so that a bincode
+// encoded `ZKMReduceProof<InnerSC>` can be decoded without the prover stack. The chip orderings
+// are hash maps in `zkm-stark`, which are encoded like the ordered maps used here.
+
+/// The x-coordinate of the zero point of the global cumulative sums.
+const CURVE_CUMULATIVE_SUM_START_X: [u32; 7] =
+    [637514027, 1595065213, 1998064738, 72333738, 1211544370, 822986770, 1518535784];
+
+/// The y-coordinate of the zero point of the global cumulative sums.
+const CURVE_CUMULATIVE_SUM_START_Y: [u32; 7] =
+    [1604177449, 90440090, 259343427, 140470264, 1162099742, 941559812, 1064053343];
+
+/// A compressed proof along with the verifying key of the compress program which produced it.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CompressedProof {
+    pub vk: CompressVerifyingKey,
+    pub proof: ShardProof,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CompressVerifyingKey {
+    pub commit: Com,
+    pub pc_start: Val,
+    pub initial_global_cumulative_sum: SepticDigest,
+    pub chip_information: Vec<(String, Domain, Dimensions)>,
+    pub chip_ordering: BTreeMap<String, usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ShardProof {
+    pub commitment: ShardCommitment,
+    pub opened_values: ShardOpenedValues,
+    pub opening_proof: PcsProof,
+    pub chip_ordering: BTreeMap<String, usize>,
+    pub public_values: Vec<Val>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ShardCommitment {
+    pub main_commit: Com,
+    pub permutation_commit: Com,
+    pub quotient_commit: Com,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ShardOpenedValues {
+    pub chips: Vec<ChipOpenedValues>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ChipOpenedValues {
+    pub preprocessed: AirOpenedValues,
+    pub main: AirOpenedValues,
+    pub permutation: AirOpenedValues,
+    pub quotient: Vec<Vec<Challenge>>,
+    pub global_cumulative_sum: SepticDigest,
+    pub local_cumulative_sum: Challenge,
+    pub log_degree: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct AirOpenedValues {
+    pub local: Vec<Challenge>,
+    pub next: Vec<Challenge>,
+}
+
+/// A point of the septic curve accumulating the global lookups.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SepticDigest {
+    pub x: [Val; 7],
+    pub y: [Val; 7],
+}
+
+impl SepticDigest {
+    /// Whether the digest is the starting point of the accumulation, i.e. accumulates nothing.
+    pub(crate) fn is_zero(&self) -> bool {
+        let is_coordinate = |coordinate: &[Val; 7], expected: &[u32; 7]| {
+            coordinate.iter().zip(expected).all(|(x, e)| x.as_canonical_u32() == *e)
+        };
+        is_coordinate(&self.x, &CURVE_CUMULATIVE_SUM_START_X)
+            && is_coordinate(&self.y, &CURVE_CUMULATIVE_SUM_START_Y)
+    }
+}
+
+impl CompressVerifyingKey {
+    /// Hashes the verifying key like `HashableKey::hash_koalabear` of `zkm-prover`.
+    pub(crate) fn hash(&self, hash: impl Fn(&[Val]) -> [Val; 8]) -> [Val; 8] {
+        let mut inputs = Vec::with_capacity(8 + 1 + 14 + 4 * self.chip_information.len());
+        inputs.extend(self.commit.as_ref());
+        inputs.push(self.pc_start);
+        inputs.extend(self.initial_global_cumulative_sum.x);
+        inputs.extend(self.initial_global_cumulative_sum.y);
+        for (_, domain, _) in self.chip_information.iter() {
+            inputs.push(Val::from_canonical_usize(domain.log_n));
+            inputs.push(Val::from_canonical_usize(1 << domain.log_n));
+            inputs.push(domain.shift);
+            inputs.push(Val::two_adic_generator(domain.log_n));
+        }
+        hash(&inputs)
+    }
+}
@@ -0,0 +1,22 @@
+use core::ops::Range;
+
+// The offsets of the fields of `RecursionPublicValues` of `zkm-recursion-core` that are checked
+// by the verifier.
+
+/// The hash of all the bytes that the program has written to public values, as 8 words of 4 bytes.
+pub(crate) const COMMITTED_VALUE_DIGEST: Range<usize> = 0..32;
+
+/// The commitment to the Ziren program being proven.
+pub(crate) const ZKM_VK_DIGEST: Range<usize> = 190..198;
+
+/// The root of the vk merkle tree.
+pub(crate) const VK_ROOT: Range<usize> = 198..206;
+
+/// Whether the proof completely proves the program execution.
+pub(crate) const IS_COMPLETE: usize = 220;
+
+/// The digest of all the previous public values elements.
+pub(crate) const DIGEST: Range<usize> = 223..231;
+
+/// The number of public values of a recursion proof.
+pub(crate) const RECURSION_NUM_PV_ELTS: usize = 231;
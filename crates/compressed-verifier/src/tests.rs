@@ -0,0 +1,82 @@
+use test_artifacts::HELLO_WORLD_ELF;
+use zkm_sdk::{HashableKey, ProverClient, ZKMStdin};
+
+#[test]
+fn test_compress_machine_constants() {
+    use crate::{proof, public_values::*};
+    use zkm_recursion_core::air::RECURSION_PUBLIC_VALUES_COL_MAP;
+
+    let map = RECURSION_PUBLIC_VALUES_COL_MAP;
+    assert_eq!(
+        COMMITTED_VALUE_DIGEST,
+        map.committed_value_digest[0][0]..map.committed_value_digest[7][3] + 1
+    );
+    assert_eq!(ZKM_VK_DIGEST, map.zkm_vk_digest[0]..map.zkm_vk_digest[7] + 1);
+    assert_eq!(VK_ROOT, map.vk_root[0]..map.vk_root[7] + 1);
+    assert_eq!(IS_COMPLETE, map.is_complete);
+    assert_eq!(DIGEST, map.digest[0]..map.digest[7] + 1);
+    assert_eq!(RECURSION_NUM_PV_ELTS, zkm_recursion_core::air::RECURSIVE_PROOF_NUM_PV_ELTS);
+
+    let zero = zkm_stark::septic_digest::SepticDigest::<p3_koala_bear::KoalaBear>::zero();
+    let zero: proof::SepticDigest =
+        bincode::deserialize(&bincode::serialize(&zero).unwrap()).unwrap();
+    assert!(zero.is_zero());
+}
+
+/// The embedded compress machine pins the verifying keys of `vk_map.bin`, so the proofs it
+/// verifies must be generated with verifying key verification.
+fn enable_vk_verification() {
+    std::env::set_var("VERIFY_VK", "true");
+}
+
+// RUST_LOG=debug cargo test -r test_compress_machine_is_up_to_date
+#[test]
+fn test_compress_machine_is_up_to_date() {
+    enable_vk_verification();
+    let prover = zkm_prover::ZKMProver::new();
+    let machine = zkm_prover::build::build_compress_machine(&prover);
+    assert_eq!(
+        bincode::serialize(&machine).unwrap(),
+        crate::COMPRESS_MACHINE_BYTES,
+        "the compress machine is outdated, regenerate it with `build_compress_machine`"
+    );
+}
+
+// RUST_LOG=debug cargo test -r test_verify_compressed
+#[test]
+fn test_verify_compressed() {
+    // Set up the pk and vk.
+    enable_vk_verification();
+    let client = ProverClient::cpu();
+    let (pk, vk) = client.setup(HELLO_WORLD_ELF);
+
+    // Generate the compressed proof.
+    let zkm_proof_with_public_values =
+        client.prove(&pk, ZKMStdin::new()).compressed().run().unwrap();
+
+    // Extract the proof and public inputs.
+    let proof = zkm_proof_with_public_values.bytes().unwrap();
+    let public_inputs = zkm_proof_with_public_values.public_values.to_vec();
+
+    // Get the vkey hash.
+    let vkey_hash = vk.hash_bytes();
+
+    let machine = crate::COMPRESS_MACHINE_BYTES;
+    crate::CompressedVerifier::verify(&proof, &public_inputs, &vkey_hash, machine)
+        .expect("compressed proof is invalid");
+
+    // Verification must fail for other public inputs.
+    assert!(matches!(
+        crate::CompressedVerifier::verify(&proof, b"other", &vkey_hash, machine),
+        Err(crate::CompressedError::CommittedValueDigestMismatch)
+    ));
+
+    // Verification must fail if the machine doesn't pin the verifying keys.
+    let mut unpinned: crate::CompressMachine = bincode::deserialize(machine).unwrap();
+    unpinned.allowed_vk_digests.clear();
+    let unpinned = bincode::serialize(&unpinned).unwrap();
+    assert!(matches!(
+        crate::CompressedVerifier::verify(&proof, &public_inputs, &vkey_hash, &unpinned),
+        Err(crate::CompressedError::MissingAllowedVkDigests)
+    ));
+}
@@ -0,0 +1,385 @@
+use alloc::{vec, vec::Vec};
+use itertools::{izip, Itertools};
+use p3_challenger::{CanObserve, FieldChallenger};
+use p3_commit::{LagrangeSelectors, Pcs as _, PolynomialSpace};
+use p3_field::{Field, FieldAlgebra, FieldExtensionAlgebra, PrimeField32, TwoAdicField};
+
+use crate::{
+    config::{Challenge, Challenger, CompressConfig, Domain, Val},
+    error::CompressedError,
+    machine::{ChipDescription, CompressMachine, ConstraintNode},
+    proof::{AirOpenedValues, ChipOpenedValues, CompressVerifyingKey, ShardProof},
+};
+
+/// Verifies a shard proof of the compress machine, like `Verifier::verify_shard` of `zkm-stark`.
+///
+/// All the lookups of the compress machine are local, so the global cumulative sums are required
+/// to be zero.
+pub(crate) fn verify_shard(
+    config: &CompressConfig,
+    machine: &CompressMachine,
+    vk: &CompressVerifyingKey,
+    challenger: &mut Challenger,
+    proof: &ShardProof,
+) -> Result<(), CompressedError> {
+    let ShardProof { commitment, opened_values, opening_proof, chip_ordering, public_values } =
+        proof;
+
+    let chips = machine
+        .chips
+        .iter()
+        .filter(|chip| chip_ordering.contains_key(&chip.name))
+        .sorted_by_key(|chip| chip_ordering.get(&chip.name))
+        .collect::<Vec<_>>();
+
+    if chips.len() != opened_values.chips.len() {
+        return Err(CompressedError::ChipOpeningLengthMismatch);
+    }
+
+    for (chip, values) in chips.iter().zip(opened_values.chips.iter()) {
+        if values.log_degree + chip.log_quotient_degree > Val::TWO_ADICITY {
+            return Err(CompressedError::InvalidLogDegree(chip.name.clone()));
+        }
+    }
+
+    // Check that the byte multiplicities don't overflow.
+    let mut max_byte_lookup_mult = 0u64;
+    for (chip, values) in chips.iter().zip(opened_values.chips.iter()) {
+        max_byte_lookup_mult = (chip.num_sent_byte_lookups as u64)
+            .checked_mul(1u64 << values.log_degree)
+            .and_then(|mult| max_byte_lookup_mult.checked_add(mult))
+            .ok_or(CompressedError::ByteMultiplicitiesOverflow)?;
+    }
+    if max_byte_lookup_mult > Val::ORDER_U32 as u64 {
+        return Err(CompressedError::ByteMultiplicitiesOverflow);
+    }
+
+    let pcs = config.pcs();
+    let log_degrees = opened_values.chips.iter().map(|val| val.log_degree).collect::<Vec<_>>();
+    let trace_domains = log_degrees
+        .iter()
+        .map(|log_degree| pcs.natural_domain_for_degree(1 << log_degree))
+        .collect::<Vec<Domain>>();
+
+    challenger.observe(commitment.main_commit);
+
+    let permutation_challenges =
+        (0..2).map(|_| challenger.sample_ext_element::<Challenge>()).collect::<Vec<_>>();
+
+    challenger.observe(commitment.permutation_commit);
+    // Observe the cumulative sums and constrain any sum without a corresponding scope to be zero.
+    for (opening, chip) in opened_values.chips.iter().zip(chips.iter()) {
+        let local_sum = opening.local_cumulative_sum;
+        let global_sum = opening.global_cumulative_sum;
+
+        challenger.observe_slice(local_sum.as_base_slice());
+        challenger.observe_slice(&global_sum.x);
+        challenger.observe_slice(&global_sum.y);
+
+        if !global_sum.is_zero() {
+            return Err(CompressedError::CumulativeSumsError(
+                "global cumulative sum is non-zero, but chip is Local",
+            ));
+        }
+
+        let has_local_lookups = !chip.sends.is_empty() || !chip.receives.is_empty();
+        if !has_local_lookups && !local_sum.is_zero() {
+            return Err(CompressedError::CumulativeSumsError(
+                "local cumulative sum is non-zero, but no local lookups",
+            ));
+        }
+    }
+
+    let alpha = challenger.sample_ext_element::<Challenge>();
+
+    // Observe the quotient commitments.
+    challenger.observe(commitment.quotient_commit);
+
+    let zeta = challenger.sample_ext_element::<Challenge>();
+
+    let mut preprocessed_domains_points_and_opens = Vec::with_capacity(vk.chip_information.len());
+    for (name, domain, _) in vk.chip_information.iter() {
+        let i = chip_ordering
+            .get(name)
+            .copied()
+            .filter(|i| *i < chips.len())
+            .ok_or_else(|| CompressedError::MissingPreprocessedChip(name.clone()))?;
+        let values = &opened_values.chips[i].preprocessed;
+        if !chips[i].local_only {
+            preprocessed_domains_points_and_opens.push((
+                *domain,
+                vec![
+                    (zeta, values.local.clone()),
+                    (domain.next_point(zeta).unwrap(), values.next.clone()),
+                ],
+            ));
+        } else {
+            preprocessed_domains_points_and_opens
+                .push((*domain, vec![(zeta, values.local.clone())]));
+        }
+    }
+
+    let main_domains_points_and_opens = izip!(trace_domains.iter(), &opened_values.chips, &chips)
+        .map(|(domain, values, chip)| {
+            if !chip.local_only {
+                (
+                    *domain,
+                    vec![
+                        (zeta, values.main.local.clone()),
+                        (domain.next_point(zeta).unwrap(), values.main.next.clone()),
+                    ],
+                )
+            } else {
+                (*domain, vec![(zeta, values.main.local.clone())])
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let perm_domains_points_and_opens = trace_domains
+        .iter()
+        .zip(opened_values.chips.iter())
+        .map(|(domain, values)| {
+            (
+                *domain,
+                vec![
+                    (zeta, values.permutation.local.clone()),
+                    (domain.next_point(zeta).unwrap(), values.permutation.next.clone()),
+                ],
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let quotient_chunk_domains = izip!(trace_domains.iter(), &log_degrees, &chips)
+        .map(|(domain, log_degree, chip)| {
+            let quotient_domain =
+                domain.create_disjoint_domain(1 << (log_degree + chip.log_quotient_degree));
+            quotient_domain.split_domains(chip.quotient_width())
+        })
+        .collect::<Vec<_>>();
+
+    let mut quotient_domains_points_and_opens = Vec::new();
+    for (chip, values, qc_domains) in izip!(&chips, &opened_values.chips, &quotient_chunk_domains) {
+        if values.quotient.len() != qc_domains.len() {
+            return Err(CompressedError::OpeningShapeMismatch(chip.name.clone()));
+        }
+        for (values, q_domain) in values.quotient.iter().zip(qc_domains) {
+            quotient_domains_points_and_opens.push((*q_domain, vec![(zeta, values.clone())]));
+        }
+    }
+
+    let rounds = vec![
+        (vk.commit, preprocessed_domains_points_and_opens),
+        (commitment.main_commit, main_domains_points_and_opens),
+        (commitment.permutation_commit, perm_domains_points_and_opens),
+        (commitment.quotient_commit, quotient_domains_points_and_opens),
+    ];
+
+    pcs.verify(rounds, opening_proof, challenger)
+        .map_err(|_| CompressedError::InvalidOpeningArgument)?;
+
+    // Verify the constraint evaluations.
+    for (chip, trace_domain, qc_domains, values) in
+        izip!(chips.iter(), trace_domains, quotient_chunk_domains, opened_values.chips.iter())
+    {
+        // Verify the shape of the opening arguments matches the expected values.
+        if !has_valid_shape(chip, values) {
+            return Err(CompressedError::OpeningShapeMismatch(chip.name.clone()));
+        }
+
+        let sels = trace_domain.selectors_at_point(zeta);
+
+        // Check that the constraints match the quotient, i.e.
+        //     folded_constraints(zeta) / Z_H(zeta) = quotient(zeta)
+        let quotient = recompute_quotient(values, &qc_domains, zeta);
+        let folded_constraints =
+            eval_constraints(chip, values, &sels, alpha, &permutation_challenges, public_values);
+        if folded_constraints * sels.inv_zeroifier != quotient {
+            return Err(CompressedError::OodEvaluationMismatch(chip.name.clone()));
+        }
+    }
+
+    // Verify that the local cumulative sum is zero.
+    let local_cumulative_sum =
+        opened_values.chips.iter().map(|c| c.local_cumulative_sum).sum::<Challenge>();
+    if local_cumulative_sum != Challenge::ZERO {
+        return Err(CompressedError::CumulativeSumsError("local cumulative sum is not zero"));
+    }
+
+    Ok(())
+}
+
+fn has_valid_shape(chip: &ChipDescription, opening: &ChipOpenedValues) -> bool {
+    let has_width =
+        |values: &AirOpenedValues, width| values.local.len() == width && values.next.len() == width;
+    has_width(&opening.preprocessed, chip.preprocessed_width)
+        && has_width(&opening.main, chip.width)
+        && has_width(&opening.permutation, chip.permutation_width() * Challenge::D)
+        && opening.quotient.len() == chip.quotient_width()
+        && opening.quotient.iter().all(|chunk| chunk.len() == Challenge::D)
+}
+
+/// Evaluates the constraints of a chip at the opening, folded with `alpha`.
+fn eval_constraints(
+    chip: &ChipDescription,
+    opening: &ChipOpenedValues,
+    selectors: &LagrangeSelectors<Challenge>,
+    alpha: Challenge,
+    permutation_challenges: &[Challenge],
+    public_values: &[Val],
+) -> Challenge {
+    let mut accumulator = Challenge::ZERO;
+    let mut assert_zero = |x: Challenge| {
+        accumulator *= alpha;
+        accumulator += x;
+    };
+
+    // Evaluate the execution trace constraints.
+    let row = |values: &AirOpenedValues, offset: usize| {
+        if offset == 0 {
+            values.local.as_slice()
+        } else {
+            values.next.as_slice()
+        }
+    };
+    let mut nodes = Vec::with_capacity(chip.nodes.len());
+    for node in chip.nodes.iter() {
+        let value = match *node {
+            ConstraintNode::Preprocessed { offset, index } => {
+                row(&opening.preprocessed, offset)[index]
+            }
+            ConstraintNode::Main { offset, index } => row(&opening.main, offset)[index],
+            ConstraintNode::Public(index) => Challenge::from_base(public_values[index]),
+            ConstraintNode::IsFirstRow => selectors.is_first_row,
+            ConstraintNode::IsLastRow => selectors.is_last_row,
+            ConstraintNode::IsTransition => selectors.is_transition,
+            ConstraintNode::Constant(c) => Challenge::from_base(c),
+            ConstraintNode::Add(x, y) => nodes[x] + nodes[y],
+            ConstraintNode::Sub(x, y) => nodes[x] - nodes[y],
+            ConstraintNode::Neg(x) => -nodes[x],
+            ConstraintNode::Mul(x, y) => nodes[x] * nodes[y],
+        };
+        nodes.push(value);
+    }
+    for constraint in chip.constraints.iter() {
+        assert_zero(nodes[*constraint]);
+    }
+
+    // Evaluate the permutation constraints, like `eval_permutation_constraints` of `zkm-stark`.
+    let permutation_width = chip.permutation_width();
+    if permutation_width > 0 {
+        // Reconstruct the permutation opening values as extension elements.
+        let unflatten = |v: &[Challenge]| {
+            v.chunks_exact(Challenge::D)
+                .map(|chunk| {
+                    chunk.iter().enumerate().map(|(e_i, &x)| Challenge::monomial(e_i) * x).sum()
+                })
+                .collect::<Vec<Challenge>>()
+        };
+        let perm_local = unflatten(&opening.permutation.local);
+        let perm_next = unflatten(&opening.permutation.next);
+        let preprocessed_local = &opening.preprocessed.local;
+        let main_local = &opening.main.local;
+
+        let (perm_alpha, beta) = (permutation_challenges[0], permutation_challenges[1]);
+        let lookups = chip
+            .sends
+            .iter()
+            .map(|lookup| (lookup, true))
+            .chain(chip.receives.iter().map(|lookup| (lookup, false)))
+            .collect::<Vec<_>>();
+
+        // Assert that each entry is the batch sum m_i/rlc_i, by constraining
+        // entry * \prod_i rlc_i = \sum_i m_i * \prod_{j!=i} rlc_j.
+        for (entry, chunk) in
+            perm_local[..permutation_width - 1].iter().zip(lookups.chunks(chip.logup_batch_size()))
+        {
+            let mut rlcs = Vec::with_capacity(chunk.len());
+            let mut multiplicities = Vec::with_capacity(chunk.len());
+            for (lookup, is_send) in chunk {
+                let mut betas = beta.powers();
+                let mut rlc = perm_alpha
+                    + betas.next().unwrap()
+                        * Challenge::from_canonical_usize(lookup.argument_index);
+                for (column, beta) in lookup.values.iter().zip(betas) {
+                    rlc += beta * column.apply(preprocessed_local, main_local);
+                }
+                rlcs.push(rlc);
+
+                let multiplicity = lookup.multiplicity.apply(preprocessed_local, main_local);
+                multiplicities.push(if *is_send { multiplicity } else { -multiplicity });
+            }
+
+            let mut product = Challenge::ONE;
+            let mut numerator = Challenge::ZERO;
+            for (i, m) in multiplicities.into_iter().enumerate() {
+                product *= rlcs[i];
+                let all_but_current = rlcs
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| i != *j)
+                    .map(|(_, rlc)| *rlc)
+                    .product::<Challenge>();
+                numerator += m * all_but_current;
+            }
+            assert_zero(product * *entry - numerator);
+        }
+
+        // Assert that the running sum is accumulated on the transitions, and ends at the local
+        // cumulative sum.
+        let sum_local = perm_local[..permutation_width - 1].iter().copied().sum::<Challenge>();
+        let sum_next = perm_next[..permutation_width - 1].iter().copied().sum::<Challenge>();
+        let phi_local = perm_local[permutation_width - 1];
+        let phi_next = perm_next[permutation_width - 1];
+
+        assert_zero((phi_local - sum_local) * selectors.is_first_row);
+        assert_zero((phi_next - phi_local - sum_next) * selectors.is_transition);
+        assert_zero((phi_local - opening.local_cumulative_sum) * selectors.is_last_row);
+    }
+
+    accumulator
+}
+
+/// Recomputes the quotient at `zeta` from its chunks.
+fn recompute_quotient(
+    opening: &ChipOpenedValues,
+    qc_domains: &[Domain],
+    zeta: Challenge,
+) -> Challenge {
+    let zps = qc_domains
+        .iter()
+        .enumerate()
+        .map(|(i, domain)| {
+            qc_domains
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, other_domain)| {
+                    other_domain.zp_at_point(zeta)
+                        * other_domain.zp_at_point(domain.first_point()).inverse()
+                })
+                .product::<Challenge>()
+        })
+        .collect::<Vec<_>>();
+
+    opening
+        .quotient
+        .iter()
+        .enumerate()
+        .map(|(ch_i, ch)| {
+            ch.iter()
+                .enumerate()
+                .map(|(e_i, &c)| zps[ch_i] * Challenge::monomial(e_i) * c)
+                .sum::<Challenge>()
+        })
+        .sum::<Challenge>()
+}
+
+/// Observes the verifying key into the challenger, like `StarkVerifyingKey::observe_into`.
+pub(crate) fn observe_vk(vk: &CompressVerifyingKey, challenger: &mut Challenger) {
+    challenger.observe(vk.commit);
+    challenger.observe(vk.pc_start);
+    challenger.observe_slice(&vk.initial_global_cumulative_sum.x);
+    challenger.observe_slice(&vk.initial_global_cumulative_sum.y);
+    // Observe the padding.
+    challenger.observe(Val::ZERO);
+}
@@ -41,6 +41,8 @@ pub enum MipsAirId {
     Secp256r1DoubleAssign = 11,
     /// The Keccak permute chip.
     KeccakSponge = 48,
+    /// The BLAKE3 compress inner chip.
+    Blake3CompressInner = 50,
     /// The bn254 add assign chip.
     Bn254AddAssign = 13,
     /// The bn254 double assign chip.
@@ -53,18 +55,24 @@ pub enum MipsAirId {
     Uint256MulMod = 17,
     /// The u256 xu2048 mul chip.
     U256XU2048Mul = 18,
+    /// The poseidon2 permute chip.
+    Poseidon2Permute = 46,
     /// The bls12-381 fp op assign chip.
     Bls12381FpOpAssign = 19,
     /// The bls12-831 fp2 add sub assign chip.
     Bls12831Fp2AddSubAssign = 20,
     /// The bls12-831 fp2 mul assign chip.
     Bls12831Fp2MulAssign = 21,
+    /// The bls12-381 fp6 mul assign chip.
+    Bls12381Fp6MulAssign = 47,
     /// The bn254 fp2 add sub assign chip.
     Bn254FpOpAssign = 22,
     /// The bn254 fp op assign chip.
     Bn254Fp2AddSubAssign = 23,
     /// The bn254 fp2 mul assign chip.
     Bn254Fp2MulAssign = 24,
+    /// The bn254 fp6 mul assign chip.
+    Bn254Fp6MulAssign = 49,
     /// The bls12-381 decompress chip.
     Bls12381Decompress = 25,
     /// The syscall core chip.
@@ -97,6 +105,12 @@ pub enum MipsAirId {
     MemoryInstrs = 39,
     /// The MiscInstructionChip.
     MiscInstrs = 40,
+    /// The floating-point arithmetic chip.
+    FpuArith = 51,
+    /// The floating-point compare chip.
+    FpuCompare = 52,
+    /// The floating-point move chip.
+    FpuMove = 53,
     /// The memory global init chip.
     MemoryGlobalInit = 41,
     /// The memory global finalize chip.
@@ -127,6 +141,9 @@ impl MipsAirId {
             MipsAirId::MemoryInstrs,
             MipsAirId::SyscallInstrs,
             MipsAirId::MiscInstrs,
+            MipsAirId::FpuArith,
+            MipsAirId::FpuCompare,
+            MipsAirId::FpuMove,
             MipsAirId::SyscallCore,
             MipsAirId::Global,
         ]
@@ -149,18 +166,22 @@ impl MipsAirId {
             Self::Secp256r1AddAssign => "Secp256r1AddAssign",
             Self::Secp256r1DoubleAssign => "Secp256r1DoubleAssign",
             Self::KeccakSponge => "KeccakSponge",
+            Self::Blake3CompressInner => "Blake3CompressInner",
             Self::Bn254AddAssign => "Bn254AddAssign",
             Self::Bn254DoubleAssign => "Bn254DoubleAssign",
             Self::Bls12381AddAssign => "Bls12381AddAssign",
             Self::Bls12381DoubleAssign => "Bls12381DoubleAssign",
             Self::Uint256MulMod => "Uint256MulMod",
             Self::U256XU2048Mul => "U256XU2048Mul",
+            Self::Poseidon2Permute => "Poseidon2Permute",
             Self::Bls12381FpOpAssign => "Bls12381FpOpAssign",
             Self::Bls12831Fp2AddSubAssign => "Bls12831Fp2AddSubAssign",
             Self::Bls12831Fp2MulAssign => "Bls12831Fp2MulAssign",
+            Self::Bls12381Fp6MulAssign => "Bls12381Fp6MulAssign",
             Self::Bn254FpOpAssign => "Bn254FpOpAssign",
             Self::Bn254Fp2AddSubAssign => "Bn254Fp2AddSubAssign",
             Self::Bn254Fp2MulAssign => "Bn254Fp2MulAssign",
+            Self::Bn254Fp6MulAssign => "Bn254Fp6MulAssign",
             Self::Bls12381Decompress => "Bls12381Decompress",
             Self::SyscallCore => "SyscallCore",
             Self::SyscallPrecompile => "SyscallPrecompile",
@@ -177,6 +198,9 @@ impl MipsAirId {
             Self::SyscallInstrs => "SyscallInstrs",
             Self::MemoryInstrs => "MemoryInstrs",
             Self::MiscInstrs => "MiscInstrs",
+            Self::FpuArith => "FpuArith",
+            Self::FpuCompare => "FpuCompare",
+            Self::FpuMove => "FpuMove",
             Self::MemoryGlobalInit => "MemoryGlobalInit",
             Self::MemoryGlobalFinalize => "MemoryGlobalFinalize",
             Self::MemoryLocal => "MemoryLocal",
@@ -0,0 +1,218 @@
+//! Backtraces of guest programs.
+//!
+//! The executor can maintain a shadow call stack of the guest, tracking its calls and returns like
+//! the [`Profiler`](crate::profiler::Profiler) does, and capture it as a [`GuestBacktrace`] when
+//! the execution fails. The backtrace can then be symbolized with the symbol table and the DWARF
+//! line info of the ELF, which are only present if the guest is built with debug info, e.g. with
+//! `debug = "line-tables-only"` in its release profile.
+
+use core::fmt;
+
+use addr2line::gimli::{self, EndianSlice, RunTimeEndian};
+use anyhow::{anyhow, Result};
+use elf::{endian::LittleEndian, ElfBytes};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    profiler::{control_transfer, function_symbols, Transfer},
+    Instruction,
+};
+
+/// The call stack of a guest program, tracked from its calls and returns.
+#[derive(Debug, Clone, Default)]
+pub struct ShadowStack {
+    /// The return addresses of the frames, from the root to the leaf.
+    return_addrs: Vec<u32>,
+    /// The pending control transfer and whether its delay slot has been executed.
+    pending: Option<(Transfer, bool)>,
+}
+
+impl ShadowStack {
+    /// Create an empty [`ShadowStack`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the execution of `instruction` at `pc`.
+    pub fn record(&mut self, pc: u32, instruction: &Instruction) {
+        // A call or return takes effect once its delay slot has been executed.
+        match self.pending {
+            Some((transfer, true)) => {
+                self.pending = None;
+                self.transfer(transfer, pc);
+            }
+            Some((transfer, false)) => self.pending = Some((transfer, true)),
+            None => {}
+        }
+
+        if let Some(transfer) = control_transfer(pc, instruction) {
+            self.pending = Some((transfer, false));
+        }
+    }
+
+    fn transfer(&mut self, transfer: Transfer, pc: u32) {
+        match transfer {
+            // A branch and link that was not taken falls through to its return address.
+            Transfer::Call { return_addr } if return_addr == pc => {}
+            Transfer::Call { return_addr } => self.return_addrs.push(return_addr),
+            Transfer::Return => {
+                // Unwind to the frame returning to `pc`, which also discards frames that were
+                // left without a return, e.g. by tail calls.
+                if let Some(idx) = self.return_addrs.iter().rposition(|&addr| addr == pc) {
+                    self.return_addrs.truncate(idx);
+                }
+            }
+        }
+    }
+
+    /// The backtrace of the guest executing the instruction at `pc`.
+    #[must_use]
+    pub fn backtrace(&self, pc: u32) -> GuestBacktrace {
+        // The calls are two instructions before their return address, because of the delay slot.
+        let call_sites = self.return_addrs.iter().rev().map(|addr| addr.wrapping_sub(8));
+        GuestBacktrace {
+            frames: core::iter::once(pc).chain(call_sites).map(BacktraceFrame::new).collect(),
+        }
+    }
+}
+
+/// A frame of a [`GuestBacktrace`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BacktraceFrame {
+    /// The program counter of the frame: the failing instruction for the innermost frame, and the
+    /// call instruction for the others.
+    pub pc: u32,
+    /// The function containing the program counter, once symbolized.
+    pub function: Option<String>,
+    /// The source file of the program counter, once symbolized.
+    pub file: Option<String>,
+    /// The source line of the program counter, once symbolized.
+    pub line: Option<u32>,
+}
+
+impl BacktraceFrame {
+    fn new(pc: u32) -> Self {
+        Self { pc, function: None, file: None, line: None }
+    }
+}
+
+/// The call stack of a guest program, from the innermost frame to the outermost one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuestBacktrace {
+    /// The frames of the call stack.
+    pub frames: Vec<BacktraceFrame>,
+}
+
+impl GuestBacktrace {
+    /// Resolve the frames to functions with the symbol table of `elf`, and to source locations
+    /// with its DWARF line info if any.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the ELF, its symbol table or its DWARF sections can't
+    /// be parsed.
+    pub fn symbolize(&mut self, elf: &[u8]) -> Result<()> {
+        let mut functions = function_symbols(elf)?;
+        functions.sort_by_key(|function| function.start);
+
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(elf)
+            .map_err(|err| anyhow!("Elf parse error: {err}"))?;
+        let dwarf = gimli::Dwarf::load(|id| -> Result<_, gimli::Error> {
+            // Missing and compressed sections are treated as empty.
+            let data = elf
+                .section_header_by_name(id.name())
+                .ok()
+                .flatten()
+                .and_then(|header| elf.section_data(&header).ok())
+                .filter(|(_, compression)| compression.is_none())
+                .map_or(&[][..], |(data, _)| data);
+            Ok(EndianSlice::new(data, RunTimeEndian::Little))
+        })
+        .map_err(|err| anyhow!("DWARF parse error: {err}"))?;
+        let context = addr2line::Context::from_dwarf(dwarf)
+            .map_err(|err| anyhow!("DWARF parse error: {err}"))?;
+
+        for frame in &mut self.frames {
+            let idx = functions.partition_point(|function| function.start <= frame.pc);
+            frame.function = idx
+                .checked_sub(1)
+                .map(|idx| &functions[idx])
+                .filter(|function| frame.pc < function.end)
+                .map(|function| function.name.clone());
+            if let Ok(Some(location)) = context.find_location(u64::from(frame.pc)) {
+                frame.file = location.file.map(str::to_string);
+                frame.line = location.line;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for GuestBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, frame) in self.frames.iter().enumerate() {
+            let function = frame.function.as_deref().unwrap_or("<unknown>");
+            writeln!(f, "{idx:>4}: {:#010x} - {function}", frame.pc)?;
+            if let Some(file) = &frame.file {
+                match frame.line {
+                    Some(line) => writeln!(f, "{:>6}at {file}:{line}", "")?,
+                    None => writeln!(f, "{:>6}at {file}", "")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Instruction, Opcode};
+
+    use super::{BacktraceFrame, GuestBacktrace, ShadowStack};
+
+    #[test]
+    fn test_shadow_stack() {
+        let nop = Instruction::new(Opcode::ADD, 0, 0, 0, false, true);
+        let jal = Instruction::new(Opcode::Jumpi, 31, 0x200, 0, true, true);
+        let jalr = Instruction::new(Opcode::Jump, 31, 2, 0, false, true);
+        let jr_ra = Instruction::new(Opcode::Jump, 0, 31, 0, false, true);
+
+        let mut stack = ShadowStack::new();
+        for (pc, instruction) in [
+            (0x100, jal),
+            (0x104, nop),
+            (0x200, jalr),
+            (0x204, nop),
+            (0x300, jr_ra),
+            (0x304, nop),
+            (0x208, jalr),
+            (0x20c, nop),
+            (0x300, nop),
+        ] {
+            stack.record(pc, &instruction);
+        }
+
+        let pcs = stack.backtrace(0x304).frames.iter().map(|frame| frame.pc).collect::<Vec<_>>();
+        assert_eq!(pcs, vec![0x304, 0x208, 0x100]);
+    }
+
+    #[test]
+    fn test_display() {
+        let backtrace = GuestBacktrace {
+            frames: vec![
+                BacktraceFrame {
+                    pc: 0x1000,
+                    function: Some("panic".to_string()),
+                    file: Some("src/main.rs".to_string()),
+                    line: Some(3),
+                },
+                BacktraceFrame { pc: 0x2000, function: None, file: None, line: None },
+            ],
+        };
+        assert_eq!(
+            backtrace.to_string(),
+            "   0: 0x00001000 - panic\n      at src/main.rs:3\n   1: 0x00002000 - <unknown>\n"
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The opt-in memory-safety checks of the [`Executor`](crate::Executor).
+///
+/// The checks turn the memory accesses which would otherwise silently corrupt the program, and
+/// usually only surface as a failing proof, into an
+/// [`ExecutionError::MemoryViolation`](crate::ExecutionError::MemoryViolation). They slow down the
+/// execution, so are meant for development.
+///
+/// The stores of the syscalls are checked too: the buffer of a Linux `read`, the words written by
+/// `hint_read` and by the precompiles. A syscall always runs to completion, and its first violation
+/// is reported at the `SYSCALL` instruction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryChecks {
+    /// The lowest address the stack pointer may reach, if any.
+    pub stack_guard: Option<u32>,
+}
+
+impl MemoryChecks {
+    /// Check the loads and stores of the program.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also check that the stack pointer stays above `stack_guard`.
+    #[must_use]
+    pub fn stack_guard(mut self, stack_guard: u32) -> Self {
+        self.stack_guard = Some(stack_guard);
+        self
+    }
+}
+
+/// A memory access caught by the [`MemoryChecks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MemoryViolationKind {
+    /// A load from the null page.
+    NullRead,
+    /// A store to the null page.
+    NullWrite,
+    /// A store to an executable segment of the program.
+    WriteToExecutable,
+    /// A store to a read-only segment of the program.
+    WriteToReadOnly,
+    /// The stack pointer went below the stack guard.
+    StackOverflow,
+}
+
+impl fmt::Display for MemoryViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NullRead => write!(f, "read from the null page"),
+            Self::NullWrite => write!(f, "write to the null page"),
+            Self::WriteToExecutable => write!(f, "write to an executable segment"),
+            Self::WriteToReadOnly => write!(f, "write to a read-only segment"),
+            Self::StackOverflow => write!(f, "stack overflow"),
+        }
+    }
+}
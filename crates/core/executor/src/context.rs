@@ -1,10 +1,14 @@
 use core::mem::take;
+use std::sync::{Arc, RwLock};
 
 use hashbrown::HashMap;
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
-    hook::{hookify, BoxedHook, HookEnv, HookRegistry},
+    hook::{hookify, typed_hook, typed_hook_fd, BoxedHook, HookEnv, HookRegistry},
+    preimage::PreimageOracle,
     subproof::SubproofVerifier,
+    MemoryChecks,
 };
 
 /// Context to run a program inside Ziren.
@@ -23,6 +27,27 @@ pub struct ZKMContext<'a> {
 
     /// Skip deferred proof verification.
     pub skip_deferred_proof_verification: bool,
+
+    /// The preimage oracle of the Cannon-compatible execution mode.
+    ///
+    /// Note: `None` disables the emulation of Linux syscalls.
+    pub preimage_oracle: Option<Arc<dyn PreimageOracle>>,
+
+    /// The environment variables of the program, read with `std::env::var` in the zkVM.
+    pub env: HashMap<String, String>,
+
+    /// The seed of the random number generator of the program.
+    ///
+    /// Note: `None` uses a fixed seed.
+    pub rng_seed: Option<[u8; 32]>,
+
+    /// The memory-safety checks of the program.
+    ///
+    /// Note: `None` disables the checks.
+    pub memory_checks: Option<MemoryChecks>,
+
+    /// Whether to attach a backtrace of the guest to the execution errors.
+    pub backtraces: bool,
 }
 
 /// A builder for [`ZKMContext`].
@@ -30,9 +55,15 @@ pub struct ZKMContext<'a> {
 pub struct ZKMContextBuilder<'a> {
     no_default_hooks: bool,
     hook_registry_entries: Vec<(u32, BoxedHook<'a>)>,
+    typed_hook_names: HashMap<u32, String>,
     subproof_verifier: Option<&'a dyn SubproofVerifier>,
     max_cycles: Option<u64>,
     skip_deferred_proof_verification: bool,
+    preimage_oracle: Option<Arc<dyn PreimageOracle>>,
+    env: HashMap<String, String>,
+    rng_seed: Option<[u8; 32]>,
+    memory_checks: Option<MemoryChecks>,
+    backtraces: bool,
 }
 
 impl<'a> ZKMContext<'a> {
@@ -69,14 +100,25 @@ impl<'a> ZKMContextBuilder<'a> {
                 table.extend(take(&mut self.hook_registry_entries));
                 HookRegistry { table }
             });
+        self.typed_hook_names.clear();
         let subproof_verifier = take(&mut self.subproof_verifier);
         let cycle_limit = take(&mut self.max_cycles);
         let skip_deferred_proof_verification = take(&mut self.skip_deferred_proof_verification);
+        let preimage_oracle = take(&mut self.preimage_oracle);
+        let env = take(&mut self.env);
+        let rng_seed = take(&mut self.rng_seed);
+        let memory_checks = take(&mut self.memory_checks);
+        let backtraces = take(&mut self.backtraces);
         ZKMContext {
             hook_registry,
             subproof_verifier,
             max_cycles: cycle_limit,
             skip_deferred_proof_verification,
+            preimage_oracle,
+            env,
+            rng_seed,
+            memory_checks,
+            backtraces,
         }
     }
 
@@ -94,6 +136,31 @@ impl<'a> ZKMContextBuilder<'a> {
         self
     }
 
+    /// Add a typed runtime hook into the context, registered under `name`.
+    ///
+    /// Typed hooks may be invoked from within Ziren with
+    /// `zkm_zkvm::hook::call::<Req, Resp>(name, &req)`, which writes the request to the file
+    /// descriptor [`typed_hook_fd`] allocates for `name` and reads back the response. Both are
+    /// encoded with `bincode`. The execution fails with
+    /// [`ExecutionError::HookFailed`](crate::ExecutionError::HookFailed) if the request cannot be
+    /// decoded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file descriptor of `name` collides with that of another typed hook.
+    pub fn typed_hook<Req: DeserializeOwned, Resp: Serialize>(
+        &mut self,
+        name: &str,
+        f: impl Fn(Req) -> Resp + Send + Sync + 'a,
+    ) -> &mut Self {
+        let fd = typed_hook_fd(name);
+        if let Some(other) = self.typed_hook_names.insert(fd, name.to_string()) {
+            assert_eq!(other, name, "typed hooks `{other}` and `{name}` have the same fd {fd:#x}");
+        }
+        self.hook_registry_entries.push((fd, Arc::new(RwLock::new(typed_hook(name, f)))));
+        self
+    }
+
     /// Avoid registering the default hooks in the runtime.
     ///
     /// It is not necessary to call this to override hooks --- instead, simply
@@ -122,11 +189,68 @@ impl<'a> ZKMContextBuilder<'a> {
         self.skip_deferred_proof_verification = skip;
         self
     }
+
+    /// Run the program in the Cannon-compatible execution mode, answering its preimage requests
+    /// with `oracle`.
+    ///
+    /// In this mode, syscalls with a MIPS o32 Linux syscall number are emulated the way Optimism's
+    /// Cannon does, see [`crate::syscalls::linux`].
+    pub fn preimage_oracle(&mut self, oracle: impl PreimageOracle + 'static) -> &mut Self {
+        self.preimage_oracle = Some(Arc::new(oracle));
+        self
+    }
+
+    /// Set the environment variable `key` of the program to `value`.
+    ///
+    /// The variables are read with `std::env::var` in the zkVM. Like the hints, they are not
+    /// constrained, so the program must commit them to its public values if the verifier needs to
+    /// know them.
+    pub fn env(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.env.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the environment variables of the program. See [`Self::env`].
+    pub fn envs<K: Into<String>, V: Into<String>>(
+        &mut self,
+        vars: impl IntoIterator<Item = (K, V)>,
+    ) -> &mut Self {
+        self.env.extend(vars.into_iter().map(|(key, value)| (key.into(), value.into())));
+        self
+    }
+
+    /// Set the seed of the random number generator of the program, which backs `sys_rand` and
+    /// `getrandom` in the zkVM.
+    ///
+    /// The seed is not constrained, so the program must commit it to its public values with
+    /// `zkm_zkvm::env::commit_rng_seed` if the verifier needs to know it.
+    pub fn rng_seed(&mut self, seed: [u8; 32]) -> &mut Self {
+        self.rng_seed = Some(seed);
+        self
+    }
+
+    /// Enable the memory-safety checks of the program, see [`MemoryChecks`].
+    pub fn memory_checks(&mut self, checks: MemoryChecks) -> &mut Self {
+        self.memory_checks = Some(checks);
+        self
+    }
+
+    /// Attach a backtrace of the guest to the execution errors, see
+    /// [`ExecutionError::WithBacktrace`](crate::ExecutionError::WithBacktrace).
+    ///
+    /// The backtrace only covers the calls made since the execution started, so an execution
+    /// resumed from a checkpoint loses the frames of the calls made before the checkpoint.
+    pub fn backtraces(&mut self) -> &mut Self {
+        self.backtraces = true;
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{subproof::NoOpSubproofVerifier, ZKMContext};
+    use crate::{
+        hook::typed_hook_fd, subproof::NoOpSubproofVerifier, KvPreimageOracle, ZKMContext,
+    };
 
     #[test]
     fn defaults() {
@@ -151,6 +275,13 @@ mod tests {
         assert!(hook_registry.unwrap().table.contains_key(&30));
     }
 
+    #[test]
+    fn with_typed_hook() {
+        let ZKMContext { hook_registry, .. } =
+            ZKMContext::builder().typed_hook("square", |x: u32| x * x).build();
+        assert!(hook_registry.unwrap().table.contains_key(&typed_hook_fd("square")));
+    }
+
     #[test]
     fn without_default_hooks_with_custom_hook() {
         let ZKMContext { hook_registry, .. } =
@@ -165,4 +296,24 @@ mod tests {
             ZKMContext::builder().subproof_verifier(&verifier).build();
         assert!(subproof_verifier.is_some());
     }
+
+    #[test]
+    fn env_and_rng_seed() {
+        let ZKMContext { env, rng_seed, .. } = ZKMContext::builder()
+            .env("A", "1")
+            .envs([("B", "2"), ("A", "3")])
+            .rng_seed([7; 32])
+            .build();
+        assert_eq!(env.len(), 2);
+        assert_eq!(env["A"], "3");
+        assert_eq!(env["B"], "2");
+        assert_eq!(rng_seed, Some([7; 32]));
+    }
+
+    #[test]
+    fn preimage_oracle() {
+        let ZKMContext { preimage_oracle, .. } =
+            ZKMContext::builder().preimage_oracle(KvPreimageOracle::new()).build();
+        assert!(preimage_oracle.is_some());
+    }
 }
@@ -75,6 +75,14 @@ pub fn estimate_mips_lde_size(
     cells += (num_events_per_air[MipsAirId::MiscInstrs]).next_power_of_two()
         * costs_per_air[&MipsAirId::MiscInstrs];
 
+    // Compute the floating-point chip contributions.
+    cells += (num_events_per_air[MipsAirId::FpuArith]).next_power_of_two()
+        * costs_per_air[&MipsAirId::FpuArith];
+    cells += (num_events_per_air[MipsAirId::FpuCompare]).next_power_of_two()
+        * costs_per_air[&MipsAirId::FpuCompare];
+    cells += (num_events_per_air[MipsAirId::FpuMove]).next_power_of_two()
+        * costs_per_air[&MipsAirId::FpuMove];
+
     // Compute the cloclz chip contribution.
     cells += (num_events_per_air[MipsAirId::CloClz]).next_power_of_two()
         * costs_per_air[&MipsAirId::CloClz];
@@ -139,7 +147,9 @@ pub fn estimate_mips_event_counts(
         + opcode_counts[Opcode::BGTZ]
         + opcode_counts[Opcode::BGEZ]
         + opcode_counts[Opcode::BLTZ]
-        + opcode_counts[Opcode::BLEZ];
+        + opcode_counts[Opcode::BLEZ]
+        + opcode_counts[Opcode::BGEZAL]
+        + opcode_counts[Opcode::BLTZAL];
 
     // Compute the number of events in the jump chip.
     events_counts[MipsAirId::Jump] = opcode_counts[Opcode::Jump]
@@ -168,11 +178,59 @@ pub fn estimate_mips_event_counts(
         + opcode_counts[Opcode::SEXT]
         + opcode_counts[Opcode::MADDU]
         + opcode_counts[Opcode::MSUBU]
+        + opcode_counts[Opcode::MADD]
+        + opcode_counts[Opcode::MSUB]
         + opcode_counts[Opcode::WSBH]
         + opcode_counts[Opcode::TEQ]
+        + opcode_counts[Opcode::TNE]
+        + opcode_counts[Opcode::TGE]
+        + opcode_counts[Opcode::TGEU]
+        + opcode_counts[Opcode::TLT]
+        + opcode_counts[Opcode::TLTU]
         + opcode_counts[Opcode::MEQ]
         + opcode_counts[Opcode::MNE];
 
+    // Compute the number of events in the floating-point chips.
+    events_counts[MipsAirId::FpuArith] = opcode_counts[Opcode::FADD_S]
+        + opcode_counts[Opcode::FSUB_S]
+        + opcode_counts[Opcode::FMUL_S]
+        + opcode_counts[Opcode::FDIV_S]
+        + opcode_counts[Opcode::FSQRT_S]
+        + opcode_counts[Opcode::FADD_D]
+        + opcode_counts[Opcode::FSUB_D]
+        + opcode_counts[Opcode::FMUL_D]
+        + opcode_counts[Opcode::FDIV_D]
+        + opcode_counts[Opcode::FSQRT_D]
+        + opcode_counts[Opcode::CVT_S_D]
+        + opcode_counts[Opcode::CVT_D_S]
+        + opcode_counts[Opcode::CVT_S_W]
+        + opcode_counts[Opcode::CVT_D_W]
+        + opcode_counts[Opcode::CVT_W_S]
+        + opcode_counts[Opcode::CVT_W_D]
+        + opcode_counts[Opcode::TRUNC_W_S]
+        + opcode_counts[Opcode::TRUNC_W_D];
+    events_counts[MipsAirId::FpuCompare] = opcode_counts[Opcode::C_UN_S]
+        + opcode_counts[Opcode::C_EQ_S]
+        + opcode_counts[Opcode::C_UEQ_S]
+        + opcode_counts[Opcode::C_OLT_S]
+        + opcode_counts[Opcode::C_ULT_S]
+        + opcode_counts[Opcode::C_OLE_S]
+        + opcode_counts[Opcode::C_ULE_S]
+        + opcode_counts[Opcode::C_UN_D]
+        + opcode_counts[Opcode::C_EQ_D]
+        + opcode_counts[Opcode::C_UEQ_D]
+        + opcode_counts[Opcode::C_OLT_D]
+        + opcode_counts[Opcode::C_ULT_D]
+        + opcode_counts[Opcode::C_OLE_D]
+        + opcode_counts[Opcode::C_ULE_D];
+    events_counts[MipsAirId::FpuMove] = opcode_counts[Opcode::LDC1]
+        + opcode_counts[Opcode::SDC1]
+        + opcode_counts[Opcode::MOV_D]
+        + opcode_counts[Opcode::ABS_D]
+        + opcode_counts[Opcode::NEG_D]
+        + opcode_counts[Opcode::MOVN_D]
+        + opcode_counts[Opcode::MOVZ_D];
+
     // Compute the number of events in the auipc chip.
     events_counts[MipsAirId::CloClz] = opcode_counts[Opcode::CLO] + opcode_counts[Opcode::CLZ];
 
@@ -215,6 +273,9 @@ pub fn pad_mips_event_counts(
         MipsAirId::MemoryInstrs => *v += 8 * num_cycles,
         MipsAirId::MiscInstrs => *v += 8 * num_cycles, // TODO: Check this value.
         MipsAirId::CloClz => *v += 3 * num_cycles,     // TODO: Check this value.
+        MipsAirId::FpuArith => *v += num_cycles,
+        MipsAirId::FpuCompare => *v += num_cycles,
+        MipsAirId::FpuMove => *v += num_cycles,
         MipsAirId::SyscallCore => *v += 2 * num_cycles,
         MipsAirId::Global => *v += 64 * num_cycles,
         _ => (),
@@ -1,8 +1,10 @@
 use crate::{
     events::{
-        AluEvent, BranchEvent, CompAluEvent, JumpEvent, MemInstrEvent, MemoryRecord,
+        AluEvent, BranchEvent, CompAluEvent, FpuEvent, JumpEvent, MemInstrEvent, MemoryRecord,
         MemoryWriteRecord, MiscEvent,
     },
+    fpu::{source_format, FpuFormat, INVALID_WORD},
+    sign_extend,
     utils::{get_msb, get_quotient_and_remainder, is_signed_operation},
     Executor, Opcode, DEFAULT_PC_INC, UNUSED_PC,
 };
@@ -179,9 +181,10 @@ pub fn emit_memory_dependencies(
 
 /// Emit the dependencies for branch instructions.
 pub fn emit_branch_dependencies(executor: &mut Executor, event: BranchEvent) {
-    let a_eq_b = event.a == event.b;
-    let a_lt_b = (event.a as i32) < (event.b as i32);
-    let a_gt_b = (event.a as i32) > (event.b as i32);
+    let (src1, src2) = event.compared_values();
+    let a_eq_b = src1 == src2;
+    let a_lt_b = (src1 as i32) < (src2 as i32);
+    let a_gt_b = (src1 as i32) > (src2 as i32);
 
     let lt_comp_event = AluEvent {
         pc: UNUSED_PC,
@@ -189,8 +192,8 @@ pub fn emit_branch_dependencies(executor: &mut Executor, event: BranchEvent) {
         opcode: Opcode::SLT,
         hi: 0,
         a: a_lt_b as u32,
-        b: event.a,
-        c: event.b,
+        b: src1,
+        c: src2,
     };
     let gt_comp_event = AluEvent {
         pc: UNUSED_PC,
@@ -198,18 +201,18 @@ pub fn emit_branch_dependencies(executor: &mut Executor, event: BranchEvent) {
         opcode: Opcode::SLT,
         hi: 0,
         a: a_gt_b as u32,
-        b: event.b,
-        c: event.a,
+        b: src2,
+        c: src1,
     };
     executor.record.lt_events.push(lt_comp_event);
     executor.record.lt_events.push(gt_comp_event);
     let branching = match event.opcode {
         Opcode::BEQ => a_eq_b,
         Opcode::BNE => !a_eq_b,
-        Opcode::BLTZ => a_lt_b,
+        Opcode::BLTZ | Opcode::BLTZAL => a_lt_b,
         Opcode::BLEZ => a_lt_b || a_eq_b,
         Opcode::BGTZ => a_gt_b,
-        Opcode::BGEZ => a_eq_b || a_gt_b,
+        Opcode::BGEZ | Opcode::BGEZAL => a_eq_b || a_gt_b,
         _ => unreachable!(),
     };
     if branching {
@@ -249,8 +252,13 @@ pub fn emit_jump_dependencies(executor: &mut Executor, event: JumpEvent) {
 
 /// Emit the dependencies for misc instructions.
 pub fn emit_misc_dependencies(executor: &mut Executor, event: MiscEvent) {
-    if matches!(event.opcode, Opcode::MADDU | Opcode::MSUBU) {
-        let multiply = event.b as u64 * event.c as u64;
+    if matches!(event.opcode, Opcode::MADDU | Opcode::MSUBU | Opcode::MADD | Opcode::MSUB) {
+        let (opcode, multiply) = match event.opcode {
+            Opcode::MADD | Opcode::MSUB => {
+                (Opcode::MULT, ((event.b as i32) as i64 * (event.c as i32) as i64) as u64)
+            }
+            _ => (Opcode::MULTU, event.b as u64 * event.c as u64),
+        };
         let mul_hi = (multiply >> 32) as u32;
         let mul_lo = multiply as u32;
         let mul_event = CompAluEvent {
@@ -258,7 +266,7 @@ pub fn emit_misc_dependencies(executor: &mut Executor, event: MiscEvent) {
             shard: 0,
             pc: UNUSED_PC,
             next_pc: UNUSED_PC + DEFAULT_PC_INC,
-            opcode: Opcode::MULTU,
+            opcode,
             hi: mul_hi,
             a: mul_lo,
             b: event.b,
@@ -267,6 +275,32 @@ pub fn emit_misc_dependencies(executor: &mut Executor, event: MiscEvent) {
             hi_record: MemoryWriteRecord::default(),
         };
         executor.record.add_mul_event(mul_event);
+    } else if matches!(
+        event.opcode,
+        Opcode::TEQ | Opcode::TGE | Opcode::TGEU | Opcode::TLT | Opcode::TLTU
+    ) {
+        // The comparisons showing that the trap is not taken.
+        let opcode = if matches!(event.opcode, Opcode::TGE | Opcode::TLT) {
+            Opcode::SLT
+        } else {
+            Opcode::SLTU
+        };
+        let mut compare = |b: u32, c: u32| {
+            let a = if opcode == Opcode::SLT { (b as i32) < (c as i32) } else { b < c };
+            executor.record.lt_events.push(AluEvent {
+                pc: UNUSED_PC,
+                next_pc: UNUSED_PC + DEFAULT_PC_INC,
+                opcode,
+                hi: 0,
+                a: a as u32,
+                b,
+                c,
+            });
+        };
+        compare(event.a, event.b);
+        if event.opcode == Opcode::TEQ {
+            compare(event.b, event.a);
+        }
     } else if matches!(event.opcode, Opcode::EXT) {
         let lsb = event.c & 0x1f;
         let msbd = event.c >> 5;
@@ -356,3 +390,48 @@ pub fn emit_misc_dependencies(executor: &mut Executor, event: MiscEvent) {
         executor.record.shift_right_events.push(ror_event2);
     }
 }
+
+/// Emits the dependencies for FPU operations.
+pub fn emit_fpu_dependencies(executor: &mut Executor, event: FpuEvent) {
+    // The negation `0 - c`, used to take the absolute value of a word.
+    let negation = |c: u32| AluEvent {
+        pc: UNUSED_PC,
+        next_pc: UNUSED_PC + DEFAULT_PC_INC,
+        opcode: Opcode::SUB,
+        hi: 0,
+        a: 0u32.wrapping_sub(c),
+        b: 0,
+        c,
+    };
+
+    match event.opcode {
+        Opcode::LDC1 | Opcode::SDC1 => {
+            let offset = sign_extend::<16>(event.c & 0xffff);
+            let add_event = AluEvent {
+                pc: UNUSED_PC,
+                next_pc: UNUSED_PC + DEFAULT_PC_INC,
+                opcode: Opcode::ADD,
+                hi: 0,
+                a: event.b.wrapping_add(offset),
+                b: event.b,
+                c: offset,
+            };
+            executor.record.add_events.push(add_event);
+        }
+        Opcode::CVT_S_W | Opcode::CVT_D_W => {
+            let word = FpuEvent::pair_value(&event.fs_access) as u32;
+            if (word as i32) < 0 {
+                executor.record.sub_events.push(negation(word));
+            }
+        }
+        Opcode::CVT_W_S | Opcode::CVT_W_D | Opcode::TRUNC_W_S | Opcode::TRUNC_W_D => {
+            let sign_bit = if source_format(event.opcode) == FpuFormat::Double { 63 } else { 31 };
+            let is_negative = (FpuEvent::pair_value(&event.fs_access) >> sign_bit) & 1 == 1;
+            let result = FpuEvent::pair_value(&event.fd_access) as u32;
+            if is_negative && result != INVALID_WORD {
+                executor.record.sub_events.push(negation(result.wrapping_neg()));
+            }
+        }
+        _ => {}
+    }
+}
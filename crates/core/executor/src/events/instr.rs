@@ -1,3 +1,4 @@
+use super::MemoryReadRecord;
 use super::MemoryRecordEnum;
 use super::MemoryWriteRecord;
 use crate::Opcode;
@@ -190,6 +191,17 @@ impl BranchEvent {
     ) -> Self {
         Self { pc, next_pc, next_next_pc, opcode, a, b, c }
     }
+
+    /// The values compared by the branch.
+    ///
+    /// Branches that link hold the return address in `a`, and compare `b` to zero.
+    #[must_use]
+    pub fn compared_values(&self) -> (u32, u32) {
+        match self.opcode {
+            Opcode::BGEZAL | Opcode::BLTZAL => (self.b, 0),
+            _ => (self.a, self.b),
+        }
+    }
 }
 
 /// Jump Instruction Event.
@@ -280,3 +292,43 @@ impl MiscEvent {
         Self { clk, shard, pc, next_pc, opcode, op_a, a, b, c, prev_a, hi_record }
     }
 }
+
+/// Floating-Point Unit (FPU) Event.
+///
+/// This object encapsulated the information needed to prove a MIPS COP1 operation. The operands of
+/// an FPU instruction are register pairs, so each access holds the even register first and, for
+/// double precision, the odd register second.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[repr(C)]
+pub struct FpuEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The program counter.
+    pub pc: u32,
+    pub next_pc: u32,
+    /// The opcode.
+    pub opcode: Opcode,
+    /// The second operand value.
+    pub b: u32,
+    /// The third operand value.
+    pub c: u32,
+    /// The reads of `fs`.
+    pub fs_access: [Option<MemoryReadRecord>; 2],
+    /// The reads of `ft`, or of the condition of a conditional move.
+    pub ft_access: [Option<MemoryReadRecord>; 2],
+    /// The writes of `fd`, or of the condition code of a comparison.
+    pub fd_access: [Option<MemoryWriteRecord>; 2],
+    /// The memory accesses of `ldc1` and `sdc1`.
+    pub mem_access: [Option<MemoryRecordEnum>; 2],
+}
+
+impl FpuEvent {
+    /// Get the value of a register pair access as a double word.
+    #[must_use]
+    pub fn pair_value<T: Copy + Into<MemoryRecordEnum>>(access: &[Option<T>; 2]) -> u64 {
+        let word = |record: Option<T>| record.map_or(0, |r| r.into().value());
+        u64::from(word(access[0])) | (u64::from(word(access[1])) << 32)
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{
+    memory::{MemoryReadRecord, MemoryWriteRecord},
+    MemoryLocalEvent,
+};
+
+/// The number of rounds of the BLAKE3 compression function.
+pub const ROUND_COUNT: usize = 7;
+
+/// The number of `G` functions applied in each round.
+pub const OPERATION_COUNT: usize = 8;
+
+/// The number of state words read and written by each `G` function.
+pub const NUM_STATE_WORDS_PER_CALL: usize = 4;
+
+/// The number of message words mixed in by each `G` function.
+pub const NUM_MSG_WORDS_PER_CALL: usize = 2;
+
+/// The number of words in the state.
+pub const BLAKE3_STATE_SIZE: usize = 16;
+
+/// The number of words in a message block.
+pub const BLAKE3_MSG_SIZE: usize = 16;
+
+/// The indices of the state words each `G` function of a round operates on.
+pub const G_INDEX: [[usize; NUM_STATE_WORDS_PER_CALL]; OPERATION_COUNT] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+/// The order in which the message words are mixed in during each round.
+pub const MSG_SCHEDULE: [[usize; BLAKE3_MSG_SIZE]; ROUND_COUNT] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8],
+    [3, 4, 10, 12, 13, 2, 7, 14, 6, 5, 9, 0, 11, 15, 8, 1],
+    [10, 7, 12, 9, 14, 3, 13, 15, 4, 0, 11, 2, 5, 8, 1, 6],
+    [12, 13, 9, 11, 15, 10, 14, 8, 7, 2, 5, 3, 0, 1, 6, 4],
+    [9, 14, 11, 5, 8, 12, 15, 1, 13, 3, 0, 10, 2, 6, 4, 7],
+    [11, 15, 5, 0, 1, 9, 8, 6, 14, 10, 2, 12, 3, 4, 7, 13],
+];
+
+/// The BLAKE3 `G` function, mixing the message words `x` and `y` into the state words `input`.
+#[must_use]
+pub fn g_func(
+    input: [u32; NUM_STATE_WORDS_PER_CALL],
+    msg: [u32; NUM_MSG_WORDS_PER_CALL],
+) -> [u32; 4] {
+    let [mut a, mut b, mut c, mut d] = input;
+    let [x, y] = msg;
+    a = a.wrapping_add(b).wrapping_add(x);
+    d = (d ^ a).rotate_right(16);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(12);
+    a = a.wrapping_add(b).wrapping_add(y);
+    d = (d ^ a).rotate_right(8);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(7);
+    [a, b, c, d]
+}
+
+/// BLAKE3 Compress Inner Event.
+///
+/// This event is emitted when the rounds of the BLAKE3 compression function are applied to a
+/// state. Each `G` function reads and writes its four state words and reads its two message words
+/// at its own clock cycle, starting at `clk`.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Blake3CompressInnerEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the state.
+    pub state_ptr: u32,
+    /// The pointer to the message block.
+    pub message_ptr: u32,
+    /// The memory records for the state, per round and per `G` function.
+    pub state_writes: Vec<[MemoryWriteRecord; NUM_STATE_WORDS_PER_CALL]>,
+    /// The memory records for the message, per round and per `G` function.
+    pub message_reads: Vec<[MemoryReadRecord; NUM_MSG_WORDS_PER_CALL]>,
+    /// The local memory access records.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
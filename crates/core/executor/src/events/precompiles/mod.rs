@@ -1,7 +1,9 @@
+mod blake3;
 mod ec;
 mod edwards;
 mod fptower;
 mod keccak_sponge;
+mod poseidon2;
 mod sha256_compress;
 mod sha256_extend;
 mod u256x2048_mul;
@@ -9,11 +11,13 @@ mod uint256;
 
 use super::{MemoryLocalEvent, SyscallEvent};
 use crate::syscalls::SyscallCode;
+pub use blake3::*;
 pub use ec::*;
 pub use edwards::*;
 pub use fptower::*;
 use hashbrown::HashMap;
 pub use keccak_sponge::*;
+pub use poseidon2::*;
 use serde::{Deserialize, Serialize};
 pub use sha256_compress::*;
 pub use sha256_extend::*;
@@ -30,6 +34,8 @@ pub enum PrecompileEvent {
     ShaCompress(ShaCompressEvent),
     /// Keccak sponge precompile event.
     KeccakSponge(KeccakSpongeEvent),
+    /// Blake3 compress inner precompile event.
+    Blake3CompressInner(Blake3CompressInnerEvent),
     /// Edwards curve add precompile event.
     EdAdd(EllipticCurveAddEvent),
     /// Edwards curve decompress precompile event.
@@ -58,6 +64,8 @@ pub enum PrecompileEvent {
     Bn254Fp2AddSub(Fp2AddSubEvent),
     /// Bn254 quadratic field mul precompile event.
     Bn254Fp2Mul(Fp2MulEvent),
+    /// Bn254 sextic field mul precompile event.
+    Bn254Fp6Mul(Fp6MulEvent),
     /// Bls12-381 curve add precompile event.
     Bls12381Add(EllipticCurveAddEvent),
     /// Bls12-381 curve double precompile event.
@@ -70,10 +78,14 @@ pub enum PrecompileEvent {
     Bls12381Fp2AddSub(Fp2AddSubEvent),
     /// Bls12-381 quadratic field mul precompile event.
     Bls12381Fp2Mul(Fp2MulEvent),
+    /// Bls12-381 sextic field mul precompile event.
+    Bls12381Fp6Mul(Fp6MulEvent),
     /// Uint256 mul precompile event.
     Uint256Mul(Uint256MulEvent),
     /// U256XU2048 mul precompile event.
     U256xU2048Mul(U256xU2048MulEvent),
+    /// Poseidon2 permute precompile event.
+    Poseidon2Permute(Poseidon2PermuteEvent),
 }
 
 /// Trait to retrieve all the local memory events from a vec of precompile events.
@@ -97,6 +109,9 @@ impl PrecompileLocalMemory for Vec<(SyscallEvent, PrecompileEvent)> {
                 PrecompileEvent::KeccakSponge(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::Blake3CompressInner(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
                 PrecompileEvent::EdDecompress(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
@@ -125,6 +140,9 @@ impl PrecompileLocalMemory for Vec<(SyscallEvent, PrecompileEvent)> {
                 PrecompileEvent::U256xU2048Mul(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::Poseidon2Permute(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
                 PrecompileEvent::Bls12381Fp(e) | PrecompileEvent::Bn254Fp(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
@@ -134,6 +152,9 @@ impl PrecompileLocalMemory for Vec<(SyscallEvent, PrecompileEvent)> {
                 PrecompileEvent::Bls12381Fp2Mul(e) | PrecompileEvent::Bn254Fp2Mul(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::Bls12381Fp6Mul(e) | PrecompileEvent::Bn254Fp6Mul(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
             }
         }
 
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{memory::MemoryWriteRecord, MemoryLocalEvent};
+
+/// The number of KoalaBear elements in the Poseidon2 state.
+pub const POSEIDON2_WIDTH: usize = 16;
+
+/// Poseidon2 Permute Event.
+///
+/// This event is emitted when a Poseidon2 permutation is performed.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Poseidon2PermuteEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the state.
+    pub state_ptr: u32,
+    /// The input state as a list of words.
+    pub state: Vec<u32>,
+    /// The memory records for the state.
+    pub state_memory_records: Vec<MemoryWriteRecord>,
+    /// The local memory access records.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
@@ -9,29 +9,39 @@ use enum_map::EnumMap;
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use zkm_stark::{shape::Shape, ZKMCoreOpts};
+use zkm_stark::{shape::Shape, MachineRecord, ZKMCoreOpts};
 
 use crate::{
+    backtrace::{GuestBacktrace, ShadowStack},
     context::ZKMContext,
     dependencies::{
         emit_branch_dependencies, emit_cloclz_dependencies, emit_divrem_dependencies,
-        emit_jump_dependencies, emit_memory_dependencies, emit_misc_dependencies,
+        emit_fpu_dependencies, emit_jump_dependencies, emit_memory_dependencies,
+        emit_misc_dependencies,
     },
     estimate_mips_event_counts, estimate_mips_lde_size,
     events::{
-        AluEvent, BranchEvent, CompAluEvent, CpuEvent, JumpEvent, MemInstrEvent,
+        AluEvent, BranchEvent, CompAluEvent, CpuEvent, FpuEvent, JumpEvent, MemInstrEvent,
         MemoryAccessPosition, MemoryInitializeFinalizeEvent, MemoryLocalEvent, MemoryReadRecord,
         MemoryRecord, MemoryRecordEnum, MemoryWriteRecord, MiscEvent, SyscallEvent,
     },
+    fpu::{fpu_arith, fpu_compare, fpu_sign_move, is_binary, result_format, source_format},
     hook::{HookEnv, HookRegistry},
     memory::{Entry, PagedMemory},
     pad_mips_event_counts,
+    preimage::PreimageOracle,
+    profiler::Profiler,
     record::{ExecutionRecord, MemoryAccessRecord},
     sign_extend,
     state::{ExecutionState, ForkState},
     subproof::SubproofVerifier,
-    syscalls::{default_syscall_map, Syscall, SyscallCode, SyscallContext},
-    ExecutionReport, Instruction, MipsAirId, Opcode, Program, Register,
+    syscalls::{
+        default_syscall_map,
+        linux::{self, LinuxSyscallCode},
+        Syscall, SyscallCode, SyscallContext,
+    },
+    ExecutionReport, Instruction, MemoryChecks, MemoryViolationKind, MipsAirId, Opcode, Program,
+    Register, PAGE_SIZE,
 };
 
 /// The maximum number of instructions in a program.
@@ -151,6 +161,9 @@ pub struct Executor<'a> {
     /// Registry of hooks, to be invoked by writing to certain file descriptors.
     pub hook_registry: HookRegistry<'a>,
 
+    /// The preimage oracle of the Cannon-compatible execution mode, which is enabled if it is set.
+    pub preimage_oracle: Option<Arc<dyn PreimageOracle>>,
+
     /// The maximal shapes for the program.
     pub maximal_shapes: Option<Vec<Shape<MipsAirId>>>,
 
@@ -165,6 +178,22 @@ pub struct Executor<'a> {
 
     /// The maximum LDE size to allow.
     pub lde_size_threshold: u64,
+
+    /// The profiler sampling the guest call stack, written to its output when execution ends.
+    pub profiler: Option<Profiler>,
+
+    /// The memory-safety checks of the program, if enabled.
+    pub memory_checks: Option<MemoryChecks>,
+
+    /// The first store of the current syscall caught by the [`MemoryChecks`], and its address.
+    syscall_memory_violation: Option<(MemoryViolationKind, u32)>,
+
+    /// The error of the current syscall, returned once it has executed.
+    pub(crate) syscall_error: Option<ExecutionError>,
+
+    /// The shadow call stack of the guest, attached to the execution errors as a backtrace if
+    /// enabled.
+    pub shadow_stack: Option<ShadowStack>,
 }
 
 /// The different modes the executor can run in.
@@ -227,6 +256,92 @@ pub enum ExecutionError {
     /// The program ended in unconstrained mode.
     #[error("program ended in unconstrained mode")]
     EndInUnconstrained(),
+
+    /// The execution failed with a trap instruction whose condition is true.
+    #[error("trap {0} taken at pc {1:#x}")]
+    Trap(Opcode, u32),
+
+    /// The connection to the GDB client failed.
+    #[error("gdb connection failed: {0}")]
+    GdbConnection(String),
+
+    /// The preimage oracle does not know the preimage of a key read by the program.
+    #[error("missing preimage for key 0x{0}")]
+    MissingPreimage(String),
+
+    /// The program called a typed hook which is not registered.
+    #[error("no typed hook is registered for fd {0:#x}")]
+    UnknownTypedHook(u32),
+
+    /// A hook failed on the data written by the program.
+    #[error("hook failed: {0}")]
+    HookFailed(String),
+
+    /// The stack grew into the heap of the program.
+    #[error(
+        "stack pointer {sp:#x} is below the end of the heap {heap_end:#x} at pc {pc:#x}, \
+         consider increasing ZKM_STACK_SIZE"
+    )]
+    HeapStackCollision {
+        /// The stack pointer.
+        sp: u32,
+        /// The end of the heap.
+        heap_end: u32,
+        /// The program counter of the instruction which moved the stack pointer.
+        pc: u32,
+    },
+
+    /// The execution failed with a memory access caught by the [`MemoryChecks`].
+    #[error("{kind} at address {addr:#x} at pc {pc:#x}")]
+    MemoryViolation {
+        /// The kind of violation.
+        kind: MemoryViolationKind,
+        /// The accessed address, or the stack pointer for a stack overflow.
+        addr: u32,
+        /// The program counter of the instruction.
+        pc: u32,
+    },
+
+    /// The execution failed with an error, raised in the guest call stack of the backtrace.
+    #[error("{error}\n\nguest backtrace:\n{trace}")]
+    WithBacktrace {
+        /// The error.
+        error: Box<ExecutionError>,
+        /// The backtrace of the guest when the error was raised.
+        trace: GuestBacktrace,
+    },
+}
+
+impl ExecutionError {
+    /// The error, without its backtrace.
+    #[must_use]
+    pub fn inner(&self) -> &ExecutionError {
+        match self {
+            ExecutionError::WithBacktrace { error, .. } => error.inner(),
+            error => error,
+        }
+    }
+
+    /// The backtrace of the guest when the error was raised, if backtraces are enabled.
+    #[must_use]
+    pub fn backtrace(&self) -> Option<&GuestBacktrace> {
+        match self {
+            ExecutionError::WithBacktrace { trace, .. } => Some(trace),
+            _ => None,
+        }
+    }
+
+    /// Symbolize the backtrace of the error with the program `elf`, see
+    /// [`GuestBacktrace::symbolize`].
+    #[must_use]
+    pub fn symbolize(mut self, elf: &[u8]) -> Self {
+        if let ExecutionError::WithBacktrace { trace, .. } = &mut self {
+            if let Err(err) = trace.symbolize(elf) {
+                tracing::warn!("failed to symbolize the guest backtrace: {}", err);
+            }
+        }
+        self
+    }
 }
 
 macro_rules! assert_valid_memory_access {
@@ -275,10 +390,14 @@ impl<'a> Executor<'a> {
         let costs: HashMap<MipsAirId, usize> =
             costs.into_iter().map(|(k, v)| (MipsAirId::from_str(&k).unwrap(), v)).collect();
 
+        let mut state = ExecutionState::new(program.pc_start, program.next_pc);
+        state.env = context.env;
+        state.rng_seed = context.rng_seed;
+
         Self {
             record,
             records: vec![],
-            state: ExecutionState::new(program.pc_start, program.next_pc),
+            state,
             program,
             memory_accesses: MemoryAccessRecord::default(),
             shard_size: (opts.shard_size as u32) * 4,
@@ -297,6 +416,7 @@ impl<'a> Executor<'a> {
             print_report: false,
             subproof_verifier: context.subproof_verifier,
             hook_registry,
+            preimage_oracle: context.preimage_oracle,
             opts,
             max_cycles: context.max_cycles,
             deferred_proof_verification: if context.skip_deferred_proof_verification {
@@ -312,6 +432,36 @@ impl<'a> Executor<'a> {
             shape_check_frequency: 16,
             lde_size_check: false,
             lde_size_threshold: 0,
+            profiler: None,
+            memory_checks: context.memory_checks,
+            syscall_memory_violation: None,
+            syscall_error: None,
+            shadow_stack: context.backtraces.then(ShadowStack::new),
+        }
+    }
+
+    /// Attach a backtrace of the guest to the execution errors.
+    pub fn enable_backtraces(&mut self) {
+        self.shadow_stack = Some(ShadowStack::new());
+    }
+
+    /// Attach the backtrace of the guest to `error`, if backtraces are enabled.
+    fn attach_backtrace(&self, error: ExecutionError) -> ExecutionError {
+        match &self.shadow_stack {
+            Some(shadow_stack) => ExecutionError::WithBacktrace {
+                error: Box::new(error),
+                trace: shadow_stack.backtrace(self.state.pc),
+            },
+            None => error,
+        }
+    }
+
+    /// Enable the [`Profiler`] for the program `elf` if the `ZKM_PROFILE` environment variable
+    /// is set, logging a warning if the profiler can't be created.
+    pub fn maybe_setup_profiler(&mut self, elf: &[u8]) {
+        match Profiler::from_env(elf) {
+            Ok(profiler) => self.profiler = profiler,
+            Err(err) => tracing::warn!("failed to set up the profiler: {}", err),
         }
     }
 
@@ -683,6 +833,7 @@ impl<'a> Executor<'a> {
         record: MemoryAccessRecord,
         exit_code: u32,
         syscall_code: u32,
+        fpu_event: Option<FpuEvent>,
     ) {
         self.emit_cpu(clk, pc, next_pc, next_next_pc, a, b, c, hi_or_prev_a, record, exit_code);
 
@@ -709,6 +860,8 @@ impl<'a> Executor<'a> {
                 hi_or_prev_a.unwrap_or(0),
                 record.hi,
             );
+        } else if instruction.is_fpu_instruction() {
+            self.emit_fpu_event(fpu_event.expect("Must have an FPU event"));
         } else {
             log::info!("wrong {}\n", instruction.opcode);
             unreachable!()
@@ -918,6 +1071,19 @@ impl<'a> Executor<'a> {
         emit_misc_dependencies(self, event);
     }
 
+    /// Emit an FPU event.
+    #[inline]
+    fn emit_fpu_event(&mut self, event: FpuEvent) {
+        if event.opcode.is_fpu_arith() {
+            self.record.fpu_arith_events.push(event);
+        } else if event.opcode.is_fpu_compare() {
+            self.record.fpu_compare_events.push(event);
+        } else {
+            self.record.fpu_move_events.push(event);
+        }
+        emit_fpu_dependencies(self, event);
+    }
+
     #[inline]
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn syscall_event(
@@ -1038,6 +1204,7 @@ impl<'a> Executor<'a> {
         let (a, mut b, mut c): (u32, u32, u32);
         let mut hi_or_prev_a = None;
         let mut syscall_code = 0u32;
+        let mut fpu_event = None;
 
         self.state.next_is_delayslot = false;
 
@@ -1065,7 +1232,12 @@ impl<'a> Executor<'a> {
                 Opcode::BEQ | Opcode::BNE => {
                     self.local_counts.event_counts[Opcode::ADD] += 1;
                 }
-                Opcode::BLTZ | Opcode::BGEZ | Opcode::BLEZ | Opcode::BGTZ => {
+                Opcode::BLTZ
+                | Opcode::BGEZ
+                | Opcode::BLEZ
+                | Opcode::BGTZ
+                | Opcode::BGEZAL
+                | Opcode::BLTZAL => {
                     self.local_counts.event_counts[Opcode::ADD] += 1;
                     self.local_counts.event_counts[Opcode::SLT] += 2;
                 }
@@ -1085,6 +1257,18 @@ impl<'a> Executor<'a> {
                 Opcode::MADDU | Opcode::MSUBU => {
                     self.local_counts.event_counts[Opcode::MULTU] += 1;
                 }
+                Opcode::MADD | Opcode::MSUB => {
+                    self.local_counts.event_counts[Opcode::MULT] += 1;
+                }
+                Opcode::TEQ => {
+                    self.local_counts.event_counts[Opcode::SLTU] += 2;
+                }
+                Opcode::TGEU | Opcode::TLTU => {
+                    self.local_counts.event_counts[Opcode::SLTU] += 1;
+                }
+                Opcode::TGE | Opcode::TLT => {
+                    self.local_counts.event_counts[Opcode::SLT] += 1;
+                }
                 Opcode::EXT => {
                     self.local_counts.event_counts[Opcode::SLL] += 1;
                     self.local_counts.event_counts[Opcode::SRL] += 1;
@@ -1095,6 +1279,17 @@ impl<'a> Executor<'a> {
                     self.local_counts.event_counts[Opcode::SRL] += 1;
                     self.local_counts.event_counts[Opcode::ADD] += 1;
                 }
+                Opcode::LDC1 | Opcode::SDC1 => {
+                    self.local_counts.event_counts[Opcode::ADD] += 1;
+                }
+                Opcode::CVT_S_W
+                | Opcode::CVT_D_W
+                | Opcode::CVT_W_S
+                | Opcode::CVT_W_D
+                | Opcode::TRUNC_W_S
+                | Opcode::TRUNC_W_D => {
+                    self.local_counts.event_counts[Opcode::SUB] += 1;
+                }
                 _ => {}
             };
         }
@@ -1105,84 +1300,102 @@ impl<'a> Executor<'a> {
                 let syscall_id = self.register(Register::V0);
                 c = self.rr(Register::A1, MemoryAccessPosition::C);
                 b = self.rr(Register::A0, MemoryAccessPosition::B);
-                let syscall = SyscallCode::from_u32(syscall_id);
-                let mut prev_a = syscall_id;
 
-                if self.print_report && !self.unconstrained {
-                    self.report.syscall_counts[syscall] += 1;
-                }
+                if let Some(code) = self.linux_syscall_code(syscall_id) {
+                    (a, next_pc, exit_code) = self.execute_linux_syscall(code, b, c)?;
+                    next_next_pc = next_pc.wrapping_add(4);
+                    syscall_code = syscall_id;
+                    hi_or_prev_a = Some(syscall_id);
+                } else {
+                    let syscall = SyscallCode::from_u32(syscall_id);
+                    let mut prev_a = syscall_id;
 
-                // `hint_slice` is allowed in unconstrained mode since it is used to write the hint.
-                // Other syscalls are not allowed because they can lead to non-deterministic
-                // behavior, especially since many syscalls modify memory in place,
-                // which is not permitted in unconstrained mode. This will result in
-                // non-zero memory lookups when generating a proof.
+                    if self.print_report && !self.unconstrained {
+                        self.report.syscall_counts[syscall] += 1;
+                    }
 
-                if self.unconstrained
-                    && (syscall != SyscallCode::EXIT_UNCONSTRAINED && syscall != SyscallCode::WRITE)
-                {
-                    return Err(ExecutionError::InvalidSyscallUsage(syscall_id as u64));
-                }
+                    // `hint_slice` is allowed in unconstrained mode since it is used to write the hint.
+                    // Other syscalls are not allowed because they can lead to non-deterministic
+                    // behavior, especially since many syscalls modify memory in place,
+                    // which is not permitted in unconstrained mode. This will result in
+                    // non-zero memory lookups when generating a proof.
+
+                    if self.unconstrained
+                        && (syscall != SyscallCode::EXIT_UNCONSTRAINED
+                            && syscall != SyscallCode::WRITE)
+                    {
+                        return Err(ExecutionError::InvalidSyscallUsage(syscall_id as u64));
+                    }
 
-                // Update the syscall counts.
-                let syscall_for_count = syscall.count_map();
-                let syscall_count = self.state.syscall_counts.entry(syscall_for_count).or_insert(0);
-                *syscall_count += 1;
-
-                let syscall_impl = self.get_syscall(syscall).cloned();
-                syscall_code = syscall.syscall_id();
-                let mut precompile_rt = SyscallContext::new(self);
-                let (precompile_next_pc, precompile_cycles, returned_exit_code) =
-                    if let Some(syscall_impl) = syscall_impl {
-                        // Executing a syscall optionally returns a value to write to the t0
-                        // register. If it returns None, we just keep the
-                        // syscall_id in t0.
-                        let res = syscall_impl.execute(&mut precompile_rt, syscall, b, c);
-                        if let Some(r0) = res {
-                            a = r0;
-                        } else {
-                            a = syscall_id;
-                        }
+                    // Update the syscall counts.
+                    let syscall_for_count = syscall.count_map();
+                    let syscall_count =
+                        self.state.syscall_counts.entry(syscall_for_count).or_insert(0);
+                    *syscall_count += 1;
+
+                    let syscall_impl = self.get_syscall(syscall).cloned();
+                    syscall_code = syscall.syscall_id();
+                    let mut precompile_rt = SyscallContext::new(self);
+                    let (precompile_next_pc, precompile_cycles, returned_exit_code) =
+                        if let Some(syscall_impl) = syscall_impl {
+                            // Executing a syscall optionally returns a value to write to the t0
+                            // register. If it returns None, we just keep the
+                            // syscall_id in t0.
+                            let res = syscall_impl.execute(&mut precompile_rt, syscall, b, c);
+                            if let Some((kind, addr)) =
+                                precompile_rt.rt.syscall_memory_violation.take()
+                            {
+                                return Err(precompile_rt.rt.memory_violation(kind, addr));
+                            }
+                            if let Some(err) = precompile_rt.rt.syscall_error.take() {
+                                return Err(err);
+                            }
+                            if let Some(r0) = res {
+                                a = r0;
+                            } else {
+                                a = syscall_id;
+                            }
 
-                        // If the syscall is `HALT` and the exit code is non-zero, return an error.
-                        if syscall == SyscallCode::HALT && precompile_rt.exit_code != 0 {
-                            return Err(ExecutionError::HaltWithNonZeroExitCode(
-                                precompile_rt.exit_code,
-                            ));
-                        }
+                            // If the syscall is `HALT` and the exit code is non-zero, return an error.
+                            if syscall == SyscallCode::HALT && precompile_rt.exit_code != 0 {
+                                return Err(ExecutionError::HaltWithNonZeroExitCode(
+                                    precompile_rt.exit_code,
+                                ));
+                            }
 
-                        (
-                            precompile_rt.next_pc,
-                            syscall_impl.num_extra_cycles(),
-                            precompile_rt.exit_code,
-                        )
-                    } else {
-                        return Err(ExecutionError::UnsupportedSyscall(syscall_id));
-                    };
+                            (
+                                precompile_rt.next_pc,
+                                syscall_impl.num_extra_cycles(),
+                                precompile_rt.exit_code,
+                            )
+                        } else {
+                            return Err(ExecutionError::UnsupportedSyscall(syscall_id));
+                        };
 
-                if syscall == SyscallCode::HALT && returned_exit_code == 0 {
-                    self.state.exited = true;
-                }
+                    if syscall == SyscallCode::HALT && returned_exit_code == 0 {
+                        self.state.exited = true;
+                    }
 
-                // If the syscall is `EXIT_UNCONSTRAINED`, the memory was restored to pre-unconstrained code
-                // in the execute function, so we need to re-read from A0 and A1.  Just do a peek on the
-                // registers.
-                if syscall == SyscallCode::EXIT_UNCONSTRAINED {
-                    b = self.register(Register::A0);
-                    c = self.register(Register::A1);
-                    prev_a = self.register(Register::V0);
-                }
+                    // If the syscall is `EXIT_UNCONSTRAINED`, the memory was restored to pre-unconstrained code
+                    // in the execute function, so we need to re-read from A0 and A1.  Just do a peek on the
+                    // registers.
+                    if syscall == SyscallCode::EXIT_UNCONSTRAINED {
+                        b = self.register(Register::A0);
+                        c = self.register(Register::A1);
+                        prev_a = self.register(Register::V0);
+                    }
 
-                // Allow the syscall impl to modify state.clk/pc (exit unconstrained does this)
-                clk = self.state.clk;
-                pc = self.state.pc;
+                    // Allow the syscall impl to modify state.clk/pc (exit unconstrained does this)
+                    clk = self.state.clk;
+                    pc = self.state.pc;
 
-                self.rw(Register::V0, a, MemoryAccessPosition::A);
-                next_pc = precompile_next_pc;
-                next_next_pc = precompile_next_pc + 4;
-                self.state.clk += precompile_cycles;
-                exit_code = returned_exit_code;
-                hi_or_prev_a = Some(prev_a);
+                    self.rw(Register::V0, a, MemoryAccessPosition::A);
+                    next_pc = precompile_next_pc;
+                    next_next_pc = precompile_next_pc + 4;
+                    self.state.clk += precompile_cycles;
+                    exit_code = returned_exit_code;
+                    hi_or_prev_a = Some(prev_a);
+                }
             }
 
             // Arithmetic instructions
@@ -1237,6 +1450,11 @@ impl<'a> Executor<'a> {
                 (a, b, c, next_next_pc) = self.execute_branch(instruction, next_pc, next_next_pc);
                 self.state.next_is_delayslot = true;
             }
+            Opcode::BGEZAL | Opcode::BLTZAL => {
+                (a, b, c, next_next_pc) =
+                    self.execute_branch_link(instruction, next_pc, next_next_pc);
+                self.state.next_is_delayslot = true;
+            }
 
             // Jump instructions.
             Opcode::Jump => {
@@ -1262,8 +1480,11 @@ impl<'a> Executor<'a> {
             Opcode::MSUBU => {
                 (hi_or_prev_a, a, b, c) = self.execute_msubu(instruction);
             }
-            Opcode::TEQ => {
-                (a, b, c) = self.execute_teq(instruction);
+            Opcode::MADD | Opcode::MSUB => {
+                (hi_or_prev_a, a, b, c) = self.execute_madd_msub(instruction);
+            }
+            Opcode::TEQ | Opcode::TNE | Opcode::TGE | Opcode::TGEU | Opcode::TLT | Opcode::TLTU => {
+                (a, b, c) = self.execute_trap(instruction)?;
             }
             Opcode::SEXT => {
                 (a, b, c) = self.execute_sext(instruction);
@@ -1278,6 +1499,51 @@ impl<'a> Executor<'a> {
                 (hi_or_prev_a, a, b, c) = self.execute_ins(instruction);
             }
 
+            // Floating-point instructions.
+            Opcode::FADD_S
+            | Opcode::FSUB_S
+            | Opcode::FMUL_S
+            | Opcode::FDIV_S
+            | Opcode::FSQRT_S
+            | Opcode::FADD_D
+            | Opcode::FSUB_D
+            | Opcode::FMUL_D
+            | Opcode::FDIV_D
+            | Opcode::FSQRT_D
+            | Opcode::CVT_S_D
+            | Opcode::CVT_D_S
+            | Opcode::CVT_S_W
+            | Opcode::CVT_D_W
+            | Opcode::CVT_W_S
+            | Opcode::CVT_W_D
+            | Opcode::TRUNC_W_S
+            | Opcode::TRUNC_W_D
+            | Opcode::C_UN_S
+            | Opcode::C_EQ_S
+            | Opcode::C_UEQ_S
+            | Opcode::C_OLT_S
+            | Opcode::C_ULT_S
+            | Opcode::C_OLE_S
+            | Opcode::C_ULE_S
+            | Opcode::C_UN_D
+            | Opcode::C_EQ_D
+            | Opcode::C_UEQ_D
+            | Opcode::C_OLT_D
+            | Opcode::C_ULT_D
+            | Opcode::C_OLE_D
+            | Opcode::C_ULE_D
+            | Opcode::LDC1
+            | Opcode::SDC1
+            | Opcode::MOV_D
+            | Opcode::ABS_D
+            | Opcode::NEG_D
+            | Opcode::MOVN_D
+            | Opcode::MOVZ_D => {
+                let event;
+                (a, b, c, event) = self.execute_fpu(instruction)?;
+                fpu_event = Some(event);
+            }
+
             Opcode::UNIMPL => {
                 log::error!("{:X}: {:X}", self.state.pc, instruction.op_c);
                 return Err(ExecutionError::UnsupportedInstruction(instruction.op_c));
@@ -1299,6 +1565,7 @@ impl<'a> Executor<'a> {
                 self.memory_accesses,
                 exit_code,
                 syscall_code,
+                fpu_event,
             );
         };
 
@@ -1351,6 +1618,30 @@ impl<'a> Executor<'a> {
         (Some(lo_val), out_lo, b, c)
     }
 
+    fn execute_madd_msub(&mut self, instruction: &Instruction) -> (Option<u32>, u32, u32, u32) {
+        let (lo, rt, rs) = (
+            instruction.op_a.into(),
+            (instruction.op_b as u8).into(),
+            (instruction.op_c as u8).into(),
+        );
+        let c = self.rr(rs, MemoryAccessPosition::C);
+        let b = self.rr(rt, MemoryAccessPosition::B);
+        let multiply = ((b as i32) as i64 * (c as i32) as i64) as u64;
+        let lo_val = self.register(32.into());
+        let hi_val = self.register(33.into());
+        let addend = ((hi_val as u64) << 32) + lo_val as u64;
+        let out = match instruction.opcode {
+            Opcode::MADD => addend.wrapping_add(multiply),
+            Opcode::MSUB => addend.wrapping_sub(multiply),
+            _ => unreachable!(),
+        };
+        let out_lo = out as u32;
+        let out_hi = (out >> 32) as u32;
+        self.rw(lo, out_lo, MemoryAccessPosition::A);
+        self.rw(Register::HI, out_hi, MemoryAccessPosition::HI);
+        (Some(lo_val), out_lo, b, c)
+    }
+
     fn execute_sext(&mut self, instruction: &Instruction) -> (u32, u32, u32) {
         let (rd, rt, c) =
             (instruction.op_a.into(), (instruction.op_b as u8).into(), instruction.op_c);
@@ -1399,16 +1690,228 @@ impl<'a> Executor<'a> {
         (Some(prev_a), a, b, c)
     }
 
-    fn execute_teq(&mut self, instruction: &Instruction) -> (u32, u32, u32) {
-        let (rs, rt) = (instruction.op_a.into(), (instruction.op_b as u8).into());
+    fn execute_trap(
+        &mut self,
+        instruction: &Instruction,
+    ) -> Result<(u32, u32, u32), ExecutionError> {
+        let rs = instruction.op_a.into();
 
-        let src2 = self.rr(rt, MemoryAccessPosition::B);
+        let src2 = if instruction.imm_b {
+            instruction.op_b
+        } else {
+            self.rr((instruction.op_b as u8).into(), MemoryAccessPosition::B)
+        };
         let src1 = self.rr(rs, MemoryAccessPosition::A);
 
-        if src1 == src2 {
-            panic!("Trap Error");
+        let trap = match instruction.opcode {
+            Opcode::TEQ => src1 == src2,
+            Opcode::TNE => src1 != src2,
+            Opcode::TGE => (src1 as i32) >= (src2 as i32),
+            Opcode::TGEU => src1 >= src2,
+            Opcode::TLT => (src1 as i32) < (src2 as i32),
+            Opcode::TLTU => src1 < src2,
+            _ => unreachable!(),
+        };
+        if trap {
+            return Err(ExecutionError::Trap(instruction.opcode, self.state.pc));
+        }
+        Ok((src1, src2, 0))
+    }
+
+    /// Execute a COP1 instruction.
+    ///
+    /// The floating-point register pairs are accessed directly rather than through the CPU operands,
+    /// which only read `$zero` as operand A. `fs` is read at position B, `ft` (or the condition of a
+    /// conditional move) at position C and `fd` is written at position A.
+    fn execute_fpu(
+        &mut self,
+        instruction: &Instruction,
+    ) -> Result<(u32, u32, u32, FpuEvent), ExecutionError> {
+        let opcode = instruction.opcode;
+        let mut event = FpuEvent {
+            shard: self.shard(),
+            clk: self.state.clk,
+            pc: self.state.pc,
+            next_pc: self.state.next_pc,
+            opcode,
+            b: instruction.op_b,
+            c: instruction.op_c,
+            fs_access: [None; 2],
+            ft_access: [None; 2],
+            fd_access: [None; 2],
+            mem_access: [None; 2],
+        };
+        let (fs, ft, fd) =
+            (instruction.op_b, instruction.op_c & 0xff, (instruction.op_c >> 8) & 0xff);
+
+        match opcode {
+            Opcode::LDC1 | Opcode::SDC1 => {
+                let ft = (instruction.op_c >> 16) & 0xff;
+                let base = self.rr((instruction.op_b as u8).into(), MemoryAccessPosition::B);
+                let addr = base.wrapping_add(sign_extend::<16>(instruction.op_c & 0xffff));
+                if addr % 8 != 0 {
+                    return Err(ExecutionError::InvalidMemoryAccess(opcode, addr));
+                }
+                if opcode == Opcode::LDC1 {
+                    self.check_load(addr)?;
+                } else {
+                    self.check_store(addr)?;
+                }
+                event.b = base;
+
+                if opcode == Opcode::LDC1 {
+                    let timestamp = self.timestamp(&MemoryAccessPosition::Memory);
+                    let mut value = 0;
+                    for i in 0..2 {
+                        let record = self.mr(addr + 4 * i, self.shard(), timestamp, None);
+                        value |= u64::from(record.value) << (32 * i);
+                        event.mem_access[i as usize] = Some(record.into());
+                    }
+                    self.rr(Register::ZERO, MemoryAccessPosition::A);
+                    self.fpu_write(ft, value, 2, &mut event.fd_access);
+                } else {
+                    let value = self.fpu_read(ft, 2, MemoryAccessPosition::C, &mut event.ft_access);
+                    self.rr(Register::ZERO, MemoryAccessPosition::A);
+                    let timestamp = self.timestamp(&MemoryAccessPosition::Memory);
+                    for i in 0..2 {
+                        let record = self.mw(
+                            addr + 4 * i,
+                            (value >> (32 * i)) as u32,
+                            self.shard(),
+                            timestamp,
+                            None,
+                        );
+                        event.mem_access[i as usize] = Some(record.into());
+                    }
+                }
+                Ok((0, base, instruction.op_c, event))
+            }
+            Opcode::MOV_D | Opcode::ABS_D | Opcode::NEG_D => {
+                let value = self.fpu_read(fs, 2, MemoryAccessPosition::B, &mut event.fs_access);
+                self.rr(Register::ZERO, MemoryAccessPosition::A);
+                self.fpu_write(fd, fpu_sign_move(opcode, value), 2, &mut event.fd_access);
+                Ok((0, fs, instruction.op_c, event))
+            }
+            Opcode::MOVN_D | Opcode::MOVZ_D => {
+                let cond = self.fpu_read(ft, 1, MemoryAccessPosition::C, &mut event.ft_access);
+                let value = self.fpu_read(fs, 2, MemoryAccessPosition::B, &mut event.fs_access);
+                self.rr(Register::ZERO, MemoryAccessPosition::A);
+                let value = if (cond != 0) == (opcode == Opcode::MOVN_D) {
+                    value
+                } else {
+                    let lo = self.register(Register::from(fd as u8));
+                    let hi = self.register(Register::from(fd as u8 + 1));
+                    u64::from(lo) | (u64::from(hi) << 32)
+                };
+                self.fpu_write(fd, value, 2, &mut event.fd_access);
+                Ok((0, fs, instruction.op_c, event))
+            }
+            _ => {
+                let words = source_format(opcode).num_words();
+                let ft_value = if is_binary(opcode) || opcode.is_fpu_compare() {
+                    self.fpu_read(ft, words, MemoryAccessPosition::C, &mut event.ft_access)
+                } else {
+                    0
+                };
+                let fs_value =
+                    self.fpu_read(fs, words, MemoryAccessPosition::B, &mut event.fs_access);
+                self.rr(Register::ZERO, MemoryAccessPosition::A);
+                if opcode.is_fpu_compare() {
+                    let value = u64::from(fpu_compare(opcode, fs_value, ft_value));
+                    self.fpu_write(fd, value, 1, &mut event.fd_access);
+                } else {
+                    let value = fpu_arith(opcode, fs_value, ft_value);
+                    self.fpu_write(
+                        fd,
+                        value,
+                        result_format(opcode).num_words(),
+                        &mut event.fd_access,
+                    );
+                }
+                Ok((0, fs, instruction.op_c, event))
+            }
+        }
+    }
+
+    /// Get the Linux syscall with the given number if the Cannon-compatible execution mode is
+    /// enabled, i.e. if a preimage oracle was provided.
+    fn linux_syscall_code(&self, syscall_id: u32) -> Option<LinuxSyscallCode> {
+        self.preimage_oracle.as_ref().and_then(|_| LinuxSyscallCode::from_u32(syscall_id))
+    }
+
+    /// Execute a Linux syscall of the Cannon-compatible execution mode.
+    ///
+    /// Returns the result written to `$v0`, the next program counter and the exit code.
+    fn execute_linux_syscall(
+        &mut self,
+        code: LinuxSyscallCode,
+        a0: u32,
+        a1: u32,
+    ) -> Result<(u32, u32, u32), ExecutionError> {
+        if self.unconstrained {
+            return Err(ExecutionError::InvalidSyscallUsage(code as u64));
+        }
+
+        // The exits halt like `HALT` does, with the exit code in `$a0`.
+        if code.is_exit() {
+            if a0 != 0 {
+                return Err(ExecutionError::HaltWithNonZeroExitCode(a0));
+            }
+            self.state.exited = true;
+            self.rw(Register::V0, code as u32, MemoryAccessPosition::A);
+            return Ok((code as u32, 0, a0));
+        }
+
+        let a2 = self.register(Register::A2);
+        let outcome = linux::emulate(self, code, a0, a1, a2)?;
+        if outcome.mem_word.is_some() {
+            self.check_store(a1 - a1 % 4)?;
+        }
+        self.rw(Register::V0, outcome.v0, MemoryAccessPosition::A);
+
+        // The mode is execution-only, so the error number and the buffer of a read are written
+        // without emitting events for them.
+        let shard = self.shard();
+        let timestamp = self.timestamp(&MemoryAccessPosition::HI);
+        self.mw(Register::A3 as u32, outcome.a3, shard, timestamp, None);
+        if let Some(word) = outcome.mem_word {
+            self.mw(a1 - a1 % 4, word, shard, timestamp, None);
+        }
+
+        Ok((outcome.v0, self.state.pc.wrapping_add(4), 0))
+    }
+
+    /// Read one register, or an even/odd register pair, of COP1.
+    fn fpu_read(
+        &mut self,
+        register: u32,
+        num_words: usize,
+        position: MemoryAccessPosition,
+        access: &mut [Option<MemoryReadRecord>; 2],
+    ) -> u64 {
+        let timestamp = self.timestamp(&position);
+        let mut value = 0;
+        for (i, access) in access.iter_mut().take(num_words).enumerate() {
+            let record = self.mr(register + i as u32, self.shard(), timestamp, None);
+            value |= u64::from(record.value) << (32 * i);
+            *access = Some(record);
+        }
+        value
+    }
+
+    /// Write one register, or an even/odd register pair, of COP1 at position A.
+    fn fpu_write(
+        &mut self,
+        register: u32,
+        value: u64,
+        num_words: usize,
+        access: &mut [Option<MemoryWriteRecord>; 2],
+    ) {
+        let timestamp = self.timestamp(&MemoryAccessPosition::A);
+        for (i, access) in access.iter_mut().take(num_words).enumerate() {
+            let word = (value >> (32 * i)) as u32;
+            *access = Some(self.mw(register + i as u32, word, self.shard(), timestamp, None));
         }
-        (src1, src2, 0)
     }
 
     fn execute_condmov(&mut self, instruction: &Instruction) -> (Option<u32>, u32, u32, u32) {
@@ -1498,6 +2001,52 @@ impl<'a> Executor<'a> {
         self.alu_rw(instruction, rd, hi, a, b, c)
     }
 
+    /// Check a load from `addr` if the [`MemoryChecks`] are enabled.
+    fn check_load(&self, addr: u32) -> Result<(), ExecutionError> {
+        if self.memory_checks.is_some() && addr < PAGE_SIZE {
+            return Err(self.memory_violation(MemoryViolationKind::NullRead, addr));
+        }
+        Ok(())
+    }
+
+    /// Check a store to `addr` if the [`MemoryChecks`] are enabled.
+    fn check_store(&self, addr: u32) -> Result<(), ExecutionError> {
+        match self.store_violation(addr) {
+            Some(kind) => Err(self.memory_violation(kind, addr)),
+            None => Ok(()),
+        }
+    }
+
+    /// Check a store of a syscall to `addr` if the [`MemoryChecks`] are enabled.
+    ///
+    /// The syscalls cannot fail, so the first violation is kept and returned once the syscall has
+    /// executed.
+    pub(crate) fn check_syscall_store(&mut self, addr: u32) {
+        if self.syscall_memory_violation.is_none() {
+            self.syscall_memory_violation = self.store_violation(addr).map(|kind| (kind, addr));
+        }
+    }
+
+    /// Returns the violation of a store to `addr`, if the [`MemoryChecks`] are enabled.
+    fn store_violation(&self, addr: u32) -> Option<MemoryViolationKind> {
+        self.memory_checks?;
+        if addr < PAGE_SIZE {
+            return Some(MemoryViolationKind::NullWrite);
+        }
+        let segment = self.program.segments.iter().find(|segment| segment.contains(addr))?;
+        if segment.is_executable() {
+            Some(MemoryViolationKind::WriteToExecutable)
+        } else if !segment.is_writable() {
+            Some(MemoryViolationKind::WriteToReadOnly)
+        } else {
+            None
+        }
+    }
+
+    fn memory_violation(&self, kind: MemoryViolationKind, addr: u32) -> ExecutionError {
+        ExecutionError::MemoryViolation { kind, addr, pc: self.state.pc }
+    }
+
     fn execute_load(
         &mut self,
         instruction: &Instruction,
@@ -1511,6 +2060,7 @@ impl<'a> Executor<'a> {
 
         let virt_raw = rs_raw.wrapping_add(offset_ext);
         let virt = virt_raw & 0xFFFF_FFFC;
+        self.check_load(virt_raw)?;
 
         let mem = self.mr_cpu(virt, MemoryAccessPosition::Memory);
         let rs = virt_raw;
@@ -1572,6 +2122,7 @@ impl<'a> Executor<'a> {
 
         let virt_raw = rs.wrapping_add(offset_ext);
         let virt = virt_raw & 0xFFFF_FFFC;
+        self.check_store(virt_raw)?;
 
         let mem = self.word(virt);
 
@@ -1652,6 +2203,33 @@ impl<'a> Executor<'a> {
         (src1, src2, target_pc, next_next_pc)
     }
 
+    fn execute_branch_link(
+        &mut self,
+        instruction: &Instruction,
+        next_pc: u32,
+        mut next_next_pc: u32,
+    ) -> (u32, u32, u32, u32) {
+        let (link, src, target_pc) =
+            (instruction.op_a.into(), (instruction.op_b as u8).into(), instruction.op_c);
+        let src = self.rr(src, MemoryAccessPosition::B);
+        let should_jump = match instruction.opcode {
+            Opcode::BGEZAL => (src as i32) >= 0,
+            Opcode::BLTZAL => (src as i32) < 0,
+            _ => {
+                unreachable!()
+            }
+        };
+
+        // The return address is written whether or not the branch is taken.
+        let return_pc = self.state.pc.wrapping_add(8);
+        self.rw(link, return_pc, MemoryAccessPosition::A);
+
+        if should_jump {
+            next_next_pc = target_pc.wrapping_add(next_pc);
+        }
+        (return_pc, src, target_pc, next_next_pc)
+    }
+
     fn execute_jump(&mut self, instruction: &Instruction) -> (u32, u32, u32, u32) {
         let (link, target) = (instruction.op_a.into(), (instruction.op_b as u8).into());
         let target_pc = self.rr(target, MemoryAccessPosition::B);
@@ -1688,7 +2266,7 @@ impl<'a> Executor<'a> {
     /// Executes one cycle of the program, returning whether the program has finished.
     #[inline]
     #[allow(clippy::too_many_lines)]
-    fn execute_cycle(&mut self) -> Result<bool, ExecutionError> {
+    pub(crate) fn execute_cycle(&mut self) -> Result<bool, ExecutionError> {
         // Fetch the instruction at the current program counter.
         let instruction = self.fetch();
 
@@ -1696,9 +2274,39 @@ impl<'a> Executor<'a> {
         #[cfg(debug_assertions)]
         self.log(&instruction);
 
+        // Sample the call stack of the guest.
+        if let Some(profiler) = &mut self.profiler {
+            if !self.unconstrained {
+                profiler.record(self.state.global_clk, self.state.pc, &instruction);
+            }
+        }
+        if let Some(shadow_stack) = &mut self.shadow_stack {
+            shadow_stack.record(self.state.pc, &instruction);
+        }
+
         // Execute the instruction.
+        let pc = self.state.pc;
         self.execute_operation(&instruction)?;
 
+        // Check that the stack did not grow into the heap, or below the stack guard.
+        if instruction.op_a == Register::SP as u8 {
+            let sp = self.state.memory.get(Register::SP as u32).map_or(0, |record| record.value);
+            if let Some(heap_end) = self.program.heap_end {
+                if sp < heap_end {
+                    return Err(ExecutionError::HeapStackCollision { sp, heap_end, pc });
+                }
+            }
+            if let Some(stack_guard) = self.memory_checks.and_then(|checks| checks.stack_guard) {
+                if sp < stack_guard {
+                    return Err(ExecutionError::MemoryViolation {
+                        kind: MemoryViolationKind::StackOverflow,
+                        addr: sp,
+                        pc,
+                    });
+                }
+            }
+        }
+
         // Increment the clock.
         self.state.global_clk += 1;
 
@@ -1832,6 +2440,12 @@ impl<'a> Executor<'a> {
 
         let removed_record =
             std::mem::replace(&mut self.record, ExecutionRecord::new(self.program.clone()));
+        self.report.execution_shards += 1;
+        if self.executor_mode == ExecutorMode::Trace {
+            for (name, count) in removed_record.stats() {
+                *self.report.event_counts.entry(name).or_default() += count as u64;
+            }
+        }
         let public_values = removed_record.public_values;
         self.record.public_values = public_values;
         self.records.push(removed_record);
@@ -1921,7 +2535,7 @@ impl<'a> Executor<'a> {
         Ok((checkpoint, done))
     }
 
-    fn initialize(&mut self) {
+    pub(crate) fn initialize(&mut self) {
         self.state.clk = 0;
 
         tracing::debug!("loading memory image");
@@ -1981,7 +2595,7 @@ impl<'a> Executor<'a> {
         let mut current_shard = self.state.current_shard;
         let mut num_shards_executed = 0;
         loop {
-            if self.execute_cycle()? {
+            if self.execute_cycle().map_err(|error| self.attach_backtrace(error))? {
                 done = true;
                 break;
             }
@@ -2036,7 +2650,7 @@ impl<'a> Executor<'a> {
         Ok(done)
     }
 
-    fn postprocess(&mut self) {
+    pub(crate) fn postprocess(&mut self) {
         // Flush remaining stdout/stderr
         for (fd, buf) in &self.io_buf {
             if !buf.is_empty() {
@@ -2057,6 +2671,14 @@ impl<'a> Executor<'a> {
             buf.flush().unwrap();
         }
 
+        // Write the profile.
+        if let Some(profiler) = self.profiler.take() {
+            match profiler.write() {
+                Ok(()) => tracing::info!("wrote profile to {}", profiler.output().display()),
+                Err(err) => tracing::warn!("failed to write profile: {}", err),
+            }
+        }
+
         // Ensure that all proofs and input bytes were read, otherwise warn the user.
         if self.state.proof_stream_ptr != self.state.proof_stream.len() {
             tracing::warn!(
@@ -2160,14 +2782,24 @@ pub const fn align(addr: u32) -> u32 {
 #[cfg(test)]
 mod tests {
     use crate::programs::tests::{
-        fibonacci_program, panic_program, secp256r1_add_program, secp256r1_double_program,
-        simple_memory_program, simple_program, ssz_withdrawals_program, u256xu2048_mul_program,
+        blake3_program, bls12381_pairing_program, bn254_pairing_program,
+        embedded_allocator_program, fibonacci_program, guest_env_program, modexp_program,
+        panic_program, secp256r1_add_program, secp256r1_double_program, simple_memory_program,
+        simple_program, ssz_withdrawals_program, typed_hook_program, u256xu2048_mul_program,
     };
     use zkm_stark::ZKMCoreOpts;
 
-    use crate::{Instruction, Opcode, Register};
+    use elf::abi::{PF_R, PF_W, PF_X};
+    use test_artifacts::{CANNON_PREIMAGE_ELF, PANIC_ELF};
 
-    use super::{Executor, Program};
+    use crate::{
+        hook::typed_hook_fd,
+        syscalls::{linux::HEAP_START, SyscallCode},
+        Instruction, KvPreimageOracle, MemoryChecks, MemoryViolationKind, Opcode, Register,
+        Segment, ZKMContext, CANNON_INIT_SP,
+    };
+
+    use super::{ExecutionError, Executor, Program};
 
     fn _assert_send<T: Send>() {}
 
@@ -2214,12 +2846,140 @@ mod tests {
     }
     //
     #[test]
+    fn test_bn254_pairing_program_run() {
+        let program = bn254_pairing_program();
+        let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+        runtime.run().unwrap();
+    }
+    //
+    #[test]
+    fn test_bls12381_pairing_program_run() {
+        let program = bls12381_pairing_program();
+        let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+        runtime.run().unwrap();
+    }
+    //
+    #[test]
+    fn test_blake3_program_run() {
+        let program = blake3_program();
+        let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+        runtime.run().unwrap();
+    }
+    //
+    #[test]
+    fn test_modexp_program_run() {
+        let program = modexp_program();
+        let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+        runtime.run().unwrap();
+    }
+    //
+    #[test]
     fn test_ssz_withdrawals_program_run() {
         let program = ssz_withdrawals_program();
         let mut runtime = Executor::new(program, ZKMCoreOpts::default());
         runtime.run().unwrap();
     }
     //
+    #[test]
+    fn test_guest_env_program_run() {
+        let run = |seed: [u8; 32]| {
+            let context = ZKMContext::builder().env("GREETING", "hello").rng_seed(seed).build();
+            let mut runtime =
+                Executor::with_context(guest_env_program(), ZKMCoreOpts::default(), context);
+            runtime.run().unwrap();
+            runtime.state.public_values_stream
+        };
+
+        let public_values = run([1; 32]);
+        assert_eq!(public_values[..32], [1; 32]);
+        let (greeting, missing): (String, bool) =
+            bincode::deserialize(&public_values[32..]).unwrap();
+        assert_eq!(greeting, "hello");
+        assert!(missing);
+
+        // The randomness is reproducible, and depends on the seed.
+        assert_eq!(run([1; 32]), public_values);
+        assert_ne!(run([2; 32])[32..], public_values[32..]);
+    }
+
+    #[test]
+    fn test_typed_hook_program_run() {
+        let context = ZKMContext::builder()
+            .typed_hook("isqrt", |n: u64| {
+                let root = n.isqrt();
+                (root, n - root * root)
+            })
+            .build();
+        let mut runtime =
+            Executor::with_context(typed_hook_program(), ZKMCoreOpts::default(), context);
+        runtime.write_stdin(&1_000_000_007u64);
+        runtime.run().unwrap();
+        let root: u64 = bincode::deserialize(&runtime.state.public_values_stream).unwrap();
+        assert_eq!(root, 31_622);
+    }
+
+    #[test]
+    fn test_typed_hook_program_errors() {
+        let run = |context: ZKMContext| {
+            let mut runtime =
+                Executor::with_context(typed_hook_program(), ZKMCoreOpts::default(), context);
+            runtime.write_stdin(&1_000_000_007u64);
+            runtime.run()
+        };
+
+        // The hook is not registered.
+        let err = run(ZKMContext::default()).unwrap_err();
+        assert!(
+            matches!(err, ExecutionError::UnknownTypedHook(fd) if fd == typed_hook_fd("isqrt"))
+        );
+
+        // The hook expects another request.
+        let context = ZKMContext::builder().typed_hook("isqrt", |n: (u64, u64)| n).build();
+        assert!(matches!(run(context), Err(ExecutionError::HookFailed(_))));
+    }
+
+    #[test]
+    fn test_embedded_allocator_program_run() {
+        let program = embedded_allocator_program();
+        assert!(program.heap_end.is_some());
+        let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+        runtime.run().unwrap();
+        let sum: u32 = bincode::deserialize(&runtime.state.public_values_stream).unwrap();
+        assert_eq!(sum, 8 * (0..256).sum::<u32>());
+    }
+
+    #[test]
+    fn test_cannon_program_run() {
+        let program = Program::from_cannon(CANNON_PREIMAGE_ELF).unwrap();
+        let mut key = [0; 32];
+        key[0] = 1;
+        key[31] = 1;
+        let mut oracle = KvPreimageOracle::new();
+        oracle.insert(key, b"hello, preimage oracle".to_vec());
+        let context = ZKMContext::builder().preimage_oracle(oracle).build();
+        let mut runtime = Executor::with_context(program, ZKMCoreOpts::default(), context);
+        runtime.run().unwrap();
+
+        // The preimage was read with its length prefix into the page returned by `mmap`.
+        let read = (0..30).map(|i| runtime.byte(HEAP_START + i)).collect::<Vec<_>>();
+        assert_eq!(read[..8], 22u64.to_be_bytes());
+        assert_eq!(&read[8..], b"hello, preimage oracle");
+
+        // The program doesn't touch the stack set up for the Go runtime.
+        assert_eq!(runtime.word(CANNON_INIT_SP), 1);
+        assert_eq!(runtime.word(CANNON_INIT_SP + 4), CANNON_INIT_SP + 4 * 21);
+        assert_eq!(runtime.word(CANNON_INIT_SP + 4 * 21).to_le_bytes(), *b"op-p");
+        assert_eq!(runtime.word(CANNON_INIT_SP + 4 * 22).to_le_bytes(), *b"rogr");
+    }
+
+    #[test]
+    fn test_cannon_program_missing_preimage() {
+        let program = Program::from_cannon(CANNON_PREIMAGE_ELF).unwrap();
+        let context = ZKMContext::builder().preimage_oracle(KvPreimageOracle::new()).build();
+        let mut runtime = Executor::with_context(program, ZKMCoreOpts::default(), context);
+        assert!(matches!(runtime.run(), Err(ExecutionError::MissingPreimage(_))));
+    }
+
     #[test]
     #[should_panic]
     fn test_panic() {
@@ -2228,6 +2988,22 @@ mod tests {
         runtime.run().unwrap();
     }
 
+    #[test]
+    fn test_panic_backtrace() {
+        let program = panic_program();
+        let context = ZKMContext::builder().backtraces().build();
+        let mut runtime = Executor::with_context(program, ZKMCoreOpts::default(), context);
+        let err = runtime.run().unwrap_err().symbolize(PANIC_ELF);
+
+        assert!(matches!(err.inner(), ExecutionError::HaltWithNonZeroExitCode(1)));
+        let backtrace = err.backtrace().unwrap();
+        assert!(backtrace.frames.len() > 1);
+        assert!(backtrace
+            .frames
+            .iter()
+            .any(|frame| frame.function.as_deref().is_some_and(|name| name.ends_with("main"))));
+    }
+
     #[test]
     fn test_beq_jump() {
         let instructions = vec![
@@ -2650,6 +3426,204 @@ mod tests {
         assert_eq!(runtime.register(5.into()), 12);
     }
 
+    #[test]
+    fn test_bgezal_bltzal() {
+        //   addi x29, x0, 1
+        //   bgezal x29, 100
+        //
+        // Branches and links, as x29 >= 0.
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 1, false, true),
+            Instruction::new(Opcode::BGEZAL, 31, 29, 100, false, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+        runtime.run().unwrap();
+        assert_eq!(runtime.state.pc + 100, runtime.state.next_pc);
+        assert_eq!(runtime.register(31.into()), 12);
+
+        // Links without branching, as x29 >= 0.
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 1, false, true),
+            Instruction::new(Opcode::BLTZAL, 31, 29, 100, false, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+        runtime.run().unwrap();
+        assert_eq!(runtime.state.pc + 4, runtime.state.next_pc);
+        assert_eq!(runtime.register(31.into()), 12);
+    }
+
+    #[test]
+    fn test_madd_msub() {
+        // (hi, lo) = 0x1_0000_0005 + (-2 * 3), then (hi, lo) -= -2 * 3.
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 32, 0, 5, false, true),
+            Instruction::new(Opcode::ADD, 33, 0, 1, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 0xffff_fffe, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 3, false, true),
+            Instruction::new(Opcode::MADD, 32, 10, 11, false, false),
+        ];
+        let program = Program::new(instructions.clone(), 0, 0);
+        let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+        runtime.run().unwrap();
+        assert_eq!(runtime.register(Register::LO), 0xffff_ffff);
+        assert_eq!(runtime.register(Register::HI), 0);
+
+        let mut instructions = instructions;
+        instructions.push(Instruction::new(Opcode::MSUB, 32, 10, 11, false, false));
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+        runtime.run().unwrap();
+        assert_eq!(runtime.register(Register::LO), 5);
+        assert_eq!(runtime.register(Register::HI), 1);
+    }
+
+    #[test]
+    fn test_trap() {
+        let run = |opcode: Opcode, a: u32, b: u32| {
+            let instructions = vec![
+                Instruction::new(Opcode::ADD, 10, 0, a, false, true),
+                Instruction::new(opcode, 10, b, 0, true, true),
+            ];
+            let program = Program::new(instructions, 0, 0);
+            Executor::new(program, ZKMCoreOpts::default()).run()
+        };
+
+        for (opcode, a, b, trap) in [
+            (Opcode::TEQ, 1, 1, true),
+            (Opcode::TEQ, 1, 2, false),
+            (Opcode::TNE, 1, 2, true),
+            (Opcode::TNE, 1, 1, false),
+            (Opcode::TGE, 1, 0xffff_ffff, true),
+            (Opcode::TGE, 0xffff_ffff, 1, false),
+            (Opcode::TGEU, 0xffff_ffff, 1, true),
+            (Opcode::TGEU, 1, 0xffff_ffff, false),
+            (Opcode::TLT, 0xffff_ffff, 1, true),
+            (Opcode::TLT, 1, 0xffff_ffff, false),
+            (Opcode::TLTU, 1, 0xffff_ffff, true),
+            (Opcode::TLTU, 0xffff_ffff, 1, false),
+        ] {
+            let result = run(opcode, a, b);
+            if trap {
+                assert!(
+                    matches!(result, Err(ExecutionError::Trap(op, 4)) if op == opcode),
+                    "{opcode} {a:#x} {b:#x}"
+                );
+            } else {
+                assert!(result.is_ok(), "{opcode} {a:#x} {b:#x}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_heap_stack_collision() {
+        let run = |sp: u32| {
+            let instructions = vec![
+                Instruction::new(Opcode::ADD, 29, 0, sp, false, true),
+                Instruction::new(Opcode::ADD, 10, 29, 4, false, true),
+            ];
+            let mut program = Program::new(instructions, 0, 0);
+            program.heap_end = Some(0x1000);
+            Executor::new(program, ZKMCoreOpts::default()).run()
+        };
+
+        assert!(run(0x1000).is_ok());
+        assert!(matches!(
+            run(0xffc),
+            Err(ExecutionError::HeapStackCollision { sp: 0xffc, heap_end: 0x1000, pc: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_memory_checks() {
+        let run = |instruction: Instruction| {
+            let instructions =
+                vec![Instruction::new(Opcode::ADD, 29, 0, 0x10000, false, true), instruction];
+            let mut program = Program::new(instructions, 0x1000, 0x1000);
+            program.segments = vec![
+                Segment { start: 0x1000, end: 0x2000, flags: PF_R | PF_X },
+                Segment { start: 0x2000, end: 0x3000, flags: PF_R },
+                Segment { start: 0x3000, end: 0x4000, flags: PF_R | PF_W },
+            ];
+            let context = ZKMContext::builder()
+                .memory_checks(MemoryChecks::new().stack_guard(0x8000))
+                .build();
+            Executor::with_context(program, ZKMCoreOpts::default(), context).run()
+        };
+
+        assert!(run(Instruction::new(Opcode::SW, 29, 0, 0x3000, false, true)).is_ok());
+        assert!(run(Instruction::new(Opcode::LW, 10, 0, 0x1000, false, true)).is_ok());
+        for (instruction, kind, addr) in [
+            (Instruction::new(Opcode::LW, 10, 0, 0, false, true), MemoryViolationKind::NullRead, 0),
+            (
+                Instruction::new(Opcode::SB, 29, 0, 0x10, false, true),
+                MemoryViolationKind::NullWrite,
+                0x10,
+            ),
+            (
+                Instruction::new(Opcode::SW, 29, 0, 0x1004, false, true),
+                MemoryViolationKind::WriteToExecutable,
+                0x1004,
+            ),
+            (
+                Instruction::new(Opcode::SW, 29, 0, 0x2000, false, true),
+                MemoryViolationKind::WriteToReadOnly,
+                0x2000,
+            ),
+            (
+                Instruction::new(Opcode::ADD, 29, 0, 0x7ffc, false, true),
+                MemoryViolationKind::StackOverflow,
+                0x7ffc,
+            ),
+        ] {
+            let result = run(instruction);
+            assert!(
+                matches!(
+                    result,
+                    Err(ExecutionError::MemoryViolation { kind: k, addr: a, pc: 0x1004 })
+                        if k == kind && a == addr
+                ),
+                "{kind}: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_memory_checks_syscall_store() {
+        let run = |w_ptr: u32| {
+            let instructions = vec![
+                Instruction::new(Opcode::ADD, 29, 0, 0x10000, false, true),
+                Instruction::new(Opcode::ADD, 2, 0, SyscallCode::SHA_EXTEND as u32, false, true),
+                Instruction::new(Opcode::ADD, 4, 0, w_ptr, false, true),
+                Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+            ];
+            let mut program = Program::new(instructions, 0x1000, 0x1000);
+            program.segments = vec![
+                Segment { start: 0x1000, end: 0x2000, flags: PF_R | PF_X },
+                Segment { start: 0x2000, end: 0x3000, flags: PF_R },
+                Segment { start: 0x3000, end: 0x4000, flags: PF_R | PF_W },
+            ];
+            let context = ZKMContext::builder().memory_checks(MemoryChecks::new()).build();
+            Executor::with_context(program, ZKMCoreOpts::default(), context).run()
+        };
+
+        assert!(run(0x3000).is_ok());
+        // The precompile writes `w[16..64]`, so the first violation is at `w_ptr + 64`.
+        let result = run(0x2000);
+        assert!(
+            matches!(
+                result,
+                Err(ExecutionError::MemoryViolation {
+                    kind: MemoryViolationKind::WriteToReadOnly,
+                    addr: 0x2040,
+                    pc: 0x100c,
+                })
+            ),
+            "{result:?}"
+        );
+    }
+
     fn simple_op_code_test(opcode: Opcode, expected: u32, a: u32, b: u32) {
         let instructions = vec![
             Instruction::new(Opcode::ADD, 10, 0, a, false, true),
@@ -0,0 +1,245 @@
+//! IEEE-754 semantics of the COP1 instructions.
+//!
+//! All arithmetic rounds to nearest, ties to even, except `trunc.w` which rounds toward zero. A NaN
+//! result is always replaced by the legacy MIPS default NaN, and a conversion to a word that is out
+//! of range or invalid produces [`INVALID_WORD`], as with the FCSR exception enables cleared.
+
+use crate::Opcode;
+
+/// The default NaN produced by single precision operations.
+pub const DEFAULT_NAN_S: u32 = 0x7fbf_ffff;
+
+/// The default NaN produced by double precision operations.
+pub const DEFAULT_NAN_D: u64 = 0x7ff7_ffff_ffff_ffff;
+
+/// The result of an invalid or out of range conversion to a word.
+pub const INVALID_WORD: u32 = 0x7fff_ffff;
+
+/// The format of a COP1 operand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FpuFormat {
+    /// A single precision value in one register.
+    Single,
+    /// A double precision value in an even/odd register pair.
+    Double,
+    /// A 32-bit signed integer in one register.
+    Word,
+}
+
+impl FpuFormat {
+    /// The number of registers holding a value of this format.
+    #[must_use]
+    pub const fn num_words(self) -> usize {
+        match self {
+            FpuFormat::Double => 2,
+            FpuFormat::Single | FpuFormat::Word => 1,
+        }
+    }
+}
+
+/// The format of the `fs` (and `ft`) operand of an FPU instruction.
+#[must_use]
+pub const fn source_format(opcode: Opcode) -> FpuFormat {
+    match opcode {
+        Opcode::FADD_S
+        | Opcode::FSUB_S
+        | Opcode::FMUL_S
+        | Opcode::FDIV_S
+        | Opcode::FSQRT_S
+        | Opcode::CVT_D_S
+        | Opcode::CVT_W_S
+        | Opcode::TRUNC_W_S
+        | Opcode::C_UN_S
+        | Opcode::C_EQ_S
+        | Opcode::C_UEQ_S
+        | Opcode::C_OLT_S
+        | Opcode::C_ULT_S
+        | Opcode::C_OLE_S
+        | Opcode::C_ULE_S => FpuFormat::Single,
+        Opcode::CVT_S_W | Opcode::CVT_D_W => FpuFormat::Word,
+        _ => FpuFormat::Double,
+    }
+}
+
+/// The format of the result of an FPU arithmetic or conversion instruction.
+#[must_use]
+pub const fn result_format(opcode: Opcode) -> FpuFormat {
+    match opcode {
+        Opcode::FADD_S
+        | Opcode::FSUB_S
+        | Opcode::FMUL_S
+        | Opcode::FDIV_S
+        | Opcode::FSQRT_S
+        | Opcode::CVT_S_D
+        | Opcode::CVT_S_W => FpuFormat::Single,
+        Opcode::CVT_W_S | Opcode::CVT_W_D | Opcode::TRUNC_W_S | Opcode::TRUNC_W_D => {
+            FpuFormat::Word
+        }
+        _ => FpuFormat::Double,
+    }
+}
+
+/// Whether an FPU arithmetic instruction reads the `ft` operand.
+#[must_use]
+pub const fn is_binary(opcode: Opcode) -> bool {
+    matches!(
+        opcode,
+        Opcode::FADD_S
+            | Opcode::FSUB_S
+            | Opcode::FMUL_S
+            | Opcode::FDIV_S
+            | Opcode::FADD_D
+            | Opcode::FSUB_D
+            | Opcode::FMUL_D
+            | Opcode::FDIV_D
+    )
+}
+
+#[inline]
+fn single(bits: u64) -> f32 {
+    f32::from_bits(bits as u32)
+}
+
+#[inline]
+fn double(bits: u64) -> f64 {
+    f64::from_bits(bits)
+}
+
+#[inline]
+fn from_single(value: f32) -> u64 {
+    if value.is_nan() {
+        u64::from(DEFAULT_NAN_S)
+    } else {
+        u64::from(value.to_bits())
+    }
+}
+
+#[inline]
+fn from_double(value: f64) -> u64 {
+    if value.is_nan() {
+        DEFAULT_NAN_D
+    } else {
+        value.to_bits()
+    }
+}
+
+/// Convert a value to a word, rounding to nearest even or toward zero.
+fn to_word(value: f64, truncate: bool) -> u64 {
+    let rounded = if truncate { value.trunc() } else { value.round_ties_even() };
+    if value.is_nan() || !(-2_147_483_648.0..2_147_483_648.0).contains(&rounded) {
+        u64::from(INVALID_WORD)
+    } else {
+        u64::from(rounded as i32 as u32)
+    }
+}
+
+/// Evaluate a floating-point arithmetic or conversion instruction.
+///
+/// Operands and the result are raw register contents: single precision values and words only use
+/// the low 32 bits.
+#[must_use]
+pub fn fpu_arith(opcode: Opcode, fs: u64, ft: u64) -> u64 {
+    match opcode {
+        Opcode::FADD_S => from_single(single(fs) + single(ft)),
+        Opcode::FSUB_S => from_single(single(fs) - single(ft)),
+        Opcode::FMUL_S => from_single(single(fs) * single(ft)),
+        Opcode::FDIV_S => from_single(single(fs) / single(ft)),
+        Opcode::FSQRT_S => from_single(single(fs).sqrt()),
+        Opcode::FADD_D => from_double(double(fs) + double(ft)),
+        Opcode::FSUB_D => from_double(double(fs) - double(ft)),
+        Opcode::FMUL_D => from_double(double(fs) * double(ft)),
+        Opcode::FDIV_D => from_double(double(fs) / double(ft)),
+        Opcode::FSQRT_D => from_double(double(fs).sqrt()),
+        Opcode::CVT_S_D => from_single(double(fs) as f32),
+        Opcode::CVT_D_S => from_double(f64::from(single(fs))),
+        Opcode::CVT_S_W => from_single(fs as u32 as i32 as f32),
+        Opcode::CVT_D_W => from_double(f64::from(fs as u32 as i32)),
+        Opcode::CVT_W_S => to_word(f64::from(single(fs)), false),
+        Opcode::CVT_W_D => to_word(double(fs), false),
+        Opcode::TRUNC_W_S => to_word(f64::from(single(fs)), true),
+        Opcode::TRUNC_W_D => to_word(double(fs), true),
+        _ => unreachable!("{opcode} is not a floating-point arithmetic instruction"),
+    }
+}
+
+/// Evaluate a floating-point comparison, returning the new value of the condition code.
+#[must_use]
+pub fn fpu_compare(opcode: Opcode, fs: u64, ft: u64) -> bool {
+    let (unordered, equal, less) = if source_format(opcode) == FpuFormat::Single {
+        let (x, y) = (single(fs), single(ft));
+        (x.is_nan() || y.is_nan(), x == y, x < y)
+    } else {
+        let (x, y) = (double(fs), double(ft));
+        (x.is_nan() || y.is_nan(), x == y, x < y)
+    };
+
+    match opcode {
+        Opcode::C_UN_S | Opcode::C_UN_D => unordered,
+        Opcode::C_EQ_S | Opcode::C_EQ_D => equal,
+        Opcode::C_UEQ_S | Opcode::C_UEQ_D => unordered || equal,
+        Opcode::C_OLT_S | Opcode::C_OLT_D => less,
+        Opcode::C_ULT_S | Opcode::C_ULT_D => unordered || less,
+        Opcode::C_OLE_S | Opcode::C_OLE_D => less || equal,
+        Opcode::C_ULE_S | Opcode::C_ULE_D => unordered || less || equal,
+        _ => unreachable!("{opcode} is not a floating-point comparison"),
+    }
+}
+
+/// Evaluate `mov.d`, `abs.d` or `neg.d`, which only touch the sign bit.
+#[must_use]
+pub fn fpu_sign_move(opcode: Opcode, fs: u64) -> u64 {
+    match opcode {
+        Opcode::MOV_D => fs,
+        Opcode::ABS_D => fs & !(1 << 63),
+        Opcode::NEG_D => fs ^ (1 << 63),
+        _ => unreachable!("{opcode} is not a sign move"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s(x: f32) -> u64 {
+        u64::from(x.to_bits())
+    }
+
+    fn d(x: f64) -> u64 {
+        x.to_bits()
+    }
+
+    #[test]
+    fn test_fpu_arith() {
+        assert_eq!(fpu_arith(Opcode::FADD_S, s(1.5), s(2.25)), s(3.75));
+        assert_eq!(fpu_arith(Opcode::FSUB_D, d(1.0), d(1.0)), d(0.0));
+        assert_eq!(fpu_arith(Opcode::FSUB_D, d(-0.0), d(0.0)), d(-0.0));
+        assert_eq!(fpu_arith(Opcode::FMUL_S, s(f32::MAX), s(2.0)), s(f32::INFINITY));
+        assert_eq!(fpu_arith(Opcode::FDIV_D, d(1.0), d(-0.0)), d(f64::NEG_INFINITY));
+        assert_eq!(fpu_arith(Opcode::FDIV_D, d(0.0), d(0.0)), DEFAULT_NAN_D);
+        assert_eq!(fpu_arith(Opcode::FSQRT_S, s(-1.0), 0), u64::from(DEFAULT_NAN_S));
+        assert_eq!(fpu_arith(Opcode::FSQRT_D, d(2.0), 0), d(std::f64::consts::SQRT_2));
+        assert_eq!(fpu_arith(Opcode::CVT_S_D, d(1.0 + f64::EPSILON), 0), s(1.0));
+        assert_eq!(fpu_arith(Opcode::CVT_D_S, s(f32::NAN), 0), DEFAULT_NAN_D);
+        assert_eq!(fpu_arith(Opcode::CVT_S_W, u64::from(-3i32 as u32), 0), s(-3.0));
+        assert_eq!(fpu_arith(Opcode::CVT_W_S, s(2.5), 0), 2);
+        assert_eq!(fpu_arith(Opcode::CVT_W_D, d(-3.5), 0), u64::from(-4i32 as u32));
+        assert_eq!(fpu_arith(Opcode::TRUNC_W_D, d(-3.7), 0), u64::from(-3i32 as u32));
+        assert_eq!(fpu_arith(Opcode::TRUNC_W_D, d(-2147483648.9), 0), 0x8000_0000);
+        assert_eq!(fpu_arith(Opcode::CVT_W_S, s(2147483648.0), 0), u64::from(INVALID_WORD));
+        assert_eq!(fpu_arith(Opcode::TRUNC_W_S, s(f32::NAN), 0), u64::from(INVALID_WORD));
+    }
+
+    #[test]
+    fn test_fpu_compare() {
+        let nan = d(f64::NAN);
+        assert!(fpu_compare(Opcode::C_EQ_D, d(0.0), d(-0.0)));
+        assert!(!fpu_compare(Opcode::C_EQ_D, nan, nan));
+        assert!(fpu_compare(Opcode::C_UEQ_D, nan, d(1.0)));
+        assert!(fpu_compare(Opcode::C_OLT_S, s(-1.0), s(1.0)));
+        assert!(!fpu_compare(Opcode::C_OLT_S, s(1.0), s(1.0)));
+        assert!(fpu_compare(Opcode::C_OLE_S, s(1.0), s(1.0)));
+        assert!(fpu_compare(Opcode::C_ULT_S, s(f32::NAN), s(1.0)));
+        assert!(fpu_compare(Opcode::C_UN_S, s(f32::NAN), s(1.0)));
+        assert!(!fpu_compare(Opcode::C_UN_D, d(1.0), d(2.0)));
+    }
+}
@@ -0,0 +1,605 @@
+//! A GDB remote serial protocol stub for interactively debugging guest programs.
+//!
+//! The stub drives the [`Executor`] in [`ExecutorMode::Simple`], so no tracing overhead is paid
+//! while debugging. Connect with `gdb-multiarch`:
+//!
+//! ```text
+//! (gdb) set architecture mips
+//! (gdb) set endian little
+//! (gdb) target remote localhost:9000
+//! ```
+
+use std::{
+    io::{self, ErrorKind, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use hashbrown::HashSet;
+
+use crate::{events::MemoryRecord, ExecutionError, Executor, ExecutorMode, Register};
+
+/// The number of registers in the default `mips` register layout used by GDB:
+/// `r0-r31, sr, lo, hi, bad, cause, pc, f0-f31, fsr, fir`.
+const NUM_GDB_REGISTERS: usize = 72;
+
+/// The GDB register number of `lo`.
+const GDB_REG_LO: usize = 33;
+
+/// The GDB register number of `hi`.
+const GDB_REG_HI: usize = 34;
+
+/// The GDB register number of `pc`.
+const GDB_REG_PC: usize = 37;
+
+/// The number of cycles between two polls for an interrupt request while continuing.
+const INTERRUPT_POLL_FREQUENCY: u64 = 1 << 12;
+
+/// The byte sent by GDB to interrupt a running target.
+const INTERRUPT: u8 = 0x03;
+
+/// `SIGINT`, reported when the target was interrupted by the client.
+const SIGINT: u8 = 2;
+
+/// `SIGTRAP`, reported after a single step or when hitting a breakpoint.
+const SIGTRAP: u8 = 5;
+
+/// A connection to a GDB client.
+pub trait GdbConnection: Read + Write {
+    /// Returns whether the client requested an interrupt, without blocking.
+    fn poll_interrupt(&mut self) -> io::Result<bool>;
+}
+
+impl GdbConnection for TcpStream {
+    fn poll_interrupt(&mut self) -> io::Result<bool> {
+        self.set_nonblocking(true)?;
+        let mut byte = [0u8; 1];
+        let result = self.read(&mut byte);
+        self.set_nonblocking(false)?;
+        poll_result(result, byte[0])
+    }
+}
+
+#[cfg(unix)]
+impl GdbConnection for std::os::unix::net::UnixStream {
+    fn poll_interrupt(&mut self) -> io::Result<bool> {
+        self.set_nonblocking(true)?;
+        let mut byte = [0u8; 1];
+        let result = self.read(&mut byte);
+        self.set_nonblocking(false)?;
+        poll_result(result, byte[0])
+    }
+}
+
+/// Interpret the result of a non-blocking read of a single byte. A closed connection is treated as
+/// an interrupt so that the stub stops running the guest.
+fn poll_result(result: io::Result<usize>, byte: u8) -> io::Result<bool> {
+    match result {
+        Ok(0) => Ok(true),
+        Ok(_) => Ok(byte == INTERRUPT),
+        Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Why the guest stopped running.
+enum StopReason {
+    /// The guest received a signal.
+    Signal(u8),
+    /// The guest exited with the given exit code.
+    Exited(u32),
+}
+
+/// What the stub should do after handling a packet.
+enum Action {
+    /// Wait for the next packet.
+    Continue,
+    /// Resume the guest, either for a single step or until the next stop event.
+    Resume { step: bool },
+    /// Let the guest run to completion without the debugger.
+    Detach,
+    /// Stop debugging and terminate the execution.
+    Kill,
+}
+
+/// A GDB remote serial protocol stub controlling an [`Executor`].
+struct GdbStub<'a, 'b, C> {
+    rt: &'a mut Executor<'b>,
+    conn: C,
+    breakpoints: HashSet<u32>,
+    no_ack: bool,
+}
+
+impl Executor<'_> {
+    /// Executes the program under the control of a GDB client connecting to `addr` over TCP.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the program execution fails or if the connection to
+    /// the client fails.
+    pub fn run_gdb_tcp(&mut self, addr: impl ToSocketAddrs) -> Result<(), ExecutionError> {
+        let listener = TcpListener::bind(addr).map_err(gdb_error)?;
+        if let Ok(local_addr) = listener.local_addr() {
+            tracing::info!("waiting for gdb to connect on {local_addr}");
+        }
+        let (stream, _) = listener.accept().map_err(gdb_error)?;
+        stream.set_nodelay(true).map_err(gdb_error)?;
+        self.run_gdb(stream)
+    }
+
+    /// Executes the program under the control of a GDB client connecting to the Unix socket at
+    /// `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the program execution fails or if the connection to
+    /// the client fails.
+    #[cfg(unix)]
+    pub fn run_gdb_unix(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), ExecutionError> {
+        let listener = std::os::unix::net::UnixListener::bind(path).map_err(gdb_error)?;
+        let (stream, _) = listener.accept().map_err(gdb_error)?;
+        self.run_gdb(stream)
+    }
+
+    /// Executes the program under the control of a GDB client over an established connection.
+    ///
+    /// The guest is stopped at its entrypoint until the client resumes it.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the program execution fails or if the connection to
+    /// the client fails.
+    pub fn run_gdb(&mut self, conn: impl GdbConnection) -> Result<(), ExecutionError> {
+        self.executor_mode = ExecutorMode::Simple;
+        if self.state.global_clk == 0 {
+            self.initialize();
+        }
+        GdbStub { rt: self, conn, breakpoints: HashSet::new(), no_ack: false }.serve()
+    }
+
+    /// Executes a single instruction, together with its delay slot if it is a branch or jump.
+    /// Returns whether the program has finished.
+    fn step_instruction(&mut self) -> Result<bool, ExecutionError> {
+        let mut done = self.execute_cycle()?;
+        while !done && self.state.next_is_delayslot {
+            done = self.execute_cycle()?;
+        }
+        // Records are not needed in simple mode, so don't let them accumulate while debugging.
+        self.records.clear();
+        if done {
+            self.postprocess();
+        }
+        Ok(done)
+    }
+}
+
+impl<C: GdbConnection> GdbStub<'_, '_, C> {
+    /// Serve packets until the client detaches or kills the guest.
+    fn serve(&mut self) -> Result<(), ExecutionError> {
+        loop {
+            let Some(packet) = self.read_packet().map_err(gdb_error)? else {
+                // The client hung up.
+                return Ok(());
+            };
+
+            let action = self.handle_packet(&packet).map_err(gdb_error)?;
+            match action {
+                Action::Continue => {}
+                Action::Resume { step } => match self.resume(step) {
+                    Ok(StopReason::Signal(signal)) => {
+                        self.write_packet(&format!("S{signal:02x}")).map_err(gdb_error)?;
+                    }
+                    Ok(StopReason::Exited(code)) => {
+                        self.write_packet(&format!("W{:02x}", code & 0xff)).map_err(gdb_error)?;
+                        return Ok(());
+                    }
+                    Err(e) => {
+                        let reply = match &e {
+                            ExecutionError::HaltWithNonZeroExitCode(code) => {
+                                format!("W{:02x}", *code & 0xff)
+                            }
+                            _ => "X0b".to_string(),
+                        };
+                        // The execution already failed, so a failure to notify is not relevant.
+                        let _ = self.write_packet(&reply);
+                        return Err(e);
+                    }
+                },
+                Action::Detach => {
+                    while !self.rt.step_instruction()? {}
+                    return Ok(());
+                }
+                Action::Kill => return Ok(()),
+            }
+        }
+    }
+
+    /// Resume the guest for a single step or until a breakpoint, an interrupt or its exit.
+    fn resume(&mut self, step: bool) -> Result<StopReason, ExecutionError> {
+        loop {
+            if self.rt.step_instruction()? {
+                // A guest halting with a syscall exits with the code in `$a0`, while running off
+                // the end of the program exits with 0.
+                let code = if self.rt.state.exited {
+                    self.read_register(Register::A0 as usize)
+                } else {
+                    0
+                };
+                return Ok(StopReason::Exited(code));
+            }
+            if step || self.breakpoints.contains(&self.rt.state.pc) {
+                return Ok(StopReason::Signal(SIGTRAP));
+            }
+            if self.rt.state.global_clk.is_multiple_of(INTERRUPT_POLL_FREQUENCY)
+                && self.conn.poll_interrupt().map_err(gdb_error)?
+            {
+                return Ok(StopReason::Signal(SIGINT));
+            }
+        }
+    }
+
+    /// Handle a single packet, replying to it if needed.
+    fn handle_packet(&mut self, packet: &str) -> io::Result<Action> {
+        let (command, args) = packet.split_at(packet.chars().next().map_or(0, char::len_utf8));
+        let reply =
+            match command {
+                "?" => format!("S{SIGTRAP:02x}"),
+                "g" => (0..NUM_GDB_REGISTERS).map(|i| encode_u32(self.read_register(i))).collect(),
+                "G" => {
+                    let values = hex::decode(args).unwrap_or_default();
+                    for (i, chunk) in values.chunks_exact(4).enumerate().take(NUM_GDB_REGISTERS) {
+                        self.write_register(i, u32::from_le_bytes(chunk.try_into().unwrap()));
+                    }
+                    "OK".to_string()
+                }
+                "p" => match usize::from_str_radix(args, 16) {
+                    Ok(i) if i < NUM_GDB_REGISTERS => encode_u32(self.read_register(i)),
+                    _ => "E01".to_string(),
+                },
+                "P" => match parse_register_write(args) {
+                    Some((i, value)) if i < NUM_GDB_REGISTERS => {
+                        self.write_register(i, value);
+                        "OK".to_string()
+                    }
+                    _ => "E01".to_string(),
+                },
+                "m" => match parse_addr_len(args) {
+                    Some((addr, len)) => {
+                        let bytes = (0..len)
+                            .map(|i| self.read_byte(addr.wrapping_add(i)))
+                            .collect::<Option<Vec<_>>>();
+                        bytes.map_or_else(|| "E14".to_string(), hex::encode)
+                    }
+                    None => "E01".to_string(),
+                },
+                "M" => {
+                    let parsed = args.split_once(':').and_then(|(range, data)| {
+                        Some((parse_addr_len(range)?, hex::decode(data).ok()?))
+                    });
+                    match parsed {
+                        Some(((addr, len), data)) if data.len() == len as usize => {
+                            if data.iter().enumerate().all(|(i, byte)| {
+                                self.write_byte(addr.wrapping_add(i as u32), *byte)
+                            }) {
+                                "OK".to_string()
+                            } else {
+                                "E14".to_string()
+                            }
+                        }
+                        _ => "E01".to_string(),
+                    }
+                }
+                "c" | "s" => {
+                    if let Ok(addr) = u32::from_str_radix(args, 16) {
+                        self.set_pc(addr);
+                    }
+                    return Ok(Action::Resume { step: command == "s" });
+                }
+                "v" => {
+                    if args == "Cont?" {
+                        "vCont;c;C;s;S".to_string()
+                    } else if let Some(actions) = args.strip_prefix("Cont;") {
+                        // There is a single guest thread, so only the first action matters.
+                        let step = actions.starts_with('s') || actions.starts_with('S');
+                        return Ok(Action::Resume { step });
+                    } else {
+                        String::new()
+                    }
+                }
+                "Z" | "z" => match parse_breakpoint(args) {
+                    Some(addr) => {
+                        if command == "Z" {
+                            self.breakpoints.insert(addr);
+                        } else {
+                            self.breakpoints.remove(&addr);
+                        }
+                        "OK".to_string()
+                    }
+                    None => String::new(),
+                },
+                "q" => self.handle_query(args),
+                "Q" if args == "StartNoAckMode" => {
+                    self.write_packet("OK")?;
+                    self.no_ack = true;
+                    return Ok(Action::Continue);
+                }
+                "H" | "T" => "OK".to_string(),
+                "D" => {
+                    self.write_packet("OK")?;
+                    return Ok(Action::Detach);
+                }
+                "k" => return Ok(Action::Kill),
+                _ => String::new(),
+            };
+        self.write_packet(&reply)?;
+        Ok(Action::Continue)
+    }
+
+    /// Handle a general query packet.
+    fn handle_query(&self, query: &str) -> String {
+        if query.starts_with("Supported") {
+            "PacketSize=4000;QStartNoAckMode+;swbreak+;hwbreak+;vContSupported+".to_string()
+        } else if query == "C" {
+            "QC1".to_string()
+        } else if query == "fThreadInfo" {
+            "m1".to_string()
+        } else if query == "sThreadInfo" {
+            "l".to_string()
+        } else if query == "Attached" {
+            "1".to_string()
+        } else if query == "Offsets" {
+            "Text=0;Data=0;Bss=0".to_string()
+        } else {
+            String::new()
+        }
+    }
+
+    /// Read the value of a register, numbered according to GDB's `mips` register layout.
+    fn read_register(&self, index: usize) -> u32 {
+        let register = match index {
+            0..32 => index as u32,
+            GDB_REG_LO => 32,
+            GDB_REG_HI => 33,
+            GDB_REG_PC => return self.rt.state.pc,
+            _ => return 0,
+        };
+        self.rt.state.memory.get(register).map_or(0, |record| record.value)
+    }
+
+    /// Write the value of a register, numbered according to GDB's `mips` register layout. Writes to
+    /// `r0` and to registers which are not modelled by the executor are ignored.
+    fn write_register(&mut self, index: usize, value: u32) {
+        let register = match index {
+            1..32 => index as u32,
+            GDB_REG_LO => 32,
+            GDB_REG_HI => 33,
+            GDB_REG_PC => return self.set_pc(value),
+            _ => return,
+        };
+        self.rt
+            .state
+            .memory
+            .entry(register)
+            .and_modify(|record| record.value = value)
+            .or_insert(MemoryRecord { value, shard: 0, timestamp: 0 });
+    }
+
+    /// Move the program counter, leaving any pending delay slot.
+    fn set_pc(&mut self, pc: u32) {
+        self.rt.state.pc = pc;
+        self.rt.state.next_pc = pc.wrapping_add(4);
+    }
+
+    /// Read a byte of guest memory. Returns `None` for addresses which alias the registers.
+    fn read_byte(&self, addr: u32) -> Option<u8> {
+        let aligned = addr & !3;
+        if aligned <= 32 {
+            return None;
+        }
+        let word = match self.rt.state.memory.get(aligned) {
+            Some(record) => record.value,
+            None => self.rt.state.uninitialized_memory.get(aligned).copied().unwrap_or(0),
+        };
+        Some((word >> ((addr % 4) * 8)) as u8)
+    }
+
+    /// Write a byte of guest memory. Returns `false` for addresses which alias the registers.
+    fn write_byte(&mut self, addr: u32, byte: u8) -> bool {
+        let aligned = addr & !3;
+        if self.read_byte(aligned).is_none() {
+            return false;
+        }
+        let shift = (addr % 4) * 8;
+        let update = |word: u32| (word & !(0xff << shift)) | ((byte as u32) << shift);
+        let initial = self.rt.state.uninitialized_memory.get(aligned).copied().unwrap_or(0);
+        self.rt
+            .state
+            .memory
+            .entry(aligned)
+            .and_modify(|record| record.value = update(record.value))
+            .or_insert(MemoryRecord { value: update(initial), shard: 0, timestamp: 0 });
+        true
+    }
+
+    /// Read the next packet from the client, acknowledging it. Returns `None` if the connection
+    /// was closed.
+    fn read_packet(&mut self) -> io::Result<Option<String>> {
+        loop {
+            // Skip acknowledgements and interrupts received while the guest is stopped.
+            match self.read_u8()? {
+                None => return Ok(None),
+                Some(b'$') => {}
+                Some(_) => continue,
+            }
+
+            let mut payload = Vec::new();
+            loop {
+                match self.read_u8()? {
+                    None => return Ok(None),
+                    Some(b'#') => break,
+                    Some(byte) => payload.push(byte),
+                }
+            }
+
+            let mut checksum = [0u8; 2];
+            self.conn.read_exact(&mut checksum)?;
+            let expected = std::str::from_utf8(&checksum)
+                .ok()
+                .and_then(|checksum| u8::from_str_radix(checksum, 16).ok());
+            let valid = expected == Some(checksum_of(&payload));
+
+            if !self.no_ack {
+                self.conn.write_all(if valid { b"+" } else { b"-" })?;
+                self.conn.flush()?;
+            }
+            if valid {
+                return Ok(Some(String::from_utf8_lossy(&payload).into_owned()));
+            }
+        }
+    }
+
+    /// Send a packet to the client, retransmitting it until it is acknowledged.
+    fn write_packet(&mut self, payload: &str) -> io::Result<()> {
+        let packet = format!("${payload}#{:02x}", checksum_of(payload.as_bytes()));
+        loop {
+            self.conn.write_all(packet.as_bytes())?;
+            self.conn.flush()?;
+            if self.no_ack {
+                return Ok(());
+            }
+            match self.read_u8()? {
+                Some(b'-') => continue,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Read a single byte from the client. Returns `None` if the connection was closed.
+    fn read_u8(&mut self) -> io::Result<Option<u8>> {
+        let mut byte = [0u8; 1];
+        match self.conn.read(&mut byte)? {
+            0 => Ok(None),
+            _ => Ok(Some(byte[0])),
+        }
+    }
+}
+
+/// Convert a connection failure into an [`ExecutionError`].
+fn gdb_error(e: io::Error) -> ExecutionError {
+    ExecutionError::GdbConnection(e.to_string())
+}
+
+/// Compute the checksum of a packet payload.
+fn checksum_of(payload: &[u8]) -> u8 {
+    payload.iter().fold(0u8, |acc, byte| acc.wrapping_add(*byte))
+}
+
+/// Encode a register value in target (little-endian) byte order.
+fn encode_u32(value: u32) -> String {
+    hex::encode(value.to_le_bytes())
+}
+
+/// Parse the `addr,length` arguments of memory packets.
+fn parse_addr_len(args: &str) -> Option<(u32, u32)> {
+    let (addr, len) = args.split_once(',')?;
+    Some((u32::from_str_radix(addr, 16).ok()?, u32::from_str_radix(len, 16).ok()?))
+}
+
+/// Parse the `n=r` arguments of the `P` packet.
+fn parse_register_write(args: &str) -> Option<(usize, u32)> {
+    let (index, value) = args.split_once('=')?;
+    let value: [u8; 4] = hex::decode(value).ok()?.try_into().ok()?;
+    Some((usize::from_str_radix(index, 16).ok()?, u32::from_le_bytes(value)))
+}
+
+/// Parse the `type,addr,kind` arguments of software and hardware breakpoint packets.
+fn parse_breakpoint(args: &str) -> Option<u32> {
+    let mut parts = args.split(',');
+    match parts.next()? {
+        "0" | "1" => u32::from_str_radix(parts.next()?, 16).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Cursor, Read, Write};
+
+    use zkm_stark::ZKMCoreOpts;
+
+    use crate::{programs::tests::simple_program, Executor, Register};
+
+    use super::{checksum_of, GdbConnection};
+
+    /// A connection replaying a fixed sequence of packets and recording the replies.
+    struct MockConnection {
+        input: Cursor<Vec<u8>>,
+        output: Vec<u8>,
+    }
+
+    impl MockConnection {
+        fn new(packets: &[&str]) -> Self {
+            let input = packets
+                .iter()
+                .map(|p| format!("${p}#{:02x}+", checksum_of(p.as_bytes())))
+                .collect::<String>();
+            Self { input: Cursor::new(input.into_bytes()), output: Vec::new() }
+        }
+    }
+
+    impl Write for &mut MockConnection {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.output.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Read for &mut MockConnection {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl GdbConnection for &mut MockConnection {
+        fn poll_interrupt(&mut self) -> io::Result<bool> {
+            Ok(false)
+        }
+    }
+
+    #[test]
+    fn test_gdb_step_and_registers() {
+        let mut conn = MockConnection::new(&["?", "s", "p1d", "P1d=2a000000", "s", "s"]);
+        let mut runtime = Executor::new(simple_program(), ZKMCoreOpts::default());
+        runtime.run_gdb(&mut conn).unwrap();
+
+        let output = String::from_utf8(conn.output).unwrap();
+        // The stub stops after every step, and `sp` (r29) holds 5 after the first step.
+        assert!(output.contains("$S05#b8"));
+        assert!(output.contains("$05000000#"));
+        // The last step finishes the program.
+        assert!(output.contains("$W00#b7"));
+        // `ra` = `fp` + `sp` was computed from the value written by the debugger.
+        assert_eq!(runtime.register(Register::SP), 42);
+        assert_eq!(runtime.register(Register::RA), 79);
+    }
+
+    #[test]
+    fn test_gdb_breakpoint_and_memory() {
+        let mut conn = MockConnection::new(&["Z0,8,4", "c", "p25", "M100,4:01020304", "m100,4"]);
+        let mut runtime = Executor::new(simple_program(), ZKMCoreOpts::default());
+        runtime.run_gdb(&mut conn).unwrap();
+
+        let output = String::from_utf8(conn.output).unwrap();
+        // Continue stops at the breakpoint on the third instruction.
+        assert!(output.contains("$S05#b8"));
+        assert!(output.contains("$08000000#"));
+        assert!(output.contains("$01020304#"));
+        assert_eq!(runtime.state.pc, 8);
+        assert_eq!(runtime.word(0x100), 0x0403_0201);
+    }
+}
@@ -1,8 +1,13 @@
 use core::fmt::Debug;
 
-use std::sync::{Arc, RwLock, RwLockWriteGuard};
+use std::{
+    marker::PhantomData,
+    sync::{Arc, RwLock, RwLockWriteGuard},
+};
 
 use hashbrown::HashMap;
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
 use zkm_curves::{BigUint, One, Zero};
 
 use crate::Executor;
@@ -18,6 +23,25 @@ pub trait Hook {
     /// Invoke the runtime hook with a standard environment and arbitrary data.
     /// Returns the computed data.
     fn invoke_hook(&mut self, env: HookEnv, buf: &[u8]) -> Vec<Vec<u8>>;
+
+    /// Invoke the runtime hook, returning an error instead of panicking if the data is invalid.
+    ///
+    /// The executor calls this method. By default, it calls [`Hook::invoke_hook`].
+    fn try_invoke_hook(&mut self, env: HookEnv, buf: &[u8]) -> Result<Vec<Vec<u8>>, HookError> {
+        Ok(self.invoke_hook(env, buf))
+    }
+}
+
+/// Errors that a [`Hook`] can return for the data written by the program.
+#[derive(Error, Debug)]
+pub enum HookError {
+    /// The request written to a typed hook could not be decoded.
+    #[error("invalid request to typed hook `{0}`: {1}")]
+    InvalidRequest(String, String),
+
+    /// The response of a typed hook could not be encoded.
+    #[error("invalid response of typed hook `{0}`: {1}")]
+    InvalidResponse(String, String),
 }
 
 impl<F: FnMut(HookEnv, &[u8]) -> Vec<Vec<u8>>> Hook for F {
@@ -36,6 +60,44 @@ pub fn hookify<'a>(
     Arc::new(RwLock::new(f))
 }
 
+/// A typed function wrapped in a hook, so it may be placed in a `HookRegistry` under the file
+/// descriptor [`typed_hook_fd`] allocates for its name.
+///
+/// The request written by the guest is decoded with `bincode`, and the response is encoded the
+/// same way as the single vector read back by `zkm_zkvm::hook::call`.
+pub struct TypedHook<F, Req, Resp> {
+    name: String,
+    f: F,
+    _marker: PhantomData<fn(Req) -> Resp>,
+}
+
+/// Wrap a typed function in a [`TypedHook`] registered under `name`.
+pub fn typed_hook<Req: DeserializeOwned, Resp: Serialize, F: Fn(Req) -> Resp>(
+    name: &str,
+    f: F,
+) -> TypedHook<F, Req, Resp> {
+    TypedHook { name: name.to_string(), f, _marker: PhantomData }
+}
+
+impl<F: Fn(Req) -> Resp, Req: DeserializeOwned, Resp: Serialize> Hook for TypedHook<F, Req, Resp> {
+    /// Invokes the typed function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the request or the response is invalid.
+    fn invoke_hook(&mut self, env: HookEnv, buf: &[u8]) -> Vec<Vec<u8>> {
+        self.try_invoke_hook(env, buf).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn try_invoke_hook(&mut self, _: HookEnv, buf: &[u8]) -> Result<Vec<Vec<u8>>, HookError> {
+        let req = bincode::deserialize(buf)
+            .map_err(|err| HookError::InvalidRequest(self.name.clone(), err.to_string()))?;
+        let resp = bincode::serialize(&(self.f)(req))
+            .map_err(|err| HookError::InvalidResponse(self.name.clone(), err.to_string()))?;
+        Ok(vec![resp])
+    }
+}
+
 /// A registry of hooks to call, indexed by the file descriptors through which they are accessed.
 #[derive(Clone)]
 pub struct HookRegistry<'a> {
@@ -539,7 +601,10 @@ mod bls {
 
 #[cfg(test)]
 pub mod tests {
+    use zkm_stark::ZKMCoreOpts;
+
     use super::*;
+    use crate::programs::tests::simple_program;
 
     #[test]
     pub fn registry_new_is_inhabited() {
@@ -551,4 +616,29 @@ pub mod tests {
     pub fn registry_empty_is_empty() {
         assert_eq!(HookRegistry::empty().table.len(), 0);
     }
+
+    #[test]
+    pub fn typed_hook_fds_are_reserved() {
+        let fd = typed_hook_fd("square");
+        assert!(fd >= TYPED_HOOK_FD_START);
+        assert_ne!(fd, typed_hook_fd("cube"));
+        assert!(HookRegistry::new().table.keys().all(|&fd| fd < TYPED_HOOK_FD_START));
+    }
+
+    #[test]
+    pub fn typed_hook_round_trip() {
+        let mut hook = typed_hook("square", |x: u64| (x * x, x % 2 == 0));
+        let runtime = Executor::new(simple_program(), ZKMCoreOpts::default());
+        let res = hook.invoke_hook(runtime.hook_env(), &bincode::serialize(&7u64).unwrap());
+        assert_eq!(res.len(), 1);
+        assert_eq!(bincode::deserialize::<(u64, bool)>(&res[0]).unwrap(), (49, false));
+    }
+
+    #[test]
+    pub fn typed_hook_invalid_request() {
+        let mut hook = typed_hook("square", |x: u64| x * x);
+        let runtime = Executor::new(simple_program(), ZKMCoreOpts::default());
+        let res = hook.try_invoke_hook(runtime.hook_env(), &[7]);
+        assert!(matches!(res, Err(HookError::InvalidRequest(name, _)) if name == "square"));
+    }
 }
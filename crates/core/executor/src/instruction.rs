@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::opcode::Opcode;
 use crate::sign_extend;
+use crate::Register;
 
 /// MIPS Instruction.
 #[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
@@ -90,9 +91,16 @@ impl Instruction {
                 | Opcode::INS
                 | Opcode::MADDU
                 | Opcode::MSUBU
+                | Opcode::MADD
+                | Opcode::MSUB
                 | Opcode::MEQ
                 | Opcode::MNE
                 | Opcode::TEQ
+                | Opcode::TNE
+                | Opcode::TGE
+                | Opcode::TGEU
+                | Opcode::TLT
+                | Opcode::TLTU
         )
     }
     /// Returns if the instruction is a syscall instruction.
@@ -109,6 +117,8 @@ impl Instruction {
                 | Opcode::INS
                 | Opcode::MADDU
                 | Opcode::MSUBU
+                | Opcode::MADD
+                | Opcode::MSUB
                 | Opcode::MEQ
                 | Opcode::MNE
         )
@@ -132,7 +142,7 @@ impl Instruction {
                 | Opcode::SWR
                 | Opcode::LL
                 | Opcode::SC
-                | Opcode::LB // | Opcode::SDC1
+                | Opcode::LB
         )
     }
 
@@ -166,7 +176,29 @@ impl Instruction {
     pub const fn is_branch_instruction(&self) -> bool {
         matches!(
             self.opcode,
-            Opcode::BEQ | Opcode::BNE | Opcode::BLTZ | Opcode::BGEZ | Opcode::BLEZ | Opcode::BGTZ
+            Opcode::BEQ
+                | Opcode::BNE
+                | Opcode::BLTZ
+                | Opcode::BGEZ
+                | Opcode::BLEZ
+                | Opcode::BGTZ
+                | Opcode::BGEZAL
+                | Opcode::BLTZAL
+        )
+    }
+
+    /// Returns if the instruction is a branch instruction that writes the return address to `op_a`.
+    #[must_use]
+    pub const fn is_branch_link_instruction(&self) -> bool {
+        matches!(self.opcode, Opcode::BGEZAL | Opcode::BLTZAL)
+    }
+
+    /// Returns if the instruction is a conditional trap instruction.
+    #[must_use]
+    pub const fn is_trap_instruction(&self) -> bool {
+        matches!(
+            self.opcode,
+            Opcode::TEQ | Opcode::TNE | Opcode::TGE | Opcode::TGEU | Opcode::TLT | Opcode::TLTU
         )
     }
 
@@ -182,6 +214,15 @@ impl Instruction {
         matches!(self.opcode, Opcode::Jump | Opcode::Jumpi | Opcode::JumpDirect)
     }
 
+    /// Returns if the instruction is executed by one of the floating-point chips.
+    ///
+    /// Single precision moves, loads, stores and conditional moves are decoded to integer
+    /// instructions on the floating-point registers instead, see [`Instruction::decode_from`].
+    #[must_use]
+    pub const fn is_fpu_instruction(&self) -> bool {
+        self.opcode.is_fpu_arith() || self.opcode.is_fpu_compare() || self.opcode.is_fpu_move()
+    }
+
     pub fn decode_from(insn: u32) -> anyhow::Result<Self> {
         let opcode = ((insn >> 26) & 0x3F).to_le_bytes()[0];
         let func = (insn & 0x3F).to_le_bytes()[0];
@@ -306,6 +347,37 @@ impl Instruction {
                         true,
                         true,
                     ))
+                } else if rt == 0x11 {
+                    // BGEZAL
+                    Ok(Self::new(
+                        Opcode::BGEZAL,
+                        31,
+                        rs,
+                        offset_ext16.overflowing_shl(2).0,
+                        false,
+                        true,
+                    ))
+                } else if rt == 0x10 {
+                    // BLTZAL
+                    Ok(Self::new(
+                        Opcode::BLTZAL,
+                        31,
+                        rs,
+                        offset_ext16.overflowing_shl(2).0,
+                        false,
+                        true,
+                    ))
+                } else if (0x08..=0x0e).contains(&rt) && rt != 0x0d {
+                    // TGEI, TGEIU, TLTI, TLTIU, TEQI, TNEI
+                    let opcode = match rt {
+                        0x08 => Opcode::TGE,
+                        0x09 => Opcode::TGEU,
+                        0x0a => Opcode::TLT,
+                        0x0b => Opcode::TLTU,
+                        0x0c => Opcode::TEQ,
+                        _ => Opcode::TNE,
+                    };
+                    Ok(Self::new(opcode, rs as u8, offset_ext16, 0, true, true))
                 } else if rt == 0x1f {
                     // SYNCI
                     Ok(Self::new(Opcode::ADD, 0, 0, 0, true, true))
@@ -427,6 +499,16 @@ impl Instruction {
             (0b110011, _) => Ok(Self::new(Opcode::ADD, 0, 0, 0, true, true)), // Pref
             // TEQ
             (0b000000, 0b110100) => Ok(Self::new(Opcode::TEQ, rs as u8, rt, 0, false, true)), // teq
+            // TNE
+            (0b000000, 0b110110) => Ok(Self::new(Opcode::TNE, rs as u8, rt, 0, false, true)), // tne
+            // TGE
+            (0b000000, 0b110000) => Ok(Self::new(Opcode::TGE, rs as u8, rt, 0, false, true)), // tge
+            // TGEU
+            (0b000000, 0b110001) => Ok(Self::new(Opcode::TGEU, rs as u8, rt, 0, false, true)), // tgeu
+            // TLT
+            (0b000000, 0b110010) => Ok(Self::new(Opcode::TLT, rs as u8, rt, 0, false, true)), // tlt
+            // TLTU
+            (0b000000, 0b110011) => Ok(Self::new(Opcode::TLTU, rs as u8, rt, 0, false, true)), // tltu
             (0b011111, 0b100000) => {
                 if sa == 0b010000 {
                     // SEB
@@ -453,6 +535,34 @@ impl Instruction {
             (0b011100, 0b000001) => Ok(Self::new(Opcode::MADDU, 32, rt, rs, false, false)),
             // MSUBU
             (0b011100, 0b000101) => Ok(Self::new(Opcode::MSUBU, 32, rt, rs, false, false)),
+            // MADD
+            (0b011100, 0b000000) => Ok(Self::new(Opcode::MADD, 32, rt, rs, false, false)),
+            // MSUB
+            (0b011100, 0b000100) => Ok(Self::new(Opcode::MSUB, 32, rt, rs, false, false)),
+            // MOVF, MOVT: rd = rs if the condition code cc is false/true
+            (0b000000, 0b000001) => {
+                let (cc, tf) = (rt >> 2, rt & 1);
+                let opcode = if tf == 1 { Opcode::MNE } else { Opcode::MEQ };
+                Ok(Self::new(opcode, rd, rs, fcc(cc), false, false))
+            }
+            // COP1
+            (0b010001, _) => Ok(Self::decode_cop1(insn)),
+            // LWC1: ft = mem[rs + sext(imm)]
+            (0b110001, _) => {
+                Ok(Self::new(Opcode::LW, fpr(rt) as u8, rs, offset_ext16, false, true))
+            }
+            // SWC1: mem[rs + sext(imm)] = ft
+            (0b111001, _) => {
+                Ok(Self::new(Opcode::SW, fpr(rt) as u8, rs, offset_ext16, false, true))
+            }
+            // LDC1: (ft, ft + 1) = mem[rs + sext(imm)]
+            (0b110101, _) if rt % 2 == 0 => {
+                Ok(Self::new(Opcode::LDC1, 0, rs, offset | fpr(rt) << 16, false, true))
+            }
+            // SDC1: mem[rs + sext(imm)] = (ft, ft + 1)
+            (0b111101, _) if rt % 2 == 0 => {
+                Ok(Self::new(Opcode::SDC1, 0, rs, offset | fpr(rt) << 16, false, true))
+            }
             _ => {
                 log::warn!("decode: invalid opcode {opcode:#08b} {func:#08b}");
                 Ok(Self::new_with_raw(Opcode::UNIMPL, 0, 0, insn, true, true, insn))
@@ -461,6 +571,167 @@ impl Instruction {
     }
 }
 
+impl Instruction {
+    /// Decode a coprocessor 1 (floating-point unit) instruction.
+    ///
+    /// The floating-point registers and condition codes are mapped to the registers
+    /// [`Register::F0`] and [`Register::FCC0`] onwards, so that the moves between the register
+    /// files and the single precision moves are executed as integer instructions. The arithmetic,
+    /// conversion and comparison instructions and the double precision moves have their own
+    /// opcodes, which take the address of `fs` in `op_b` and the addresses of `ft` and `fd` in the
+    /// lower two bytes of `op_c`.
+    ///
+    /// The FPU always runs in the `FR = 0` mode, in which a double precision value is held in an
+    /// even/odd register pair, and always rounds to nearest. Of the control registers, `FIR` reads
+    /// as a constant, `FEXR` and `FENR` read as zero since the exception flags are not recorded,
+    /// the writes to `FEXR` are ignored and a nonzero write to `FENR` traps. The accesses to
+    /// `FCCR` and `FCSR`, the branch likely instructions, the fused multiply-adds and the `L`
+    /// format are not supported.
+    fn decode_cop1(insn: u32) -> Self {
+        let fmt = (insn >> 21) & 0x1F;
+        let ft = (insn >> 16) & 0x1F;
+        let fs = (insn >> 11) & 0x1F;
+        let fd = (insn >> 6) & 0x1F;
+        let func = insn & 0x3F;
+        let offset_ext16 = sign_extend::<16>(insn & 0xffff);
+        let unimpl = Self::new_with_raw(Opcode::UNIMPL, 0, 0, insn, true, true, insn);
+
+        // An instruction on the floating-point registers `fs` and `ft`, writing to `fd`.
+        let binary =
+            |opcode: Opcode| Self::new(opcode, 0, fpr(fs), fpr(ft) | fpr(fd) << 8, true, true);
+        // An instruction on the floating-point register `fs`, writing to `fd`.
+        let unary = |opcode: Opcode| Self::new(opcode, 0, fpr(fs), fpr(fd) << 8, true, true);
+
+        match fmt {
+            // MFC1: rt = fs
+            0x00 => Self::new(Opcode::ADD, ft as u8, fpr(fs), 0, false, true),
+            // CFC1: rt = fs, for the control registers FIR, FEXR and FENR
+            0x02 => match fs {
+                0 => Self::new(Opcode::ADD, ft as u8, 0, FIR, false, true),
+                26 | 28 => Self::new(Opcode::ADD, ft as u8, 0, 0, false, true),
+                _ => unimpl,
+            },
+            // MTC1: fs = rt
+            0x04 => Self::new(Opcode::ADD, fpr(fs) as u8, ft, 0, false, true),
+            // CTC1: fs = rt, for the control registers FEXR and FENR
+            0x06 => match fs {
+                // The exception flags are not recorded, so clearing or setting them is a nop.
+                26 => Self::new(Opcode::ADD, 0, 0, 0, true, true),
+                // Only rounding to nearest without any exception enabled is supported.
+                28 => Self::new(Opcode::TNE, ft as u8, 0, 0, true, true),
+                _ => unimpl,
+            },
+            // BC1F, BC1T
+            0x08 => {
+                let (cc, nd, tf) = (ft >> 2, (ft >> 1) & 1, ft & 1);
+                if nd == 1 {
+                    return unimpl;
+                }
+                let opcode = if tf == 1 { Opcode::BNE } else { Opcode::BEQ };
+                Self::new(opcode, fcc(cc) as u8, 0, offset_ext16.overflowing_shl(2).0, false, true)
+            }
+            0x10 | 0x11 => {
+                let double = fmt == 0x11;
+                // A double precision operand must be held in an even/odd register pair.
+                let even = |reg: u32| !double || reg % 2 == 0;
+                let pick =
+                    |single: Opcode, double_op: Opcode| if double { double_op } else { single };
+                match func {
+                    // ADD.fmt, SUB.fmt, MUL.fmt, DIV.fmt: fd = fs op ft
+                    0x00..=0x03 if even(fs) && even(ft) && even(fd) => {
+                        let opcode = match func {
+                            0x00 => pick(Opcode::FADD_S, Opcode::FADD_D),
+                            0x01 => pick(Opcode::FSUB_S, Opcode::FSUB_D),
+                            0x02 => pick(Opcode::FMUL_S, Opcode::FMUL_D),
+                            _ => pick(Opcode::FDIV_S, Opcode::FDIV_D),
+                        };
+                        binary(opcode)
+                    }
+                    // SQRT.fmt: fd = sqrt(fs)
+                    0x04 if even(fs) && even(fd) => unary(pick(Opcode::FSQRT_S, Opcode::FSQRT_D)),
+                    // ABS.fmt: fd = |fs|
+                    0x05 if double && even(fs) && even(fd) => unary(Opcode::ABS_D),
+                    0x05 if !double => {
+                        Self::new(Opcode::AND, fpr(fd) as u8, fpr(fs), 0x7fff_ffff, false, true)
+                    }
+                    // MOV.fmt: fd = fs
+                    0x06 if double && even(fs) && even(fd) => unary(Opcode::MOV_D),
+                    0x06 if !double => {
+                        Self::new(Opcode::ADD, fpr(fd) as u8, fpr(fs), 0, false, true)
+                    }
+                    // NEG.fmt: fd = -fs
+                    0x07 if double && even(fs) && even(fd) => unary(Opcode::NEG_D),
+                    0x07 if !double => {
+                        Self::new(Opcode::XOR, fpr(fd) as u8, fpr(fs), 0x8000_0000, false, true)
+                    }
+                    // TRUNC.W.fmt: fd = trunc(fs)
+                    0x0d if even(fs) => unary(pick(Opcode::TRUNC_W_S, Opcode::TRUNC_W_D)),
+                    // MOVF.fmt, MOVT.fmt: fd = fs if the condition code cc is false/true
+                    0x11 | 0x12 | 0x13 if even(fs) && even(fd) => {
+                        // The condition register: a condition code, or the GPR rt for MOVZ/MOVN.
+                        let (cond, is_movn) = match func {
+                            0x11 => (fcc(ft >> 2), ft & 1 == 1),
+                            0x12 => (ft, false),
+                            _ => (ft, true),
+                        };
+                        if double {
+                            let opcode = if is_movn { Opcode::MOVN_D } else { Opcode::MOVZ_D };
+                            Self::new(opcode, 0, fpr(fs), cond | fpr(fd) << 8, true, true)
+                        } else {
+                            let opcode = if is_movn { Opcode::MNE } else { Opcode::MEQ };
+                            Self::new(opcode, fpr(fd) as u8, fpr(fs), cond, false, false)
+                        }
+                    }
+                    // CVT.S.D: fd = (single) fs
+                    0x20 if double && even(fs) => unary(Opcode::CVT_S_D),
+                    // CVT.D.S: fd = (double) fs
+                    0x21 if !double && fd % 2 == 0 => unary(Opcode::CVT_D_S),
+                    // CVT.W.fmt: fd = round(fs)
+                    0x24 if even(fs) => unary(pick(Opcode::CVT_W_S, Opcode::CVT_W_D)),
+                    // C.cond.fmt: cc = fs cond ft
+                    0x30..=0x3f if even(fs) && even(ft) => {
+                        let cc = fd >> 2;
+                        let opcode = match func & 0x7 {
+                            // C.F, C.SF: cc = false
+                            0 => return Self::new(Opcode::ADD, fcc(cc) as u8, 0, 0, false, true),
+                            1 => pick(Opcode::C_UN_S, Opcode::C_UN_D),
+                            2 => pick(Opcode::C_EQ_S, Opcode::C_EQ_D),
+                            3 => pick(Opcode::C_UEQ_S, Opcode::C_UEQ_D),
+                            4 => pick(Opcode::C_OLT_S, Opcode::C_OLT_D),
+                            5 => pick(Opcode::C_ULT_S, Opcode::C_ULT_D),
+                            6 => pick(Opcode::C_OLE_S, Opcode::C_OLE_D),
+                            _ => pick(Opcode::C_ULE_S, Opcode::C_ULE_D),
+                        };
+                        Self::new(opcode, 0, fpr(fs), fpr(ft) | fcc(cc) << 8, true, true)
+                    }
+                    _ => unimpl,
+                }
+            }
+            0x14 => match func {
+                // CVT.S.W: fd = (single) fs
+                0x20 => unary(Opcode::CVT_S_W),
+                // CVT.D.W: fd = (double) fs
+                0x21 if fd % 2 == 0 => unary(Opcode::CVT_D_W),
+                _ => unimpl,
+            },
+            _ => unimpl,
+        }
+    }
+}
+
+/// The value of the FPU implementation register `FIR`, which has the `S`, `D` and `W` formats.
+const FIR: u32 = 0x0013_0000;
+
+/// The register address of the floating-point register `index`.
+fn fpr(index: u32) -> u32 {
+    Register::fpr(index) as u32
+}
+
+/// The register address of the floating-point condition code `index`.
+fn fcc(index: u32) -> u32 {
+    Register::fcc(index) as u32
+}
+
 impl Debug for Instruction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mnemonic = self.opcode.mnemonic();
@@ -477,3 +748,132 @@ impl Debug for Instruction {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Instruction;
+    use crate::Opcode;
+
+    #[test]
+    fn test_decode_integer_isa() {
+        let decode = |insn: u32| Instruction::decode_from(insn).unwrap();
+
+        // madd $10, $11
+        assert_eq!(decode(0x714b_0000), Instruction::new(Opcode::MADD, 32, 11, 10, false, false));
+        // msub $10, $11
+        assert_eq!(decode(0x714b_0004), Instruction::new(Opcode::MSUB, 32, 11, 10, false, false));
+        // bgezal $4, 3
+        assert_eq!(decode(0x0491_0003), Instruction::new(Opcode::BGEZAL, 31, 4, 12, false, true));
+        // bltzal $4, -1
+        assert_eq!(
+            decode(0x0490_ffff),
+            Instruction::new(Opcode::BLTZAL, 31, 4, 0xffff_fffc, false, true)
+        );
+        // bal 3
+        assert_eq!(
+            decode(0x0411_0003),
+            Instruction::new(Opcode::JumpDirect, 31, 12, 0, true, true)
+        );
+        // tne $5, $6
+        assert_eq!(decode(0x00a6_0036), Instruction::new(Opcode::TNE, 5, 6, 0, false, true));
+        // tltu $5, $6
+        assert_eq!(decode(0x00a6_0033), Instruction::new(Opcode::TLTU, 5, 6, 0, false, true));
+        // tgei $5, -1
+        assert_eq!(
+            decode(0x04a8_ffff),
+            Instruction::new(Opcode::TGE, 5, 0xffff_ffff, 0, true, true)
+        );
+        // teqi $5, 7
+        assert_eq!(decode(0x04ac_0007), Instruction::new(Opcode::TEQ, 5, 7, 0, true, true));
+        // tltiu $5, 7
+        assert_eq!(decode(0x04ab_0007), Instruction::new(Opcode::TLTU, 5, 7, 0, true, true));
+    }
+
+    #[test]
+    fn test_decode_cop1() {
+        let decode = |insn: u32| Instruction::decode_from(insn).unwrap();
+        // The register addresses of $f0 and $fcc0.
+        let (f, fcc) = (|i: u32| 34 + i, |i: u32| 66 + i);
+
+        // add.s $f0, $f2, $f4
+        assert_eq!(
+            decode(0x4604_1000),
+            Instruction::new(Opcode::FADD_S, 0, f(2), f(4) | f(0) << 8, true, true)
+        );
+        // add.d $f0, $f2, $f4
+        assert_eq!(
+            decode(0x4624_1000),
+            Instruction::new(Opcode::FADD_D, 0, f(2), f(4) | f(0) << 8, true, true)
+        );
+        // add.d $f1, $f2, $f4 is not a valid register pair
+        assert_eq!(decode(0x4624_1040).opcode, Opcode::UNIMPL);
+        // sqrt.d $f2, $f4
+        assert_eq!(
+            decode(0x4620_2084),
+            Instruction::new(Opcode::FSQRT_D, 0, f(4), f(2) << 8, true, true)
+        );
+        // trunc.w.s $f0, $f2
+        assert_eq!(
+            decode(0x4600_100d),
+            Instruction::new(Opcode::TRUNC_W_S, 0, f(2), f(0) << 8, true, true)
+        );
+        // cvt.d.w $f0, $f3
+        assert_eq!(
+            decode(0x4680_1821),
+            Instruction::new(Opcode::CVT_D_W, 0, f(3), f(0) << 8, true, true)
+        );
+        // c.lt.d $fcc1, $f2, $f4
+        assert_eq!(
+            decode(0x4624_113c),
+            Instruction::new(Opcode::C_OLT_D, 0, f(2), f(4) | fcc(1) << 8, true, true)
+        );
+        // c.f.s $fcc0, $f0, $f0
+        assert_eq!(decode(0x4600_0030), Instruction::new(Opcode::ADD, 66, 0, 0, false, true));
+        // movz.d $f0, $f4, $2
+        assert_eq!(
+            decode(0x4622_2012),
+            Instruction::new(Opcode::MOVZ_D, 0, f(4), 2 | f(0) << 8, true, true)
+        );
+        // ldc1 $f2, 8($sp)
+        assert_eq!(
+            decode(0xd7a2_0008),
+            Instruction::new(Opcode::LDC1, 0, 29, 8 | f(2) << 16, false, true)
+        );
+
+        // The accesses to the control registers are integer instructions too.
+        // cfc1 $8, $0
+        assert_eq!(
+            decode(0x4448_0000),
+            Instruction::new(Opcode::ADD, 8, 0, 0x0013_0000, false, true)
+        );
+        // cfc1 $8, $28
+        assert_eq!(decode(0x4448_e000), Instruction::new(Opcode::ADD, 8, 0, 0, false, true));
+        // ctc1 $8, $26
+        assert_eq!(decode(0x44c8_d000), Instruction::new(Opcode::ADD, 0, 0, 0, true, true));
+        // ctc1 $8, $28
+        assert_eq!(decode(0x44c8_e000), Instruction::new(Opcode::TNE, 8, 0, 0, true, true));
+        // cfc1 $8, $31 and ctc1 $8, $31 are not supported
+        assert_eq!(decode(0x4448_f800).opcode, Opcode::UNIMPL);
+        assert_eq!(decode(0x44c8_f800).opcode, Opcode::UNIMPL);
+
+        // Moves between the register files and single precision moves are integer instructions.
+        // mfc1 $8, $f1
+        assert_eq!(decode(0x4408_0800), Instruction::new(Opcode::ADD, 8, f(1), 0, false, true));
+        // mtc1 $8, $f1
+        assert_eq!(decode(0x4488_0800), Instruction::new(Opcode::ADD, 35, 8, 0, false, true));
+        // abs.s $f0, $f2
+        assert_eq!(
+            decode(0x4600_1005),
+            Instruction::new(Opcode::AND, 34, f(2), 0x7fff_ffff, false, true)
+        );
+        // movt $8, $9, $fcc1
+        assert_eq!(decode(0x0125_4001), Instruction::new(Opcode::MNE, 8, 9, fcc(1), false, false));
+        // bc1t 3
+        assert_eq!(decode(0x4501_0003), Instruction::new(Opcode::BNE, 66, 0, 12, false, true));
+        // bc1f $fcc2, -1
+        assert_eq!(
+            decode(0x4508_ffff),
+            Instruction::new(Opcode::BEQ, 68, 0, 0xffff_fffc, false, true)
+        );
+    }
+}
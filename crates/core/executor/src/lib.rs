@@ -1,14 +1,20 @@
 mod air;
+pub mod backtrace;
+mod checks;
 mod context;
 mod cost;
 mod dependencies;
 pub mod events;
 mod executor;
+pub mod fpu;
+pub mod gdb;
 pub mod hook;
 mod instruction;
 mod io;
 pub mod memory;
 mod opcode;
+pub mod preimage;
+pub mod profiler;
 mod program;
 #[cfg(test)]
 pub mod programs;
@@ -22,12 +28,14 @@ pub mod syscalls;
 mod utils;
 
 pub use air::*;
+pub use checks::*;
 pub use context::*;
 pub use cost::*;
 pub use executor::*;
 pub use hook::*;
 pub use instruction::*;
 pub use opcode::*;
+pub use preimage::*;
 pub use program::*;
 pub use record::*;
 pub use reduce::*;
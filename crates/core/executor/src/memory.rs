@@ -1,6 +1,8 @@
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use vec_map::VecMap;
 
+use crate::Register;
+
 /// A page of memory.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Page<V>(VecMap<V>);
@@ -47,8 +49,8 @@ pub struct PagedMemory<V: Copy> {
 impl<V: Copy> PagedMemory<V> {
     /// The number of lower bits to ignore, since addresses (except registers) are a multiple of 4.
     const NUM_IGNORED_LOWER_BITS: usize = 2;
-    /// The number of registers in the virtual machine.
-    const NUM_REGISTERS: usize = 34;
+    /// The number of registers in the virtual machine, including the COP1 registers.
+    const NUM_REGISTERS: usize = Register::COUNT;
     /// The offset subtracted from the main address space to make it contiguous.
     const ADDR_COMPRESS_OFFSET: usize = Self::NUM_REGISTERS;
 
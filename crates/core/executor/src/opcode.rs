@@ -62,7 +62,7 @@ pub enum Opcode {
     // Misc
     MEQ = 43,   // MOVCOND
     MNE = 44,   // MOVCOND
-    TEQ = 45,   // MOVCOND
+    TEQ = 45,   // TRAP
     SEXT = 46,  // SEXT
     WSBH = 47,  // MISC
     EXT = 48,   // EXT
@@ -71,6 +71,57 @@ pub enum Opcode {
     INS = 51,   // INS
     MOD = 52,   // DIVREM
     MODU = 53,  // DIVREM
+    MADD = 54,  // MADDSUB
+    MSUB = 55,  // MADDSUB
+    // Control Flow
+    BGEZAL = 56, // BRANCH
+    BLTZAL = 57, // BRANCH
+    // Misc
+    TNE = 58,  // TRAP
+    TGE = 59,  // TRAP
+    TGEU = 60, // TRAP
+    TLT = 61,  // TRAP
+    TLTU = 62, // TRAP
+    // Floating point
+    FADD_S = 63,    // FPU_ARITH
+    FSUB_S = 64,    // FPU_ARITH
+    FMUL_S = 65,    // FPU_ARITH
+    FDIV_S = 66,    // FPU_ARITH
+    FSQRT_S = 67,   // FPU_ARITH
+    FADD_D = 68,    // FPU_ARITH
+    FSUB_D = 69,    // FPU_ARITH
+    FMUL_D = 70,    // FPU_ARITH
+    FDIV_D = 71,    // FPU_ARITH
+    FSQRT_D = 72,   // FPU_ARITH
+    CVT_S_D = 73,   // FPU_ARITH
+    CVT_D_S = 74,   // FPU_ARITH
+    CVT_S_W = 75,   // FPU_ARITH
+    CVT_D_W = 76,   // FPU_ARITH
+    CVT_W_S = 77,   // FPU_ARITH
+    CVT_W_D = 78,   // FPU_ARITH
+    TRUNC_W_S = 79, // FPU_ARITH
+    TRUNC_W_D = 80, // FPU_ARITH
+    C_UN_S = 81,    // FPU_CMP
+    C_EQ_S = 82,    // FPU_CMP
+    C_UEQ_S = 83,   // FPU_CMP
+    C_OLT_S = 84,   // FPU_CMP
+    C_ULT_S = 85,   // FPU_CMP
+    C_OLE_S = 86,   // FPU_CMP
+    C_ULE_S = 87,   // FPU_CMP
+    C_UN_D = 88,    // FPU_CMP
+    C_EQ_D = 89,    // FPU_CMP
+    C_UEQ_D = 90,   // FPU_CMP
+    C_OLT_D = 91,   // FPU_CMP
+    C_ULT_D = 92,   // FPU_CMP
+    C_OLE_D = 93,   // FPU_CMP
+    C_ULE_D = 94,   // FPU_CMP
+    LDC1 = 95,      // FPU_MOVE
+    SDC1 = 96,      // FPU_MOVE
+    MOV_D = 97,     // FPU_MOVE
+    ABS_D = 98,     // FPU_MOVE
+    NEG_D = 99,     // FPU_MOVE
+    MOVN_D = 100,   // FPU_MOVE
+    MOVZ_D = 101,   // FPU_MOVE
     UNIMPL = 0xff,
 }
 
@@ -133,6 +184,54 @@ impl Opcode {
             Opcode::MSUBU => "msubu",
             Opcode::MOD => "mod",
             Opcode::MODU => "modu",
+            Opcode::MADD => "madd",
+            Opcode::MSUB => "msub",
+            Opcode::BGEZAL => "bgezal",
+            Opcode::BLTZAL => "bltzal",
+            Opcode::TNE => "tne",
+            Opcode::TGE => "tge",
+            Opcode::TGEU => "tgeu",
+            Opcode::TLT => "tlt",
+            Opcode::TLTU => "tltu",
+            Opcode::FADD_S => "add.s",
+            Opcode::FSUB_S => "sub.s",
+            Opcode::FMUL_S => "mul.s",
+            Opcode::FDIV_S => "div.s",
+            Opcode::FSQRT_S => "sqrt.s",
+            Opcode::FADD_D => "add.d",
+            Opcode::FSUB_D => "sub.d",
+            Opcode::FMUL_D => "mul.d",
+            Opcode::FDIV_D => "div.d",
+            Opcode::FSQRT_D => "sqrt.d",
+            Opcode::CVT_S_D => "cvt.s.d",
+            Opcode::CVT_D_S => "cvt.d.s",
+            Opcode::CVT_S_W => "cvt.s.w",
+            Opcode::CVT_D_W => "cvt.d.w",
+            Opcode::CVT_W_S => "cvt.w.s",
+            Opcode::CVT_W_D => "cvt.w.d",
+            Opcode::TRUNC_W_S => "trunc.w.s",
+            Opcode::TRUNC_W_D => "trunc.w.d",
+            Opcode::C_UN_S => "c.un.s",
+            Opcode::C_EQ_S => "c.eq.s",
+            Opcode::C_UEQ_S => "c.ueq.s",
+            Opcode::C_OLT_S => "c.olt.s",
+            Opcode::C_ULT_S => "c.ult.s",
+            Opcode::C_OLE_S => "c.ole.s",
+            Opcode::C_ULE_S => "c.ule.s",
+            Opcode::C_UN_D => "c.un.d",
+            Opcode::C_EQ_D => "c.eq.d",
+            Opcode::C_UEQ_D => "c.ueq.d",
+            Opcode::C_OLT_D => "c.olt.d",
+            Opcode::C_ULT_D => "c.ult.d",
+            Opcode::C_OLE_D => "c.ole.d",
+            Opcode::C_ULE_D => "c.ule.d",
+            Opcode::LDC1 => "ldc1",
+            Opcode::SDC1 => "sdc1",
+            Opcode::MOV_D => "mov.d",
+            Opcode::ABS_D => "abs.d",
+            Opcode::NEG_D => "neg.d",
+            Opcode::MOVN_D => "movn.d",
+            Opcode::MOVZ_D => "movz.d",
             Opcode::UNIMPL => "unimpl",
         }
     }
@@ -152,12 +251,77 @@ impl Opcode {
                 | Opcode::MULTU
                 | Opcode::MADDU
                 | Opcode::MSUBU
+                | Opcode::MADD
+                | Opcode::MSUB
         )
     }
 
     pub fn only_one_operand(&self) -> bool {
         matches!(self, Opcode::BGEZ | Opcode::BLEZ | Opcode::BGTZ | Opcode::BLTZ)
     }
+
+    /// Returns if the opcode is a floating-point arithmetic or conversion operation.
+    #[must_use]
+    pub const fn is_fpu_arith(&self) -> bool {
+        matches!(
+            self,
+            Opcode::FADD_S
+                | Opcode::FSUB_S
+                | Opcode::FMUL_S
+                | Opcode::FDIV_S
+                | Opcode::FSQRT_S
+                | Opcode::FADD_D
+                | Opcode::FSUB_D
+                | Opcode::FMUL_D
+                | Opcode::FDIV_D
+                | Opcode::FSQRT_D
+                | Opcode::CVT_S_D
+                | Opcode::CVT_D_S
+                | Opcode::CVT_S_W
+                | Opcode::CVT_D_W
+                | Opcode::CVT_W_S
+                | Opcode::CVT_W_D
+                | Opcode::TRUNC_W_S
+                | Opcode::TRUNC_W_D
+        )
+    }
+
+    /// Returns if the opcode is a floating-point comparison.
+    #[must_use]
+    pub const fn is_fpu_compare(&self) -> bool {
+        matches!(
+            self,
+            Opcode::C_UN_S
+                | Opcode::C_EQ_S
+                | Opcode::C_UEQ_S
+                | Opcode::C_OLT_S
+                | Opcode::C_ULT_S
+                | Opcode::C_OLE_S
+                | Opcode::C_ULE_S
+                | Opcode::C_UN_D
+                | Opcode::C_EQ_D
+                | Opcode::C_UEQ_D
+                | Opcode::C_OLT_D
+                | Opcode::C_ULT_D
+                | Opcode::C_OLE_D
+                | Opcode::C_ULE_D
+        )
+    }
+
+    /// Returns if the opcode moves a double precision value, or loads or stores one.
+    #[must_use]
+    pub const fn is_fpu_move(&self) -> bool {
+        matches!(
+            self,
+            Opcode::LDC1
+                | Opcode::SDC1
+                | Opcode::MOV_D
+                | Opcode::ABS_D
+                | Opcode::NEG_D
+                | Opcode::MOVN_D
+                | Opcode::MOVZ_D
+        )
+    }
 }
 
 impl Display for Opcode {
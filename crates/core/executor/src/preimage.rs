@@ -0,0 +1,124 @@
+//! Preimage oracles backing the Linux syscall mode of the [`crate::Executor`].
+//!
+//! Programs built for Optimism's Cannon (e.g. `op-program`) fetch their inputs by writing a
+//! 32-byte key to a special file descriptor and reading the preimage of that key back from
+//! another one. Hints, written to a third descriptor, tell the host which data the program is
+//! about to request.
+
+use std::{fs::File, io::BufReader, path::Path};
+
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A preimage key, whose first byte is the key type as defined by the Cannon preimage oracle ABI.
+pub type PreimageKey = [u8; 32];
+
+/// A source of preimages for programs running in the Linux syscall mode.
+///
+/// Note that the proof only attests to the execution given the preimages returned by the oracle:
+/// a program must check the preimages it reads against their keys (e.g. by hashing them) if the
+/// oracle is not trusted.
+pub trait PreimageOracle: Send + Sync {
+    /// Get the preimage of `key`, if it is known.
+    fn get(&self, key: &PreimageKey) -> Option<Vec<u8>>;
+
+    /// Receive a hint from the program.
+    ///
+    /// Hints are advisory, so by default they are ignored.
+    fn hint(&self, _hint: &[u8]) {}
+}
+
+/// Errors that can occur when loading a [`KvPreimageOracle`].
+#[derive(Error, Debug)]
+pub enum PreimageOracleError {
+    /// The file could not be read.
+    #[error("failed to read the preimage file: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The file is not a JSON object.
+    #[error("failed to parse the preimage file: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// A key or a value is not valid hex, or a key is not 32 bytes long.
+    #[error("invalid preimage entry {0}")]
+    InvalidEntry(String),
+}
+
+/// A [`PreimageOracle`] backed by an in-memory key/value store.
+///
+/// The store can be loaded from a JSON object mapping hex-encoded keys to hex-encoded preimages,
+///
+/// ```json
+/// { "0x02d1...": "0xf901..." }
+/// ```
+///
+/// or from a fixture directory holding one `<key>.txt` file with the hex-encoded preimage per key,
+/// the layout written by `op-program --datadir`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KvPreimageOracle {
+    preimages: HashMap<PreimageKey, Vec<u8>>,
+}
+
+impl KvPreimageOracle {
+    /// Create an empty [`KvPreimageOracle`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a [`KvPreimageOracle`] from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, PreimageOracleError> {
+        let reader = BufReader::new(File::open(path)?);
+        let entries: HashMap<String, String> = serde_json::from_reader(reader)?;
+
+        let mut oracle = Self::new();
+        for (key, value) in entries {
+            let key_bytes = decode_hex(&key)
+                .and_then(|bytes| PreimageKey::try_from(bytes).ok())
+                .ok_or_else(|| PreimageOracleError::InvalidEntry(key.clone()))?;
+            let value = decode_hex(&value).ok_or(PreimageOracleError::InvalidEntry(key))?;
+            oracle.insert(key_bytes, value);
+        }
+        Ok(oracle)
+    }
+
+    /// Load a [`KvPreimageOracle`] from a fixture directory.
+    ///
+    /// Files that are not named after a key are skipped.
+    pub fn from_dir(path: impl AsRef<Path>) -> Result<Self, PreimageOracleError> {
+        let mut oracle = Self::new();
+        for entry in std::fs::read_dir(path)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else { continue };
+            let Some(key) = name
+                .strip_suffix(".txt")
+                .and_then(decode_hex)
+                .and_then(|bytes| PreimageKey::try_from(bytes).ok())
+            else {
+                continue;
+            };
+            let value = std::fs::read_to_string(&path)?;
+            let value = decode_hex(value.trim())
+                .ok_or_else(|| PreimageOracleError::InvalidEntry(name.to_string()))?;
+            oracle.insert(key, value);
+        }
+        Ok(oracle)
+    }
+
+    /// Insert the preimage of `key`.
+    pub fn insert(&mut self, key: PreimageKey, preimage: Vec<u8>) {
+        self.preimages.insert(key, preimage);
+    }
+}
+
+impl PreimageOracle for KvPreimageOracle {
+    fn get(&self, key: &PreimageKey) -> Option<Vec<u8>> {
+        self.preimages.get(key).cloned()
+    }
+}
+
+/// Decode a hex string, with or without a `0x` prefix.
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    hex::decode(s.strip_prefix("0x").unwrap_or(s)).ok()
+}
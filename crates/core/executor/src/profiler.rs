@@ -0,0 +1,445 @@
+//! A cycle-accurate profiler for guest programs.
+//!
+//! The profiler tracks the call stack of the guest by observing calls (`JAL`, `JALR` and `BAL`)
+//! and returns (`JR $ra`), and resolves the executed addresses to function names through the
+//! symbol table of the ELF. The collected samples can be written as folded stacks, which can be
+//! rendered with `inferno-flamegraph` or `flamegraph.pl`, or as a pprof profile.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+use elf::{endian::LittleEndian, ElfBytes};
+
+use crate::{Instruction, Opcode};
+
+/// The environment variable containing the path the profile is written to.
+pub const PROFILE_ENV: &str = "ZKM_PROFILE";
+
+/// The environment variable containing the number of cycles between two samples.
+pub const PROFILE_SAMPLE_RATE_ENV: &str = "ZKM_PROFILE_SAMPLE_RATE";
+
+/// The name used for addresses which are not covered by a function symbol.
+const UNKNOWN_FUNCTION: &str = "[unknown]";
+
+/// The return address register.
+const RA: u32 = 31;
+
+/// The output format of a [`Profiler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileFormat {
+    /// One line per call stack, with the frames separated by `;` followed by the cycle count.
+    Folded,
+    /// An uncompressed pprof protobuf profile.
+    Pprof,
+}
+
+impl ProfileFormat {
+    /// Infer the format from the extension of `path`, using pprof for `.pb` and `.pprof` files and
+    /// folded stacks otherwise.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("pb" | "pprof") => ProfileFormat::Pprof,
+            _ => ProfileFormat::Folded,
+        }
+    }
+}
+
+/// A function symbol of the profiled program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Function {
+    pub(crate) name: String,
+    pub(crate) start: u32,
+    pub(crate) end: u32,
+}
+
+/// A control transfer which takes effect after its delay slot has been executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Transfer {
+    Call { return_addr: u32 },
+    Return,
+}
+
+/// The call or return performed by `instruction` at `pc`, if any.
+pub(crate) fn control_transfer(pc: u32, instruction: &Instruction) -> Option<Transfer> {
+    match instruction.opcode {
+        // JALR
+        Opcode::Jump if instruction.op_a != 0 => {
+            Some(Transfer::Call { return_addr: pc.wrapping_add(8) })
+        }
+        // JR $ra
+        Opcode::Jump if instruction.op_b == RA => Some(Transfer::Return),
+        // JAL and BAL
+        Opcode::Jumpi | Opcode::JumpDirect if u32::from(instruction.op_a) == RA => {
+            Some(Transfer::Call { return_addr: pc.wrapping_add(8) })
+        }
+        // BGEZAL and BLTZAL, which are only calls if the branch is taken.
+        Opcode::BGEZAL | Opcode::BLTZAL => Some(Transfer::Call { return_addr: pc.wrapping_add(8) }),
+        _ => None,
+    }
+}
+
+/// A frame of the guest call stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Frame {
+    function: usize,
+    return_addr: u32,
+}
+
+/// Samples the call stack of a guest program.
+#[derive(Debug, Clone)]
+pub struct Profiler {
+    /// The function symbols, sorted by their start address.
+    functions: Vec<Function>,
+    /// The number of cycles between two samples.
+    sample_rate: u64,
+    /// The path the profile is written to.
+    output: PathBuf,
+    /// The current call stack.
+    stack: Vec<Frame>,
+    /// The pending control transfer and whether its delay slot has been executed.
+    pending: Option<(Transfer, bool)>,
+    /// The number of sampled cycles for each call stack, from the root to the leaf.
+    samples: HashMap<Vec<usize>, u64>,
+    /// A scratch buffer for the call stack of a sample.
+    scratch: Vec<usize>,
+}
+
+impl Profiler {
+    /// Create a new profiler for the program `elf`, sampling every `sample_rate` cycles and
+    /// writing the profile to `output`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the ELF or its symbol table can't be parsed.
+    pub fn new(elf: &[u8], sample_rate: u64, output: impl Into<PathBuf>) -> Result<Self> {
+        Ok(Self::from_functions(function_symbols(elf)?, sample_rate, output.into()))
+    }
+
+    /// Create a new profiler from the environment variables [`PROFILE_ENV`] and
+    /// [`PROFILE_SAMPLE_RATE_ENV`], returning `None` if profiling is not enabled.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the sample rate or the ELF can't be parsed.
+    pub fn from_env(elf: &[u8]) -> Result<Option<Self>> {
+        let Ok(output) = std::env::var(PROFILE_ENV) else {
+            return Ok(None);
+        };
+        let sample_rate = match std::env::var(PROFILE_SAMPLE_RATE_ENV) {
+            Ok(rate) => rate.parse().map_err(|err| anyhow!("invalid sample rate {rate}: {err}"))?,
+            Err(_) => 1,
+        };
+        Self::new(elf, sample_rate, output).map(Some)
+    }
+
+    fn from_functions(mut functions: Vec<Function>, sample_rate: u64, output: PathBuf) -> Self {
+        functions.sort_by_key(|function| function.start);
+        functions.push(Function { name: UNKNOWN_FUNCTION.to_string(), start: 0, end: 0 });
+        Self {
+            functions,
+            sample_rate: sample_rate.max(1),
+            output,
+            stack: Vec::new(),
+            pending: None,
+            samples: HashMap::new(),
+            scratch: Vec::new(),
+        }
+    }
+
+    /// The path the profile is written to.
+    #[must_use]
+    pub fn output(&self) -> &Path {
+        &self.output
+    }
+
+    /// Record the execution of `instruction` at `pc` in cycle `clk`.
+    pub fn record(&mut self, clk: u64, pc: u32, instruction: &Instruction) {
+        // The first executed function is the root of all call stacks, and is never returned from.
+        if self.stack.is_empty() {
+            let function = self.resolve(pc);
+            self.stack.push(Frame { function, return_addr: u32::MAX });
+        }
+
+        // A call or return takes effect once its delay slot has been executed.
+        match self.pending {
+            Some((transfer, true)) => {
+                self.pending = None;
+                self.transfer(transfer, pc);
+            }
+            Some((transfer, false)) => self.pending = Some((transfer, true)),
+            None => {}
+        }
+
+        if clk.is_multiple_of(self.sample_rate) {
+            self.sample(pc);
+        }
+
+        if let Some(transfer) = control_transfer(pc, instruction) {
+            self.pending = Some((transfer, false));
+        }
+    }
+
+    fn transfer(&mut self, transfer: Transfer, pc: u32) {
+        match transfer {
+            // A branch and link that was not taken falls through to its return address.
+            Transfer::Call { return_addr } if return_addr == pc => {}
+            Transfer::Call { return_addr } => {
+                let function = self.resolve(pc);
+                self.stack.push(Frame { function, return_addr });
+            }
+            Transfer::Return => {
+                // Unwind to the frame returning to `pc`, which also discards frames that were
+                // left without a return, e.g. by tail calls.
+                if let Some(idx) = self.stack.iter().rposition(|frame| frame.return_addr == pc) {
+                    self.stack.truncate(idx);
+                }
+            }
+        }
+    }
+
+    fn sample(&mut self, pc: u32) {
+        self.scratch.clear();
+        self.scratch.extend(self.stack.iter().map(|frame| frame.function));
+        let leaf = self.resolve(pc);
+        if self.scratch.last() != Some(&leaf) {
+            self.scratch.push(leaf);
+        }
+
+        if let Some(count) = self.samples.get_mut(self.scratch.as_slice()) {
+            *count += self.sample_rate;
+        } else {
+            self.samples.insert(self.scratch.clone(), self.sample_rate);
+        }
+    }
+
+    /// Resolve `pc` to the index of the function containing it.
+    fn resolve(&self, pc: u32) -> usize {
+        let unknown = self.functions.len() - 1;
+        let idx = self.functions[..unknown].partition_point(|function| function.start <= pc);
+        match idx.checked_sub(1) {
+            Some(idx) if pc < self.functions[idx].end => idx,
+            _ => unknown,
+        }
+    }
+
+    /// The sampled call stacks as function names from the root to the leaf, together with their
+    /// cycle counts, sorted by the call stacks.
+    #[must_use]
+    pub fn stacks(&self) -> Vec<(Vec<&str>, u64)> {
+        let mut stacks: Vec<_> = self
+            .samples
+            .iter()
+            .map(|(stack, count)| {
+                (stack.iter().map(|&idx| self.functions[idx].name.as_str()).collect(), *count)
+            })
+            .collect();
+        stacks.sort();
+        stacks
+    }
+
+    /// Write the profile to [`Self::output`], in the format inferred from its extension.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the profile can't be written.
+    pub fn write(&self) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(&self.output)?);
+        match ProfileFormat::from_path(&self.output) {
+            ProfileFormat::Folded => self.write_folded(&mut writer)?,
+            ProfileFormat::Pprof => self.write_pprof(&mut writer)?,
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write the samples as folded stacks.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if writing to `writer` fails.
+    pub fn write_folded(&self, writer: &mut impl Write) -> Result<()> {
+        for (stack, count) in self.stacks() {
+            writeln!(writer, "{} {}", stack.join(";"), count)?;
+        }
+        Ok(())
+    }
+
+    /// Write the samples as an uncompressed pprof profile.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if writing to `writer` fails.
+    pub fn write_pprof(&self, writer: &mut impl Write) -> Result<()> {
+        // The string table must start with the empty string.
+        let mut strings = vec!["", "cycles", "count"];
+        let mut profile = ProtoWriter::default();
+
+        // Profile.sample_type and Profile.period_type
+        let mut value_type = ProtoWriter::default();
+        value_type.varint(1, 1);
+        value_type.varint(2, 2);
+        profile.message(1, &value_type);
+        profile.message(11, &value_type);
+        profile.varint(12, self.sample_rate);
+
+        // Profile.sample, with the leaf location first.
+        for (stack, count) in &self.samples {
+            let mut sample = ProtoWriter::default();
+            sample.packed(1, stack.iter().rev().map(|&idx| idx as u64 + 1));
+            sample.packed(2, [*count]);
+            profile.message(2, &sample);
+        }
+
+        // Profile.location and Profile.function, with one location for each function.
+        for (idx, function) in self.functions.iter().enumerate() {
+            let id = idx as u64 + 1;
+            let mut line = ProtoWriter::default();
+            line.varint(1, id);
+            let mut location = ProtoWriter::default();
+            location.varint(1, id);
+            location.varint(3, u64::from(function.start));
+            location.message(4, &line);
+            profile.message(4, &location);
+
+            strings.push(&function.name);
+            let mut func = ProtoWriter::default();
+            func.varint(1, id);
+            func.varint(2, strings.len() as u64 - 1);
+            func.varint(3, strings.len() as u64 - 1);
+            profile.message(5, &func);
+        }
+
+        // Profile.string_table
+        for string in strings {
+            profile.bytes(6, string.as_bytes());
+        }
+
+        writer.write_all(&profile.0)?;
+        Ok(())
+    }
+}
+
+/// Parse the function symbols of the ELF, demangling their names.
+pub(crate) fn function_symbols(elf: &[u8]) -> Result<Vec<Function>> {
+    let elf = ElfBytes::<LittleEndian>::minimal_parse(elf)
+        .map_err(|err| anyhow!("Elf parse error: {err}"))?;
+    let (symbols, names) = elf
+        .symbol_table()
+        .map_err(|err| anyhow!("Symbol table parse error: {err}"))?
+        .ok_or(anyhow!("Missing symbol table"))?;
+
+    let mut functions = Vec::new();
+    for symbol in symbols.iter() {
+        if symbol.st_symtype() != elf::abi::STT_FUNC || symbol.st_size == 0 {
+            continue;
+        }
+        let name = names
+            .get(symbol.st_name as usize)
+            .map_err(|err| anyhow!("Symbol name parse error: {err}"))?;
+        let start = symbol.st_value as u32;
+        functions.push(Function {
+            name: rustc_demangle::demangle(name).to_string(),
+            start,
+            end: start.wrapping_add(symbol.st_size as u32),
+        });
+    }
+    Ok(functions)
+}
+
+/// A minimal writer for the protobuf wire format.
+#[derive(Default)]
+struct ProtoWriter(Vec<u8>);
+
+impl ProtoWriter {
+    fn raw_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn varint(&mut self, field: u32, value: u64) {
+        self.raw_varint(u64::from(field) << 3);
+        self.raw_varint(value);
+    }
+
+    fn bytes(&mut self, field: u32, bytes: &[u8]) {
+        self.raw_varint((u64::from(field) << 3) | 2);
+        self.raw_varint(bytes.len() as u64);
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn message(&mut self, field: u32, message: &ProtoWriter) {
+        self.bytes(field, &message.0);
+    }
+
+    fn packed(&mut self, field: u32, values: impl IntoIterator<Item = u64>) {
+        let mut packed = ProtoWriter::default();
+        for value in values {
+            packed.raw_varint(value);
+        }
+        self.message(field, &packed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{Instruction, Opcode};
+
+    use super::{Function, ProfileFormat, Profiler, ProtoWriter};
+
+    fn function(name: &str, start: u32, end: u32) -> Function {
+        Function { name: name.to_string(), start, end }
+    }
+
+    #[test]
+    fn test_call_stack() {
+        let functions = vec![function("main", 0x100, 0x200), function("foo", 0x200, 0x300)];
+        let mut profiler = Profiler::from_functions(functions, 1, PathBuf::from("profile.folded"));
+
+        let nop = Instruction::new(Opcode::ADD, 0, 0, 0, false, true);
+        let jal = Instruction::new(Opcode::Jumpi, 31, 0x200, 0, true, true);
+        let jr_ra = Instruction::new(Opcode::Jump, 0, 31, 0, false, true);
+        let trace = [
+            (0x100, nop),
+            (0x104, jal),
+            (0x108, nop),
+            (0x200, nop),
+            (0x204, jr_ra),
+            (0x208, nop),
+            (0x10c, nop),
+            (0x400, nop),
+        ];
+        for (clk, (pc, instruction)) in trace.iter().enumerate() {
+            profiler.record(clk as u64, *pc, instruction);
+        }
+
+        assert_eq!(
+            profiler.stacks(),
+            vec![(vec!["main"], 4), (vec!["main", "[unknown]"], 1), (vec!["main", "foo"], 3),]
+        );
+
+        let mut folded = Vec::new();
+        profiler.write_folded(&mut folded).unwrap();
+        assert_eq!(String::from_utf8(folded).unwrap(), "main 4\nmain;[unknown] 1\nmain;foo 3\n");
+    }
+
+    #[test]
+    fn test_format_and_encoding() {
+        assert_eq!(ProfileFormat::from_path(&PathBuf::from("a.pb")), ProfileFormat::Pprof);
+        assert_eq!(ProfileFormat::from_path(&PathBuf::from("a.folded")), ProfileFormat::Folded);
+
+        let mut writer = ProtoWriter::default();
+        writer.varint(1, 300);
+        assert_eq!(writer.0, vec![0x08, 0xac, 0x02]);
+    }
+}
@@ -26,7 +26,11 @@ use crate::{Instruction, MipsAirId};
 pub const PAGE_SIZE: u32 = 4096;
 pub const MAX_MEMORY: usize = 0x10000000;
 pub const INIT_SP: u32 = MAX_MEMORY as u32 - 0x4000;
+/// The initial stack pointer of programs built for Optimism's Cannon.
+pub const CANNON_INIT_SP: u32 = 0x77ff_d000;
 pub const WORD_SIZE: usize = core::mem::size_of::<u32>();
+/// The symbol holding the end of the heap of the guest, exported by `zkm-zkvm`.
+const HEAP_END_SYMBOL: &str = "ZKM_HEAP_END";
 
 /// A program that can be executed by the ZKM.
 #[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
@@ -40,6 +44,44 @@ pub struct Program {
     pub image: BTreeMap<u32, u32>,
     /// The shape for the preprocessed tables.
     pub preprocessed_shape: Option<Shape<MipsAirId>>,
+    /// The end of the heap of the guest, below which the stack must not grow, read from the
+    /// `ZKM_HEAP_END` symbol of the ELF.
+    #[serde(default)]
+    pub heap_end: Option<u32>,
+    /// The loadable segments of the ELF, with their permissions.
+    #[serde(default)]
+    pub segments: Vec<Segment>,
+}
+
+/// A loadable segment of a program.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Segment {
+    /// The first address of the segment.
+    pub start: u32,
+    /// The address past the end of the segment.
+    pub end: u32,
+    /// The `PF_R`, `PF_W` and `PF_X` flags of the ELF program header.
+    pub flags: u32,
+}
+
+impl Segment {
+    /// Whether the segment contains the address.
+    #[must_use]
+    pub fn contains(&self, addr: u32) -> bool {
+        (self.start..self.end).contains(&addr)
+    }
+
+    /// Whether the segment holds instructions.
+    #[must_use]
+    pub fn is_executable(&self) -> bool {
+        self.flags & elf::abi::PF_X != 0
+    }
+
+    /// Whether the segment may be written to.
+    #[must_use]
+    pub fn is_writable(&self) -> bool {
+        self.flags & elf::abi::PF_W != 0
+    }
 }
 
 impl Program {
@@ -81,6 +123,7 @@ impl Program {
         let mut base_address = u32::MAX;
 
         let mut hiaddr = 0u32;
+        let mut program_segments = Vec::new();
 
         for segment in segments.iter().filter(|x| x.p_type == elf::abi::PT_LOAD) {
             let file_size: u32 = segment
@@ -112,6 +155,7 @@ impl Program {
             if a > hiaddr {
                 hiaddr = a;
             }
+            program_segments.push(Segment { start: vaddr, end: a, flags: segment.p_flags });
 
             let offset: u32 = segment
                 .p_offset
@@ -147,6 +191,8 @@ impl Program {
         let instructions: Vec<_> =
             instructions.par_iter().map(|inst| Instruction::decode_from(*inst).unwrap()).collect();
 
+        let heap_end = Self::heap_end(&elf, &image)?;
+
         Ok(Program {
             instructions,
             pc_start: entry,
@@ -154,9 +200,127 @@ impl Program {
             next_pc: entry + 4,
             image,
             preprocessed_shape: None,
+            heap_end,
+            segments: program_segments,
         })
     }
 
+    /// Read the end of the heap from the `ZKM_HEAP_END` symbol exported by `zkm-zkvm`, if any.
+    fn heap_end(
+        elf: &ElfBytes<'_, LittleEndian>,
+        image: &BTreeMap<u32, u32>,
+    ) -> Result<Option<u32>> {
+        let Some((symbols, strings)) =
+            elf.symbol_table().map_err(|err| anyhow!("Symbol table parse error: {err}"))?
+        else {
+            return Ok(None);
+        };
+        let Some(symbol) = symbols.iter().find(|symbol| {
+            strings.get(symbol.st_name as usize).is_ok_and(|name| name == HEAP_END_SYMBOL)
+        }) else {
+            return Ok(None);
+        };
+        let addr = u32::try_from(symbol.st_value)?;
+        let heap_end = image.get(&addr).context("Invalid ZKM_HEAP_END symbol")?;
+        Ok(Some(*heap_end))
+    }
+
+    /// Initialize a MIPS Program from a Go ELF built for Optimism's Cannon, such as `op-program`.
+    ///
+    /// The ELF must be built for little-endian MIPS (`GOARCH=mipsle GOMIPS=softfloat`). Like
+    /// Cannon's loader, this patches out the parts of the Go runtime that need threads or a
+    /// garbage collector, and sets up the initial stack with the arguments, the environment and
+    /// the auxiliary vector the Go runtime expects. The program must then be executed with a
+    /// preimage oracle, which enables the emulation of Linux syscalls.
+    pub fn from_cannon(elf_code: &[u8]) -> Result<Program> {
+        let mut program = Self::from(elf_code)?;
+        program.patch_go(elf_code)?;
+        program.patch_stack();
+        Ok(program)
+    }
+
+    /// Make the Go runtime functions that Cannon patches out return immediately.
+    fn patch_go(&mut self, elf_code: &[u8]) -> Result<()> {
+        const PATCHED_SYMBOLS: [&str; 14] = [
+            "runtime.gcenable",
+            "runtime.init.5",
+            "runtime.main.func1",
+            "runtime.deductSweepCredit",
+            "runtime.(*gcControllerState).commit",
+            "github.com/prometheus/client_golang/prometheus.init",
+            "github.com/prometheus/client_golang/prometheus.init.0",
+            "github.com/prometheus/procfs.init",
+            "github.com/prometheus/common/model.init",
+            "github.com/prometheus/client_model/go.init",
+            "github.com/prometheus/client_model/go.init.0",
+            "github.com/prometheus/client_model/go.init.1",
+            "flag.init",
+            "runtime.check",
+        ];
+        // `jr $ra` followed by a `nop` in the delay slot.
+        const RETURN: [u32; 2] = [0x03e0_0008, 0];
+
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(elf_code)
+            .map_err(|err| anyhow!("Elf parse error: {err}"))?;
+        let (symbols, strings) = elf
+            .symbol_table()
+            .map_err(|err| anyhow!("Symbol table parse error: {err}"))?
+            .ok_or(anyhow!("Missing symbol table"))?;
+        for symbol in symbols.iter() {
+            let name = strings.get(symbol.st_name as usize).unwrap_or_default();
+            if !PATCHED_SYMBOLS.contains(&name) {
+                continue;
+            }
+            let addr = u32::try_from(symbol.st_value)?;
+            for (i, word) in RETURN.into_iter().enumerate() {
+                let addr = addr + 4 * i as u32;
+                let idx = (addr.checked_sub(self.pc_base).context("Symbol outside of text")? / 4)
+                    as usize;
+                *self.instructions.get_mut(idx).context("Symbol outside of text")? =
+                    Instruction::decode_from(word)?;
+                self.image.insert(addr, word);
+            }
+        }
+        Ok(())
+    }
+
+    /// Set up the initial stack the way Cannon does.
+    fn patch_stack(&mut self) {
+        // Cannon puts the stack at 0x7fffd000, past the end of the memory of the executor at
+        // 0x78000000, so it is moved down by 128 MiB.
+        let sp = CANNON_INIT_SP;
+        for addr in (sp - 4 * PAGE_SIZE..sp + PAGE_SIZE).step_by(WORD_SIZE) {
+            self.image.insert(addr, 0);
+        }
+        self.image.insert(29, sp);
+
+        let words = [
+            1,           // argc
+            sp + 4 * 21, // argv[0]
+            0,           // argv[1]
+            sp + 4 * 14, // envp[0]
+            0,           // envp[1]
+            6,           // auxv[0] = AT_PAGESZ
+            PAGE_SIZE,   // auxv[1]
+            25,          // auxv[2] = AT_RANDOM
+            sp + 4 * 10, // auxv[3], the address of 16 random bytes
+            0,           // auxv[4]
+        ];
+        for (i, word) in words.into_iter().enumerate() {
+            self.image.insert(sp + 4 * i as u32, word);
+        }
+        let mut write_bytes = |addr: u32, bytes: &[u8]| {
+            for (i, chunk) in bytes.chunks(WORD_SIZE).enumerate() {
+                let mut word = [0u8; WORD_SIZE];
+                word[..chunk.len()].copy_from_slice(chunk);
+                self.image.insert(addr + (i * WORD_SIZE) as u32, u32::from_le_bytes(word));
+            }
+        };
+        write_bytes(sp + 4 * 10, b"4;byfairdiceroll");
+        write_bytes(sp + 4 * 14, b"GODEBUG=memprofilerate=0\0\0\0\0");
+        write_bytes(sp + 4 * 21, b"op-program\0\0");
+    }
+
     /// Custom logic for padding the trace to a power of two according to the proof shape.
     pub fn fixed_log2_rows<F: Field, A: MachineAir<F>>(&self, air: &A) -> Option<usize> {
         let id = MipsAirId::from_str(&air.name()).unwrap();
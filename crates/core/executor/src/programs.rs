@@ -6,8 +6,10 @@ pub mod tests {
     use crate::{Instruction, Opcode, Program};
 
     use test_artifacts::{
-        FIBONACCI_ELF, HELLO_WORLD_ELF, KECCAK_SPONGE_ELF, PANIC_ELF, SECP256R1_ADD_ELF,
-        SECP256R1_DOUBLE_ELF, SHA3_CHAIN_ELF, U256XU2048_MUL_ELF, UNCONSTRAINED_ELF,
+        BLAKE3_ELF, BLS12381_PAIRING_ELF, BN254_PAIRING_ELF, EMBEDDED_ALLOCATOR_ELF, FIBONACCI_ELF,
+        GUEST_ENV_ELF, HELLO_WORLD_ELF, KECCAK_SPONGE_ELF, MODEXP_ELF, PANIC_ELF,
+        SECP256R1_ADD_ELF, SECP256R1_DOUBLE_ELF, SHA3_CHAIN_ELF, TYPED_HOOK_ELF,
+        U256XU2048_MUL_ELF, UNCONSTRAINED_ELF,
     };
 
     #[must_use]
@@ -80,6 +82,46 @@ pub mod tests {
         Program::from(U256XU2048_MUL_ELF).unwrap()
     }
 
+    /// Get the bn254 pairing program.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the program fails to load.
+    #[must_use]
+    pub fn bn254_pairing_program() -> Program {
+        Program::from(BN254_PAIRING_ELF).unwrap()
+    }
+
+    /// Get the bls12-381 pairing program.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the program fails to load.
+    #[must_use]
+    pub fn bls12381_pairing_program() -> Program {
+        Program::from(BLS12381_PAIRING_ELF).unwrap()
+    }
+
+    /// Get the blake3 program.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the program fails to load.
+    #[must_use]
+    pub fn blake3_program() -> Program {
+        Program::from(BLAKE3_ELF).unwrap()
+    }
+
+    /// Get the modexp program.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the program fails to load.
+    #[must_use]
+    pub fn modexp_program() -> Program {
+        Program::from(MODEXP_ELF).unwrap()
+    }
+
     /// Get the SSZ withdrawals program.
     ///
     /// # Panics
@@ -100,6 +142,36 @@ pub mod tests {
         Program::from(UNCONSTRAINED_ELF).unwrap()
     }
 
+    /// Get the program reading its environment.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the program fails to load.
+    #[must_use]
+    pub fn guest_env_program() -> Program {
+        Program::from(GUEST_ENV_ELF).unwrap()
+    }
+
+    /// Get the program calling a typed hook.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the program fails to load.
+    #[must_use]
+    pub fn typed_hook_program() -> Program {
+        Program::from(TYPED_HOOK_ELF).unwrap()
+    }
+
+    /// Get the program freeing and reusing memory with the embedded allocator.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the program fails to load.
+    #[must_use]
+    pub fn embedded_allocator_program() -> Program {
+        Program::from(EMBEDDED_ALLOCATOR_ELF).unwrap()
+    }
+
     /// Get the panic program.
     ///
     /// # Panics
@@ -13,7 +13,7 @@ use std::{mem::take, str::FromStr, sync::Arc};
 
 use crate::{
     events::{
-        AluEvent, BranchEvent, ByteLookupEvent, ByteRecord, CompAluEvent, CpuEvent,
+        AluEvent, BranchEvent, ByteLookupEvent, ByteRecord, CompAluEvent, CpuEvent, FpuEvent,
         GlobalLookupEvent, JumpEvent, MemInstrEvent, MemoryInitializeFinalizeEvent,
         MemoryLocalEvent, MemoryRecordEnum, MiscEvent, PrecompileEvent, PrecompileEvents,
         SyscallEvent,
@@ -58,6 +58,12 @@ pub struct ExecutionRecord {
     pub jump_events: Vec<JumpEvent>,
     /// A trace of the misc events.
     pub misc_events: Vec<MiscEvent>,
+    /// A trace of the floating-point arithmetic and conversion events.
+    pub fpu_arith_events: Vec<FpuEvent>,
+    /// A trace of the floating-point comparison events.
+    pub fpu_compare_events: Vec<FpuEvent>,
+    /// A trace of the floating-point move, load and store events.
+    pub fpu_move_events: Vec<FpuEvent>,
     /// A trace of the byte lookups that are needed.
     pub byte_lookups: HashMap<ByteLookupEvent, usize>,
     /// A trace of the precompile events.
@@ -124,6 +130,7 @@ impl ExecutionRecord {
                 SyscallCode::KECCAK_SPONGE => opts.keccak,
                 SyscallCode::SHA_EXTEND => opts.sha_extend,
                 SyscallCode::SHA_COMPRESS => opts.sha_compress,
+                SyscallCode::BLAKE3_COMPRESS_INNER => opts.blake3_compress_inner,
                 _ => opts.deferred,
             };
 
@@ -298,6 +305,9 @@ impl MachineRecord for ExecutionRecord {
         stats.insert("branch_events".to_string(), self.branch_events.len());
         stats.insert("jump_events".to_string(), self.jump_events.len());
         stats.insert("misc_events".to_string(), self.misc_events.len());
+        stats.insert("fpu_arith_events".to_string(), self.fpu_arith_events.len());
+        stats.insert("fpu_compare_events".to_string(), self.fpu_compare_events.len());
+        stats.insert("fpu_move_events".to_string(), self.fpu_move_events.len());
 
         for (syscall_code, events) in self.precompile_events.iter() {
             stats.insert(format!("syscall {syscall_code:?}"), events.len());
@@ -335,6 +345,9 @@ impl MachineRecord for ExecutionRecord {
         self.branch_events.append(&mut other.branch_events);
         self.jump_events.append(&mut other.jump_events);
         self.misc_events.append(&mut other.misc_events);
+        self.fpu_arith_events.append(&mut other.fpu_arith_events);
+        self.fpu_compare_events.append(&mut other.fpu_compare_events);
+        self.fpu_move_events.append(&mut other.fpu_move_events);
         self.syscall_events.append(&mut other.syscall_events);
 
         self.precompile_events.append(&mut other.precompile_events);
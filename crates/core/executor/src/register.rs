@@ -1,6 +1,10 @@
 //! Registers for the Zkm zkVM.
 
 /// A register stores a 32-bit value used by operations.
+///
+/// The general purpose registers are followed by the 32 floating-point registers `F0..F31` and
+/// the 8 floating-point condition codes `FCC0..FCC7` of COP1. A double precision value occupies
+/// an even/odd pair of floating-point registers, with the low word in the even register.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Register {
     ZERO = 0,
@@ -37,6 +41,47 @@ pub enum Register {
     RA = 31,
     LO = 32,
     HI = 33,
+    // The floating-point registers of COP1.
+    F0 = 34,
+    F1 = 35,
+    F2 = 36,
+    F3 = 37,
+    F4 = 38,
+    F5 = 39,
+    F6 = 40,
+    F7 = 41,
+    F8 = 42,
+    F9 = 43,
+    F10 = 44,
+    F11 = 45,
+    F12 = 46,
+    F13 = 47,
+    F14 = 48,
+    F15 = 49,
+    F16 = 50,
+    F17 = 51,
+    F18 = 52,
+    F19 = 53,
+    F20 = 54,
+    F21 = 55,
+    F22 = 56,
+    F23 = 57,
+    F24 = 58,
+    F25 = 59,
+    F26 = 60,
+    F27 = 61,
+    F28 = 62,
+    F29 = 63,
+    F30 = 64,
+    F31 = 65,
+    FCC0 = 66,
+    FCC1 = 67,
+    FCC2 = 68,
+    FCC3 = 69,
+    FCC4 = 70,
+    FCC5 = 71,
+    FCC6 = 72,
+    FCC7 = 73,
 }
 
 impl From<u8> for Register {
@@ -76,7 +121,66 @@ impl From<u8> for Register {
             31 => Register::RA,
             32 => Register::LO,
             33 => Register::HI,
+            34 => Register::F0,
+            35 => Register::F1,
+            36 => Register::F2,
+            37 => Register::F3,
+            38 => Register::F4,
+            39 => Register::F5,
+            40 => Register::F6,
+            41 => Register::F7,
+            42 => Register::F8,
+            43 => Register::F9,
+            44 => Register::F10,
+            45 => Register::F11,
+            46 => Register::F12,
+            47 => Register::F13,
+            48 => Register::F14,
+            49 => Register::F15,
+            50 => Register::F16,
+            51 => Register::F17,
+            52 => Register::F18,
+            53 => Register::F19,
+            54 => Register::F20,
+            55 => Register::F21,
+            56 => Register::F22,
+            57 => Register::F23,
+            58 => Register::F24,
+            59 => Register::F25,
+            60 => Register::F26,
+            61 => Register::F27,
+            62 => Register::F28,
+            63 => Register::F29,
+            64 => Register::F30,
+            65 => Register::F31,
+            66 => Register::FCC0,
+            67 => Register::FCC1,
+            68 => Register::FCC2,
+            69 => Register::FCC3,
+            70 => Register::FCC4,
+            71 => Register::FCC5,
+            72 => Register::FCC6,
+            73 => Register::FCC7,
             _ => panic!("invalid register {value}"),
         }
     }
 }
+
+impl Register {
+    /// The number of registers, including the COP1 registers.
+    pub const COUNT: usize = 74;
+
+    /// The floating-point register with the given index.
+    #[must_use]
+    pub fn fpr(index: u32) -> Self {
+        debug_assert!(index < 32);
+        Self::from(Self::F0 as u8 + index as u8)
+    }
+
+    /// The floating-point condition code with the given index.
+    #[must_use]
+    pub fn fcc(index: u32) -> Self {
+        debug_assert!(index < 8);
+        Self::from(Self::FCC0 as u8 + index as u8)
+    }
+}
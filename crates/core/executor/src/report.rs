@@ -1,15 +1,17 @@
 use std::{
+    collections::BTreeMap,
     fmt::{Display, Formatter, Result as FmtResult},
     ops::{Add, AddAssign},
 };
 
 use enum_map::{EnumArray, EnumMap};
 use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
 
 use crate::{events::generate_execution_report, syscalls::SyscallCode, Opcode};
 
 /// An execution report.
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExecutionReport {
     /// The opcode counts.
     pub opcode_counts: Box<EnumMap<Opcode, u64>>,
@@ -17,8 +19,31 @@ pub struct ExecutionReport {
     pub syscall_counts: Box<EnumMap<SyscallCode, u64>>,
     /// The cycle tracker counts.
     pub cycle_tracker: HashMap<String, u64>,
+    /// The cycle tracker spans, keyed by the `;` separated names of the enclosing spans.
+    pub cycle_tracker_spans: BTreeMap<String, CycleTrackerSpan>,
     /// The unique memory address counts.
     pub touched_memory_addresses: u64,
+    /// The number of execution shards.
+    pub execution_shards: u64,
+    /// The number of events of each kind, as in `ExecutionRecord::stats`.
+    ///
+    /// This is only populated when the execution is traced, as with [`Executor::run`]. Reports of
+    /// untraced executions, such as [`Executor::run_fast`], leave it empty.
+    ///
+    /// [`Executor::run`]: crate::Executor::run
+    /// [`Executor::run_fast`]: crate::Executor::run_fast
+    pub event_counts: BTreeMap<String, u64>,
+}
+
+/// The cycles spent in a cycle tracker span, over all of its invocations.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleTrackerSpan {
+    /// The nesting depth of the span.
+    pub depth: u32,
+    /// The number of times the span was entered.
+    pub invocations: u64,
+    /// The total number of cycles spent in the span.
+    pub cycles: u64,
 }
 
 impl ExecutionReport {
@@ -33,6 +58,159 @@ impl ExecutionReport {
     pub fn total_syscall_count(&self) -> u64 {
         self.syscall_counts.values().sum()
     }
+
+    /// Serialize the report to pretty-printed JSON.
+    ///
+    /// The `event_counts` of a report are only present if the execution was traced.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the report fails to serialize.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Deserialize a report from JSON.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `json` is not a valid report.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Serialize the report to CSV, with one `category,name,value` row per non-zero count.
+    ///
+    /// The rows of `event_counts` are only present if the execution was traced.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("category,name,value\n");
+        for ((category, name), value) in self.entries() {
+            csv.push_str(&format!("{category},{},{value}\n", csv_field(&name)));
+        }
+        csv
+    }
+
+    /// Compare this report to the report `new` of another execution.
+    #[must_use]
+    pub fn diff(&self, new: &ExecutionReport) -> ReportDiff {
+        let base = self.entries();
+        let new = new.entries();
+
+        let mut keys = base.keys().chain(new.keys()).cloned().collect::<Vec<_>>();
+        keys.sort();
+        keys.dedup();
+
+        let entries = keys
+            .into_iter()
+            .map(|key| ReportDiffEntry {
+                base: base.get(&key).copied().unwrap_or_default(),
+                new: new.get(&key).copied().unwrap_or_default(),
+                category: key.0.to_string(),
+                name: key.1,
+            })
+            .collect();
+        ReportDiff { entries }
+    }
+
+    /// The non-zero counts of the report, keyed by category and name.
+    fn entries(&self) -> BTreeMap<(&'static str, String), u64> {
+        let mut entries = BTreeMap::new();
+        entries.insert(("total", "instructions".to_string()), self.total_instruction_count());
+        entries.insert(("total", "syscalls".to_string()), self.total_syscall_count());
+        entries.insert(
+            ("total", "touched_memory_addresses".to_string()),
+            self.touched_memory_addresses,
+        );
+        entries.insert(("total", "execution_shards".to_string()), self.execution_shards);
+        for (opcode, count) in self.opcode_counts.iter() {
+            entries.insert(("opcode", opcode.to_string()), *count);
+        }
+        for (syscall, count) in self.syscall_counts.iter() {
+            entries.insert(("syscall", syscall.to_string()), *count);
+        }
+        for (name, span) in self.cycle_tracker_spans.iter() {
+            entries.insert(("cycle_tracker", name.clone()), span.cycles);
+        }
+        for (name, count) in self.event_counts.iter() {
+            entries.insert(("event", name.clone()), *count);
+        }
+        entries.retain(|_, count| *count != 0);
+        entries
+    }
+}
+
+/// Quote a CSV field if it contains a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// The difference between the counts of two execution reports.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportDiff {
+    /// The counts that are non-zero in at least one of the reports.
+    pub entries: Vec<ReportDiffEntry>,
+}
+
+/// A count in two execution reports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportDiffEntry {
+    /// The category of the count, e.g. `opcode` or `cycle_tracker`.
+    pub category: String,
+    /// The name of the count.
+    pub name: String,
+    /// The count in the base report.
+    pub base: u64,
+    /// The count in the new report.
+    pub new: u64,
+}
+
+impl ReportDiffEntry {
+    /// The change of the count, relative to the base report.
+    ///
+    /// Counts that are zero in the base report have an infinite relative change.
+    #[must_use]
+    pub fn relative_change(&self) -> f64 {
+        if self.base == 0 {
+            return if self.new == 0 { 0.0 } else { f64::INFINITY };
+        }
+        (self.new as f64 - self.base as f64) / self.base as f64
+    }
+}
+
+impl ReportDiff {
+    /// The counts that changed between the reports.
+    pub fn changes(&self) -> impl Iterator<Item = &ReportDiffEntry> {
+        self.entries.iter().filter(|entry| entry.base != entry.new)
+    }
+
+    /// The counts that increased by more than `threshold`, relative to the base report.
+    ///
+    /// For example, a threshold of `0.05` returns the counts that regressed by more than 5%.
+    pub fn regressions(&self, threshold: f64) -> impl Iterator<Item = &ReportDiffEntry> {
+        self.entries.iter().filter(move |entry| entry.relative_change() > threshold)
+    }
+}
+
+impl Display for ReportDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for entry in self.changes() {
+            writeln!(
+                f,
+                "{}/{}: {} -> {} ({:+.2}%)",
+                entry.category,
+                entry.name,
+                entry.base,
+                entry.new,
+                entry.relative_change() * 100.0
+            )?;
+        }
+        Ok(())
+    }
 }
 
 /// Combines two `HashMap`s together. If a key is in both maps, the values are added together.
@@ -50,7 +228,17 @@ impl AddAssign for ExecutionReport {
     fn add_assign(&mut self, rhs: Self) {
         counts_add_assign(&mut self.opcode_counts, *rhs.opcode_counts);
         counts_add_assign(&mut self.syscall_counts, *rhs.syscall_counts);
+        for (name, rhs) in rhs.cycle_tracker_spans {
+            let span = self.cycle_tracker_spans.entry(name).or_default();
+            span.depth = rhs.depth;
+            span.invocations += rhs.invocations;
+            span.cycles += rhs.cycles;
+        }
         self.touched_memory_addresses += rhs.touched_memory_addresses;
+        self.execution_shards += rhs.execution_shards;
+        for (name, count) in rhs.event_counts {
+            *self.event_counts.entry(name).or_default() += count;
+        }
     }
 }
 
@@ -77,3 +265,54 @@ impl Display for ExecutionReport {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{CycleTrackerSpan, ExecutionReport};
+    use crate::{syscalls::SyscallCode, Opcode};
+
+    fn report(adds: u64, span_cycles: u64) -> ExecutionReport {
+        let mut report = ExecutionReport::default();
+        report.opcode_counts[Opcode::ADD] = adds;
+        report.opcode_counts[Opcode::LW] = 10;
+        report.syscall_counts[SyscallCode::KECCAK_SPONGE] = 2;
+        report.cycle_tracker_spans.insert(
+            "main;hash, then verify".to_string(),
+            CycleTrackerSpan { depth: 1, invocations: 2, cycles: span_cycles },
+        );
+        report.execution_shards = 1;
+        report
+    }
+
+    #[test]
+    fn test_report_json_roundtrip() {
+        let report = report(100, 50);
+        let json = report.to_json().unwrap();
+        assert_eq!(ExecutionReport::from_json(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn test_report_csv() {
+        let csv = report(100, 50).to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("category,name,value"));
+        assert!(csv.contains("total,instructions,110\n"));
+        assert!(csv.contains("cycle_tracker,\"main;hash, then verify\",50\n"));
+        assert!(!csv.contains("opcode,SUB"));
+    }
+
+    #[test]
+    fn test_report_diff() {
+        let diff = report(100, 50).diff(&report(104, 60));
+        assert_eq!(diff.changes().count(), 3);
+
+        let regressions = diff.regressions(0.1).collect::<Vec<_>>();
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].category, "cycle_tracker");
+        assert_eq!(regressions[0].base, 50);
+        assert_eq!(regressions[0].new, 60);
+
+        assert_eq!(diff.regressions(0.01).count(), 3);
+        assert_eq!(report(100, 50).diff(&report(100, 50)).changes().count(), 0);
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
     events::MemoryRecord,
     memory::PagedMemory,
     record::{ExecutionRecord, MemoryAccessRecord},
-    syscalls::SyscallCode,
+    syscalls::{linux::LinuxState, SyscallCode},
     ExecutorMode, ZKMReduceProof,
 };
 
@@ -55,6 +55,13 @@ pub struct ExecutionState {
     /// A ptr to the current position in the input stream incremented by `HINT_READ` opcode.
     pub input_stream_ptr: usize,
 
+    /// The environment variables of the program, read through [`FD_GETENV`](crate::FD_GETENV).
+    pub env: HashMap<String, String>,
+
+    /// The seed of the random number generator of the program, read through
+    /// [`FD_RNG_SEED`](crate::FD_RNG_SEED).
+    pub rng_seed: Option<[u8; 32]>,
+
     /// A stream of proofs (reduce vk, proof, verifying key) inputted to the program.
     pub proof_stream:
         Vec<(ZKMReduceProof<KoalaBearPoseidon2>, StarkVerifyingKey<KoalaBearPoseidon2>)>,
@@ -70,6 +77,9 @@ pub struct ExecutionState {
     pub public_values_stream_ptr: usize,
     // /// Keeps track of how many times a certain syscall has been called.
     pub syscall_counts: HashMap<SyscallCode, u64>,
+
+    /// The state of the emulated Linux environment, used in the Cannon-compatible execution mode.
+    pub linux: LinuxState,
 }
 
 impl ExecutionState {
@@ -89,11 +99,14 @@ impl ExecutionState {
             uninitialized_memory: PagedMemory::default(),
             input_stream: Vec::new(),
             input_stream_ptr: 0,
+            env: HashMap::new(),
+            rng_seed: None,
             public_values_stream: Vec::new(),
             public_values_stream_ptr: 0,
             proof_stream: Vec::new(),
             proof_stream_ptr: 0,
             syscall_counts: HashMap::new(),
+            linux: LinuxState::default(),
         }
     }
 }
@@ -89,6 +89,18 @@ pub enum SyscallCode {
     /// Executes the `U256XU2048_MUL` precompile.
     U256XU2048_MUL = 0x00_01_01_2F,
 
+    /// Executes the `POSEIDON2_PERMUTE` precompile.
+    POSEIDON2_PERMUTE = 0x00_01_01_30,
+
+    /// Executes the `BN254_FP6_MUL` precompile.
+    BN254_FP6_MUL = 0x00_01_01_31,
+
+    /// Executes the `BLS12381_FP6_MUL` precompile.
+    BLS12381_FP6_MUL = 0x00_01_01_32,
+
+    /// Executes the `BLAKE3_COMPRESS_INNER` precompile.
+    BLAKE3_COMPRESS_INNER = 0x00_38_01_33,
+
     /// Executes the `BLS12381_ADD` precompile.
     BLS12381_ADD = 0x00_01_01_1E,
 
@@ -173,6 +185,10 @@ impl SyscallCode {
             0x00_00_00_1B => SyscallCode::VERIFY_ZKM_PROOF,
             0x00_01_01_1D => SyscallCode::UINT256_MUL,
             0x00_01_01_2F => SyscallCode::U256XU2048_MUL,
+            0x00_01_01_30 => SyscallCode::POSEIDON2_PERMUTE,
+            0x00_01_01_31 => SyscallCode::BN254_FP6_MUL,
+            0x00_01_01_32 => SyscallCode::BLS12381_FP6_MUL,
+            0x00_38_01_33 => SyscallCode::BLAKE3_COMPRESS_INNER,
             0x00_01_01_20 => SyscallCode::BLS12381_FP_ADD,
             0x00_01_01_21 => SyscallCode::BLS12381_FP_SUB,
             0x00_01_01_22 => SyscallCode::BLS12381_FP_MUL,
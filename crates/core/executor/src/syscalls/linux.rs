@@ -0,0 +1,338 @@
+//! Emulation of the Linux syscalls used by programs built for Optimism's Cannon.
+//!
+//! In this mode, the `syscall` instruction takes a MIPS o32 Linux syscall number in `$v0` and its
+//! arguments in `$a0`..`$a2`, and returns its result in `$v0` and the error number in `$a3`. The
+//! emulated subset and its semantics follow Cannon: only reads and writes to the standard streams
+//! and the hint and preimage channels do anything, memory is handed out by a bump allocator, and
+//! the other syscalls a Go runtime needs to start up succeed without effect.
+
+use serde::{Deserialize, Serialize};
+use strum::{EnumCount, IntoEnumIterator};
+use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
+
+use crate::{preimage::PreimageKey, ExecutionError, Executor, PAGE_SIZE};
+
+/// The file descriptor of the standard input.
+pub const FD_STDIN: u32 = 0;
+/// The file descriptor of the standard output.
+pub const FD_STDOUT: u32 = 1;
+/// The file descriptor of the standard error.
+pub const FD_STDERR: u32 = 2;
+/// The file descriptor from which the host answers hints.
+pub const FD_HINT_READ: u32 = 3;
+/// The file descriptor to which the program writes hints.
+pub const FD_HINT_WRITE: u32 = 4;
+/// The file descriptor from which the program reads the preimage of the current key.
+pub const FD_PREIMAGE_READ: u32 = 5;
+/// The file descriptor to which the program writes the current key.
+pub const FD_PREIMAGE_WRITE: u32 = 6;
+
+/// The error number of a bad file descriptor.
+pub const EBADF: u32 = 9;
+/// The error number of an invalid argument.
+pub const EINVAL: u32 = 22;
+
+/// The start of the memory handed out by `mmap`.
+pub const HEAP_START: u32 = 0x0500_0000;
+/// The program break returned by `brk`.
+pub const PROGRAM_BREAK: u32 = 0x4000_0000;
+
+/// The number of emulated Linux syscalls.
+pub const NUM_LINUX_SYSCALLS: usize = LinuxSyscallCode::COUNT;
+
+/// The MIPS o32 Linux syscalls emulated in the Cannon-compatible execution mode.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    EnumCountMacro,
+    Ord,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+)]
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum LinuxSyscallCode {
+    READ = 4003,
+    WRITE = 4004,
+    BRK = 4045,
+    FCNTL = 4055,
+    MMAP = 4090,
+    CLONE = 4120,
+    EXIT = 4001,
+    EXIT_GROUP = 4246,
+
+    // The syscalls below succeed without any effect.
+    CLOSE = 4006,
+    GETUID = 4024,
+    GETGID = 4047,
+    IOCTL = 4054,
+    READLINK = 4085,
+    MUNMAP = 4091,
+    SETITIMER = 4104,
+    UNAME = 4122,
+    LLSEEK = 4140,
+    SCHED_YIELD = 4162,
+    NANOSLEEP = 4166,
+    RT_SIGACTION = 4194,
+    RT_SIGPROCMASK = 4195,
+    PREAD64 = 4200,
+    SIGALTSTACK = 4206,
+    STAT64 = 4213,
+    FSTAT64 = 4215,
+    MINCORE = 4217,
+    MADVISE = 4218,
+    GETTID = 4222,
+    SCHED_GETAFFINITY = 4240,
+    EPOLL_CTL = 4249,
+    TIMER_CREATE = 4257,
+    TIMER_SETTIME = 4258,
+    TIMER_DELETE = 4261,
+    CLOCK_GETTIME = 4263,
+    TGKILL = 4266,
+    OPENAT = 4288,
+    READLINKAT = 4298,
+    EPOLL_PWAIT = 4313,
+    EPOLL_CREATE1 = 4326,
+    PIPE2 = 4328,
+    PRLIMIT64 = 4338,
+    GETRANDOM = 4353,
+}
+
+impl LinuxSyscallCode {
+    /// Get the syscall with the given number, if it is emulated.
+    #[must_use]
+    pub fn from_u32(value: u32) -> Option<Self> {
+        Self::iter().find(|code| *code as u32 == value)
+    }
+
+    /// Get whether the syscall exits the program.
+    #[must_use]
+    pub fn is_exit(self) -> bool {
+        matches!(self, Self::EXIT | Self::EXIT_GROUP)
+    }
+
+    /// Get whether the syscall succeeds without any effect.
+    #[must_use]
+    pub fn is_noop(self) -> bool {
+        !matches!(
+            self,
+            Self::READ
+                | Self::WRITE
+                | Self::BRK
+                | Self::FCNTL
+                | Self::MMAP
+                | Self::CLONE
+                | Self::EXIT
+                | Self::EXIT_GROUP
+        )
+    }
+}
+
+impl std::fmt::Display for LinuxSyscallCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// The state of the emulated Linux environment, which is part of the execution state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinuxState {
+    /// The next address handed out by `mmap`.
+    pub heap: u32,
+    /// The key whose preimage is read from [`FD_PREIMAGE_READ`].
+    pub preimage_key: PreimageKey,
+    /// The offset of the next read into the preimage, prefixed with its length.
+    pub preimage_offset: u32,
+    /// The preimage of the current key prefixed with its length, once it has been fetched.
+    pub preimage: Option<Vec<u8>>,
+    /// The hint bytes that do not yet form a complete hint.
+    pub hint_buf: Vec<u8>,
+}
+
+impl Default for LinuxState {
+    fn default() -> Self {
+        Self {
+            heap: HEAP_START,
+            preimage_key: PreimageKey::default(),
+            preimage_offset: 0,
+            preimage: None,
+            hint_buf: Vec::new(),
+        }
+    }
+}
+
+/// The outcome of an emulated Linux syscall.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct LinuxSyscallOutcome {
+    /// The result, written to `$v0`.
+    pub v0: u32,
+    /// The error number, written to `$a3`.
+    pub a3: u32,
+    /// The new value of the word holding the buffer of a read.
+    pub mem_word: Option<u32>,
+}
+
+impl LinuxSyscallOutcome {
+    fn ok(v0: u32) -> Self {
+        Self { v0, ..Default::default() }
+    }
+
+    fn read(len: u32, mem_word: Option<u32>) -> Self {
+        Self { v0: len, mem_word, ..Default::default() }
+    }
+
+    fn error(errno: u32) -> Self {
+        Self { v0: u32::MAX, a3: errno, ..Default::default() }
+    }
+}
+
+/// Emulate a Linux syscall other than the exits, without recording any memory access.
+pub(crate) fn emulate(
+    rt: &mut Executor,
+    code: LinuxSyscallCode,
+    a0: u32,
+    a1: u32,
+    a2: u32,
+) -> Result<LinuxSyscallOutcome, ExecutionError> {
+    let outcome = match code {
+        LinuxSyscallCode::READ => read(rt, a0, a1, a2)?,
+        LinuxSyscallCode::WRITE => write(rt, a0, a1, a2),
+        LinuxSyscallCode::MMAP => {
+            let size = a1.next_multiple_of(PAGE_SIZE);
+            if a0 == 0 {
+                let addr = rt.state.linux.heap;
+                rt.state.linux.heap = addr.wrapping_add(size);
+                LinuxSyscallOutcome::ok(addr)
+            } else {
+                LinuxSyscallOutcome::ok(a0)
+            }
+        }
+        LinuxSyscallCode::BRK => LinuxSyscallOutcome::ok(PROGRAM_BREAK),
+        // Only a single thread is supported, so the child never runs.
+        LinuxSyscallCode::CLONE => LinuxSyscallOutcome::ok(1),
+        LinuxSyscallCode::FCNTL => fcntl(a0, a1),
+        LinuxSyscallCode::EXIT | LinuxSyscallCode::EXIT_GROUP => {
+            unreachable!("{code} is handled by the executor")
+        }
+        _ => LinuxSyscallOutcome::ok(0),
+    };
+    Ok(outcome)
+}
+
+/// Read at most up to the end of the word holding the buffer, like Cannon does.
+fn read(
+    rt: &mut Executor,
+    fd: u32,
+    addr: u32,
+    count: u32,
+) -> Result<LinuxSyscallOutcome, ExecutionError> {
+    match fd {
+        FD_STDIN => Ok(LinuxSyscallOutcome::ok(0)),
+        FD_HINT_READ => Ok(LinuxSyscallOutcome::ok(count)),
+        FD_PREIMAGE_READ => {
+            let preimage = match rt.state.linux.preimage.take() {
+                Some(preimage) => preimage,
+                None => fetch_preimage(rt)?,
+            };
+            let offset = rt.state.linux.preimage_offset as usize;
+            let alignment = addr % 4;
+            let len = (preimage.len().saturating_sub(offset) as u32).min(4 - alignment).min(count);
+
+            let aligned = addr - alignment;
+            let mut bytes = rt.word(aligned).to_le_bytes();
+            bytes[alignment as usize..(alignment + len) as usize]
+                .copy_from_slice(&preimage[offset..offset + len as usize]);
+
+            rt.state.linux.preimage_offset += len;
+            rt.state.linux.preimage = Some(preimage);
+            let mem_word = (len > 0).then(|| u32::from_le_bytes(bytes));
+            Ok(LinuxSyscallOutcome::read(len, mem_word))
+        }
+        _ => Ok(LinuxSyscallOutcome::error(EBADF)),
+    }
+}
+
+/// Fetch the preimage of the current key from the oracle, prefixed with its big-endian length.
+fn fetch_preimage(rt: &mut Executor) -> Result<Vec<u8>, ExecutionError> {
+    let key = rt.state.linux.preimage_key;
+    let preimage = rt
+        .preimage_oracle
+        .as_ref()
+        .and_then(|oracle| oracle.get(&key))
+        .ok_or_else(|| ExecutionError::MissingPreimage(hex::encode(key)))?;
+    let mut prefixed = (preimage.len() as u64).to_be_bytes().to_vec();
+    prefixed.extend_from_slice(&preimage);
+    Ok(prefixed)
+}
+
+fn write(rt: &mut Executor, fd: u32, addr: u32, count: u32) -> LinuxSyscallOutcome {
+    match fd {
+        FD_STDOUT | FD_STDERR => {
+            let bytes = (0..count).map(|i| rt.byte(addr + i)).collect::<Vec<u8>>();
+            let s = String::from_utf8_lossy(&bytes);
+            let entry = rt.io_buf.entry(fd).or_default();
+            entry.push_str(&s);
+            if let Some(end) = entry.rfind('\n') {
+                let rest = entry.split_off(end + 1);
+                let name = if fd == FD_STDOUT { "stdout" } else { "stderr" };
+                for line in std::mem::replace(entry, rest).lines() {
+                    println!("{name}: {line}");
+                }
+            }
+            LinuxSyscallOutcome::ok(count)
+        }
+        FD_HINT_WRITE => {
+            let bytes = (0..count).map(|i| rt.byte(addr + i)).collect::<Vec<u8>>();
+            let linux = &mut rt.state.linux;
+            linux.hint_buf.extend_from_slice(&bytes);
+            // Hints are prefixed with their big-endian length.
+            while linux.hint_buf.len() >= 4 {
+                let len = u32::from_be_bytes(linux.hint_buf[..4].try_into().unwrap()) as usize;
+                if linux.hint_buf.len() < 4 + len {
+                    break;
+                }
+                let hint = linux.hint_buf.drain(..4 + len).skip(4).collect::<Vec<u8>>();
+                if let Some(oracle) = rt.preimage_oracle.as_ref() {
+                    oracle.hint(&hint);
+                }
+            }
+            LinuxSyscallOutcome::ok(count)
+        }
+        FD_PREIMAGE_WRITE => {
+            // The key is shifted left by the written bytes, which are at most up to the end of the
+            // word holding the buffer.
+            let len = count.min(4 - addr % 4);
+            let bytes = (0..len).map(|i| rt.byte(addr + i)).collect::<Vec<u8>>();
+            let linux = &mut rt.state.linux;
+            linux.preimage_key.rotate_left(len as usize);
+            linux.preimage_key[32 - len as usize..].copy_from_slice(&bytes);
+            linux.preimage_offset = 0;
+            linux.preimage = None;
+            LinuxSyscallOutcome::ok(len)
+        }
+        _ => LinuxSyscallOutcome::error(EBADF),
+    }
+}
+
+fn fcntl(fd: u32, cmd: u32) -> LinuxSyscallOutcome {
+    const F_GETFD: u32 = 1;
+    const F_GETFL: u32 = 3;
+    const O_RDONLY: u32 = 0;
+    const O_WRONLY: u32 = 1;
+
+    match (cmd, fd) {
+        (F_GETFD, FD_STDIN..=FD_PREIMAGE_WRITE) => LinuxSyscallOutcome::ok(0),
+        (F_GETFL, FD_STDIN | FD_HINT_READ | FD_PREIMAGE_READ) => LinuxSyscallOutcome::ok(O_RDONLY),
+        (F_GETFL, FD_STDOUT | FD_STDERR | FD_HINT_WRITE | FD_PREIMAGE_WRITE) => {
+            LinuxSyscallOutcome::ok(O_WRONLY)
+        }
+        (F_GETFD | F_GETFL, _) => LinuxSyscallOutcome::error(EBADF),
+        _ => LinuxSyscallOutcome::error(EINVAL),
+    }
+}
@@ -6,6 +6,7 @@ mod context;
 mod deferred;
 mod halt;
 mod hint;
+pub mod linux;
 pub(crate) mod precompiles;
 mod unconstrained;
 mod verify;
@@ -22,8 +23,10 @@ pub use code::*;
 pub use context::*;
 use hint::{HintLenSyscall, HintReadSyscall};
 use precompiles::{
+    blake3::Blake3CompressInnerSyscall,
     edwards::{add::EdwardsAddAssignSyscall, decompress::EdwardsDecompressSyscall},
-    fptower::{Fp2AddSubSyscall, Fp2MulSyscall, FpOpSyscall},
+    fptower::{Fp2AddSubSyscall, Fp2MulSyscall, Fp6MulSyscall, FpOpSyscall},
+    poseidon2::Poseidon2PermuteSyscall,
     sha256::{compress::Sha256CompressSyscall, extend::Sha256ExtendSyscall},
     u256x2048_mul::U256xU2048MulSyscall,
     uint256::Uint256MulSyscall,
@@ -96,6 +99,8 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
 
     syscall_map.insert(SyscallCode::KECCAK_SPONGE, Arc::new(KeccakSpongeSyscall));
 
+    syscall_map.insert(SyscallCode::BLAKE3_COMPRESS_INNER, Arc::new(Blake3CompressInnerSyscall));
+
     syscall_map.insert(
         SyscallCode::SECP256K1_ADD,
         Arc::new(WeierstrassAddAssignSyscall::<Secp256k1>::new()),
@@ -148,6 +153,8 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
 
     syscall_map.insert(SyscallCode::U256XU2048_MUL, Arc::new(U256xU2048MulSyscall));
 
+    syscall_map.insert(SyscallCode::POSEIDON2_PERMUTE, Arc::new(Poseidon2PermuteSyscall));
+
     syscall_map.insert(
         SyscallCode::BLS12381_FP_ADD,
         Arc::new(FpOpSyscall::<Bls12381BaseField>::new(FieldOperation::Add)),
@@ -175,6 +182,8 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
 
     syscall_map
         .insert(SyscallCode::BLS12381_FP2_MUL, Arc::new(Fp2MulSyscall::<Bls12381BaseField>::new()));
+    syscall_map
+        .insert(SyscallCode::BLS12381_FP6_MUL, Arc::new(Fp6MulSyscall::<Bls12381BaseField>::new()));
 
     syscall_map.insert(
         SyscallCode::BN254_FP_ADD,
@@ -203,6 +212,8 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
 
     syscall_map
         .insert(SyscallCode::BN254_FP2_MUL, Arc::new(Fp2MulSyscall::<Bn254BaseField>::new()));
+    syscall_map
+        .insert(SyscallCode::BN254_FP6_MUL, Arc::new(Fp6MulSyscall::<Bn254BaseField>::new()));
 
     syscall_map.insert(SyscallCode::ENTER_UNCONSTRAINED, Arc::new(EnterUnconstrainedSyscall));
 
@@ -0,0 +1,81 @@
+use crate::{
+    events::{
+        g_func, Blake3CompressInnerEvent, PrecompileEvent, BLAKE3_STATE_SIZE, G_INDEX,
+        MSG_SCHEDULE, NUM_MSG_WORDS_PER_CALL, NUM_STATE_WORDS_PER_CALL, OPERATION_COUNT,
+        ROUND_COUNT,
+    },
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+};
+
+pub(crate) struct Blake3CompressInnerSyscall;
+
+impl Syscall for Blake3CompressInnerSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        (ROUND_COUNT * OPERATION_COUNT) as u32
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Option<u32> {
+        let state_ptr = arg1;
+        let message_ptr = arg2;
+        assert!(state_ptr.is_multiple_of(4), "state pointer must be word aligned");
+        assert!(message_ptr.is_multiple_of(4), "message pointer must be word aligned");
+        assert!(
+            state_ptr.abs_diff(message_ptr) >= (BLAKE3_STATE_SIZE * 4) as u32,
+            "state and message must not overlap"
+        );
+
+        let start_clk = rt.clk;
+        let mut state_writes = Vec::with_capacity(ROUND_COUNT * OPERATION_COUNT);
+        let mut message_reads = Vec::with_capacity(ROUND_COUNT * OPERATION_COUNT);
+
+        // Every `G` function reads and writes its state words and reads its message words at its
+        // own clock cycle, since the state words are updated several times per round.
+        for round in 0..ROUND_COUNT {
+            for operation in 0..OPERATION_COUNT {
+                let state_index = G_INDEX[operation];
+                let msg_index =
+                    [MSG_SCHEDULE[round][2 * operation], MSG_SCHEDULE[round][2 * operation + 1]];
+
+                let mut msg = [0u32; NUM_MSG_WORDS_PER_CALL];
+                let reads: [_; NUM_MSG_WORDS_PER_CALL] = core::array::from_fn(|i| {
+                    let (record, value) = rt.mr(message_ptr + msg_index[i] as u32 * 4);
+                    msg[i] = value;
+                    record
+                });
+
+                let input: [u32; NUM_STATE_WORDS_PER_CALL] =
+                    core::array::from_fn(|i| rt.word_unsafe(state_ptr + state_index[i] as u32 * 4));
+                let output = g_func(input, msg);
+                let writes: [_; NUM_STATE_WORDS_PER_CALL] = core::array::from_fn(|i| {
+                    rt.mw(state_ptr + state_index[i] as u32 * 4, output[i])
+                });
+
+                message_reads.push(reads);
+                state_writes.push(writes);
+                rt.clk += 1;
+            }
+        }
+
+        let shard = rt.current_shard();
+        let event = PrecompileEvent::Blake3CompressInner(Blake3CompressInnerEvent {
+            shard,
+            clk: start_clk,
+            state_ptr,
+            message_ptr,
+            state_writes,
+            message_reads,
+            local_mem_access: rt.postprocess(),
+        });
+        let syscall_event =
+            rt.rt.syscall_event(start_clk, None, rt.next_pc, syscall_code.syscall_id(), arg1, arg2);
+        rt.add_precompile_event(syscall_code, syscall_event, event);
+
+        None
+    }
+}
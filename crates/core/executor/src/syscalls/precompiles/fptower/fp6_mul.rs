@@ -0,0 +1,145 @@
+use std::marker::PhantomData;
+
+use num::BigUint;
+use typenum::Unsigned;
+use zkm_curves::{
+    params::NumWords,
+    weierstrass::{FieldType, FpOpField},
+};
+
+use crate::{
+    events::{Fp6MulEvent, PrecompileEvent},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+};
+
+pub struct Fp6MulSyscall<P> {
+    _marker: PhantomData<P>,
+}
+
+impl<P> Fp6MulSyscall<P> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+/// Multiplies two elements `a = a0 + a1 * u` and `b = b0 + b1 * u` of `Fp2 = Fp[u] / (u^2 + 1)`.
+fn fp2_mul(a: &[BigUint; 2], b: &[BigUint; 2], modulus: &BigUint) -> [BigUint; 2] {
+    let a0_b0 = (&a[0] * &b[0]) % modulus;
+    let a1_b1 = (&a[1] * &b[1]) % modulus;
+    let c0 = (modulus + a0_b0 - a1_b1) % modulus;
+    let c1 = (&a[0] * &b[1] + &a[1] * &b[0]) % modulus;
+    [c0, c1]
+}
+
+fn fp2_add(a: &[BigUint; 2], b: &[BigUint; 2], modulus: &BigUint) -> [BigUint; 2] {
+    [(&a[0] + &b[0]) % modulus, (&a[1] + &b[1]) % modulus]
+}
+
+/// Multiplies `a` by the non-residue `xi = c0 + u`.
+fn fp2_mul_by_non_residue(a: &[BigUint; 2], c0: u32, modulus: &BigUint) -> [BigUint; 2] {
+    let xi_a0 = (&a[0] * c0) % modulus;
+    let xi_a1 = (&a[1] * c0) % modulus;
+    [(modulus + xi_a0 - &a[1]) % modulus, (xi_a1 + &a[0]) % modulus]
+}
+
+/// Multiplies two elements `a = a0 + a1 * v + a2 * v^2` and `b` of `Fp6 = Fp2[v] / (v^3 - xi)`.
+pub(crate) fn fp6_mul<P: FpOpField>(
+    a: &[[BigUint; 2]; 3],
+    b: &[[BigUint; 2]; 3],
+) -> [[BigUint; 2]; 3] {
+    let modulus = &BigUint::from_bytes_le(P::MODULUS);
+    let xi = P::FP6_NON_RESIDUE_C0;
+    let p: [[[BigUint; 2]; 3]; 3] =
+        core::array::from_fn(|i| core::array::from_fn(|j| fp2_mul(&a[i], &b[j], modulus)));
+
+    // c0 = a0 * b0 + xi * (a1 * b2 + a2 * b1)
+    let c0 = fp2_add(
+        &p[0][0],
+        &fp2_mul_by_non_residue(&fp2_add(&p[1][2], &p[2][1], modulus), xi, modulus),
+        modulus,
+    );
+    // c1 = a0 * b1 + a1 * b0 + xi * a2 * b2
+    let c1 = fp2_add(
+        &fp2_add(&p[0][1], &p[1][0], modulus),
+        &fp2_mul_by_non_residue(&p[2][2], xi, modulus),
+        modulus,
+    );
+    // c2 = a0 * b2 + a1 * b1 + a2 * b0
+    let c2 = fp2_add(&fp2_add(&p[0][2], &p[1][1], modulus), &p[2][0], modulus);
+
+    [c0, c1, c2]
+}
+
+impl<P: FpOpField> Syscall for Fp6MulSyscall<P> {
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Option<u32> {
+        let clk = rt.clk;
+        let x_ptr = arg1;
+        if !x_ptr.is_multiple_of(4) {
+            panic!();
+        }
+        let y_ptr = arg2;
+        if !y_ptr.is_multiple_of(4) {
+            panic!();
+        }
+
+        let num_words_field_element = <P as NumWords>::WordsFieldElement::USIZE;
+        let num_words = 6 * num_words_field_element;
+
+        let x = rt.slice_unsafe(x_ptr, num_words);
+        let (y_memory_records, y) = rt.mr_slice(y_ptr, num_words);
+        rt.clk += 1;
+
+        let to_fp6 = |words: &[u32]| -> [[BigUint; 2]; 3] {
+            let mut coeffs = words.chunks(num_words_field_element).map(BigUint::from_slice);
+            core::array::from_fn(|_| core::array::from_fn(|_| coeffs.next().unwrap()))
+        };
+        let c = fp6_mul::<P>(&to_fp6(&x), &to_fp6(&y));
+
+        let mut result = Vec::with_capacity(num_words);
+        for coeff in c.iter().flatten() {
+            let mut words = coeff.to_u32_digits();
+            words.resize(num_words_field_element, 0);
+            result.extend_from_slice(&words);
+        }
+
+        let x_memory_records = rt.mw_slice(x_ptr, &result);
+
+        let shard = rt.current_shard();
+        let event = Fp6MulEvent {
+            shard,
+            clk,
+            x_ptr,
+            x,
+            y_ptr,
+            y,
+            x_memory_records,
+            y_memory_records,
+            local_mem_access: rt.postprocess(),
+        };
+        let syscall_event =
+            rt.rt.syscall_event(clk, None, rt.next_pc, syscall_code.syscall_id(), arg1, arg2);
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => rt.add_precompile_event(
+                syscall_code,
+                syscall_event,
+                PrecompileEvent::Bn254Fp6Mul(event),
+            ),
+            FieldType::Bls12381 => rt.add_precompile_event(
+                syscall_code,
+                syscall_event,
+                PrecompileEvent::Bls12381Fp6Mul(event),
+            ),
+        };
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
@@ -0,0 +1,68 @@
+use p3_field::{FieldAlgebra, PrimeField32};
+use p3_koala_bear::KoalaBear;
+use zkm_primitives::poseidon2_permute;
+
+use crate::{
+    events::{Poseidon2PermuteEvent, PrecompileEvent, POSEIDON2_WIDTH},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+};
+
+pub(crate) struct Poseidon2PermuteSyscall;
+
+impl Syscall for Poseidon2PermuteSyscall {
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Option<u32> {
+        let clk = rt.clk;
+
+        let state_ptr = arg1;
+        if !state_ptr.is_multiple_of(4) {
+            panic!();
+        }
+        assert_eq!(arg2, 0, "arg2 must be 0");
+
+        // Read the state. We can read a slice_unsafe here because we write the permuted state
+        // back to the same location later.
+        let state = rt.slice_unsafe(state_ptr, POSEIDON2_WIDTH);
+
+        // Every element of the state must be a canonical KoalaBear element.
+        let input: [KoalaBear; POSEIDON2_WIDTH] = core::array::from_fn(|i| {
+            assert!(
+                state[i] < KoalaBear::ORDER_U32,
+                "poseidon2 state element {i} is not a canonical KoalaBear element: {}",
+                state[i]
+            );
+            KoalaBear::from_canonical_u32(state[i])
+        });
+
+        let output = poseidon2_permute(input).map(|x| x.as_canonical_u32());
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        // Write the permuted state and keep track of the memory records.
+        let state_memory_records = rt.mw_slice(state_ptr, &output);
+
+        let shard = rt.current_shard();
+        let event = PrecompileEvent::Poseidon2Permute(Poseidon2PermuteEvent {
+            shard,
+            clk,
+            state_ptr,
+            state,
+            state_memory_records,
+            local_mem_access: rt.postprocess(),
+        });
+        let syscall_event =
+            rt.rt.syscall_event(clk, None, rt.next_pc, syscall_code.syscall_id(), arg1, arg2);
+        rt.add_precompile_event(syscall_code, syscall_event, event);
+
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
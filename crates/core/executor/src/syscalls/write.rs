@@ -1,6 +1,7 @@
+use itertools::Itertools;
 use zkm_primitives::consts::num_to_comma_separated;
 
-use crate::{Executor, Register};
+use crate::{ExecutionError, Executor, Register};
 
 use super::{Syscall, SyscallCode, SyscallContext};
 
@@ -46,11 +47,34 @@ impl Syscall for WriteSyscall {
             rt.state.public_values_stream.extend_from_slice(slice);
         } else if fd == FD_HINT {
             rt.state.input_stream.push(slice.to_vec());
-        } else if let Some(mut hook) = rt.hook_registry.get(fd) {
-            let res = hook.invoke_hook(rt.hook_env(), slice);
-            // Add result vectors to the beginning of the stream.
+        } else if fd == FD_GETENV {
+            // The value is prefixed with whether the variable is set.
+            let name = String::from_utf8_lossy(slice);
+            let value = match rt.state.env.get(name.as_ref()) {
+                Some(value) => [&[1], value.as_bytes()].concat(),
+                None => vec![0],
+            };
             let ptr = rt.state.input_stream_ptr;
-            rt.state.input_stream.splice(ptr..ptr, res);
+            rt.state.input_stream.insert(ptr, value);
+        } else if fd == FD_RNG_SEED {
+            // The seed is empty if it is not set.
+            let seed = rt.state.rng_seed.map(|seed| seed.to_vec()).unwrap_or_default();
+            let ptr = rt.state.input_stream_ptr;
+            rt.state.input_stream.insert(ptr, seed);
+        } else if let Some(res) =
+            rt.hook_registry.get(fd).map(|mut hook| hook.try_invoke_hook(rt.hook_env(), slice))
+        {
+            match res {
+                Ok(res) => {
+                    // Add result vectors to the beginning of the stream.
+                    let ptr = rt.state.input_stream_ptr;
+                    rt.state.input_stream.splice(ptr..ptr, res);
+                }
+                Err(err) => rt.syscall_error = Some(ExecutionError::HookFailed(err.to_string())),
+            }
+        } else if fd >= TYPED_HOOK_FD_START {
+            // The program would read the next hint as the response of the hook.
+            rt.syscall_error = Some(ExecutionError::UnknownTypedHook(fd));
         } else {
             tracing::warn!("tried to write to unknown file descriptor {fd}");
         }
@@ -113,13 +137,31 @@ fn start_cycle_tracker(rt: &mut Executor, name: &str) {
     log::info!("{padding}┌╴{name}");
 }
 
-/// End tracking cycles for the given name, print out the log, and return the total number of cycles
-/// in the span. If the name is not found in the cycle tracker cache, returns None.
+/// End tracking cycles for the given name, print out the log, record the span in the report and
+/// return the total number of cycles in the span. If the name is not found in the cycle tracker
+/// cache, returns None.
 fn end_cycle_tracker(rt: &mut Executor, name: &str) -> Option<u64> {
     if let Some((start, depth)) = rt.cycle_tracker.remove(name) {
         let padding = "│ ".repeat(depth as usize);
         let total_cycles = rt.state.global_clk - start;
         log::info!("{}└╴{} cycles", padding, num_to_comma_separated(total_cycles));
+
+        // The span is keyed by the names of the spans enclosing it, outermost first.
+        let mut enclosing = rt
+            .cycle_tracker
+            .iter()
+            .filter(|(_, (_, enclosing_depth))| *enclosing_depth < depth)
+            .map(|(enclosing_name, (_, enclosing_depth))| {
+                (*enclosing_depth, enclosing_name.as_str())
+            })
+            .collect::<Vec<_>>();
+        enclosing.sort_unstable();
+        let path = enclosing.into_iter().map(|(_, name)| name).chain([name]).join(";");
+        let span = rt.report.cycle_tracker_spans.entry(path).or_default();
+        span.depth = depth;
+        span.invocations += 1;
+        span.cycles += total_cycles;
+
         return Some(total_cycles);
     }
     None
@@ -142,3 +184,36 @@ fn update_io_buf(ctx: &mut SyscallContext, fd: u32, s: &str) -> Vec<String> {
         vec![]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zkm_stark::ZKMCoreOpts;
+
+    use super::{handle_cycle_tracker_command, CycleTrackerCommand};
+    use crate::{programs::tests::simple_program, Executor};
+
+    #[test]
+    fn test_cycle_tracker_spans() {
+        let mut rt = Executor::new(simple_program(), ZKMCoreOpts::default());
+        for _ in 0..2 {
+            handle_cycle_tracker_command(&mut rt, CycleTrackerCommand::Start("outer".to_string()));
+            rt.state.global_clk += 10;
+            handle_cycle_tracker_command(
+                &mut rt,
+                CycleTrackerCommand::ReportStart("inner".to_string()),
+            );
+            rt.state.global_clk += 5;
+            handle_cycle_tracker_command(
+                &mut rt,
+                CycleTrackerCommand::ReportEnd("inner".to_string()),
+            );
+            handle_cycle_tracker_command(&mut rt, CycleTrackerCommand::End("outer".to_string()));
+        }
+
+        let outer = rt.report.cycle_tracker_spans["outer"];
+        assert_eq!((outer.depth, outer.invocations, outer.cycles), (0, 2, 30));
+        let inner = rt.report.cycle_tracker_spans["outer;inner"];
+        assert_eq!((inner.depth, inner.invocations, inner.cycles), (1, 2, 10));
+        assert_eq!(rt.report.cycle_tracker["inner"], 10);
+    }
+}
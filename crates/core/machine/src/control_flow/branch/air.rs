@@ -20,7 +20,9 @@ use super::{BranchChip, BranchColumns};
 ///    boolean that indicates whether the branch condition is true.
 /// 2. It verifies the correct value of branching based on the helper bool columns (a_eq_b,
 ///    a_gt_b, a_lt_b).
-/// 3. It verifier the correct values of the helper bool columns based on op_a and op_b.
+/// 3. It verifier the correct values of the helper bool columns based on the compared values,
+///    which are op_a and op_b, or op_b and zero for branches that link.
+/// 4. For branches that link, it verifies that the return address is written to op_a.
 ///
 impl<AB> Air<AB> for BranchChip
 where
@@ -33,8 +35,9 @@ where
         let local = main.row_slice(0);
         let local: &BranchColumns<AB::Var> = (*local).borrow();
 
-        // SAFETY: All selectors `is_beq`, `is_bne`, `is_bltz`, `is_bgez`, `is_blez`, `is_bgtz` are checked to be boolean.
-        // Each "real" row has exactly one selector turned on, as `is_real`, the sum of the six selectors, is boolean.
+        // SAFETY: All selectors `is_beq`, `is_bne`, `is_bltz`, `is_bgez`, `is_blez`, `is_bgtz`,
+        // `is_bgezal`, `is_bltzal` are checked to be boolean.
+        // Each "real" row has exactly one selector turned on, as `is_real`, the sum of the eight selectors, is boolean.
         // Therefore, the `opcode` matches the corresponding opcode.
         builder.assert_bool(local.is_beq);
         builder.assert_bool(local.is_bne);
@@ -42,12 +45,16 @@ where
         builder.assert_bool(local.is_bgez);
         builder.assert_bool(local.is_blez);
         builder.assert_bool(local.is_bgtz);
+        builder.assert_bool(local.is_bgezal);
+        builder.assert_bool(local.is_bltzal);
+        let is_link = local.is_bgezal + local.is_bltzal;
         let is_real = local.is_beq
             + local.is_bne
             + local.is_bltz
             + local.is_bgez
             + local.is_blez
-            + local.is_bgtz;
+            + local.is_bgtz
+            + is_link.clone();
         builder.assert_bool(is_real.clone());
 
         let opcode = local.is_beq * Opcode::BEQ.as_field::<AB::F>()
@@ -55,12 +62,15 @@ where
             + local.is_bltz * Opcode::BLTZ.as_field::<AB::F>()
             + local.is_bgez * Opcode::BGEZ.as_field::<AB::F>()
             + local.is_blez * Opcode::BLEZ.as_field::<AB::F>()
-            + local.is_bgtz * Opcode::BGTZ.as_field::<AB::F>();
+            + local.is_bgtz * Opcode::BGTZ.as_field::<AB::F>()
+            + local.is_bgezal * Opcode::BGEZAL.as_field::<AB::F>()
+            + local.is_bltzal * Opcode::BLTZAL.as_field::<AB::F>();
 
         // SAFETY: This checks the following.
         // - `num_extra_cycles = 0`
-        // - `op_a_val` will be constrained in the BranchChip as `op_a_immutable = 1`
-        // - `op_a_immutable = 1`, as this is a branch instruction
+        // - `op_a_val` will be constrained in the BranchChip as `op_a_immutable = 1`, except for
+        //   branches that link, which constrain it to be the return address below
+        // - `op_a_immutable = 1 - is_link`, as this is a branch instruction
         // - `is_memory = 0`
         // - `is_syscall = 0`
         // - `is_halt = 0`
@@ -76,7 +86,7 @@ where
             local.op_b_value,
             local.op_c_value,
             Word([AB::Expr::ZERO; 4]),
-            AB::Expr::ONE,
+            AB::Expr::ONE - is_link.clone(),
             AB::Expr::ZERO,
             AB::Expr::ZERO,
             AB::Expr::ZERO,
@@ -134,6 +144,34 @@ where
             builder.when(is_real.clone()).assert_bool(local.is_branching);
         }
 
+        // Evaluate link constraints.
+        {
+            // Verify that the return address `next_pc + 4` is saved in op_a for branches that link.
+            builder.when(is_link.clone()).assert_eq(
+                local.op_a_value.reduce::<AB>(),
+                local.next_pc.reduce::<AB>() + AB::F::from_canonical_u32(4),
+            );
+
+            // SAFETY: `is_link` is boolean, and zero for padding rows.
+            // `op_a_value` is checked to be a valid word in the CpuChip's `eval_registers`.
+            KoalaBearWordRangeChecker::<AB::F>::range_check(
+                builder,
+                local.op_a_value,
+                local.op_a_range_checker,
+                is_link.clone(),
+            );
+
+            // Branches that link compare op_b to zero, the other branches compare op_a to op_b.
+            builder
+                .when(is_real.clone() - is_link.clone())
+                .assert_word_eq(local.src1_value, local.op_a_value);
+            builder
+                .when(is_real.clone() - is_link.clone())
+                .assert_word_eq(local.src2_value, local.op_b_value);
+            builder.when(is_link.clone()).assert_word_eq(local.src1_value, local.op_b_value);
+            builder.when(is_link).assert_word_zero(local.src2_value);
+        }
+
         // Evaluate branching value constraints.
         {
             // When the opcode is BEQ and we are branching, assert that a_gt_b + a_lt_b is false.
@@ -158,11 +196,16 @@ where
                 .when_not(local.is_branching)
                 .assert_zero(local.a_gt_b + local.a_lt_b);
 
-            // When the opcode is BLTZ and we are branching, assert that a_lt_b is true.
-            builder.when(local.is_bltz * local.is_branching).assert_one(local.a_lt_b);
+            // When the opcode is BLTZ or BLTZAL and we are branching, assert that a_lt_b is true.
+            builder
+                .when((local.is_bltz + local.is_bltzal) * local.is_branching)
+                .assert_one(local.a_lt_b);
 
-            // When the opcode is BLTZ and we are not branching, assert a_lt_b is false.
-            builder.when(local.is_bltz).when_not(local.is_branching).assert_zero(local.a_lt_b);
+            // When the opcode is BLTZ or BLTZAL and we are not branching, assert a_lt_b is false.
+            builder
+                .when(local.is_bltz + local.is_bltzal)
+                .when_not(local.is_branching)
+                .assert_zero(local.a_lt_b);
 
             // When the opcode is BLEZ and we are branching, assert that either a_gt_b is false
             builder.when(local.is_blez * local.is_branching).assert_zero(local.a_gt_b);
@@ -176,11 +219,17 @@ where
             // When the opcode is BGTZ and we are not branching, assert that a_gt_b is false.
             builder.when(local.is_bgtz).when_not(local.is_branching).assert_zero(local.a_gt_b);
 
-            // When the opcode is BGEZ and we are branching, assert that a_lt_b is false.
-            builder.when(local.is_bgez * local.is_branching).assert_zero(local.a_lt_b);
+            // When the opcode is BGEZ or BGEZAL and we are branching, assert that a_lt_b is false.
+            builder
+                .when((local.is_bgez + local.is_bgezal) * local.is_branching)
+                .assert_zero(local.a_lt_b);
 
-            // When the opcode is BGEZ and we are not branching, assert that a_lt_b is true.
-            builder.when(local.is_bgez).when_not(local.is_branching).assert_one(local.a_lt_b);
+            // When the opcode is BGEZ or BGEZAL and we are not branching, assert that a_lt_b is
+            // true.
+            builder
+                .when(local.is_bgez + local.is_bgezal)
+                .when_not(local.is_branching)
+                .assert_one(local.a_lt_b);
         }
 
         // Calculate a_lt_b <==> a < b (using appropriate signedness).
@@ -188,8 +237,8 @@ where
         builder.send_alu(
             Opcode::SLT.as_field::<AB::F>(),
             Word::extend_var::<AB>(local.a_lt_b),
-            local.op_a_value,
-            local.op_b_value,
+            local.src1_value,
+            local.src2_value,
             is_real.clone(),
         );
 
@@ -197,8 +246,8 @@ where
         builder.send_alu(
             Opcode::SLT.as_field::<AB::F>(),
             Word::extend_var::<AB>(local.a_gt_b),
-            local.op_b_value,
-            local.op_a_value,
+            local.src2_value,
+            local.src1_value,
             is_real.clone(),
         );
     }
@@ -35,6 +35,16 @@ pub struct BranchColumns<T> {
     /// The value of the third operand.
     pub op_c_value: Word<T>,
 
+    /// The values compared by the branch.
+    ///
+    /// These are `op_a` and `op_b`, except for branches that link, which compare `op_b` to zero
+    /// and write the return address to `op_a`.
+    pub src1_value: Word<T>,
+    pub src2_value: Word<T>,
+
+    /// A range checker for `op_a`, which contains `next_pc + 4` for branches that link.
+    pub op_a_range_checker: KoalaBearWordRangeChecker<T>,
+
     /// Branch Instructions Selectors.
     pub is_beq: T,
     pub is_bne: T,
@@ -42,15 +52,17 @@ pub struct BranchColumns<T> {
     pub is_blez: T,
     pub is_bgtz: T,
     pub is_bgez: T,
+    pub is_bgezal: T,
+    pub is_bltzal: T,
 
     /// The branching column is equal to:
     ///
     /// > is_beq & a_eq_b ||
     /// > is_bne & !a_eq_b ||
-    /// > is_bltz & a_lt_0 ||
+    /// > (is_bltz | is_bltzal) & a_lt_0 ||
     /// > is_bgtz & a_gt_0 ||
     /// > is_blez & (a_lt_0  | a_eq_0) ||
-    /// > is_bgez & (a_gt_0  | a_eq_0)
+    /// > (is_bgez | is_bgezal) & (a_gt_0  | a_eq_0)
     pub is_branching: T,
 
     /// Whether a is greater than b.
@@ -88,15 +88,24 @@ impl BranchChip {
         cols.is_bgtz = F::from_bool(matches!(event.opcode, Opcode::BGTZ));
         cols.is_blez = F::from_bool(matches!(event.opcode, Opcode::BLEZ));
         cols.is_bgez = F::from_bool(matches!(event.opcode, Opcode::BGEZ));
+        cols.is_bgezal = F::from_bool(matches!(event.opcode, Opcode::BGEZAL));
+        cols.is_bltzal = F::from_bool(matches!(event.opcode, Opcode::BLTZAL));
 
         cols.op_a_value = event.a.into();
         cols.op_b_value = event.b.into();
         cols.op_c_value = event.c.into();
+        if matches!(event.opcode, Opcode::BGEZAL | Opcode::BLTZAL) {
+            cols.op_a_range_checker.populate(event.a);
+        }
+
+        let (src1, src2) = event.compared_values();
+        cols.src1_value = src1.into();
+        cols.src2_value = src2.into();
 
-        let a_eq_b = event.a == event.b;
+        let a_eq_b = src1 == src2;
 
-        let a_lt_b = (event.a as i32) < (event.b as i32);
-        let a_gt_b = (event.a as i32) > (event.b as i32);
+        let a_lt_b = (src1 as i32) < (src2 as i32);
+        let a_gt_b = (src1 as i32) > (src2 as i32);
 
         cols.a_lt_b = F::from_bool(a_lt_b);
         cols.a_gt_b = F::from_bool(a_gt_b);
@@ -104,10 +113,10 @@ impl BranchChip {
         let branching = match event.opcode {
             Opcode::BEQ => a_eq_b,
             Opcode::BNE => !a_eq_b,
-            Opcode::BLTZ => a_lt_b,
+            Opcode::BLTZ | Opcode::BLTZAL => a_lt_b,
             Opcode::BLEZ => a_lt_b || a_eq_b,
             Opcode::BGTZ => a_gt_b,
-            Opcode::BGEZ => a_eq_b || a_gt_b,
+            Opcode::BGEZ | Opcode::BGEZAL => a_eq_b || a_gt_b,
             _ => panic!("Invalid opcode: {}", event.opcode),
         };
 
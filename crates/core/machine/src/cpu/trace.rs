@@ -122,11 +122,18 @@ impl CpuChip {
 
         cols.op_a_immutable = F::from_bool(
             instruction.is_memory_store_instruction_except_sc()
-                || instruction.is_branch_instruction(),
+                || (instruction.is_branch_instruction()
+                    && !instruction.is_branch_link_instruction())
+                || instruction.is_trap_instruction()
+                || instruction.is_fpu_instruction(),
         );
 
+        // FPU instructions access the floating-point registers themselves, so they need the
+        // shard and clk just like memory instructions.
         cols.is_memory = F::from_bool(
-            instruction.is_memory_load_instruction() || instruction.is_memory_store_instruction(),
+            instruction.is_memory_load_instruction()
+                || instruction.is_memory_store_instruction()
+                || instruction.is_fpu_instruction(),
         );
 
         cols.is_rw_a = F::from_bool(instruction.is_rw_a_instruction());
@@ -145,6 +152,7 @@ impl CpuChip {
             || instruction.is_memory_store_instruction()
             || instruction.is_rw_a_instruction()
             || instruction.is_mult_div_instruction()
+            || instruction.is_fpu_instruction()
         {
             cols.shard
         } else {
@@ -154,6 +162,7 @@ impl CpuChip {
             || instruction.is_memory_store_instruction()
             || instruction.is_rw_a_instruction()
             || instruction.is_mult_div_instruction()
+            || instruction.is_fpu_instruction()
         {
             F::from_canonical_u32(event.clk)
         } else {
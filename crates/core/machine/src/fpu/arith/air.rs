@@ -0,0 +1,874 @@
+use std::{array, borrow::Borrow};
+
+use p3_air::{Air, AirBuilder};
+use p3_field::FieldAlgebra;
+use p3_matrix::Matrix;
+use zkm_core_executor::{
+    events::MemoryAccessPosition,
+    fpu::{DEFAULT_NAN_D, DEFAULT_NAN_S, INVALID_WORD},
+    ByteOpcode, Opcode,
+};
+use zkm_stark::{
+    air::{BaseAirBuilder, ZKMAirBuilder},
+    Word,
+};
+
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    fpu::utils::{eval_carry_identity, limb_product},
+    memory::{MemoryCols, MemoryReadCols},
+    operations::IsZeroOperation,
+};
+
+use super::{FpuArithChip, FpuArithColumns, FpuOperandCols};
+
+/// The encoding of an infinity in each format.
+const INF_S: u64 = 0x7f80_0000;
+const INF_D: u64 = 0x7ff0_0000_0000_0000;
+
+/// The classes of instructions, as sums of the selectors.
+struct Selectors<AB: ZKMAirBuilder> {
+    is_real: AB::Expr,
+    is_add: AB::Expr,
+    is_sub: AB::Expr,
+    is_addsub: AB::Expr,
+    is_mul: AB::Expr,
+    is_div: AB::Expr,
+    is_sqrt: AB::Expr,
+    /// All conversions, and the conversions to a floating-point format.
+    is_cvt: AB::Expr,
+    is_cvt_f: AB::Expr,
+    is_trunc: AB::Expr,
+    /// The format of `fs`.
+    in_s: AB::Expr,
+    in_d: AB::Expr,
+    in_w: AB::Expr,
+    /// The format of `ft`, which is only read by the binary operations.
+    binary_s: AB::Expr,
+    binary_d: AB::Expr,
+    /// The format of the result.
+    out_s: AB::Expr,
+    out_d: AB::Expr,
+    out_w: AB::Expr,
+    out_f: AB::Expr,
+}
+
+impl<AB: ZKMAirBuilder> Selectors<AB> {
+    fn new(local: &FpuArithColumns<AB::Var>) -> Self {
+        let is_add = local.is_add_s + local.is_add_d;
+        let is_sub = local.is_sub_s + local.is_sub_d;
+        let is_mul = local.is_mul_s + local.is_mul_d;
+        let is_div = local.is_div_s + local.is_div_d;
+        let is_sqrt = local.is_sqrt_s + local.is_sqrt_d;
+        let is_cvt_f = local.is_cvt_s_d + local.is_cvt_d_s + local.is_cvt_s_w + local.is_cvt_d_w;
+        let is_trunc = local.is_trunc_w_s + local.is_trunc_w_d;
+        let out_w = local.is_cvt_w_s + local.is_cvt_w_d + is_trunc.clone();
+        let binary_s = local.is_add_s + local.is_sub_s + local.is_mul_s + local.is_div_s;
+        let binary_d = local.is_add_d + local.is_sub_d + local.is_mul_d + local.is_div_d;
+        let in_s = binary_s.clone()
+            + local.is_sqrt_s
+            + local.is_cvt_d_s
+            + local.is_cvt_w_s
+            + local.is_trunc_w_s;
+        let in_d = binary_d.clone()
+            + local.is_sqrt_d
+            + local.is_cvt_s_d
+            + local.is_cvt_w_d
+            + local.is_trunc_w_d;
+        let in_w = local.is_cvt_s_w + local.is_cvt_d_w;
+        let out_s = binary_s.clone() + local.is_sqrt_s + local.is_cvt_s_d + local.is_cvt_s_w;
+        let out_d = binary_d.clone() + local.is_sqrt_d + local.is_cvt_d_s + local.is_cvt_d_w;
+
+        Self {
+            is_real: in_s.clone() + in_d.clone() + in_w.clone(),
+            is_addsub: is_add.clone() + is_sub.clone(),
+            is_add,
+            is_sub,
+            is_mul,
+            is_div,
+            is_sqrt,
+            is_cvt: is_cvt_f.clone() + out_w.clone(),
+            is_cvt_f,
+            is_trunc,
+            in_s,
+            in_d,
+            in_w,
+            binary_s,
+            binary_d,
+            out_f: out_s.clone() + out_d.clone(),
+            out_s,
+            out_d,
+            out_w,
+        }
+    }
+}
+
+impl<AB> Air<AB> for FpuArithChip
+where
+    AB: ZKMAirBuilder,
+    AB::Var: Sized,
+{
+    #[inline(never)]
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &FpuArithColumns<AB::Var> = (*local).borrow();
+
+        let selectors = [
+            (local.is_add_s, Opcode::FADD_S),
+            (local.is_sub_s, Opcode::FSUB_S),
+            (local.is_mul_s, Opcode::FMUL_S),
+            (local.is_div_s, Opcode::FDIV_S),
+            (local.is_sqrt_s, Opcode::FSQRT_S),
+            (local.is_add_d, Opcode::FADD_D),
+            (local.is_sub_d, Opcode::FSUB_D),
+            (local.is_mul_d, Opcode::FMUL_D),
+            (local.is_div_d, Opcode::FDIV_D),
+            (local.is_sqrt_d, Opcode::FSQRT_D),
+            (local.is_cvt_s_d, Opcode::CVT_S_D),
+            (local.is_cvt_d_s, Opcode::CVT_D_S),
+            (local.is_cvt_s_w, Opcode::CVT_S_W),
+            (local.is_cvt_d_w, Opcode::CVT_D_W),
+            (local.is_cvt_w_s, Opcode::CVT_W_S),
+            (local.is_cvt_w_d, Opcode::CVT_W_D),
+            (local.is_trunc_w_s, Opcode::TRUNC_W_S),
+            (local.is_trunc_w_d, Opcode::TRUNC_W_D),
+        ];
+
+        // SAFETY: All selectors are boolean and at most one of them is set, as `is_real` is boolean.
+        let mut opcode = AB::Expr::ZERO;
+        for &(selector, op) in selectors.iter() {
+            builder.assert_bool(selector);
+            opcode = opcode.clone() + selector * op.as_field::<AB::F>();
+        }
+        let sel = Selectors::<AB>::new(local);
+        builder.assert_bool(sel.is_real.clone());
+
+        // The instruction reads `$zero` as `op_a`, which is never written.
+        builder.receive_instruction(
+            local.shard,
+            local.clk,
+            local.pc,
+            local.next_pc,
+            AB::Expr::ZERO,
+            opcode,
+            Word([AB::Expr::ZERO; 4]),
+            local.op_b_value,
+            local.op_c_value,
+            Word([AB::Expr::ZERO; 4]),
+            AB::Expr::ONE,
+            AB::Expr::ONE,
+            AB::Expr::ZERO,
+            AB::Expr::ZERO,
+            AB::Expr::ZERO,
+            AB::Expr::ONE,
+            sel.is_real.clone(),
+        );
+
+        // Read `fs` at position B and `ft` at position C, and write `fd` at position A.
+        let accesses = [
+            (&local.fs_access, local.op_b_value[0], sel.is_real.clone(), sel.in_d.clone()),
+            (
+                &local.ft_access,
+                local.op_c_value[0],
+                sel.binary_s.clone() + sel.binary_d.clone(),
+                sel.binary_d.clone(),
+            ),
+        ];
+        for ((access, addr, is_first, is_second), position) in
+            accesses.into_iter().zip([MemoryAccessPosition::B, MemoryAccessPosition::C])
+        {
+            for (i, do_check) in [is_first, is_second].into_iter().enumerate() {
+                builder.eval_memory_access(
+                    local.shard,
+                    local.clk + AB::F::from_canonical_u32(position as u32),
+                    addr + AB::F::from_canonical_usize(i),
+                    &access[i],
+                    do_check,
+                );
+            }
+        }
+        for (i, do_check) in [sel.is_real.clone(), sel.out_d.clone()].into_iter().enumerate() {
+            builder.eval_memory_access(
+                local.shard,
+                local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::A as u32),
+                local.op_c_value[1] + AB::F::from_canonical_usize(i),
+                &local.fd_access[i],
+                do_check,
+            );
+        }
+
+        self.eval_operand(
+            builder,
+            &local.a,
+            &local.fs_access,
+            sel.in_s.clone(),
+            sel.in_d.clone(),
+            sel.in_w.clone(),
+            sel.is_real.clone(),
+        );
+        self.eval_operand(
+            builder,
+            &local.b,
+            &local.ft_access,
+            sel.binary_s.clone(),
+            sel.binary_d.clone(),
+            AB::Expr::ZERO,
+            sel.is_real.clone(),
+        );
+
+        self.eval_exact(builder, local, &sel);
+        self.eval_quotient(builder, local, &sel);
+        self.eval_rounding(builder, local, &sel);
+        self.eval_result(builder, local, &sel);
+    }
+}
+
+impl FpuArithChip {
+    /// Unpacks an operand into its sign, exponent and significand, and classifies it.
+    #[allow(clippy::too_many_arguments)]
+    fn eval_operand<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        cols: &FpuOperandCols<AB::Var>,
+        access: &[MemoryReadCols<AB::Var>; 2],
+        is_single: AB::Expr,
+        is_double: AB::Expr,
+        is_word: AB::Expr,
+        is_real: AB::Expr,
+    ) {
+        let v: [AB::Expr; 8] = array::from_fn(|i| access[i / 4].value()[i % 4].into());
+        let is_float = is_single.clone() + is_double.clone();
+        let is_active = is_float.clone() + is_word.clone();
+
+        // The sign is the top bit of the most significant byte.
+        builder.assert_eq(
+            cols.top_byte,
+            (is_single.clone() + is_word.clone()) * v[3].clone() + is_double.clone() * v[7].clone(),
+        );
+        builder.send_byte(
+            ByteOpcode::MSB.as_field::<AB::F>(),
+            cols.sign,
+            cols.top_byte,
+            AB::Expr::ZERO,
+            is_active.clone(),
+        );
+        builder.when_not(is_active).assert_zero(cols.sign);
+
+        // The biased exponent continues below the top byte into the top bit of byte 2 for
+        // singles, and the top four bits of byte 6 for doubles.
+        builder.assert_eq(
+            cols.exponent_byte,
+            is_single.clone() * v[2].clone() + is_double.clone() * v[6].clone(),
+        );
+        builder.send_byte(
+            ByteOpcode::MSB.as_field::<AB::F>(),
+            cols.exponent_low,
+            cols.exponent_byte,
+            AB::Expr::ZERO,
+            is_single.clone(),
+        );
+        builder.send_byte(
+            ByteOpcode::U8Range.as_field::<AB::F>(),
+            AB::Expr::ZERO,
+            cols.exponent_low,
+            cols.exponent_low * AB::F::from_canonical_u32(16),
+            is_double.clone(),
+        );
+        builder.send_byte(
+            ByteOpcode::U8Range.as_field::<AB::F>(),
+            AB::Expr::ZERO,
+            (cols.exponent_byte - cols.exponent_low * AB::F::from_canonical_u32(16))
+                * AB::F::from_canonical_u32(16),
+            AB::Expr::ZERO,
+            is_double.clone(),
+        );
+        builder.when_not(is_float.clone()).assert_zero(cols.exponent_low);
+        builder.assert_eq(
+            cols.exponent,
+            (is_single.clone() * AB::F::TWO + is_double.clone() * AB::F::from_canonical_u32(16))
+                * (cols.top_byte - cols.sign * AB::F::from_canonical_u32(128))
+                + cols.exponent_low,
+        );
+
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            cols.exponent.into(),
+            cols.exponent_zero,
+            is_real.clone(),
+        );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            cols.exponent + is_real.clone()
+                - is_single.clone() * AB::F::from_canonical_u32(256)
+                - is_double.clone() * AB::F::from_canonical_u32(2048),
+            cols.exponent_max,
+            is_real.clone(),
+        );
+        let hidden = AB::Expr::ONE - cols.exponent_zero.result;
+
+        // A negative word is negated.
+        builder.send_alu(
+            Opcode::SUB.as_field::<AB::F>(),
+            cols.abs_value,
+            Word([AB::Expr::ZERO; 4]),
+            Word(array::from_fn(|i| v[i].clone())),
+            is_word.clone() * cols.sign,
+        );
+        for i in 0..4 {
+            builder
+                .when(is_word.clone())
+                .when_not(cols.sign)
+                .assert_eq(cols.abs_value[i], v[i].clone());
+        }
+
+        // The significand is the fraction with the hidden bit for floats, and the absolute value
+        // for words.
+        let abs = cols.abs_value;
+        let m = cols.significand;
+        builder.assert_eq(m[0], is_float.clone() * v[0].clone() + is_word.clone() * abs[0]);
+        builder.assert_eq(m[1], is_float.clone() * v[1].clone() + is_word.clone() * abs[1]);
+        builder.assert_eq(
+            m[2],
+            is_single.clone()
+                * (cols.exponent_byte - cols.exponent_low * AB::F::from_canonical_u32(128)
+                    + hidden.clone() * AB::F::from_canonical_u32(128))
+                + is_double.clone() * v[2].clone()
+                + is_word.clone() * abs[2],
+        );
+        builder.assert_eq(m[3], is_double.clone() * v[3].clone() + is_word * abs[3]);
+        builder.assert_eq(m[4], is_double.clone() * v[4].clone());
+        builder.assert_eq(m[5], is_double.clone() * v[5].clone());
+        builder.assert_eq(
+            m[6],
+            is_double.clone()
+                * (cols.exponent_byte - cols.exponent_low * AB::F::from_canonical_u32(16)
+                    + hidden * AB::F::from_canonical_u32(16)),
+        );
+
+        let sum = m.iter().fold(AB::Expr::ZERO, |acc, &limb| acc + limb);
+        IsZeroOperation::<AB::F>::eval(builder, sum.clone(), cols.is_zero, is_real.clone());
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            sum - is_single.clone() * AB::F::from_canonical_u32(128)
+                - is_double.clone() * AB::F::from_canonical_u32(16),
+            cols.fraction_zero,
+            is_real,
+        );
+        builder.assert_eq(cols.is_inf, cols.exponent_max.result * cols.fraction_zero.result);
+        builder.assert_eq(
+            cols.is_nan,
+            cols.exponent_max.result * (AB::Expr::ONE - cols.fraction_zero.result),
+        );
+
+        // The scale of a subnormal is the one of the smallest normal exponent.
+        builder.assert_eq(
+            cols.scale,
+            is_float * (cols.exponent + cols.exponent_zero.result)
+                - is_single * AB::F::from_canonical_u32(150)
+                - is_double * AB::F::from_canonical_u32(1075),
+        );
+    }
+
+    /// Constrains the exact result `num * 2^scale / den` of the operation.
+    fn eval_exact<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &FpuArithColumns<AB::Var>,
+        sel: &Selectors<AB>,
+    ) {
+        let (a, b) = (&local.a, &local.b);
+
+        // An addition or subtraction adds or subtracts the magnitudes of `x` and `y`, where `x`
+        // has the larger scale, or the larger significand if the scales are equal.
+        builder.assert_eq(
+            local.b_sign_eff,
+            sel.is_add.clone() * b.sign + sel.is_sub.clone() * (AB::Expr::ONE - b.sign),
+        );
+        builder.assert_eq(
+            local.is_eff_sub,
+            sel.is_addsub.clone()
+                * (a.sign + local.b_sign_eff - a.sign * local.b_sign_eff * AB::F::TWO),
+        );
+        builder.assert_bool(local.swap);
+        builder.assert_eq(local.x_sign, a.sign + local.swap * (local.b_sign_eff - a.sign));
+        builder.assert_eq(local.both_neg, a.sign * local.b_sign_eff);
+        for i in 0..7 {
+            let (ma, mb) = (a.significand[i], b.significand[i]);
+            builder.assert_eq(local.x_significand[i], ma + local.swap * (mb - ma));
+            builder.assert_eq(local.y_significand[i], mb + local.swap * (ma - mb));
+        }
+
+        // The difference of the scales, clamped to 56. Beyond that, `y` only matters as a sticky
+        // bit below the rounding position.
+        let mut diff = AB::Expr::ZERO;
+        let mut diff_low_pow = AB::Expr::ZERO;
+        let mut high_sum = AB::Expr::ZERO;
+        let mut low_sum = AB::Expr::ZERO;
+        for (j, &high) in local.scale_diff_high.iter().enumerate() {
+            builder.assert_bool(high);
+            high_sum = high_sum.clone() + high;
+            diff = diff.clone() + high * AB::F::from_canonical_usize(8 * j);
+        }
+        for (i, &low) in local.scale_diff_low.iter().enumerate() {
+            builder.assert_bool(low);
+            low_sum = low_sum.clone() + low;
+            diff = diff.clone() + low * AB::F::from_canonical_usize(i);
+            diff_low_pow = diff_low_pow.clone() + low * AB::F::from_canonical_u32(1 << i);
+        }
+        builder.assert_eq(high_sum, sel.is_addsub.clone());
+        builder.assert_eq(low_sum, sel.is_addsub.clone());
+        builder.when(local.scale_diff_high[7]).assert_one(local.scale_diff_low[0]);
+        builder.assert_bool(local.scale_diff_clamped);
+        builder.when(local.scale_diff_clamped).assert_one(local.scale_diff_high[7]);
+        builder.when_not(local.scale_diff_clamped).assert_zero(local.scale_diff_excess);
+        builder.slice_range_check_u16(&[local.scale_diff_excess], sel.is_addsub.clone());
+        builder.when(sel.is_addsub.clone()).assert_eq(
+            (AB::Expr::ONE - local.swap * AB::F::TWO) * (a.scale - b.scale),
+            diff.clone() + local.scale_diff_clamped * (local.scale_diff_excess + AB::Expr::ONE),
+        );
+        for (j, &high) in local.scale_diff_high.iter().enumerate() {
+            builder.assert_eq(local.scale_diff_pow[j], high * diff_low_pow.clone());
+        }
+        let y_zero = b.is_zero.result + local.swap * (a.is_zero.result - b.is_zero.result);
+        for i in 0..7 {
+            let sticky = if i == 0 {
+                local.scale_diff_clamped * (AB::Expr::ONE - y_zero.clone())
+            } else {
+                AB::Expr::ZERO
+            };
+            builder.assert_eq(
+                local.y_significand_eff[i],
+                (AB::Expr::ONE - local.scale_diff_clamped) * local.y_significand[i] + sticky,
+            );
+        }
+
+        // A square root takes an even scale, doubling the significand if needed.
+        builder.assert_bool(local.sqrt_parity);
+        builder
+            .when(sel.is_sqrt.clone())
+            .assert_eq(a.scale, local.sqrt_parity + local.sqrt_half_scale * AB::F::TWO);
+        builder.send_byte(
+            ByteOpcode::U16Range.as_field::<AB::F>(),
+            local.sqrt_half_scale + AB::F::from_canonical_u32(1024),
+            AB::Expr::ZERO,
+            AB::Expr::ZERO,
+            sel.is_sqrt.clone(),
+        );
+
+        // A conversion to a word of a value of at least 2^32, including infinities and NaNs, is
+        // invalid, and its exact result is replaced by zero.
+        let bias = sel.in_s.clone() * AB::F::from_canonical_u32(127)
+            + sel.in_d.clone() * AB::F::from_canonical_u32(1023);
+        builder.assert_bool(local.is_big);
+        builder.when_not(sel.out_w.clone()).assert_zero(local.is_big);
+        builder
+            .when(sel.out_w.clone())
+            .when(local.is_big)
+            .assert_eq(a.exponent, bias.clone() + AB::F::from_canonical_u32(32) + local.big_excess);
+        builder
+            .when(sel.out_w.clone())
+            .when_not(local.is_big)
+            .assert_eq(a.exponent + local.big_excess, bias + AB::F::from_canonical_u32(31));
+        builder.slice_range_check_u16(&[local.big_excess], sel.out_w.clone());
+
+        // The numerator.
+        let ma = a.significand.map(Into::into);
+        let mb = b.significand.map(Into::into);
+        let mx = local.x_significand.map(Into::into);
+        let pow = local.scale_diff_pow.map(Into::into);
+        let mut expected: Vec<AB::Expr> = vec![AB::Expr::ZERO; local.num.len()];
+        for (k, c) in limb_product(&ma, &mb, AB::Expr::ZERO).into_iter().enumerate() {
+            expected[k] = expected[k].clone() + sel.is_mul.clone() * c;
+        }
+        for (k, c) in limb_product(&mx, &pow, AB::Expr::ZERO).into_iter().enumerate() {
+            expected[k] = expected[k].clone() + sel.is_addsub.clone() * c;
+        }
+        let a_factor = sel.is_div.clone()
+            + sel.is_sqrt.clone()
+            + sel.is_sqrt.clone() * local.sqrt_parity
+            + sel.is_cvt.clone()
+            - local.is_big;
+        let y_factor = sel.is_addsub.clone() - local.is_eff_sub * AB::F::TWO;
+        for i in 0..7 {
+            expected[i] = expected[i].clone()
+                + a_factor.clone() * a.significand[i]
+                + y_factor.clone() * local.y_significand_eff[i];
+        }
+        let p = expected.into_iter().zip(local.num).map(|(e, n)| e - n).collect::<Vec<_>>();
+        eval_carry_identity(builder, &p, &local.num_carries, sel.is_real.clone());
+        builder.slice_range_check_u8(&local.num, sel.is_real.clone());
+        let num_sum = local.num.iter().fold(AB::Expr::ZERO, |acc, &limb| acc + limb);
+        IsZeroOperation::<AB::F>::eval(builder, num_sum, local.num_zero, sel.is_real.clone());
+
+        // The scale.
+        builder.assert_eq(
+            local.scale,
+            sel.is_mul.clone() * (a.scale + b.scale)
+                + sel.is_div.clone() * (a.scale - b.scale)
+                + sel.is_sqrt.clone() * a.scale
+                - sel.is_sqrt.clone() * local.sqrt_parity
+                + sel.is_addsub.clone() * (a.scale + local.swap * (b.scale - a.scale) - diff)
+                + sel.is_cvt.clone() * a.scale,
+        );
+
+        // The denominator, which is the quotient itself for a square root, and one for the
+        // operations without a division.
+        for (i, &den) in local.den.iter().enumerate() {
+            let mut expected = sel.is_sqrt.clone() * local.quotient[i];
+            if i < 7 {
+                expected = expected + sel.is_div.clone() * mb[i].clone();
+            }
+            if i == 0 {
+                expected = expected + sel.is_div.clone() * b.is_zero.result + sel.is_real.clone()
+                    - sel.is_div.clone()
+                    - sel.is_sqrt.clone();
+            }
+            builder.assert_eq(den, expected);
+        }
+    }
+
+    /// Constrains the shifted quotient of the exact result and its remainder.
+    fn eval_quotient<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &FpuArithColumns<AB::Var>,
+        sel: &Selectors<AB>,
+    ) {
+        let is_real = sel.is_real.clone();
+
+        // The shift is determined by the exponent of the result, unless the exact result is zero.
+        let mut shift_abs = AB::Expr::ZERO;
+        let mut low_pow = AB::Expr::ZERO;
+        let mut high_sum = AB::Expr::ZERO;
+        let mut low_sum = AB::Expr::ZERO;
+        for (j, &high) in local.shift_high.iter().enumerate() {
+            builder.assert_bool(high);
+            high_sum = high_sum.clone() + high;
+            shift_abs = shift_abs.clone() + high * AB::F::from_canonical_usize(8 * j);
+        }
+        for (i, &low) in local.shift_low.iter().enumerate() {
+            builder.assert_bool(low);
+            low_sum = low_sum.clone() + low;
+            shift_abs = shift_abs.clone() + low * AB::F::from_canonical_usize(i);
+            low_pow = low_pow.clone() + low * AB::F::from_canonical_u32(1 << i);
+        }
+        builder.assert_eq(high_sum, is_real.clone());
+        builder.assert_eq(low_sum, is_real.clone());
+        builder.assert_bool(local.shift_neg);
+
+        let result_scale = local.result_exponent
+            - sel.out_s.clone() * AB::F::from_canonical_u32(150)
+            - sel.out_d.clone() * AB::F::from_canonical_u32(1075)
+            - sel.out_w.clone();
+        let true_shift = local.scale + is_real.clone() - result_scale.clone() + sel.is_sqrt.clone()
+            - sel.is_sqrt.clone() * result_scale;
+        let shift = (is_real.clone() - local.shift_neg * AB::F::TWO) * shift_abs
+            - local.shift_clamped * local.shift_excess;
+        builder.when(is_real.clone() - local.num_zero.result).assert_eq(true_shift, shift);
+
+        // A right shift by more than 120 bits is clamped to 120 bits.
+        builder.assert_bool(local.shift_clamped);
+        builder.when(local.shift_clamped).assert_one(local.shift_neg);
+        builder.when(local.shift_clamped).assert_one(local.shift_high[15]);
+        builder.when(local.shift_clamped).assert_one(local.shift_low[0]);
+        builder.when_not(local.shift_clamped).assert_zero(local.shift_excess);
+        builder.slice_range_check_u16(&[local.shift_excess], is_real.clone());
+
+        for (j, &high) in local.shift_high.iter().enumerate() {
+            let pow = high * low_pow.clone();
+            let (left_one, right_one) = if j == 0 {
+                (local.shift_neg.into(), is_real.clone() - local.shift_neg)
+            } else {
+                (AB::Expr::ZERO, AB::Expr::ZERO)
+            };
+            builder.assert_eq(
+                local.shift_left[j],
+                (AB::Expr::ONE - local.shift_neg) * pow.clone() + left_one,
+            );
+            builder.assert_eq(local.shift_right[j], local.shift_neg * pow + right_one);
+        }
+
+        // `divisor = den * shift_right`.
+        let den = local.den.map(Into::into);
+        let shift_right = local.shift_right.map(Into::<AB::Expr>::into);
+        let product = limb_product(&den, &shift_right, AB::Expr::ZERO);
+        let p = product.into_iter().zip(local.divisor).map(|(c, d)| c - d).collect::<Vec<_>>();
+        eval_carry_identity(builder, &p, &local.divisor_carries, is_real.clone());
+        builder.slice_range_check_u8(&local.divisor, is_real.clone());
+
+        // `num * shift_left = quotient * divisor + remainder`.
+        let num = local.num.map(Into::into);
+        let shift_left = local.shift_left.map(Into::into);
+        let quotient = local.quotient.map(Into::into);
+        let divisor = local.divisor.map(Into::<AB::Expr>::into);
+        let mut p = limb_product(&quotient, &divisor, AB::Expr::ZERO)
+            .into_iter()
+            .map(|c| -c)
+            .collect::<Vec<_>>();
+        for (k, c) in limb_product(&num, &shift_left, AB::Expr::ZERO).into_iter().enumerate() {
+            p[k] = p[k].clone() + c;
+        }
+        for (k, &r) in local.remainder.iter().enumerate() {
+            p[k] = p[k].clone() - r;
+        }
+        eval_carry_identity(builder, &p, &local.quotient_carries, is_real.clone());
+        builder.slice_range_check_u8(&local.quotient, is_real.clone());
+        builder.slice_range_check_u8(&local.remainder, is_real.clone());
+
+        // `remainder < divisor`, or `remainder < 2 * divisor + shift_right` for a square root.
+        let mut p = local
+            .remainder
+            .iter()
+            .zip(local.remainder_gap)
+            .map(|(&r, g)| r + g)
+            .collect::<Vec<AB::Expr>>();
+        p[0] = p[0].clone() + is_real.clone();
+        for (k, d) in divisor.iter().enumerate() {
+            p[k] = p[k].clone() - d.clone() - sel.is_sqrt.clone() * d.clone();
+        }
+        for (k, s) in shift_right.iter().enumerate() {
+            p[k] = p[k].clone() - sel.is_sqrt.clone() * s.clone();
+        }
+        eval_carry_identity(builder, &p, &local.remainder_carries, is_real.clone());
+        builder.slice_range_check_u8(&local.remainder_gap, is_real.clone());
+
+        let remainder_sum = local.remainder.iter().fold(AB::Expr::ZERO, |acc, &limb| acc + limb);
+        IsZeroOperation::<AB::F>::eval(builder, remainder_sum, local.remainder_zero, is_real);
+    }
+
+    /// Constrains the normalization and rounding of the quotient.
+    fn eval_rounding<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &FpuArithColumns<AB::Var>,
+        sel: &Selectors<AB>,
+    ) {
+        let is_real = sel.is_real.clone();
+        let q = local.quotient;
+
+        // A quotient that is not normalized has the smallest exponent.
+        builder.assert_bool(local.is_normal);
+        builder
+            .when(is_real.clone() - local.is_normal)
+            .assert_eq(local.result_exponent, is_real.clone());
+        builder.send_byte(
+            ByteOpcode::U16Range.as_field::<AB::F>(),
+            local.result_exponent - is_real.clone(),
+            AB::Expr::ZERO,
+            AB::Expr::ZERO,
+            is_real.clone(),
+        );
+
+        // A normalized quotient has 25 bits for singles and 54 bits for doubles, and a word
+        // quotient has at most 34 bits.
+        builder.when(sel.out_s.clone()).assert_eq(q[3], local.is_normal);
+        for &limb in q[4..].iter() {
+            builder.when(sel.out_s.clone()).assert_zero(limb);
+        }
+        builder.when(sel.out_d.clone()).assert_eq(
+            q[6],
+            local.is_normal * AB::F::from_canonical_u32(32) + local.quotient_low_bits,
+        );
+        builder.when(sel.out_d.clone()).assert_zero(q[7]);
+        builder.send_byte(
+            ByteOpcode::U8Range.as_field::<AB::F>(),
+            AB::Expr::ZERO,
+            local.quotient_low_bits,
+            local.quotient_low_bits * AB::F::from_canonical_u32(8),
+            sel.out_d.clone(),
+        );
+        builder.when(sel.out_w.clone()).assert_zero(local.is_normal);
+        builder.when(sel.out_w.clone()).assert_one(local.result_exponent);
+        builder.send_byte(
+            ByteOpcode::U8Range.as_field::<AB::F>(),
+            AB::Expr::ZERO,
+            q[4] * AB::F::from_canonical_u32(64),
+            AB::Expr::ZERO,
+            sel.out_w.clone(),
+        );
+        for &limb in q[5..].iter() {
+            builder.when(sel.out_w.clone()).assert_zero(limb);
+        }
+
+        // Round to nearest, ties to even, or toward zero for `trunc.w`.
+        builder.assert_bool(local.guard);
+        builder.assert_bool(local.odd);
+        builder.assert_eq(
+            q[0],
+            local.guard
+                + local.odd * AB::F::TWO
+                + local.quotient_low_rest * AB::F::from_canonical_u32(4),
+        );
+        builder.send_byte(
+            ByteOpcode::U8Range.as_field::<AB::F>(),
+            AB::Expr::ZERO,
+            local.quotient_low_rest * AB::F::from_canonical_u32(4),
+            AB::Expr::ZERO,
+            is_real.clone(),
+        );
+        let is_inexact = is_real.clone() - local.remainder_zero.result;
+        builder.assert_eq(
+            local.sticky_or_odd,
+            is_inexact.clone() + local.odd - is_inexact * local.odd,
+        );
+        builder.assert_eq(
+            local.round_up,
+            (is_real.clone() - sel.is_trunc.clone()) * local.guard * local.sticky_or_odd,
+        );
+
+        // `2 * magnitude = quotient - guard + 2 * round_up`, plus the biased exponent above the
+        // fraction for floating-point results.
+        let exponent = local.result_exponent - is_real.clone();
+        let mut p = Vec::with_capacity(local.magnitude.len());
+        for (k, &magnitude) in local.magnitude.iter().enumerate() {
+            let mut c = if k < q.len() { q[k].into() } else { AB::Expr::ZERO };
+            if k == 0 {
+                c = c - local.guard + local.round_up * AB::F::TWO;
+            }
+            if k == 3 {
+                c = c + sel.out_s.clone() * exponent.clone();
+            }
+            if k == 6 {
+                c = c + sel.out_d.clone() * exponent.clone() * AB::F::from_canonical_u32(32);
+            }
+            p.push(c - magnitude * AB::F::TWO);
+        }
+        eval_carry_identity(builder, &p, &local.magnitude_carries, is_real.clone());
+        builder.slice_range_check_u8(&local.magnitude, is_real.clone());
+
+        // The magnitude must be below the encoding of infinity, or at most 2^31 - 1 for a
+        // positive word and 2^31 for a negative one.
+        let magnitude = local.magnitude.map(Into::into);
+        let limit = [
+            sel.out_w.clone() * local.a.sign,
+            AB::Expr::ZERO,
+            sel.out_s.clone() * AB::F::from_canonical_u32(0x80),
+            sel.out_s.clone() * AB::F::from_canonical_u32(0x7f)
+                + sel.out_w.clone() * AB::F::from_canonical_u32(0x80),
+            AB::Expr::ZERO,
+            AB::Expr::ZERO,
+            sel.out_d.clone() * AB::F::from_canonical_u32(0xf0),
+            sel.out_d.clone() * AB::F::from_canonical_u32(0x7f),
+            AB::Expr::ZERO,
+        ];
+        local.in_range.eval(builder, &magnitude, &limit, is_real);
+    }
+
+    /// Constrains the special cases, the sign and the written result.
+    fn eval_result<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &FpuArithColumns<AB::Var>,
+        sel: &Selectors<AB>,
+    ) {
+        let (a, b) = (&local.a, &local.b);
+        let one = || AB::Expr::ONE;
+        let (a_zero, b_zero) = (a.is_zero.result, b.is_zero.result);
+
+        builder.assert_eq(local.any_nan, a.is_nan + b.is_nan - a.is_nan * b.is_nan);
+        builder.assert_eq(local.inf_inf, a.is_inf * b.is_inf);
+        builder.assert_eq(local.zero_zero, a_zero * b_zero);
+        builder.assert_eq(local.inf_zero, a.is_inf * b_zero + a_zero * b.is_inf);
+        builder.assert_eq(
+            local.one_inf,
+            (a.is_inf + b.is_inf - local.inf_inf) * (one() - local.any_nan),
+        );
+        builder.assert_eq(
+            local.div_inf,
+            a.is_inf * (one() - b.is_inf - b.is_nan)
+                + b_zero * (one() - a_zero - a.is_nan - a.is_inf),
+        );
+        builder.assert_eq(local.div_zero, b.is_inf * (one() - a.is_inf - a.is_nan));
+        builder.assert_eq(local.a_neg_nonzero, a.sign * (one() - a_zero - a.is_nan));
+
+        // The default NaN results from a NaN operand and from the invalid operations.
+        builder.assert_eq(
+            local.is_special_nan,
+            sel.out_f.clone() * local.any_nan
+                + local.inf_inf * local.is_eff_sub
+                + sel.is_mul.clone() * local.inf_zero
+                + sel.is_div.clone() * (local.zero_zero + local.inf_inf)
+                + sel.is_sqrt.clone() * local.a_neg_nonzero,
+        );
+        builder.assert_eq(
+            local.is_special_inf,
+            sel.is_addsub.clone() * local.one_inf - local.inf_inf * local.is_eff_sub
+                + sel.is_mul.clone() * (local.one_inf - local.inf_zero)
+                + sel.is_div.clone() * local.div_inf
+                + sel.is_sqrt.clone() * a.is_inf * (one() - a.sign)
+                + sel.is_cvt_f.clone() * a.is_inf,
+        );
+        builder.assert_eq(local.is_special_zero, sel.is_div.clone() * local.div_zero);
+
+        // Otherwise, the rounded result is used, or an infinity if it overflows.
+        let is_rounded =
+            sel.out_f.clone() - local.is_special_nan - local.is_special_inf - local.is_special_zero;
+        builder.assert_eq(local.is_finite, is_rounded.clone() * local.in_range.result);
+        let is_overflow = is_rounded.clone() - local.is_finite;
+
+        // The sign of the result. An exact zero sum is negative only if both operands are.
+        let nz = local.num_zero.result;
+        builder.assert_eq(local.sign_xor, a.sign + b.sign - a.sign * b.sign * AB::F::TWO);
+        builder.assert_eq(
+            local.special_sign,
+            sel.is_addsub.clone() * (a.is_inf * a.sign + (one() - a.is_inf) * local.b_sign_eff)
+                + (sel.is_mul.clone() + sel.is_div.clone()) * local.sign_xor
+                + sel.is_cvt_f.clone() * a.sign,
+        );
+        builder.assert_eq(
+            local.rounded_sign,
+            sel.is_addsub.clone() * ((one() - nz) * local.x_sign + nz * local.both_neg)
+                + (sel.is_mul.clone() + sel.is_div.clone()) * local.sign_xor
+                + (sel.is_sqrt.clone() + sel.is_cvt_f.clone()) * a.sign,
+        );
+        builder.assert_eq(
+            local.result_sign,
+            is_rounded * local.rounded_sign
+                + (local.is_special_inf + local.is_special_zero) * local.special_sign,
+        );
+
+        // A conversion to a word is invalid if the value is too large, and negated if negative.
+        builder.assert_eq(
+            local.is_invalid,
+            local.is_big
+                + sel.out_w.clone() * (one() - local.is_big) * (one() - local.in_range.result),
+        );
+        builder.assert_eq(local.is_negate, (sel.out_w.clone() - local.is_invalid) * a.sign);
+        let is_positive = sel.out_w.clone() - local.is_invalid - local.is_negate;
+        builder.send_alu(
+            Opcode::SUB.as_field::<AB::F>(),
+            local.negated,
+            Word([AB::Expr::ZERO; 4]),
+            Word(array::from_fn(|i| local.magnitude[i])),
+            local.is_negate,
+        );
+
+        let nan_s = u64::from(DEFAULT_NAN_S).to_le_bytes();
+        let nan_d = DEFAULT_NAN_D.to_le_bytes();
+        let inf_s = INF_S.to_le_bytes();
+        let inf_d = INF_D.to_le_bytes();
+        let invalid = INVALID_WORD.to_le_bytes();
+        let is_inf_result = local.is_special_inf + is_overflow;
+        let byte = |x: u8| AB::F::from_canonical_u8(x);
+        for i in 0..8 {
+            let mut expected = local.is_special_nan
+                * (sel.out_s.clone() * byte(nan_s[i]) + sel.out_d.clone() * byte(nan_d[i]))
+                + is_inf_result.clone()
+                    * (sel.out_s.clone() * byte(inf_s[i]) + sel.out_d.clone() * byte(inf_d[i]))
+                + (is_positive.clone() + local.is_finite) * local.magnitude[i];
+            if i < 4 {
+                expected = expected
+                    + local.is_invalid * byte(invalid[i])
+                    + local.is_negate * local.negated[i];
+            }
+            if i == 3 {
+                expected = expected + local.result_sign * sel.out_s.clone() * byte(0x80);
+            }
+            if i == 7 {
+                expected = expected + local.result_sign * sel.out_d.clone() * byte(0x80);
+            }
+            builder.assert_eq(local.fd_access[i / 4].value()[i % 4], expected);
+        }
+    }
+}
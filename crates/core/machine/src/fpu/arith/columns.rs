@@ -0,0 +1,217 @@
+use std::mem::size_of;
+use zkm_derive::AlignedBorrow;
+use zkm_stark::Word;
+
+use crate::{
+    memory::{MemoryReadCols, MemoryWriteCols},
+    operations::{IsZeroOperation, LtBytesOperation},
+};
+
+pub const NUM_FPU_ARITH_COLS: usize = size_of::<FpuArithColumns<u8>>();
+
+/// The columns unpacking an operand of a floating-point arithmetic instruction.
+///
+/// A finite operand is `(-1)^sign * significand * 2^scale`, where the significand includes the
+/// hidden bit. A word operand has a scale of zero and its absolute value as the significand.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FpuOperandCols<T> {
+    /// The most significant byte of the value, and its sign bit.
+    pub top_byte: T,
+    pub sign: T,
+
+    /// The byte holding the least significant bits of the biased exponent, and those bits: the
+    /// top bit of byte 2 for singles, and the top four bits of byte 6 for doubles.
+    pub exponent_byte: T,
+    pub exponent_low: T,
+    /// The biased exponent.
+    pub exponent: T,
+    /// Whether the biased exponent is zero (a zero or subnormal value) or maximal (an infinity or
+    /// a NaN).
+    pub exponent_zero: IsZeroOperation<T>,
+    pub exponent_max: IsZeroOperation<T>,
+
+    /// The absolute value of a word operand.
+    pub abs_value: Word<T>,
+
+    /// The significand, including the hidden bit.
+    pub significand: [T; 7],
+    /// Whether the significand is zero, and whether it is zero apart from the hidden bit.
+    pub is_zero: IsZeroOperation<T>,
+    pub fraction_zero: IsZeroOperation<T>,
+
+    pub is_inf: T,
+    pub is_nan: T,
+
+    /// The exponent of the least significant bit of the significand.
+    pub scale: T,
+}
+
+/// The column layout for the floating-point arithmetic and conversions.
+///
+/// The result is computed in three steps:
+///
+/// 1. The exact result is written as `num * 2^t / den`, or `sqrt(num * 2^t)` for square roots,
+///    where `num` and `den` are integers.
+/// 2. The result is shifted by `2^s` and truncated to `quotient`, whose least significant bit is
+///    the guard bit of the rounding and whose remainder is the sticky bit. The shift is chosen so
+///    that `quotient` has one more bit than the significand of the result, unless the result is
+///    subnormal.
+/// 3. The quotient is rounded and packed, and replaced by a special value if an operand is a NaN
+///    or an infinity, if the operation is invalid, or if the result overflows.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FpuArithColumns<T> {
+    /// The current/next program counter of the instruction.
+    pub pc: T,
+    pub next_pc: T,
+
+    /// The shard number.
+    pub shard: T,
+    /// The clock cycle number.
+    pub clk: T,
+
+    /// The address of `fs`.
+    pub op_b_value: Word<T>,
+    /// The addresses of `ft` and `fd`.
+    pub op_c_value: Word<T>,
+
+    /// The reads of `fs` and `ft` and the write of `fd`. The second register of a pair is only
+    /// accessed for doubles.
+    pub fs_access: [MemoryReadCols<T>; 2],
+    pub ft_access: [MemoryReadCols<T>; 2],
+    pub fd_access: [MemoryWriteCols<T>; 2],
+
+    pub a: FpuOperandCols<T>,
+    pub b: FpuOperandCols<T>,
+
+    /// The sign of `ft`, flipped for subtractions.
+    pub b_sign_eff: T,
+    /// Whether an addition or subtraction subtracts the magnitudes.
+    pub is_eff_sub: T,
+    /// Whether the operands are swapped so that `x`, the first one, has the larger scale.
+    pub swap: T,
+    pub x_sign: T,
+    pub both_neg: T,
+    pub x_significand: [T; 7],
+    pub y_significand: [T; 7],
+    /// The significand of `y`, replaced by a sticky one if the scales differ by more than 56.
+    pub y_significand_eff: [T; 7],
+    /// The difference of the scales as `8 * high + low`, where both are one-hot encoded.
+    pub scale_diff_high: [T; 8],
+    pub scale_diff_low: [T; 8],
+    /// Whether the difference of the scales exceeds 56, and by how much it exceeds 57.
+    pub scale_diff_clamped: T,
+    pub scale_diff_excess: T,
+    /// `2^min(diff, 56)` as bytes.
+    pub scale_diff_pow: [T; 8],
+
+    /// The parity and half of the scale of the radicand of a square root.
+    pub sqrt_parity: T,
+    pub sqrt_half_scale: T,
+
+    /// Whether the operand of a conversion to a word has an exponent of at least 32, and the
+    /// distance of the exponent to 32.
+    pub is_big: T,
+    pub big_excess: T,
+
+    /// The exact result.
+    pub num: [T; 14],
+    pub num_carries: [T; 13],
+    pub num_zero: IsZeroOperation<T>,
+    pub scale: T,
+    pub den: [T; 8],
+
+    /// The shift `s = +-(8 * high + low)`, where both are one-hot encoded. A right shift by more
+    /// than 120 is clamped to 120, as it leaves no bits of the quotient.
+    pub shift_neg: T,
+    pub shift_high: [T; 16],
+    pub shift_low: [T; 8],
+    pub shift_clamped: T,
+    pub shift_excess: T,
+    /// `2^s` for a left shift and one otherwise, and `2^-s` for a right shift and one otherwise.
+    pub shift_left: [T; 16],
+    pub shift_right: [T; 16],
+
+    /// `den * shift_right`.
+    pub divisor: [T; 23],
+    pub divisor_carries: [T; 22],
+
+    /// `num * shift_left = quotient * divisor + remainder`, with `remainder < divisor`, or
+    /// `remainder < 2 * divisor + shift_right` for square roots, whose divisor is the quotient
+    /// shifted right.
+    pub quotient: [T; 8],
+    pub remainder: [T; 24],
+    pub remainder_gap: [T; 24],
+    pub quotient_carries: [T; 29],
+    pub remainder_carries: [T; 23],
+    pub remainder_zero: IsZeroOperation<T>,
+
+    /// The biased exponent of the result, and whether the quotient is normalized.
+    pub result_exponent: T,
+    pub is_normal: T,
+    /// The bits of byte 6 of a double quotient below the hidden bit.
+    pub quotient_low_bits: T,
+
+    /// The rounding bits of the quotient: the guard bit, the least significant bit of the
+    /// significand, and the rest of its least significant byte.
+    pub guard: T,
+    pub odd: T,
+    pub quotient_low_rest: T,
+    pub sticky_or_odd: T,
+    pub round_up: T,
+
+    /// The rounded magnitude, packed with the exponent for floating-point results.
+    pub magnitude: [T; 9],
+    pub magnitude_carries: [T; 8],
+    /// Whether the magnitude is below the encoding of infinity, or fits in a word.
+    pub in_range: LtBytesOperation<T, 9>,
+
+    /// Flags for the special cases.
+    pub any_nan: T,
+    pub inf_inf: T,
+    pub zero_zero: T,
+    pub inf_zero: T,
+    pub one_inf: T,
+    pub div_inf: T,
+    pub div_zero: T,
+    pub a_neg_nonzero: T,
+
+    /// Whether the result is the default NaN, an infinity or a zero because of a special case.
+    pub is_special_nan: T,
+    pub is_special_inf: T,
+    pub is_special_zero: T,
+    /// Whether the rounded result is finite.
+    pub is_finite: T,
+
+    /// The sign of a special and of a rounded result, and the sign of the result.
+    pub sign_xor: T,
+    pub special_sign: T,
+    pub rounded_sign: T,
+    pub result_sign: T,
+
+    /// Whether a conversion to a word is invalid, and whether its result is negated.
+    pub is_invalid: T,
+    pub is_negate: T,
+    pub negated: Word<T>,
+
+    /// Selectors for the instructions.
+    pub is_add_s: T,
+    pub is_sub_s: T,
+    pub is_mul_s: T,
+    pub is_div_s: T,
+    pub is_sqrt_s: T,
+    pub is_add_d: T,
+    pub is_sub_d: T,
+    pub is_mul_d: T,
+    pub is_div_d: T,
+    pub is_sqrt_d: T,
+    pub is_cvt_s_d: T,
+    pub is_cvt_d_s: T,
+    pub is_cvt_s_w: T,
+    pub is_cvt_d_w: T,
+    pub is_cvt_w_s: T,
+    pub is_cvt_w_d: T,
+    pub is_trunc_w_s: T,
+    pub is_trunc_w_d: T,
+}
@@ -0,0 +1,17 @@
+mod air;
+mod columns;
+mod trace;
+
+pub use columns::*;
+use p3_air::BaseAir;
+
+/// A chip that implements the floating-point arithmetic and the conversions between the single
+/// precision, double precision and word formats.
+#[derive(Default)]
+pub struct FpuArithChip;
+
+impl<F> BaseAir<F> for FpuArithChip {
+    fn width(&self) -> usize {
+        NUM_FPU_ARITH_COLS
+    }
+}
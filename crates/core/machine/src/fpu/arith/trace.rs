@@ -0,0 +1,715 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use num::{BigUint, One, Zero};
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, FpuEvent},
+    fpu::{
+        is_binary, result_format, source_format, FpuFormat, DEFAULT_NAN_D, DEFAULT_NAN_S,
+        INVALID_WORD,
+    },
+    ByteOpcode, ExecutionRecord, Opcode, Program,
+};
+use zkm_stark::air::MachineAir;
+
+use crate::{
+    fpu::utils::{field_from_i64, limb_product, populate_carry_identity},
+    utils::{next_power_of_two, zeroed_f_vec},
+};
+
+use super::{FpuArithChip, FpuArithColumns, FpuOperandCols, NUM_FPU_ARITH_COLS};
+
+/// The largest right shift of the exact result, beyond which the quotient is always zero.
+const MAX_RIGHT_SHIFT: i64 = 120;
+
+/// An unpacked operand.
+#[derive(Debug, Clone, Copy, Default)]
+struct Operand {
+    sign: bool,
+    exponent: i64,
+    significand: u64,
+    scale: i64,
+    is_zero: bool,
+    is_inf: bool,
+    is_nan: bool,
+}
+
+impl<F: PrimeField32> MachineAir<F> for FpuArithChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "FpuArith".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let chunk_size = std::cmp::max((input.fpu_arith_events.len()) / num_cpus::get(), 1);
+        let nb_rows = input.fpu_arith_events.len();
+        let size_log2 = input.fixed_log2_rows::<F, _>(self);
+        let padded_nb_rows = next_power_of_two(nb_rows, size_log2);
+        let mut values = zeroed_f_vec(padded_nb_rows * NUM_FPU_ARITH_COLS);
+
+        let blu_events = values
+            .chunks_mut(chunk_size * NUM_FPU_ARITH_COLS)
+            .enumerate()
+            .par_bridge()
+            .map(|(i, rows)| {
+                let mut blu: HashMap<ByteLookupEvent, usize> = HashMap::new();
+                rows.chunks_mut(NUM_FPU_ARITH_COLS).enumerate().for_each(|(j, row)| {
+                    let idx = i * chunk_size + j;
+                    let cols: &mut FpuArithColumns<F> = row.borrow_mut();
+
+                    if idx < input.fpu_arith_events.len() {
+                        let event = &input.fpu_arith_events[idx];
+                        self.event_to_row(event, cols, &mut blu);
+                    }
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_byte_lookup_events_from_maps(blu_events.iter().collect_vec());
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(values, NUM_FPU_ARITH_COLS)
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.fpu_arith_events.is_empty()
+        }
+    }
+
+    fn local_only(&self) -> bool {
+        true
+    }
+}
+
+impl FpuArithChip {
+    fn event_to_row<F: PrimeField32>(
+        &self,
+        event: &FpuEvent,
+        cols: &mut FpuArithColumns<F>,
+        blu: &mut impl ByteRecord,
+    ) {
+        cols.shard = F::from_canonical_u32(event.shard);
+        cols.clk = F::from_canonical_u32(event.clk);
+        cols.pc = F::from_canonical_u32(event.pc);
+        cols.next_pc = F::from_canonical_u32(event.next_pc);
+        cols.op_b_value = event.b.into();
+        cols.op_c_value = event.c.into();
+
+        let opcode = event.opcode;
+        match opcode {
+            Opcode::FADD_S => cols.is_add_s = F::ONE,
+            Opcode::FSUB_S => cols.is_sub_s = F::ONE,
+            Opcode::FMUL_S => cols.is_mul_s = F::ONE,
+            Opcode::FDIV_S => cols.is_div_s = F::ONE,
+            Opcode::FSQRT_S => cols.is_sqrt_s = F::ONE,
+            Opcode::FADD_D => cols.is_add_d = F::ONE,
+            Opcode::FSUB_D => cols.is_sub_d = F::ONE,
+            Opcode::FMUL_D => cols.is_mul_d = F::ONE,
+            Opcode::FDIV_D => cols.is_div_d = F::ONE,
+            Opcode::FSQRT_D => cols.is_sqrt_d = F::ONE,
+            Opcode::CVT_S_D => cols.is_cvt_s_d = F::ONE,
+            Opcode::CVT_D_S => cols.is_cvt_d_s = F::ONE,
+            Opcode::CVT_S_W => cols.is_cvt_s_w = F::ONE,
+            Opcode::CVT_D_W => cols.is_cvt_d_w = F::ONE,
+            Opcode::CVT_W_S => cols.is_cvt_w_s = F::ONE,
+            Opcode::CVT_W_D => cols.is_cvt_w_d = F::ONE,
+            Opcode::TRUNC_W_S => cols.is_trunc_w_s = F::ONE,
+            Opcode::TRUNC_W_D => cols.is_trunc_w_d = F::ONE,
+            _ => unreachable!("{} is not a floating-point arithmetic instruction", opcode),
+        }
+
+        for (access, record) in cols.fs_access.iter_mut().zip(event.fs_access) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+        for (access, record) in cols.ft_access.iter_mut().zip(event.ft_access) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+        for (access, record) in cols.fd_access.iter_mut().zip(event.fd_access) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+
+        let in_format = source_format(opcode);
+        let out_format = result_format(opcode);
+        let a = Self::populate_operand(
+            &mut cols.a,
+            FpuEvent::pair_value(&event.fs_access),
+            Some(in_format),
+            blu,
+        );
+        let b = Self::populate_operand(
+            &mut cols.b,
+            FpuEvent::pair_value(&event.ft_access),
+            is_binary(opcode).then_some(in_format),
+            blu,
+        );
+
+        let is_add = matches!(opcode, Opcode::FADD_S | Opcode::FADD_D);
+        let is_sub = matches!(opcode, Opcode::FSUB_S | Opcode::FSUB_D);
+        let is_mul = matches!(opcode, Opcode::FMUL_S | Opcode::FMUL_D);
+        let is_div = matches!(opcode, Opcode::FDIV_S | Opcode::FDIV_D);
+        let is_sqrt = matches!(opcode, Opcode::FSQRT_S | Opcode::FSQRT_D);
+        let is_trunc = matches!(opcode, Opcode::TRUNC_W_S | Opcode::TRUNC_W_D);
+        let is_addsub = is_add || is_sub;
+        let is_cvt = !(is_addsub || is_mul || is_div || is_sqrt);
+        let out_s = out_format == FpuFormat::Single;
+        let out_d = out_format == FpuFormat::Double;
+        let out_w = out_format == FpuFormat::Word;
+        let is_cvt_f = is_cvt && !out_w;
+
+        // Addition and subtraction.
+        let b_sign_eff = (is_add && b.sign) || (is_sub && !b.sign);
+        let is_eff_sub = is_addsub && a.sign != b_sign_eff;
+        let swap = is_addsub && (b.scale, b.significand) > (a.scale, a.significand);
+        let (x, y) = if swap { (b, a) } else { (a, b) };
+        let x_sign = if swap { b_sign_eff } else { a.sign };
+        let both_neg = a.sign && b_sign_eff;
+        cols.b_sign_eff = F::from_bool(b_sign_eff);
+        cols.is_eff_sub = F::from_bool(is_eff_sub);
+        cols.swap = F::from_bool(swap);
+        cols.x_sign = F::from_bool(x_sign);
+        cols.both_neg = F::from_bool(both_neg);
+
+        let diff = if is_addsub { x.scale - y.scale } else { 0 };
+        let diff_clamped = diff.min(56);
+        let is_diff_clamped = diff > 56;
+        let diff_excess = if is_diff_clamped { diff - 57 } else { 0 };
+        let y_significand_eff = if is_diff_clamped { u64::from(!y.is_zero) } else { y.significand };
+        let diff_pow = if is_addsub { 1u64 << diff_clamped } else { 0 };
+        if is_addsub {
+            cols.scale_diff_high[(diff_clamped / 8) as usize] = F::ONE;
+            cols.scale_diff_low[(diff_clamped % 8) as usize] = F::ONE;
+            blu.add_u16_range_check(diff_excess as u16);
+        }
+        cols.scale_diff_clamped = F::from_bool(is_diff_clamped);
+        cols.scale_diff_excess = F::from_canonical_u32(diff_excess as u32);
+        cols.scale_diff_pow = diff_pow.to_le_bytes().map(F::from_canonical_u8);
+        let mx = significand_bytes(x.significand);
+        let my_eff = significand_bytes(y_significand_eff);
+        cols.x_significand = mx.map(F::from_canonical_u8);
+        cols.y_significand = significand_bytes(y.significand).map(F::from_canonical_u8);
+        cols.y_significand_eff = my_eff.map(F::from_canonical_u8);
+
+        // Square roots.
+        let parity = is_sqrt && a.scale.rem_euclid(2) == 1;
+        let half_scale = if is_sqrt { (a.scale - i64::from(parity)) / 2 } else { 0 };
+        cols.sqrt_parity = F::from_bool(parity);
+        cols.sqrt_half_scale = field_from_i64(half_scale);
+        if is_sqrt {
+            blu.add_u16_range_check((half_scale + 1024) as u16);
+        }
+
+        // Conversions to a word.
+        let bias = if in_format == FpuFormat::Single { 127 } else { 1023 };
+        let is_big = out_w && a.exponent >= bias + 32;
+        let big_excess = match (out_w, is_big) {
+            (false, _) => 0,
+            (true, true) => a.exponent - bias - 32,
+            (true, false) => bias + 31 - a.exponent,
+        };
+        cols.is_big = F::from_bool(is_big);
+        cols.big_excess = F::from_canonical_u32(big_excess as u32);
+        if out_w {
+            blu.add_u16_range_check(big_excess as u16);
+        }
+
+        // The exact result `num * 2^scale / den`.
+        let num: u128 = if is_mul {
+            u128::from(a.significand) * u128::from(b.significand)
+        } else if is_addsub {
+            let shifted = u128::from(x.significand) << diff_clamped;
+            if is_eff_sub {
+                shifted - u128::from(y_significand_eff)
+            } else {
+                shifted + u128::from(y_significand_eff)
+            }
+        } else if is_sqrt {
+            u128::from(a.significand) << u32::from(parity)
+        } else if is_big {
+            0
+        } else {
+            u128::from(a.significand)
+        };
+        let num_bytes: [u8; 14] = num.to_le_bytes()[..14].try_into().unwrap();
+        cols.num = num_bytes.map(F::from_canonical_u8);
+        blu.add_u8_range_checks(&num_bytes);
+        let ma = to_limbs(&significand_bytes(a.significand));
+        let mb = to_limbs(&significand_bytes(b.significand));
+        let mut p = vec![0i64; 14];
+        for (k, c) in limb_product(&ma, &mb, 0).into_iter().enumerate() {
+            p[k] += i64::from(is_mul) * c;
+        }
+        for (k, c) in limb_product(&to_limbs(&mx), &to_limbs(&diff_pow.to_le_bytes()), 0)
+            .into_iter()
+            .enumerate()
+        {
+            p[k] += i64::from(is_addsub) * c;
+        }
+        let a_factor =
+            i64::from(is_div) + i64::from(is_sqrt) * (1 + i64::from(parity)) + i64::from(is_cvt)
+                - i64::from(is_big);
+        let y_factor = i64::from(is_addsub) - 2 * i64::from(is_eff_sub);
+        for i in 0..7 {
+            p[i] += a_factor * ma[i] + y_factor * i64::from(my_eff[i]);
+        }
+        for (p_k, &n) in p.iter_mut().zip(num_bytes.iter()) {
+            *p_k -= i64::from(n);
+        }
+        populate_carry_identity(&p, &mut cols.num_carries, blu);
+        cols.num_zero.populate(num_bytes.iter().map(|&x| u32::from(x)).sum());
+
+        let scale = if is_mul {
+            a.scale + b.scale
+        } else if is_div {
+            a.scale - b.scale
+        } else if is_sqrt {
+            a.scale - i64::from(parity)
+        } else if is_addsub {
+            x.scale - diff_clamped
+        } else {
+            a.scale
+        };
+        cols.scale = field_from_i64(scale);
+
+        // A square root is divided by its own quotient.
+        let den = if is_div {
+            Some(if b.is_zero { 1 } else { b.significand })
+        } else if is_sqrt {
+            None
+        } else {
+            Some(1)
+        };
+
+        // Find the exponent of the result, for which the quotient is normalized or the exponent
+        // is the smallest one.
+        let (result_bias, precision) = match out_format {
+            FpuFormat::Single => (150, 24),
+            FpuFormat::Double => (1075, 53),
+            FpuFormat::Word => (1, 0),
+        };
+        let true_shift = |exponent: i64| {
+            let result_scale = exponent - result_bias;
+            if is_sqrt {
+                scale + 2 - 2 * result_scale
+            } else {
+                scale + 1 - result_scale
+            }
+        };
+        let result_exponent = if num == 0 || out_w {
+            1
+        } else {
+            let num_bits = i64::from(128 - num.leading_zeros());
+            let den_bits = den.map_or(0, |den| i64::from(64 - den.leading_zeros()));
+            let log2 = if is_sqrt { (scale + num_bits) / 2 } else { scale + num_bits - den_bits };
+            let lower = BigUint::one() << precision as usize;
+            let upper = BigUint::one() << (precision + 1) as usize;
+            let mut exponent = (log2 - precision + result_bias).max(1);
+            loop {
+                let (quotient, _, _) = divide(num, den, true_shift(exponent));
+                if quotient >= upper {
+                    exponent += 1;
+                } else if quotient < lower && exponent > 1 {
+                    exponent -= 1;
+                } else {
+                    break exponent;
+                }
+            }
+        };
+        let shift = if num == 0 { 0 } else { true_shift(result_exponent) };
+        cols.result_exponent = F::from_canonical_u32(result_exponent as u32);
+        blu.add_u16_range_check((result_exponent - 1) as u16);
+
+        // The shift, clamped to the right.
+        let shift_neg = shift < 0;
+        let shift_clamped = shift < -MAX_RIGHT_SHIFT;
+        let shift_abs = if shift_clamped { MAX_RIGHT_SHIFT } else { shift.abs() };
+        let shift_excess = if shift_clamped { -shift - MAX_RIGHT_SHIFT } else { 0 };
+        debug_assert!(shift_abs < 128, "shift out of range: {shift}");
+        cols.shift_neg = F::from_bool(shift_neg);
+        cols.shift_high[(shift_abs / 8) as usize] = F::ONE;
+        cols.shift_low[(shift_abs % 8) as usize] = F::ONE;
+        cols.shift_clamped = F::from_bool(shift_clamped);
+        cols.shift_excess = F::from_canonical_u32(shift_excess as u32);
+        blu.add_u16_range_check(shift_excess as u16);
+        let (shift_left, shift_right) =
+            if shift_neg { (1u128, 1u128 << shift_abs) } else { (1u128 << shift_abs, 1u128) };
+        let shift_left = shift_left.to_le_bytes();
+        let shift_right = shift_right.to_le_bytes();
+        cols.shift_left = shift_left.map(F::from_canonical_u8);
+        cols.shift_right = shift_right.map(F::from_canonical_u8);
+
+        // The quotient and the remainder.
+        let (quotient, divisor, remainder) = divide(num, den, shift);
+        let is_sqrt_limb = i64::from(is_sqrt);
+        let gap = &divisor * (1 + u32::from(is_sqrt))
+            + if is_sqrt { BigUint::from_bytes_le(&shift_right) } else { BigUint::zero() }
+            - &remainder
+            - 1u32;
+        let quotient = to_bytes::<8>(&quotient);
+        let divisor = to_bytes::<23>(&divisor);
+        let remainder = to_bytes::<24>(&remainder);
+        let gap = to_bytes::<24>(&gap);
+        let den = den.unwrap_or_else(|| u64::from_le_bytes(quotient)).to_le_bytes();
+        cols.den = den.map(F::from_canonical_u8);
+        cols.divisor = divisor.map(F::from_canonical_u8);
+        cols.quotient = quotient.map(F::from_canonical_u8);
+        cols.remainder = remainder.map(F::from_canonical_u8);
+        cols.remainder_gap = gap.map(F::from_canonical_u8);
+        blu.add_u8_range_checks(&divisor);
+        blu.add_u8_range_checks(&quotient);
+        blu.add_u8_range_checks(&remainder);
+        blu.add_u8_range_checks(&gap);
+
+        let mut p = limb_product(&to_limbs(&den), &to_limbs(&shift_right), 0);
+        for (p_k, &d) in p.iter_mut().zip(divisor.iter()) {
+            *p_k -= i64::from(d);
+        }
+        populate_carry_identity(&p, &mut cols.divisor_carries, blu);
+
+        let mut p = limb_product(&to_limbs(&quotient), &to_limbs(&divisor), 0)
+            .into_iter()
+            .map(|c| -c)
+            .collect::<Vec<_>>();
+        for (k, c) in
+            limb_product(&to_limbs(&num_bytes), &to_limbs(&shift_left), 0).into_iter().enumerate()
+        {
+            p[k] += c;
+        }
+        for (k, &r) in remainder.iter().enumerate() {
+            p[k] -= i64::from(r);
+        }
+        populate_carry_identity(&p, &mut cols.quotient_carries, blu);
+
+        let mut p = remainder
+            .iter()
+            .zip(gap.iter())
+            .map(|(&r, &g)| i64::from(r) + i64::from(g))
+            .collect::<Vec<_>>();
+        p[0] += 1;
+        for (k, &d) in divisor.iter().enumerate() {
+            p[k] -= (1 + is_sqrt_limb) * i64::from(d);
+        }
+        for (k, &s) in shift_right.iter().enumerate() {
+            p[k] -= is_sqrt_limb * i64::from(s);
+        }
+        populate_carry_identity(&p, &mut cols.remainder_carries, blu);
+        let is_exact =
+            cols.remainder_zero.populate(remainder.iter().map(|&x| u32::from(x)).sum()) == 1;
+
+        // Normalization.
+        let q = u64::from_le_bytes(quotient);
+        let is_normal = !out_w && (q >> precision) & 1 == 1;
+        cols.is_normal = F::from_bool(is_normal);
+        if out_d {
+            let low_bits = quotient[6] & 0x1f;
+            cols.quotient_low_bits = F::from_canonical_u8(low_bits);
+            blu.add_u8_range_check(low_bits, low_bits * 8);
+        }
+        if out_w {
+            blu.add_u8_range_check(quotient[4] * 64, 0);
+        }
+
+        // Rounding.
+        let guard = q & 1 == 1;
+        let odd = (q >> 1) & 1 == 1;
+        let sticky_or_odd = !is_exact || odd;
+        let round_up = !is_trunc && guard && sticky_or_odd;
+        cols.guard = F::from_bool(guard);
+        cols.odd = F::from_bool(odd);
+        cols.quotient_low_rest = F::from_canonical_u8(quotient[0] >> 2);
+        blu.add_u8_range_check(quotient[0] & 0xfc, 0);
+        cols.sticky_or_odd = F::from_bool(sticky_or_odd);
+        cols.round_up = F::from_bool(round_up);
+
+        let exponent_bits = if out_s {
+            u128::from(result_exponent as u64 - 1) << 23
+        } else if out_d {
+            u128::from(result_exponent as u64 - 1) << 52
+        } else {
+            0
+        };
+        let magnitude = exponent_bits + u128::from(q >> 1) + u128::from(round_up);
+        let magnitude: [u8; 9] = magnitude.to_le_bytes()[..9].try_into().unwrap();
+        cols.magnitude = magnitude.map(F::from_canonical_u8);
+        blu.add_u8_range_checks(&magnitude);
+        let exponent = result_exponent - 1;
+        let p = (0..9)
+            .map(|k| {
+                let mut c = if k < 8 { i64::from(quotient[k]) } else { 0 };
+                if k == 0 {
+                    c += 2 * i64::from(round_up) - i64::from(guard);
+                }
+                if k == 3 {
+                    c += i64::from(out_s) * exponent;
+                }
+                if k == 6 {
+                    c += 32 * i64::from(out_d) * exponent;
+                }
+                c - 2 * i64::from(magnitude[k])
+            })
+            .collect::<Vec<_>>();
+        populate_carry_identity(&p, &mut cols.magnitude_carries, blu);
+
+        let limit = [
+            u8::from(out_w && a.sign),
+            0,
+            0x80 * u8::from(out_s),
+            0x7f * u8::from(out_s) + 0x80 * u8::from(out_w),
+            0,
+            0,
+            0xf0 * u8::from(out_d),
+            0x7f * u8::from(out_d),
+            0,
+        ];
+        let in_range = i64::from(cols.in_range.populate(blu, &magnitude, &limit));
+
+        // The special cases.
+        let flag = i64::from;
+        let (a_nan, b_nan, a_inf, b_inf) =
+            (flag(a.is_nan), flag(b.is_nan), flag(a.is_inf), flag(b.is_inf));
+        let (a_zero, b_zero, a_sign) = (flag(a.is_zero), flag(b.is_zero), flag(a.sign));
+        let (is_addsub, is_mul, is_div, is_sqrt) =
+            (flag(is_addsub), flag(is_mul), flag(is_div), flag(is_sqrt));
+        let (is_cvt_f, out_f, out_w) = (flag(is_cvt_f), flag(!out_w), flag(out_w));
+        let is_eff_sub = flag(is_eff_sub);
+
+        let any_nan = a_nan + b_nan - a_nan * b_nan;
+        let inf_inf = a_inf * b_inf;
+        let zero_zero = a_zero * b_zero;
+        let inf_zero = a_inf * b_zero + a_zero * b_inf;
+        let one_inf = (a_inf + b_inf - inf_inf) * (1 - any_nan);
+        let div_inf = a_inf * (1 - b_inf - b_nan) + b_zero * (1 - a_zero - a_nan - a_inf);
+        let div_zero = b_inf * (1 - a_inf - a_nan);
+        let a_neg_nonzero = a_sign * (1 - a_zero - a_nan);
+        let special_nan = out_f * any_nan
+            + inf_inf * is_eff_sub
+            + is_mul * inf_zero
+            + is_div * (zero_zero + inf_inf)
+            + is_sqrt * a_neg_nonzero;
+        let special_inf = is_addsub * one_inf - inf_inf * is_eff_sub
+            + is_mul * (one_inf - inf_zero)
+            + is_div * div_inf
+            + is_sqrt * a_inf * (1 - a_sign)
+            + is_cvt_f * a_inf;
+        let special_zero = is_div * div_zero;
+        let is_rounded = out_f - special_nan - special_inf - special_zero;
+        let is_finite = is_rounded * in_range;
+        let is_overflow = is_rounded - is_finite;
+
+        let num_zero = flag(num == 0);
+        let sign_xor = flag(a.sign != b.sign);
+        let special_sign = is_addsub * (a_inf * a_sign + (1 - a_inf) * flag(b_sign_eff))
+            + (is_mul + is_div) * sign_xor
+            + is_cvt_f * a_sign;
+        let rounded_sign = is_addsub * ((1 - num_zero) * flag(x_sign) + num_zero * flag(both_neg))
+            + (is_mul + is_div) * sign_xor
+            + (is_sqrt + is_cvt_f) * a_sign;
+        let result_sign = is_rounded * rounded_sign + (special_inf + special_zero) * special_sign;
+
+        let is_invalid = flag(is_big) + out_w * (1 - flag(is_big)) * (1 - in_range);
+        let is_negate = (out_w - is_invalid) * a_sign;
+        let is_positive = out_w - is_invalid - is_negate;
+        let negated = if is_negate == 1 {
+            u32::from_le_bytes(magnitude[..4].try_into().unwrap()).wrapping_neg()
+        } else {
+            0
+        };
+
+        cols.any_nan = field_from_i64(any_nan);
+        cols.inf_inf = field_from_i64(inf_inf);
+        cols.zero_zero = field_from_i64(zero_zero);
+        cols.inf_zero = field_from_i64(inf_zero);
+        cols.one_inf = field_from_i64(one_inf);
+        cols.div_inf = field_from_i64(div_inf);
+        cols.div_zero = field_from_i64(div_zero);
+        cols.a_neg_nonzero = field_from_i64(a_neg_nonzero);
+        cols.is_special_nan = field_from_i64(special_nan);
+        cols.is_special_inf = field_from_i64(special_inf);
+        cols.is_special_zero = field_from_i64(special_zero);
+        cols.is_finite = field_from_i64(is_finite);
+        cols.sign_xor = field_from_i64(sign_xor);
+        cols.special_sign = field_from_i64(special_sign);
+        cols.rounded_sign = field_from_i64(rounded_sign);
+        cols.result_sign = field_from_i64(result_sign);
+        cols.is_invalid = field_from_i64(is_invalid);
+        cols.is_negate = field_from_i64(is_negate);
+        cols.negated = negated.into();
+
+        // The written value must agree with the executor.
+        let out_s = flag(out_s);
+        let out_d = flag(out_d);
+        let nan = u64::from(DEFAULT_NAN_S) * out_s as u64 + DEFAULT_NAN_D * out_d as u64;
+        let inf = 0x7f80_0000 * out_s as u64 + 0x7ff0_0000_0000_0000 * out_d as u64;
+        let magnitude = u64::from_le_bytes(magnitude[..8].try_into().unwrap());
+        let sign_bit = (out_s as u64) << 31 | (out_d as u64) << 63;
+        let expected = special_nan as u64 * nan
+            + (special_inf + is_overflow) as u64 * inf
+            + (is_finite + is_positive) as u64 * magnitude
+            + is_invalid as u64 * u64::from(INVALID_WORD)
+            + u64::from(negated)
+            + result_sign as u64 * sign_bit;
+        debug_assert_eq!(
+            expected,
+            FpuEvent::pair_value(&event.fd_access),
+            "{} of {:#x} and {:#x}",
+            opcode,
+            FpuEvent::pair_value(&event.fs_access),
+            FpuEvent::pair_value(&event.ft_access),
+        );
+    }
+
+    /// Populates the columns of an operand, which is zero if `format` is `None`.
+    fn populate_operand<F: PrimeField32>(
+        cols: &mut FpuOperandCols<F>,
+        value: u64,
+        format: Option<FpuFormat>,
+        blu: &mut impl ByteRecord,
+    ) -> Operand {
+        let Some(format) = format else {
+            cols.exponent_zero.populate(0);
+            cols.exponent_max.populate(1);
+            cols.is_zero.populate(0);
+            cols.fraction_zero.populate(0);
+            return Operand { is_zero: true, ..Default::default() };
+        };
+
+        let bytes = value.to_le_bytes();
+        let top_byte = if format == FpuFormat::Double { bytes[7] } else { bytes[3] };
+        let sign = top_byte >> 7;
+        cols.top_byte = F::from_canonical_u8(top_byte);
+        cols.sign = F::from_canonical_u8(sign);
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            opcode: ByteOpcode::MSB,
+            a1: sign as u16,
+            a2: 0,
+            b: top_byte,
+            c: 0,
+        });
+
+        let high = i64::from(top_byte & 0x7f);
+        let (exponent, significand, exponent_max, fraction_offset, scale_offset) = match format {
+            FpuFormat::Single => {
+                let exponent_low = bytes[2] >> 7;
+                cols.exponent_byte = F::from_canonical_u8(bytes[2]);
+                cols.exponent_low = F::from_canonical_u8(exponent_low);
+                blu.add_byte_lookup_event(ByteLookupEvent {
+                    opcode: ByteOpcode::MSB,
+                    a1: exponent_low as u16,
+                    a2: 0,
+                    b: bytes[2],
+                    c: 0,
+                });
+                let exponent = (high << 1) | i64::from(exponent_low);
+                let significand = (value & 0x7f_ffff) | (u64::from(exponent != 0) << 23);
+                (exponent, significand, 256, 128, 150)
+            }
+            FpuFormat::Double => {
+                let exponent_low = bytes[6] >> 4;
+                cols.exponent_byte = F::from_canonical_u8(bytes[6]);
+                cols.exponent_low = F::from_canonical_u8(exponent_low);
+                blu.add_u8_range_check(exponent_low, exponent_low * 16);
+                blu.add_u8_range_check((bytes[6] & 0xf) * 16, 0);
+                let exponent = (high << 4) | i64::from(exponent_low);
+                let significand = (value & 0xf_ffff_ffff_ffff) | (u64::from(exponent != 0) << 52);
+                (exponent, significand, 2048, 16, 1075)
+            }
+            FpuFormat::Word => {
+                let word = value as u32;
+                let abs = if sign == 1 { word.wrapping_neg() } else { word };
+                cols.abs_value = abs.into();
+                (0, u64::from(abs), 0, 0, 0)
+            }
+        };
+        let is_float = format != FpuFormat::Word;
+
+        cols.exponent = F::from_canonical_u32(exponent as u32);
+        let is_exponent_zero = cols.exponent_zero.populate(exponent as u32) == 1;
+        let is_exponent_max = cols
+            .exponent_max
+            .populate_from_field_element(field_from_i64(exponent + 1 - exponent_max))
+            == 1;
+
+        let limbs = significand_bytes(significand);
+        cols.significand = limbs.map(F::from_canonical_u8);
+        let sum: i64 = limbs.iter().map(|&x| i64::from(x)).sum();
+        let is_zero = cols.is_zero.populate(sum as u32) == 1;
+        let is_fraction_zero =
+            cols.fraction_zero.populate_from_field_element(field_from_i64(sum - fraction_offset))
+                == 1;
+        let is_inf = is_exponent_max && is_fraction_zero;
+        let is_nan = is_exponent_max && !is_fraction_zero;
+        cols.is_inf = F::from_bool(is_inf);
+        cols.is_nan = F::from_bool(is_nan);
+
+        let scale =
+            if is_float { exponent + i64::from(is_exponent_zero) - scale_offset } else { 0 };
+        cols.scale = field_from_i64(scale);
+
+        Operand { sign: sign == 1, exponent, significand, scale, is_zero, is_inf, is_nan }
+    }
+}
+
+/// Computes `quotient = floor(num * 2^shift / den)`, or `floor(sqrt(num * 2^shift))` if `den` is
+/// `None`, along with the divisor and the remainder of the division.
+fn divide(num: u128, den: Option<u64>, shift: i64) -> (BigUint, BigUint, BigUint) {
+    let shift = shift.max(-MAX_RIGHT_SHIFT);
+    let one = BigUint::one();
+    let (left, right) = if shift >= 0 {
+        (&one << shift as usize, one)
+    } else {
+        (one.clone(), one << (-shift) as usize)
+    };
+    let lhs = BigUint::from(num) * left;
+    let (quotient, divisor) = match den {
+        Some(den) => {
+            let divisor = BigUint::from(den) * right;
+            (&lhs / &divisor, divisor)
+        }
+        None => {
+            let quotient = (&lhs / &right).sqrt();
+            let divisor = &quotient * right;
+            (quotient, divisor)
+        }
+    };
+    let remainder = lhs - &quotient * &divisor;
+    (quotient, divisor, remainder)
+}
+
+/// The low seven bytes of a significand.
+fn significand_bytes(significand: u64) -> [u8; 7] {
+    significand.to_le_bytes()[..7].try_into().unwrap()
+}
+
+/// The bytes of an integer as polynomial coefficients.
+fn to_limbs(bytes: &[u8]) -> Vec<i64> {
+    bytes.iter().map(|&x| i64::from(x)).collect()
+}
+
+/// The little-endian bytes of an integer, which must fit in `N` bytes.
+fn to_bytes<const N: usize>(value: &BigUint) -> [u8; N] {
+    let bytes = value.to_bytes_le();
+    assert!(bytes.len() <= N, "value does not fit in {N} bytes");
+    let mut result = [0u8; N];
+    result[..bytes.len()].copy_from_slice(&bytes);
+    result
+}
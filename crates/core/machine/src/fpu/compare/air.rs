@@ -0,0 +1,257 @@
+use std::{array, borrow::Borrow};
+
+use p3_air::{Air, AirBuilder};
+use p3_field::FieldAlgebra;
+use p3_matrix::Matrix;
+use zkm_core_executor::{events::MemoryAccessPosition, ByteOpcode, Opcode};
+use zkm_stark::{
+    air::{BaseAirBuilder, ZKMAirBuilder},
+    Word,
+};
+
+use crate::{
+    air::MemoryAirBuilder,
+    fpu::utils::eval_carry_identity,
+    memory::{MemoryCols, MemoryReadCols},
+};
+
+use super::{FpuCompareChip, FpuCompareColumns, FpuCompareOperandCols};
+
+impl<AB> Air<AB> for FpuCompareChip
+where
+    AB: ZKMAirBuilder,
+    AB::Var: Sized,
+{
+    #[inline(never)]
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &FpuCompareColumns<AB::Var> = (*local).borrow();
+
+        let single = [
+            (local.is_un_s, Opcode::C_UN_S),
+            (local.is_eq_s, Opcode::C_EQ_S),
+            (local.is_ueq_s, Opcode::C_UEQ_S),
+            (local.is_olt_s, Opcode::C_OLT_S),
+            (local.is_ult_s, Opcode::C_ULT_S),
+            (local.is_ole_s, Opcode::C_OLE_S),
+            (local.is_ule_s, Opcode::C_ULE_S),
+        ];
+        let double = [
+            (local.is_un_d, Opcode::C_UN_D),
+            (local.is_eq_d, Opcode::C_EQ_D),
+            (local.is_ueq_d, Opcode::C_UEQ_D),
+            (local.is_olt_d, Opcode::C_OLT_D),
+            (local.is_ult_d, Opcode::C_ULT_D),
+            (local.is_ole_d, Opcode::C_OLE_D),
+            (local.is_ule_d, Opcode::C_ULE_D),
+        ];
+
+        // SAFETY: All selectors are boolean and at most one of them is set, as `is_real` is boolean.
+        let mut is_single = AB::Expr::ZERO;
+        let mut is_double = AB::Expr::ZERO;
+        let mut opcode = AB::Expr::ZERO;
+        for &(selector, op) in single.iter() {
+            builder.assert_bool(selector);
+            is_single = is_single.clone() + selector;
+            opcode = opcode.clone() + selector * op.as_field::<AB::F>();
+        }
+        for &(selector, op) in double.iter() {
+            builder.assert_bool(selector);
+            is_double = is_double.clone() + selector;
+            opcode = opcode.clone() + selector * op.as_field::<AB::F>();
+        }
+        let is_real = is_single.clone() + is_double.clone();
+        builder.assert_bool(is_real.clone());
+
+        // The instruction reads `$zero` as `op_a`, which is never written.
+        builder.receive_instruction(
+            local.shard,
+            local.clk,
+            local.pc,
+            local.next_pc,
+            AB::Expr::ZERO,
+            opcode,
+            Word([AB::Expr::ZERO; 4]),
+            local.op_b_value,
+            local.op_c_value,
+            Word([AB::Expr::ZERO; 4]),
+            AB::Expr::ONE,
+            AB::Expr::ONE,
+            AB::Expr::ZERO,
+            AB::Expr::ZERO,
+            AB::Expr::ZERO,
+            AB::Expr::ONE,
+            is_real.clone(),
+        );
+
+        // Read `fs` and `ft`, and write the condition code.
+        builder.eval_memory_access(
+            local.shard,
+            local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::B as u32),
+            local.op_b_value[0],
+            &local.fs_access[0],
+            is_real.clone(),
+        );
+        builder.eval_memory_access(
+            local.shard,
+            local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::B as u32),
+            local.op_b_value[0] + AB::Expr::ONE,
+            &local.fs_access[1],
+            is_double.clone(),
+        );
+        builder.eval_memory_access(
+            local.shard,
+            local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::C as u32),
+            local.op_c_value[0],
+            &local.ft_access[0],
+            is_real.clone(),
+        );
+        builder.eval_memory_access(
+            local.shard,
+            local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::C as u32),
+            local.op_c_value[0] + AB::Expr::ONE,
+            &local.ft_access[1],
+            is_double.clone(),
+        );
+        builder.eval_memory_access(
+            local.shard,
+            local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::A as u32),
+            local.op_c_value[1],
+            &local.fcc_access,
+            is_real.clone(),
+        );
+
+        let key_a = self.eval_operand(
+            builder,
+            &local.a,
+            &local.fs_access,
+            is_single.clone(),
+            is_double.clone(),
+            is_real.clone(),
+        );
+        let key_b = self.eval_operand(
+            builder,
+            &local.b,
+            &local.ft_access,
+            is_single.clone(),
+            is_double.clone(),
+            is_real.clone(),
+        );
+
+        // Compare the keys, which are equal if no byte flag is set.
+        local.key_lt.eval(builder, &key_a, &key_b, is_real.clone());
+        let is_eq = local.key_lt.byte_flags.iter().fold(is_real.clone(), |acc, &flag| acc - flag);
+
+        let is_a_nan = local.a.is_nan.result;
+        let is_b_nan = local.b.is_nan.result;
+        builder.assert_eq(local.is_unordered, is_a_nan + is_b_nan - is_a_nan * is_b_nan);
+
+        // The condition holds if the operands are unordered, equal or less, depending on the
+        // predicate.
+        let when_unordered = local.is_un_s
+            + local.is_ueq_s
+            + local.is_ult_s
+            + local.is_ule_s
+            + local.is_un_d
+            + local.is_ueq_d
+            + local.is_ult_d
+            + local.is_ule_d;
+        let when_eq = local.is_eq_s
+            + local.is_ueq_s
+            + local.is_ole_s
+            + local.is_ule_s
+            + local.is_eq_d
+            + local.is_ueq_d
+            + local.is_ole_d
+            + local.is_ule_d;
+        let when_lt = local.is_olt_s
+            + local.is_ult_s
+            + local.is_ole_s
+            + local.is_ule_s
+            + local.is_olt_d
+            + local.is_ult_d
+            + local.is_ole_d
+            + local.is_ule_d;
+        let ordered = is_real.clone() - local.is_unordered;
+        let condition = when_unordered * local.is_unordered
+            + when_eq * ordered.clone() * is_eq
+            + when_lt * ordered * local.key_lt.result;
+
+        let fcc = local.fcc_access.value();
+        builder.assert_eq(fcc[0], condition);
+        for i in 1..4 {
+            builder.assert_zero(fcc[i]);
+        }
+    }
+}
+
+impl FpuCompareChip {
+    /// Evaluates the sign, the ordering key and the NaN flag of an operand, returning the key.
+    fn eval_operand<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        cols: &FpuCompareOperandCols<AB::Var>,
+        access: &[MemoryReadCols<AB::Var>; 2],
+        is_single: AB::Expr,
+        is_double: AB::Expr,
+        is_real: AB::Expr,
+    ) -> [AB::Expr; 8] {
+        let value: [AB::Expr; 8] = array::from_fn(|i| access[i / 4].value()[i % 4].into());
+
+        builder.assert_eq(
+            cols.top_byte,
+            is_single.clone() * value[3].clone() + is_double.clone() * value[7].clone(),
+        );
+        builder.send_byte(
+            ByteOpcode::MSB.as_field::<AB::F>(),
+            cols.sign,
+            cols.top_byte,
+            AB::Expr::ZERO,
+            is_real.clone(),
+        );
+
+        // The value without its sign bit.
+        let top = cols.top_byte - cols.sign * AB::F::from_canonical_u32(128);
+        let mag = [
+            value[0].clone(),
+            value[1].clone(),
+            value[2].clone(),
+            is_single.clone() * top.clone() + is_double.clone() * value[3].clone(),
+            is_double.clone() * value[4].clone(),
+            is_double.clone() * value[5].clone(),
+            is_double.clone() * value[6].clone(),
+            is_double.clone() * top,
+        ];
+
+        // key = 2^63 + (1 - 2 * sign) * mag.
+        let signum = is_real.clone() - cols.sign * AB::F::TWO;
+        let p: Vec<AB::Expr> = (0..8)
+            .map(|i| {
+                let offset = if i == 7 {
+                    is_real.clone() * AB::F::from_canonical_u32(128)
+                } else {
+                    AB::Expr::ZERO
+                };
+                offset + signum.clone() * mag[i].clone() - cols.key[i]
+            })
+            .collect();
+        eval_carry_identity(builder, &p, &cols.key_carries, is_real.clone());
+        builder.slice_range_check_u8(&cols.key, is_real.clone());
+
+        // A NaN has a larger magnitude than infinity.
+        let infinity = [
+            AB::Expr::ZERO,
+            AB::Expr::ZERO,
+            is_single.clone() * AB::F::from_canonical_u32(0x80),
+            is_single * AB::F::from_canonical_u32(0x7f),
+            AB::Expr::ZERO,
+            AB::Expr::ZERO,
+            is_double.clone() * AB::F::from_canonical_u32(0xf0),
+            is_double * AB::F::from_canonical_u32(0x7f),
+        ];
+        cols.is_nan.eval(builder, &infinity, &mag, is_real);
+
+        cols.key.map(Into::into)
+    }
+}
@@ -0,0 +1,81 @@
+use std::mem::size_of;
+use zkm_derive::AlignedBorrow;
+use zkm_stark::Word;
+
+use crate::{
+    memory::{MemoryReadCols, MemoryWriteCols},
+    operations::LtBytesOperation,
+};
+
+pub const NUM_FPU_COMPARE_COLS: usize = size_of::<FpuCompareColumns<u8>>();
+
+/// The columns for one operand of a floating-point comparison.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FpuCompareOperandCols<T> {
+    /// The most significant byte of the value, and its sign bit.
+    pub top_byte: T,
+    pub sign: T,
+
+    /// The ordering key of the value, `2^63 + mag` for positive and `2^63 - mag` for negative
+    /// values, where `mag` is the value with its sign bit cleared. The keys of two numbers compare
+    /// like the numbers themselves, with `-0` and `+0` sharing a key.
+    pub key: [T; 8],
+    /// The carries of the identity defining `key`.
+    pub key_carries: [T; 7],
+
+    /// Whether the value is a NaN, which is the case if `mag` is larger than the encoding of
+    /// infinity.
+    pub is_nan: LtBytesOperation<T, 8>,
+}
+
+/// The column layout for the floating-point comparisons.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FpuCompareColumns<T> {
+    /// The current/next program counter of the instruction.
+    pub pc: T,
+    pub next_pc: T,
+
+    /// The shard number.
+    pub shard: T,
+    /// The clock cycle number.
+    pub clk: T,
+
+    /// The address of `fs`.
+    pub op_b_value: Word<T>,
+    /// The addresses of `ft` and of the condition code.
+    pub op_c_value: Word<T>,
+
+    /// The reads of `fs` and `ft`. The second register of a pair is only read for doubles.
+    pub fs_access: [MemoryReadCols<T>; 2],
+    pub ft_access: [MemoryReadCols<T>; 2],
+    /// The write of the condition code.
+    pub fcc_access: MemoryWriteCols<T>,
+
+    pub a: FpuCompareOperandCols<T>,
+    pub b: FpuCompareOperandCols<T>,
+
+    /// Whether the key of `fs` is smaller than the key of `ft`.
+    pub key_lt: LtBytesOperation<T, 8>,
+    /// Whether either operand is a NaN.
+    pub is_unordered: T,
+
+    /// Selectors for the single precision comparisons.
+    pub is_un_s: T,
+    pub is_eq_s: T,
+    pub is_ueq_s: T,
+    pub is_olt_s: T,
+    pub is_ult_s: T,
+    pub is_ole_s: T,
+    pub is_ule_s: T,
+
+    /// Selectors for the double precision comparisons.
+    pub is_un_d: T,
+    pub is_eq_d: T,
+    pub is_ueq_d: T,
+    pub is_olt_d: T,
+    pub is_ult_d: T,
+    pub is_ole_d: T,
+    pub is_ule_d: T,
+}
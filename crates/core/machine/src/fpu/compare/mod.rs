@@ -0,0 +1,17 @@
+mod air;
+mod columns;
+mod trace;
+
+pub use columns::*;
+use p3_air::BaseAir;
+
+/// A chip that implements the `c.cond.s` and `c.cond.d` comparisons, except for `c.f`, which is
+/// decoded to an integer instruction.
+#[derive(Default)]
+pub struct FpuCompareChip;
+
+impl<F> BaseAir<F> for FpuCompareChip {
+    fn width(&self) -> usize {
+        NUM_FPU_COMPARE_COLS
+    }
+}
@@ -0,0 +1,176 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, FpuEvent, MemoryReadRecord},
+    fpu::{source_format, FpuFormat},
+    ByteOpcode, ExecutionRecord, Opcode, Program,
+};
+use zkm_stark::air::MachineAir;
+
+use crate::{
+    fpu::utils::populate_carry_identity,
+    utils::{next_power_of_two, zeroed_f_vec},
+};
+
+use super::{FpuCompareChip, FpuCompareColumns, FpuCompareOperandCols, NUM_FPU_COMPARE_COLS};
+
+impl<F: PrimeField32> MachineAir<F> for FpuCompareChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "FpuCompare".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let chunk_size = std::cmp::max((input.fpu_compare_events.len()) / num_cpus::get(), 1);
+        let nb_rows = input.fpu_compare_events.len();
+        let size_log2 = input.fixed_log2_rows::<F, _>(self);
+        let padded_nb_rows = next_power_of_two(nb_rows, size_log2);
+        let mut values = zeroed_f_vec(padded_nb_rows * NUM_FPU_COMPARE_COLS);
+
+        let blu_events = values
+            .chunks_mut(chunk_size * NUM_FPU_COMPARE_COLS)
+            .enumerate()
+            .par_bridge()
+            .map(|(i, rows)| {
+                let mut blu: HashMap<ByteLookupEvent, usize> = HashMap::new();
+                rows.chunks_mut(NUM_FPU_COMPARE_COLS).enumerate().for_each(|(j, row)| {
+                    let idx = i * chunk_size + j;
+                    let cols: &mut FpuCompareColumns<F> = row.borrow_mut();
+
+                    if idx < input.fpu_compare_events.len() {
+                        let event = &input.fpu_compare_events[idx];
+                        self.event_to_row(event, cols, &mut blu);
+                    }
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_byte_lookup_events_from_maps(blu_events.iter().collect_vec());
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(values, NUM_FPU_COMPARE_COLS)
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.fpu_compare_events.is_empty()
+        }
+    }
+
+    fn local_only(&self) -> bool {
+        true
+    }
+}
+
+impl FpuCompareChip {
+    fn event_to_row<F: PrimeField32>(
+        &self,
+        event: &FpuEvent,
+        cols: &mut FpuCompareColumns<F>,
+        blu: &mut impl ByteRecord,
+    ) {
+        cols.shard = F::from_canonical_u32(event.shard);
+        cols.clk = F::from_canonical_u32(event.clk);
+        cols.pc = F::from_canonical_u32(event.pc);
+        cols.next_pc = F::from_canonical_u32(event.next_pc);
+        cols.op_b_value = event.b.into();
+        cols.op_c_value = event.c.into();
+
+        match event.opcode {
+            Opcode::C_UN_S => cols.is_un_s = F::ONE,
+            Opcode::C_EQ_S => cols.is_eq_s = F::ONE,
+            Opcode::C_UEQ_S => cols.is_ueq_s = F::ONE,
+            Opcode::C_OLT_S => cols.is_olt_s = F::ONE,
+            Opcode::C_ULT_S => cols.is_ult_s = F::ONE,
+            Opcode::C_OLE_S => cols.is_ole_s = F::ONE,
+            Opcode::C_ULE_S => cols.is_ule_s = F::ONE,
+            Opcode::C_UN_D => cols.is_un_d = F::ONE,
+            Opcode::C_EQ_D => cols.is_eq_d = F::ONE,
+            Opcode::C_UEQ_D => cols.is_ueq_d = F::ONE,
+            Opcode::C_OLT_D => cols.is_olt_d = F::ONE,
+            Opcode::C_ULT_D => cols.is_ult_d = F::ONE,
+            Opcode::C_OLE_D => cols.is_ole_d = F::ONE,
+            Opcode::C_ULE_D => cols.is_ule_d = F::ONE,
+            _ => unreachable!("{} is not a floating-point comparison", event.opcode),
+        }
+
+        for (access, record) in cols.fs_access.iter_mut().zip(event.fs_access) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+        for (access, record) in cols.ft_access.iter_mut().zip(event.ft_access) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+        cols.fcc_access.populate(event.fd_access[0].expect("Must write a condition code"), blu);
+
+        let is_double = source_format(event.opcode) == FpuFormat::Double;
+        let (key_a, is_a_nan) =
+            Self::populate_operand(&mut cols.a, &event.fs_access, is_double, blu);
+        let (key_b, is_b_nan) =
+            Self::populate_operand(&mut cols.b, &event.ft_access, is_double, blu);
+
+        cols.key_lt.populate(blu, &key_a.to_le_bytes(), &key_b.to_le_bytes());
+        cols.is_unordered = F::from_bool(is_a_nan || is_b_nan);
+    }
+
+    /// Populates the columns of an operand, returning its ordering key and whether it is a NaN.
+    fn populate_operand<F: PrimeField32>(
+        cols: &mut FpuCompareOperandCols<F>,
+        access: &[Option<MemoryReadRecord>; 2],
+        is_double: bool,
+        blu: &mut impl ByteRecord,
+    ) -> (u64, bool) {
+        let value = FpuEvent::pair_value(access);
+        let (top_byte, mag, infinity) = if is_double {
+            ((value >> 56) as u8, value & !(1 << 63), 0x7ff0_0000_0000_0000)
+        } else {
+            ((value >> 24) as u8, value & 0x7fff_ffff, 0x7f80_0000)
+        };
+        let sign = top_byte >> 7;
+        cols.top_byte = F::from_canonical_u8(top_byte);
+        cols.sign = F::from_canonical_u8(sign);
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            opcode: ByteOpcode::MSB,
+            a1: sign as u16,
+            a2: 0,
+            b: top_byte,
+            c: 0,
+        });
+
+        let key = if sign == 1 { (1 << 63) - mag } else { (1 << 63) + mag };
+        let key_bytes = key.to_le_bytes();
+        let mag_bytes = mag.to_le_bytes();
+        cols.key = key_bytes.map(F::from_canonical_u8);
+        let signum = if sign == 1 { -1 } else { 1 };
+        let p = (0..8)
+            .map(|i| {
+                let offset = if i == 7 { 128 } else { 0 };
+                offset + signum * i64::from(mag_bytes[i]) - i64::from(key_bytes[i])
+            })
+            .collect::<Vec<_>>();
+        populate_carry_identity(&p, &mut cols.key_carries, blu);
+        blu.add_u8_range_checks(&key_bytes);
+
+        let infinity_bytes = u64::to_le_bytes(infinity);
+        let is_nan = cols.is_nan.populate(blu, &infinity_bytes, &mag_bytes);
+        (key, is_nan)
+    }
+}
@@ -0,0 +1,220 @@
+//! The chips of the floating-point unit.
+//!
+//! The floating-point registers and condition codes are registers of the machine (see
+//! [`zkm_core_executor::Register::F0`]), so the FPU chips read and write them through the memory
+//! argument themselves. The CPU only reads `$zero` as `op_a` of an FPU instruction.
+
+mod arith;
+mod compare;
+mod moves;
+mod utils;
+
+pub use arith::*;
+pub use compare::*;
+pub use moves::*;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        io::ZKMStdin,
+        utils,
+        utils::{run_test, run_test_io},
+    };
+
+    use test_artifacts::HARD_FLOAT_ELF;
+    use zkm_core_executor::{Instruction, Opcode, Program, Register};
+
+    use zkm_stark::CpuProver;
+
+    /// The register address of the floating-point register `index`.
+    fn fpr(index: u32) -> u32 {
+        Register::fpr(index) as u32
+    }
+
+    /// Writes `value` to the floating-point register `index`.
+    fn set_single(index: u32, value: f32) -> Instruction {
+        Instruction::new(Opcode::ADD, fpr(index) as u8, 0, value.to_bits(), false, true)
+    }
+
+    /// Writes `value` to the floating-point register pair starting at `index`.
+    fn set_double(index: u32, value: f64) -> [Instruction; 2] {
+        let bits = value.to_bits();
+        [
+            Instruction::new(Opcode::ADD, fpr(index) as u8, 0, bits as u32, false, true),
+            Instruction::new(
+                Opcode::ADD,
+                fpr(index + 1) as u8,
+                0,
+                (bits >> 32) as u32,
+                false,
+                true,
+            ),
+        ]
+    }
+
+    fn binary(opcode: Opcode, fd: u32, fs: u32, ft: u32) -> Instruction {
+        Instruction::new(opcode, 0, fpr(fs), fpr(ft) | fpr(fd) << 8, true, true)
+    }
+
+    fn unary(opcode: Opcode, fd: u32, fs: u32) -> Instruction {
+        Instruction::new(opcode, 0, fpr(fs), fpr(fd) << 8, true, true)
+    }
+
+    #[test]
+    fn test_fpu_arith_single_prove() {
+        utils::setup_logger();
+        let operands = [
+            (1.5f32, 2.25f32),
+            (-3.0, 0.1),
+            (1e-40, -1e-40),
+            (f32::MAX, f32::MAX),
+            (f32::INFINITY, 0.0),
+            (-0.0, f32::NAN),
+            (2.5e9, -7.0),
+        ];
+        let mut instructions = vec![];
+        for (a, b) in operands {
+            instructions.push(set_single(2, a));
+            instructions.push(set_single(4, b));
+            for opcode in [Opcode::FADD_S, Opcode::FSUB_S, Opcode::FMUL_S, Opcode::FDIV_S] {
+                instructions.push(binary(opcode, 6, 2, 4));
+            }
+            for opcode in [
+                Opcode::FSQRT_S,
+                Opcode::CVT_D_S,
+                Opcode::CVT_W_S,
+                Opcode::TRUNC_W_S,
+                Opcode::CVT_S_W,
+            ] {
+                instructions.push(unary(opcode, 8, 2));
+            }
+        }
+        let program = Program::new(instructions, 0, 0);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_fpu_arith_double_prove() {
+        utils::setup_logger();
+        let operands = [
+            (1.5f64, 2.25f64),
+            (-3.0, 0.1),
+            (5e-324, 1e-310),
+            (f64::MAX, -f64::MAX),
+            (f64::NEG_INFINITY, f64::INFINITY),
+            (0.0, -0.0),
+            (-2147483648.4, 1e300),
+        ];
+        let mut instructions = vec![];
+        for (a, b) in operands {
+            instructions.extend(set_double(2, a));
+            instructions.extend(set_double(4, b));
+            for opcode in [Opcode::FADD_D, Opcode::FSUB_D, Opcode::FMUL_D, Opcode::FDIV_D] {
+                instructions.push(binary(opcode, 6, 2, 4));
+            }
+            for opcode in [
+                Opcode::FSQRT_D,
+                Opcode::CVT_S_D,
+                Opcode::CVT_W_D,
+                Opcode::TRUNC_W_D,
+                Opcode::CVT_D_W,
+            ] {
+                instructions.push(unary(opcode, 8, 2));
+            }
+        }
+        let program = Program::new(instructions, 0, 0);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_fpu_compare_prove() {
+        utils::setup_logger();
+        let compares = [
+            (Opcode::C_UN_S, Opcode::C_UN_D),
+            (Opcode::C_EQ_S, Opcode::C_EQ_D),
+            (Opcode::C_UEQ_S, Opcode::C_UEQ_D),
+            (Opcode::C_OLT_S, Opcode::C_OLT_D),
+            (Opcode::C_ULT_S, Opcode::C_ULT_D),
+            (Opcode::C_OLE_S, Opcode::C_OLE_D),
+            (Opcode::C_ULE_S, Opcode::C_ULE_D),
+        ];
+        let operands = [(1.0f32, 2.0f32), (-1.0, -2.0), (0.0, -0.0), (f32::NAN, 1.0)];
+        let mut instructions = vec![];
+        for (a, b) in operands {
+            instructions.push(set_single(2, a));
+            instructions.push(set_single(4, b));
+            instructions.extend(set_double(6, a.into()));
+            instructions.extend(set_double(8, b.into()));
+            for (cc, (single, double)) in compares.into_iter().enumerate() {
+                let cc = Register::fcc(cc as u32) as u32;
+                instructions.push(Instruction::new(
+                    single,
+                    0,
+                    fpr(2),
+                    fpr(4) | cc << 8,
+                    true,
+                    true,
+                ));
+                instructions.push(Instruction::new(
+                    double,
+                    0,
+                    fpr(6),
+                    fpr(8) | cc << 8,
+                    true,
+                    true,
+                ));
+            }
+        }
+        let program = Program::new(instructions, 0, 0);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_fpu_move_prove() {
+        utils::setup_logger();
+        let mut instructions = vec![Instruction::new(Opcode::ADD, 29, 0, 0x27654320, false, true)];
+        instructions.extend(set_double(2, -1.25));
+        instructions.extend([
+            unary(Opcode::MOV_D, 4, 2),
+            unary(Opcode::ABS_D, 6, 2),
+            unary(Opcode::NEG_D, 8, 6),
+            Instruction::new(Opcode::MOVN_D, 0, fpr(2), 29 | fpr(10) << 8, true, true),
+            Instruction::new(Opcode::MOVZ_D, 0, fpr(2), 29 | fpr(12) << 8, true, true),
+            Instruction::new(Opcode::MOVZ_D, 0, fpr(2), fpr(18) << 8, true, true),
+            Instruction::new(Opcode::SDC1, 0, 29, 8 | fpr(8) << 16, false, true),
+            Instruction::new(Opcode::LDC1, 0, 29, 8 | fpr(14) << 16, false, true),
+            Instruction::new(Opcode::SDC1, 0, 29, 0xfff8 | fpr(2) << 16, false, true),
+            Instruction::new(Opcode::LDC1, 0, 29, 0xfff8 | fpr(16) << 16, false, true),
+        ]);
+        let program = Program::new(instructions, 0, 0);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_fpu_fix_shape_prove() {
+        utils::setup_logger();
+        // More floating-point events than the shapes of programs without them allow for.
+        let mut instructions = vec![set_single(2, 1.5), set_single(4, 2.25)];
+        for _ in 0..2048 {
+            instructions.push(binary(Opcode::FADD_S, 6, 2, 4));
+            instructions.push(Instruction::new(
+                Opcode::C_OLT_S,
+                0,
+                fpr(2),
+                fpr(4) | (Register::fcc(0) as u32) << 8,
+                true,
+                true,
+            ));
+            instructions.push(unary(Opcode::MOV_D, 8, 2));
+        }
+        let program = Program::new(instructions, 0, 0);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_hard_float_guest_prove() {
+        utils::setup_logger();
+        let program = Program::from(HARD_FLOAT_ELF).unwrap();
+        run_test_io::<CpuProver<_, _>>(program, ZKMStdin::new()).unwrap();
+    }
+}
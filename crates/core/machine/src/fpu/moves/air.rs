@@ -0,0 +1,238 @@
+use std::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder};
+use p3_field::FieldAlgebra;
+use p3_matrix::Matrix;
+use zkm_core_executor::{events::MemoryAccessPosition, ByteOpcode, Opcode};
+use zkm_stark::{
+    air::{BaseAirBuilder, ZKMAirBuilder},
+    Word,
+};
+
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::MemoryCols,
+    operations::{IsZeroWordOperation, KoalaBearWordRangeChecker},
+};
+
+use super::{FpuMoveChip, FpuMoveColumns};
+
+impl<AB> Air<AB> for FpuMoveChip
+where
+    AB: ZKMAirBuilder,
+    AB::Var: Sized,
+{
+    #[inline(never)]
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &FpuMoveColumns<AB::Var> = (*local).borrow();
+
+        // SAFETY: All selectors are boolean and at most one of them is set, as `is_real` is boolean.
+        builder.assert_bool(local.is_ldc1);
+        builder.assert_bool(local.is_sdc1);
+        builder.assert_bool(local.is_mov);
+        builder.assert_bool(local.is_abs);
+        builder.assert_bool(local.is_neg);
+        builder.assert_bool(local.is_movn);
+        builder.assert_bool(local.is_movz);
+
+        let is_mem = local.is_ldc1 + local.is_sdc1;
+        let is_sign_move = local.is_mov + local.is_abs + local.is_neg;
+        let is_cond_move = local.is_movn + local.is_movz;
+        let is_real = is_mem.clone() + is_sign_move.clone() + is_cond_move.clone();
+        builder.assert_bool(is_real.clone());
+
+        let opcode = local.is_ldc1 * Opcode::LDC1.as_field::<AB::F>()
+            + local.is_sdc1 * Opcode::SDC1.as_field::<AB::F>()
+            + local.is_mov * Opcode::MOV_D.as_field::<AB::F>()
+            + local.is_abs * Opcode::ABS_D.as_field::<AB::F>()
+            + local.is_neg * Opcode::NEG_D.as_field::<AB::F>()
+            + local.is_movn * Opcode::MOVN_D.as_field::<AB::F>()
+            + local.is_movz * Opcode::MOVZ_D.as_field::<AB::F>();
+
+        // The instruction reads `$zero` as `op_a`, which is never written.
+        builder.receive_instruction(
+            local.shard,
+            local.clk,
+            local.pc,
+            local.next_pc,
+            AB::Expr::ZERO,
+            opcode,
+            Word([AB::Expr::ZERO; 4]),
+            local.op_b_value,
+            local.op_c_value,
+            Word([AB::Expr::ZERO; 4]),
+            AB::Expr::ONE,
+            AB::Expr::ONE,
+            AB::Expr::ZERO,
+            AB::Expr::ZERO,
+            AB::Expr::ZERO,
+            AB::Expr::ONE,
+            is_real.clone(),
+        );
+
+        // The moves read `fs` at position B.
+        for (i, access) in local.fs_access.iter().enumerate() {
+            builder.eval_memory_access(
+                local.shard,
+                local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::B as u32),
+                local.op_b_value[0] + AB::F::from_canonical_usize(i),
+                access,
+                is_sign_move.clone() + is_cond_move.clone(),
+            );
+        }
+
+        // The conditional moves read their condition register, and `sdc1` reads `ft`, at
+        // position C.
+        builder.assert_eq(
+            local.cond_addr,
+            is_cond_move.clone() * local.op_c_value[0] + local.is_sdc1 * local.op_c_value[2],
+        );
+        builder.eval_memory_access(
+            local.shard,
+            local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::C as u32),
+            local.cond_addr,
+            &local.cond_access[0],
+            is_cond_move.clone() + local.is_sdc1,
+        );
+        builder.eval_memory_access(
+            local.shard,
+            local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::C as u32),
+            local.cond_addr + AB::Expr::ONE,
+            &local.cond_access[1],
+            local.is_sdc1,
+        );
+
+        // All instructions but `sdc1` write a register pair at position A.
+        builder.assert_eq(
+            local.fd_addr,
+            (is_sign_move.clone() + is_cond_move.clone()) * local.op_c_value[1]
+                + local.is_ldc1 * local.op_c_value[2],
+        );
+        for (i, access) in local.fd_access.iter().enumerate() {
+            builder.eval_memory_access(
+                local.shard,
+                local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::A as u32),
+                local.fd_addr + AB::F::from_canonical_usize(i),
+                access,
+                is_real.clone() - local.is_sdc1,
+            );
+        }
+
+        self.eval_load_store(builder, local, is_mem);
+
+        // The sign bit of `fs`, which `abs.d` clears and `neg.d` flips.
+        let fs_top_byte = local.fs_access[1].value()[3];
+        builder.send_byte(
+            ByteOpcode::MSB.as_field::<AB::F>(),
+            local.fs_sign,
+            fs_top_byte,
+            AB::Expr::ZERO,
+            local.is_abs + local.is_neg,
+        );
+        let sign_adjustment = (local.is_neg
+            - (local.is_abs + local.is_neg * AB::F::TWO) * local.fs_sign)
+            * AB::F::from_canonical_u32(128);
+
+        // A conditional move takes place if the condition is nonzero for `movn.d`, and zero for
+        // `movz.d`.
+        let cond = *local.cond_access[0].value();
+        IsZeroWordOperation::<AB::F>::eval(
+            builder,
+            cond.map(Into::into),
+            local.cond_zero,
+            is_cond_move.clone(),
+        );
+        builder.assert_eq(
+            local.is_taken,
+            local.is_movn * (AB::Expr::ONE - local.cond_zero.result)
+                + local.is_movz * local.cond_zero.result,
+        );
+
+        // The written value is `fs` with its sign bit adjusted, `fs` or the previous value of `fd`
+        // for conditional moves, or the loaded value.
+        for i in 0..8 {
+            let (word, byte) = (i / 4, i % 4);
+            let fs = local.fs_access[word].value()[byte];
+            let prev = local.fd_access[word].prev_value()[byte];
+            let loaded = local.mem_access[word].value()[byte];
+            let mut expected = (is_sign_move.clone() + local.is_taken) * fs
+                + (is_cond_move.clone() - local.is_taken) * prev
+                + local.is_ldc1 * loaded;
+            if i == 7 {
+                expected = expected + sign_adjustment.clone();
+            }
+            builder.assert_eq(local.fd_access[word].value()[byte], expected);
+        }
+    }
+}
+
+impl FpuMoveChip {
+    /// Constrains the address and the memory accesses of `ldc1` and `sdc1`.
+    fn eval_load_store<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &FpuMoveColumns<AB::Var>,
+        is_mem: AB::Expr,
+    ) {
+        // The address is the base plus the sign extended 16-bit offset.
+        builder.send_byte(
+            ByteOpcode::MSB.as_field::<AB::F>(),
+            local.offset_sign,
+            local.op_c_value[1],
+            AB::Expr::ZERO,
+            is_mem.clone(),
+        );
+        let sign_byte = local.offset_sign * AB::F::from_canonical_u32(0xff);
+        let offset = Word([
+            local.op_c_value[0].into(),
+            local.op_c_value[1].into(),
+            sign_byte.clone(),
+            sign_byte,
+        ]);
+        builder.send_alu(
+            Opcode::ADD.as_field::<AB::F>(),
+            local.addr_word,
+            local.op_b_value,
+            offset,
+            is_mem.clone(),
+        );
+        KoalaBearWordRangeChecker::<AB::F>::range_check(
+            builder,
+            local.addr_word,
+            local.addr_word_range_checker,
+            is_mem.clone(),
+        );
+
+        // The address must be aligned to 8 bytes.
+        builder.assert_eq(
+            local.addr_word[0],
+            local.addr_ls_byte_quotient * AB::F::from_canonical_u32(8),
+        );
+        builder.slice_range_check_u8(
+            &[local.addr_ls_byte_quotient, local.addr_word[1], local.addr_word[2]],
+            is_mem.clone(),
+        );
+
+        for (i, access) in local.mem_access.iter().enumerate() {
+            builder.eval_memory_access(
+                local.shard,
+                local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::Memory as u32),
+                local.addr_word.reduce::<AB>() + AB::F::from_canonical_usize(4 * i),
+                access,
+                is_mem.clone(),
+            );
+        }
+
+        // `ldc1` leaves the memory unchanged, and `sdc1` writes the register pair `ft`.
+        for i in 0..2 {
+            builder
+                .when(local.is_ldc1)
+                .assert_word_eq(*local.mem_access[i].value(), *local.mem_access[i].prev_value());
+            builder
+                .when(local.is_sdc1)
+                .assert_word_eq(*local.mem_access[i].value(), *local.cond_access[i].value());
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::mem::size_of;
+use zkm_derive::AlignedBorrow;
+use zkm_stark::Word;
+
+use crate::{
+    memory::{MemoryReadCols, MemoryReadWriteCols, MemoryWriteCols},
+    operations::{IsZeroWordOperation, KoalaBearWordRangeChecker},
+};
+
+pub const NUM_FPU_MOVE_COLS: usize = size_of::<FpuMoveColumns<u8>>();
+
+/// The column layout for the double precision moves, loads and stores.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FpuMoveColumns<T> {
+    /// The current/next program counter of the instruction.
+    pub pc: T,
+    pub next_pc: T,
+
+    /// The shard number.
+    pub shard: T,
+    /// The clock cycle number.
+    pub clk: T,
+
+    /// The address of `fs`, or the base address of a load or store.
+    pub op_b_value: Word<T>,
+    /// The register addresses, and the offset of a load or store.
+    pub op_c_value: Word<T>,
+
+    /// The read of the register pair `fs`.
+    pub fs_access: [MemoryReadCols<T>; 2],
+
+    /// The address of the condition register of a conditional move, or of the register pair
+    /// `ft` stored by `sdc1`.
+    pub cond_addr: T,
+    /// The read of the condition register, or of the register pair `ft` stored by `sdc1`.
+    pub cond_access: [MemoryReadCols<T>; 2],
+
+    /// The address of the register pair written by a move or `ldc1`.
+    pub fd_addr: T,
+    /// The write of the register pair `fd`.
+    pub fd_access: [MemoryWriteCols<T>; 2],
+
+    /// The address of a load or store, and the sign bit of its offset.
+    pub addr_word: Word<T>,
+    pub addr_word_range_checker: KoalaBearWordRangeChecker<T>,
+    pub offset_sign: T,
+    /// The least significant byte of the address divided by 8.
+    pub addr_ls_byte_quotient: T,
+    /// The memory accesses of a load or store.
+    pub mem_access: [MemoryReadWriteCols<T>; 2],
+
+    /// The sign bit of `fs`, used by `abs.d` and `neg.d`.
+    pub fs_sign: T,
+
+    /// Whether the condition register is zero.
+    pub cond_zero: IsZeroWordOperation<T>,
+    /// Whether a conditional move takes place.
+    pub is_taken: T,
+
+    /// Selectors for the instructions.
+    pub is_ldc1: T,
+    pub is_sdc1: T,
+    pub is_mov: T,
+    pub is_abs: T,
+    pub is_neg: T,
+    pub is_movn: T,
+    pub is_movz: T,
+}
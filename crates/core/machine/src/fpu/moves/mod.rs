@@ -0,0 +1,17 @@
+mod air;
+mod columns;
+mod trace;
+
+pub use columns::*;
+use p3_air::BaseAir;
+
+/// A chip that implements the double precision moves `mov.d`, `abs.d` and `neg.d`, the double
+/// precision conditional moves, and the double word load and store `ldc1` and `sdc1`.
+#[derive(Default)]
+pub struct FpuMoveChip;
+
+impl<F> BaseAir<F> for FpuMoveChip {
+    fn width(&self) -> usize {
+        NUM_FPU_MOVE_COLS
+    }
+}
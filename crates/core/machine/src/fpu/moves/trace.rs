@@ -0,0 +1,173 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, FpuEvent},
+    sign_extend, ByteOpcode, ExecutionRecord, Opcode, Program,
+};
+use zkm_stark::air::MachineAir;
+
+use crate::utils::{next_power_of_two, zeroed_f_vec};
+
+use super::{FpuMoveChip, FpuMoveColumns, NUM_FPU_MOVE_COLS};
+
+impl<F: PrimeField32> MachineAir<F> for FpuMoveChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "FpuMove".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let chunk_size = std::cmp::max((input.fpu_move_events.len()) / num_cpus::get(), 1);
+        let nb_rows = input.fpu_move_events.len();
+        let size_log2 = input.fixed_log2_rows::<F, _>(self);
+        let padded_nb_rows = next_power_of_two(nb_rows, size_log2);
+        let mut values = zeroed_f_vec(padded_nb_rows * NUM_FPU_MOVE_COLS);
+
+        let blu_events = values
+            .chunks_mut(chunk_size * NUM_FPU_MOVE_COLS)
+            .enumerate()
+            .par_bridge()
+            .map(|(i, rows)| {
+                let mut blu: HashMap<ByteLookupEvent, usize> = HashMap::new();
+                rows.chunks_mut(NUM_FPU_MOVE_COLS).enumerate().for_each(|(j, row)| {
+                    let idx = i * chunk_size + j;
+                    let cols: &mut FpuMoveColumns<F> = row.borrow_mut();
+
+                    if idx < input.fpu_move_events.len() {
+                        let event = &input.fpu_move_events[idx];
+                        self.event_to_row(event, cols, &mut blu);
+                    }
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_byte_lookup_events_from_maps(blu_events.iter().collect_vec());
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(values, NUM_FPU_MOVE_COLS)
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.fpu_move_events.is_empty()
+        }
+    }
+
+    fn local_only(&self) -> bool {
+        true
+    }
+}
+
+impl FpuMoveChip {
+    fn event_to_row<F: PrimeField32>(
+        &self,
+        event: &FpuEvent,
+        cols: &mut FpuMoveColumns<F>,
+        blu: &mut impl ByteRecord,
+    ) {
+        cols.shard = F::from_canonical_u32(event.shard);
+        cols.clk = F::from_canonical_u32(event.clk);
+        cols.pc = F::from_canonical_u32(event.pc);
+        cols.next_pc = F::from_canonical_u32(event.next_pc);
+        cols.op_b_value = event.b.into();
+        cols.op_c_value = event.c.into();
+
+        match event.opcode {
+            Opcode::LDC1 => cols.is_ldc1 = F::ONE,
+            Opcode::SDC1 => cols.is_sdc1 = F::ONE,
+            Opcode::MOV_D => cols.is_mov = F::ONE,
+            Opcode::ABS_D => cols.is_abs = F::ONE,
+            Opcode::NEG_D => cols.is_neg = F::ONE,
+            Opcode::MOVN_D => cols.is_movn = F::ONE,
+            Opcode::MOVZ_D => cols.is_movz = F::ONE,
+            _ => unreachable!("{} is not a floating-point move", event.opcode),
+        }
+
+        for (access, record) in cols.fs_access.iter_mut().zip(event.fs_access) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+        for (access, record) in cols.cond_access.iter_mut().zip(event.ft_access) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+        for (access, record) in cols.fd_access.iter_mut().zip(event.fd_access) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+        for (access, record) in cols.mem_access.iter_mut().zip(event.mem_access) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+
+        let c_bytes = event.c.to_le_bytes();
+        match event.opcode {
+            Opcode::LDC1 | Opcode::SDC1 => {
+                let offset_sign = c_bytes[1] >> 7;
+                cols.offset_sign = F::from_canonical_u8(offset_sign);
+                blu.add_byte_lookup_event(ByteLookupEvent {
+                    opcode: ByteOpcode::MSB,
+                    a1: offset_sign as u16,
+                    a2: 0,
+                    b: c_bytes[1],
+                    c: 0,
+                });
+
+                let addr = event.b.wrapping_add(sign_extend::<16>(event.c & 0xffff));
+                let addr_bytes = addr.to_le_bytes();
+                cols.addr_word = addr.into();
+                cols.addr_word_range_checker.populate(addr);
+                cols.addr_ls_byte_quotient = F::from_canonical_u8(addr_bytes[0] / 8);
+                blu.add_u8_range_checks(&[addr_bytes[0] / 8, addr_bytes[1], addr_bytes[2]]);
+
+                if event.opcode == Opcode::LDC1 {
+                    cols.fd_addr = F::from_canonical_u8(c_bytes[2]);
+                } else {
+                    cols.cond_addr = F::from_canonical_u8(c_bytes[2]);
+                }
+            }
+            Opcode::MOV_D | Opcode::ABS_D | Opcode::NEG_D => {
+                cols.fd_addr = F::from_canonical_u8(c_bytes[1]);
+                if event.opcode != Opcode::MOV_D {
+                    let fs_top_byte = (FpuEvent::pair_value(&event.fs_access) >> 56) as u8;
+                    let fs_sign = fs_top_byte >> 7;
+                    cols.fs_sign = F::from_canonical_u8(fs_sign);
+                    blu.add_byte_lookup_event(ByteLookupEvent {
+                        opcode: ByteOpcode::MSB,
+                        a1: fs_sign as u16,
+                        a2: 0,
+                        b: fs_top_byte,
+                        c: 0,
+                    });
+                }
+            }
+            Opcode::MOVN_D | Opcode::MOVZ_D => {
+                cols.fd_addr = F::from_canonical_u8(c_bytes[1]);
+                cols.cond_addr = F::from_canonical_u8(c_bytes[0]);
+                let cond = FpuEvent::pair_value(&event.ft_access) as u32;
+                let cond_zero = cols.cond_zero.populate(cond) == 1;
+                cols.is_taken = F::from_bool(cond_zero == (event.opcode == Opcode::MOVZ_D));
+            }
+            _ => unreachable!(),
+        }
+    }
+}
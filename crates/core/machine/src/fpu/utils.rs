@@ -0,0 +1,90 @@
+//! Limb arithmetic shared by the floating-point chips.
+//!
+//! Large integers are represented as little-endian byte limbs. An identity between products of
+//! such integers is checked by evaluating it as a polynomial at `x = 256`, see
+//! [`eval_carry_identity`].
+
+use std::ops::{Add, Mul};
+
+use p3_field::{FieldAlgebra, PrimeField32};
+use zkm_core_executor::{events::ByteRecord, ByteOpcode};
+use zkm_stark::air::{BaseAirBuilder, ZKMAirBuilder};
+
+/// The offset added to the carries of a carry identity, so that they are range checked as u16.
+const CARRY_OFFSET: i64 = 1 << 14;
+
+/// The coefficients of the product of two polynomials.
+pub(crate) fn limb_product<T>(a: &[T], b: &[T], zero: T) -> Vec<T>
+where
+    T: Clone + Add<Output = T> + Mul<Output = T>,
+{
+    let mut product = vec![zero; a.len() + b.len() - 1];
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate() {
+            product[i + j] = product[i + j].clone() + a.clone() * b.clone();
+        }
+    }
+    product
+}
+
+/// Constrains `p(256) = 0` for the polynomial `p` with the given coefficients.
+///
+/// This holds if and only if `p(x) = (x - 256) * w(x)`, where the coefficients of `w` are
+/// `carries[k] - 2^14`. As long as every coefficient of `p` is at most `255 * 2^14` in absolute
+/// value, the carries are u16 values and the identity holds over the integers.
+pub(crate) fn eval_carry_identity<AB: ZKMAirBuilder>(
+    builder: &mut AB,
+    p: &[AB::Expr],
+    carries: &[AB::Var],
+    is_real: AB::Expr,
+) {
+    assert_eq!(p.len(), carries.len() + 1);
+    let base = AB::F::from_canonical_u32(256);
+    let offset = is_real.clone() * AB::F::from_canonical_u32(CARRY_OFFSET as u32);
+    let w = |k: usize| -> AB::Expr { carries[k].into() - offset.clone() };
+
+    for (k, p_k) in p.iter().enumerate() {
+        let lower = if k == 0 { AB::Expr::ZERO } else { w(k - 1) };
+        let upper = if k == carries.len() { AB::Expr::ZERO } else { w(k) * base };
+        builder.assert_eq(p_k.clone(), lower - upper);
+    }
+
+    for &carry in carries {
+        builder.send_byte(
+            ByteOpcode::U16Range.as_field::<AB::F>(),
+            carry,
+            AB::Expr::ZERO,
+            AB::Expr::ZERO,
+            is_real.clone(),
+        );
+    }
+}
+
+/// Populates the carries of [`eval_carry_identity`] for a polynomial with `p(256) = 0`.
+pub(crate) fn populate_carry_identity<F: PrimeField32>(
+    p: &[i64],
+    carries: &mut [F],
+    blu: &mut impl ByteRecord,
+) {
+    assert_eq!(p.len(), carries.len() + 1);
+    let mut w = 0i64;
+    for (p_k, carry) in p.iter().zip(carries.iter_mut()) {
+        let shifted = w - p_k;
+        debug_assert_eq!(shifted % 256, 0, "the polynomial does not vanish at 256");
+        w = shifted / 256;
+        let value = u16::try_from(w + CARRY_OFFSET).expect("carry out of range");
+        *carry = F::from_canonical_u16(value);
+        blu.add_u16_range_check(value);
+    }
+    debug_assert_eq!(w, p[carries.len()], "the polynomial does not vanish at 256");
+}
+
+/// Converts a signed integer to a field element.
+pub(crate) fn field_from_i64<F: PrimeField32>(value: i64) -> F {
+    let magnitude = F::from_canonical_u64(value.unsigned_abs());
+    if value < 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
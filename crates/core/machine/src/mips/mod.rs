@@ -1,7 +1,9 @@
 use crate::{
     global::GlobalChip,
     memory::{MemoryChipType, MemoryLocalChip, NUM_LOCAL_MEMORY_ENTRIES_PER_ROW},
-    syscall::precompiles::fptower::{Fp2AddSubAssignChip, Fp2MulAssignChip, FpOpChip},
+    syscall::precompiles::fptower::{
+        Fp2AddSubAssignChip, Fp2MulAssignChip, Fp6MulAssignChip, FpOpChip,
+    },
 };
 use core::fmt;
 use hashbrown::{HashMap, HashSet};
@@ -29,6 +31,7 @@ pub(crate) mod mips_chips {
         bytes::ByteChip,
         control_flow::{BranchChip, JumpChip},
         cpu::CpuChip,
+        fpu::{FpuArithChip, FpuCompareChip, FpuMoveChip},
         memory::{MemoryGlobalChip, MemoryInstructionsChip},
         misc::MiscInstrsChip,
         program::ProgramChip,
@@ -36,8 +39,10 @@ pub(crate) mod mips_chips {
             chip::SyscallChip,
             instructions::SyscallInstrsChip,
             precompiles::{
+                blake3::Blake3CompressInnerChip,
                 edwards::{EdAddAssignChip, EdDecompressChip},
                 keccak_sponge::KeccakSpongeChip,
+                poseidon2::Poseidon2PermuteChip,
                 sha256::{ShaCompressChip, ShaExtendChip},
                 u256x2048_mul::U256x2048MulChip,
                 uint256::Uint256MulChip,
@@ -103,6 +108,12 @@ pub enum MipsAir<F: PrimeField32> {
     MemoryInstrs(MemoryInstructionsChip),
     /// An AIR for MIPS misc instructions.
     MiscInstrs(MiscInstrsChip),
+    /// An AIR for MIPS floating-point arithmetic and conversion instructions.
+    FpuArith(FpuArithChip),
+    /// An AIR for MIPS floating-point compare instructions.
+    FpuCompare(FpuCompareChip),
+    /// An AIR for MIPS double precision moves, loads and stores.
+    FpuMove(FpuMoveChip),
     /// An AIR for MIPS syscall instructions.
     SyscallInstrs(SyscallInstrsChip),
     /// A table for initializing the global memory state.
@@ -139,6 +150,8 @@ pub enum MipsAir<F: PrimeField32> {
     Secp256r1Double(WeierstrassDoubleAssignChip<SwCurve<Secp256r1Parameters>>),
     /// A precompile for the Keccak Sponge
     KeccakSponge(KeccakSpongeChip),
+    /// A precompile for the rounds of the BLAKE3 compression function.
+    Blake3CompressInner(Blake3CompressInnerChip),
     /// A precompile for addition on the Elliptic curve bn254.
     Bn254Add(WeierstrassAddAssignChip<SwCurve<Bn254Parameters>>),
     /// A precompile for doubling a point on the Elliptic curve bn254.
@@ -151,18 +164,24 @@ pub enum MipsAir<F: PrimeField32> {
     Uint256Mul(Uint256MulChip),
     /// A precompile for u256x2048 mul.
     U256x2048Mul(U256x2048MulChip),
+    /// A precompile for the Poseidon2 permutation.
+    Poseidon2Permute(Poseidon2PermuteChip),
     /// A precompile for decompressing a point on the BLS12-381 curve.
     Bls12381Decompress(WeierstrassDecompressChip<SwCurve<Bls12381Parameters>>),
     /// A precompile for BLS12-381 fp operation.
     Bls12381Fp(FpOpChip<Bls12381BaseField>),
     /// A precompile for BLS12-381 fp2 multiplication.
     Bls12381Fp2Mul(Fp2MulAssignChip<Bls12381BaseField>),
+    /// A precompile for BLS12-381 fp6 multiplication.
+    Bls12381Fp6Mul(Fp6MulAssignChip<Bls12381BaseField>),
     /// A precompile for BLS12-381 fp2 addition/subtraction.
     Bls12381Fp2AddSub(Fp2AddSubAssignChip<Bls12381BaseField>),
     /// A precompile for BN-254 fp operation.
     Bn254Fp(FpOpChip<Bn254BaseField>),
     /// A precompile for BN-254 fp2 multiplication.
     Bn254Fp2Mul(Fp2MulAssignChip<Bn254BaseField>),
+    /// A precompile for BN-254 fp6 multiplication.
+    Bn254Fp6Mul(Fp6MulAssignChip<Bn254BaseField>),
     /// A precompile for BN-254 fp2 addition/subtraction.
     Bn254Fp2AddSub(Fp2AddSubAssignChip<Bn254BaseField>),
 }
@@ -266,6 +285,11 @@ impl<F: PrimeField32> MipsAir<F> {
         costs.insert(keccak_sponge.name(), 24 * keccak_sponge.cost());
         chips.push(keccak_sponge);
 
+        let blake3_compress_inner =
+            Chip::new(MipsAir::Blake3CompressInner(Blake3CompressInnerChip::new()));
+        costs.insert(blake3_compress_inner.name(), 56 * blake3_compress_inner.cost());
+        chips.push(blake3_compress_inner);
+
         let bn254_add_assign = Chip::new(MipsAir::Bn254Add(WeierstrassAddAssignChip::<
             SwCurve<Bn254Parameters>,
         >::new()));
@@ -298,6 +322,10 @@ impl<F: PrimeField32> MipsAir<F> {
         costs.insert(u256x2048_mul.name(), u256x2048_mul.cost());
         chips.push(u256x2048_mul);
 
+        let poseidon2_permute = Chip::new(MipsAir::Poseidon2Permute(Poseidon2PermuteChip::new()));
+        costs.insert(poseidon2_permute.name(), poseidon2_permute.cost());
+        chips.push(poseidon2_permute);
+
         let bls12381_fp = Chip::new(MipsAir::Bls12381Fp(FpOpChip::<Bls12381BaseField>::new()));
         costs.insert(bls12381_fp.name(), bls12381_fp.cost());
         chips.push(bls12381_fp);
@@ -312,6 +340,11 @@ impl<F: PrimeField32> MipsAir<F> {
         costs.insert(bls12381_fp2_mul.name(), bls12381_fp2_mul.cost());
         chips.push(bls12381_fp2_mul);
 
+        let bls12381_fp6_mul =
+            Chip::new(MipsAir::Bls12381Fp6Mul(Fp6MulAssignChip::<Bls12381BaseField>::new()));
+        costs.insert(bls12381_fp6_mul.name(), bls12381_fp6_mul.cost());
+        chips.push(bls12381_fp6_mul);
+
         let bn254_fp = Chip::new(MipsAir::Bn254Fp(FpOpChip::<Bn254BaseField>::new()));
         costs.insert(bn254_fp.name(), bn254_fp.cost());
         chips.push(bn254_fp);
@@ -326,6 +359,11 @@ impl<F: PrimeField32> MipsAir<F> {
         costs.insert(bn254_fp2_mul.name(), bn254_fp2_mul.cost());
         chips.push(bn254_fp2_mul);
 
+        let bn254_fp6_mul =
+            Chip::new(MipsAir::Bn254Fp6Mul(Fp6MulAssignChip::<Bn254BaseField>::new()));
+        costs.insert(bn254_fp6_mul.name(), bn254_fp6_mul.cost());
+        chips.push(bn254_fp6_mul);
+
         let bls12381_decompress =
             Chip::new(MipsAir::Bls12381Decompress(WeierstrassDecompressChip::<
                 SwCurve<Bls12381Parameters>,
@@ -394,6 +432,18 @@ impl<F: PrimeField32> MipsAir<F> {
         costs.insert(misc_instrs.name(), misc_instrs.cost());
         chips.push(misc_instrs);
 
+        let fpu_arith = Chip::new(MipsAir::FpuArith(FpuArithChip::default()));
+        costs.insert(fpu_arith.name(), fpu_arith.cost());
+        chips.push(fpu_arith);
+
+        let fpu_compare = Chip::new(MipsAir::FpuCompare(FpuCompareChip::default()));
+        costs.insert(fpu_compare.name(), fpu_compare.cost());
+        chips.push(fpu_compare);
+
+        let fpu_move = Chip::new(MipsAir::FpuMove(FpuMoveChip::default()));
+        costs.insert(fpu_move.name(), fpu_move.cost());
+        chips.push(fpu_move);
+
         let memory_global_init =
             Chip::new(MipsAir::MemoryGlobalInit(MemoryGlobalChip::new(MemoryChipType::Initialize)));
         costs.insert(memory_global_init.name(), memory_global_init.cost());
@@ -431,6 +481,9 @@ impl<F: PrimeField32> MipsAir<F> {
             (MipsAirId::Branch, record.branch_events.len()),
             (MipsAirId::Jump, record.jump_events.len()),
             (MipsAirId::MiscInstrs, record.misc_events.len()),
+            (MipsAirId::FpuArith, record.fpu_arith_events.len()),
+            (MipsAirId::FpuCompare, record.fpu_compare_events.len()),
+            (MipsAirId::FpuMove, record.fpu_move_events.len()),
             (MipsAirId::MemoryInstrs, record.memory_instr_events.len()),
             (MipsAirId::SyscallInstrs, record.syscall_events.len()),
             (MipsAirId::DivRem, record.divrem_events.len()),
@@ -503,6 +556,9 @@ impl<F: PrimeField32> MipsAir<F> {
             MipsAir::SyscallInstrs(SyscallInstrsChip::default()),
             MipsAir::MemoryInstrs(MemoryInstructionsChip::default()),
             MipsAir::MiscInstrs(MiscInstrsChip::default()),
+            MipsAir::FpuArith(FpuArithChip::default()),
+            MipsAir::FpuCompare(FpuCompareChip::default()),
+            MipsAir::FpuMove(FpuMoveChip::default()),
             MipsAir::MemoryLocal(MemoryLocalChip::new()),
             MipsAir::Global(GlobalChip),
             MipsAir::SyscallCore(SyscallChip::core()),
@@ -555,6 +611,7 @@ impl<F: PrimeField32> MipsAir<F> {
         match self {
             Self::Sha256Compress(_) => 80,
             Self::Sha256Extend(_) => 48,
+            Self::Blake3CompressInner(_) => 56,
             Self::KeccakSponge(_) => {
                 if let Some(record) = record {
                     self.keccak_rows_per_event(record)
@@ -610,6 +667,7 @@ impl<F: PrimeField32> MipsAir<F> {
             Self::Bn254Fp(_) => SyscallCode::BN254_FP_ADD,
             Self::Bn254Fp2AddSub(_) => SyscallCode::BN254_FP2_ADD,
             Self::Bn254Fp2Mul(_) => SyscallCode::BN254_FP2_MUL,
+            Self::Bn254Fp6Mul(_) => SyscallCode::BN254_FP6_MUL,
             Self::Ed25519Add(_) => SyscallCode::ED_ADD,
             Self::Ed25519Decompress(_) => SyscallCode::ED_DECOMPRESS,
             Self::Secp256k1Add(_) => SyscallCode::SECP256K1_ADD,
@@ -620,14 +678,17 @@ impl<F: PrimeField32> MipsAir<F> {
             Self::Sha256Extend(_) => SyscallCode::SHA_EXTEND,
             Self::Uint256Mul(_) => SyscallCode::UINT256_MUL,
             Self::U256x2048Mul(_) => SyscallCode::U256XU2048_MUL,
+            Self::Poseidon2Permute(_) => SyscallCode::POSEIDON2_PERMUTE,
             Self::Bls12381Decompress(_) => SyscallCode::BLS12381_DECOMPRESS,
             Self::K256Decompress(_) => SyscallCode::SECP256K1_DECOMPRESS,
             Self::P256Decompress(_) => SyscallCode::SECP256R1_DECOMPRESS,
             Self::Bls12381Double(_) => SyscallCode::BLS12381_DOUBLE,
             Self::Bls12381Fp(_) => SyscallCode::BLS12381_FP_ADD,
             Self::Bls12381Fp2Mul(_) => SyscallCode::BLS12381_FP2_MUL,
+            Self::Bls12381Fp6Mul(_) => SyscallCode::BLS12381_FP6_MUL,
             Self::Bls12381Fp2AddSub(_) => SyscallCode::BLS12381_FP2_ADD,
             Self::KeccakSponge(_) => SyscallCode::KECCAK_SPONGE,
+            Self::Blake3CompressInner(_) => SyscallCode::BLAKE3_COMPRESS_INNER,
             Self::Add(_) => unreachable!("Invalid for core chip"),
             Self::Bitwise(_) => unreachable!("Invalid for core chip"),
             Self::DivRem(_) => unreachable!("Invalid for core chip"),
@@ -651,6 +712,9 @@ impl<F: PrimeField32> MipsAir<F> {
             Self::SyscallInstrs(_) => unreachable!("Invalid for core chip"),
             Self::MemoryInstrs(_) => unreachable!("Invalid for core chip"),
             Self::MiscInstrs(_) => unreachable!("Invalid for core chip"),
+            Self::FpuArith(_) => unreachable!("Invalid for core chip"),
+            Self::FpuCompare(_) => unreachable!("Invalid for core chip"),
+            Self::FpuMove(_) => unreachable!("Invalid for core chip"),
         }
     }
 }
@@ -687,7 +751,10 @@ pub mod tests {
         io::ZKMStdin,
         mips::MipsAir,
         utils,
-        utils::{prove, run_test, setup_logger},
+        utils::{
+            prove, prove_with_checkpoints, run_test, setup_logger, CheckpointOpts, ProverMonitor,
+            ProvingCheckpoint,
+        },
     };
 
     use hashbrown::HashMap;
@@ -695,11 +762,11 @@ pub mod tests {
     use p3_koala_bear::KoalaBear;
     use strum::IntoEnumIterator;
 
-    use zkm_core_executor::{Instruction, MipsAirId, Opcode, Program};
+    use zkm_core_executor::{Instruction, MipsAirId, Opcode, Program, ZKMContext};
     use zkm_stark::air::MachineAir;
     use zkm_stark::{
-        koala_bear_poseidon2::KoalaBearPoseidon2, CpuProver, StarkProvingKey, StarkVerifyingKey,
-        ZKMCoreOpts,
+        koala_bear_poseidon2::KoalaBearPoseidon2, CpuProver, MachineProver, StarkGenericConfig,
+        StarkProvingKey, StarkVerifyingKey, ZKMCoreOpts,
     };
 
     #[test]
@@ -806,6 +873,24 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_branch_link_prove() {
+        utils::setup_logger();
+        let branch_ops = [Opcode::BGEZAL, Opcode::BLTZAL];
+        let operands = [0, 1, 0xFFFF_FFFF];
+        for branch_op in branch_ops.iter() {
+            for operand in operands.iter() {
+                let instructions = vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *operand, false, true),
+                    Instruction::new(*branch_op, 31, 29, 100, false, true),
+                    Instruction::new(Opcode::ADD, 30, 31, 4, false, true),
+                ];
+                let program = Program::new(instructions, 0, 0);
+                run_test::<CpuProver<_, _>>(program).unwrap();
+            }
+        }
+    }
+
     #[test]
     fn test_shift_prove() {
         utils::setup_logger();
@@ -1081,6 +1166,54 @@ pub mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_fibonacci_prove_resume() {
+        setup_logger();
+
+        let program = fibonacci_program();
+        let stdin = ZKMStdin::new();
+        let mut opts = ZKMCoreOpts::default();
+        opts.shard_size = 1024;
+        opts.shard_batch_size = 2;
+        let prover = CpuProver::new(MipsAir::machine(KoalaBearPoseidon2::new()));
+        let (pk, vk) = prover.setup(&program);
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoint_opts = CheckpointOpts::new(dir.path(), 1);
+
+        let prove = |checkpoint_opts, resume| {
+            prove_with_checkpoints::<_, CpuProver<_, _>>(
+                &prover,
+                &pk,
+                program.clone(),
+                &stdin,
+                opts,
+                ZKMContext::default(),
+                None,
+                checkpoint_opts,
+                resume,
+                &ProverMonitor::default(),
+            )
+            .unwrap()
+        };
+        let (proof, public_values, _) = prove(Some(&checkpoint_opts), None);
+
+        // Resume from the last checkpoint, which has the most shards proven before it.
+        let path = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .max_by_key(|path| ProvingCheckpoint::load(path).unwrap().header.batch_index)
+            .expect("no checkpoint was written");
+        let checkpoint = ProvingCheckpoint::load(path).unwrap();
+        assert!(!checkpoint.shard_proofs::<KoalaBearPoseidon2>().unwrap().is_empty());
+        let (resumed, resumed_public_values, _) = prove(None, Some(checkpoint));
+
+        // The resumed proof covers the whole execution.
+        assert_eq!(resumed_public_values, public_values);
+        assert_eq!(resumed.shard_proofs.len(), proof.shard_proofs.len());
+        let mut challenger = prover.config().challenger();
+        prover.machine().verify(&vk, &resumed, &mut challenger).unwrap();
+    }
+
     #[test]
     fn test_fibonacci_prove_batch() {
         setup_logger();
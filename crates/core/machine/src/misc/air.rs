@@ -35,9 +35,23 @@ where
             + local.is_ext * Opcode::EXT.as_field::<AB::F>()
             + local.is_maddu * Opcode::MADDU.as_field::<AB::F>()
             + local.is_msubu * Opcode::MSUBU.as_field::<AB::F>()
+            + local.is_madd * Opcode::MADD.as_field::<AB::F>()
+            + local.is_msub * Opcode::MSUB.as_field::<AB::F>()
             + local.is_meq * Opcode::MEQ.as_field::<AB::F>()
             + local.is_mne * Opcode::MNE.as_field::<AB::F>()
-            + local.is_teq * Opcode::TEQ.as_field::<AB::F>();
+            + local.is_teq * Opcode::TEQ.as_field::<AB::F>()
+            + local.is_tne * Opcode::TNE.as_field::<AB::F>()
+            + local.is_tge * Opcode::TGE.as_field::<AB::F>()
+            + local.is_tgeu * Opcode::TGEU.as_field::<AB::F>()
+            + local.is_tlt * Opcode::TLT.as_field::<AB::F>()
+            + local.is_tltu * Opcode::TLTU.as_field::<AB::F>();
+
+        let is_trap = local.is_teq
+            + local.is_tne
+            + local.is_tge
+            + local.is_tgeu
+            + local.is_tlt
+            + local.is_tltu;
 
         let is_real = local.is_wsbh
             + local.is_sext
@@ -45,9 +59,11 @@ where
             + local.is_ext
             + local.is_maddu
             + local.is_msubu
+            + local.is_madd
+            + local.is_msub
             + local.is_meq
             + local.is_mne
-            + local.is_teq;
+            + is_trap.clone();
 
         builder.assert_bool(local.is_wsbh);
         builder.assert_bool(local.is_sext);
@@ -55,12 +71,25 @@ where
         builder.assert_bool(local.is_ext);
         builder.assert_bool(local.is_maddu);
         builder.assert_bool(local.is_msubu);
+        builder.assert_bool(local.is_madd);
+        builder.assert_bool(local.is_msub);
         builder.assert_bool(local.is_meq);
         builder.assert_bool(local.is_mne);
         builder.assert_bool(local.is_teq);
+        builder.assert_bool(local.is_tne);
+        builder.assert_bool(local.is_tge);
+        builder.assert_bool(local.is_tgeu);
+        builder.assert_bool(local.is_tlt);
+        builder.assert_bool(local.is_tltu);
         builder.assert_bool(is_real.clone());
 
-        let is_rw_a = local.is_maddu + local.is_msubu + local.is_ins + local.is_mne + local.is_meq;
+        let is_rw_a = local.is_maddu
+            + local.is_msubu
+            + local.is_madd
+            + local.is_msub
+            + local.is_ins
+            + local.is_mne
+            + local.is_meq;
         builder.receive_instruction(
             local.shard,
             local.clk,
@@ -92,19 +121,20 @@ where
             local.op_b_value,
             local.op_c_value,
             local.prev_a_value,
-            AB::Expr::ZERO,
+            is_trap.clone(),
             AB::Expr::ZERO,
             AB::Expr::ZERO,
             AB::Expr::ZERO,
             AB::Expr::ZERO,
             AB::Expr::ONE,
-            local.is_wsbh + local.is_sext + local.is_teq + local.is_ext,
+            local.is_wsbh + local.is_sext + local.is_ext + is_trap,
         );
 
         self.eval_wsbh(builder, local);
         self.eval_ext(builder, local);
         self.eval_ins(builder, local);
         self.eval_movcond(builder, local);
+        self.eval_trap(builder, local);
         self.eval_maddsub(builder, local);
         self.eval_sext(builder, local);
 
@@ -179,10 +209,15 @@ impl MiscInstrsChip {
         local: &MiscInstrColumns<AB::Var>,
     ) {
         let maddsub_cols = local.misc_specific_columns.maddsub();
-        let is_real = local.is_maddu + local.is_msubu;
+        let is_add = local.is_maddu + local.is_madd;
+        let is_sub = local.is_msubu + local.is_msub;
+        let is_real = is_add.clone() + is_sub.clone();
 
+        // maddu/msubu multiply unsigned, madd/msub multiply signed.
+        let mul_opcode = (local.is_maddu + local.is_msubu) * Opcode::MULTU.as_field::<AB::F>()
+            + (local.is_madd + local.is_msub) * Opcode::MULT.as_field::<AB::F>();
         builder.send_alu_with_hi(
-            Opcode::MULTU.as_field::<AB::F>(),
+            mul_opcode,
             maddsub_cols.mul_lo,
             local.op_b_value,
             local.op_c_value,
@@ -193,12 +228,12 @@ impl MiscInstrsChip {
         for i in 0..WORD_SIZE {
             builder.when(is_real.clone()).assert_eq(
                 maddsub_cols.src2_hi[i],
-                maddsub_cols.op_hi_access.prev_value[i] * local.is_maddu
-                    + (*maddsub_cols.op_hi_access.value())[i] * local.is_msubu,
+                maddsub_cols.op_hi_access.prev_value[i] * is_add.clone()
+                    + (*maddsub_cols.op_hi_access.value())[i] * is_sub.clone(),
             );
             builder.when(is_real.clone()).assert_eq(
                 maddsub_cols.src2_lo[i],
-                local.prev_a_value[i] * local.is_maddu + local.op_a_value[i] * local.is_msubu,
+                local.prev_a_value[i] * is_add.clone() + local.op_a_value[i] * is_sub.clone(),
             );
         }
 
@@ -213,19 +248,19 @@ impl MiscInstrsChip {
         );
 
         builder
-            .when(local.is_maddu)
+            .when(is_add.clone())
             .assert_word_eq(local.op_a_value, maddsub_cols.add_operation.value);
 
-        builder.when(local.is_maddu).assert_word_eq(
+        builder.when(is_add).assert_word_eq(
             *maddsub_cols.op_hi_access.value(),
             maddsub_cols.add_operation.value_hi,
         );
 
         builder
-            .when(local.is_msubu)
+            .when(is_sub.clone())
             .assert_word_eq(local.prev_a_value, maddsub_cols.add_operation.value);
 
-        builder.when(local.is_msubu).assert_word_eq(
+        builder.when(is_sub).assert_word_eq(
             maddsub_cols.op_hi_access.prev_value,
             maddsub_cols.add_operation.value_hi,
         );
@@ -245,7 +280,7 @@ impl MiscInstrsChip {
         local: &MiscInstrColumns<AB::Var>,
     ) {
         let cond_cols = local.misc_specific_columns.movcond();
-        let is_real = local.is_meq + local.is_mne + local.is_teq;
+        let is_real = local.is_meq + local.is_mne;
 
         builder
             .when(is_real.clone() * cond_cols.a_eq_b)
@@ -253,9 +288,6 @@ impl MiscInstrsChip {
 
         builder.when(is_real.clone() * cond_cols.c_eq_0).assert_word_zero(local.op_c_value);
 
-        // For teq, a cannot equal b, otherwise trap will be triggered.
-        builder.when(local.is_teq).assert_zero(cond_cols.a_eq_b);
-
         // Constraints for condition move result:
         // op_a = op_b, when condition is true.
         // Otherwise, op_a remains unchanged.
@@ -282,6 +314,42 @@ impl MiscInstrsChip {
         }
     }
 
+    pub(crate) fn eval_trap<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &MiscInstrColumns<AB::Var>,
+    ) {
+        let trap_cols = local.misc_specific_columns.trap();
+
+        // A trap row shows that its trap condition is false:
+        //    teq:       a < b or b < a (unsigned)
+        //    tne:       a == b
+        //    tge/tgeu:  a < b
+        //    tlt/tltu:  !(a < b)
+        let is_signed = local.is_tge + local.is_tlt;
+        let lt_opcode = is_signed.clone() * Opcode::SLT.as_field::<AB::F>()
+            + (AB::Expr::ONE - is_signed) * Opcode::SLTU.as_field::<AB::F>();
+        builder.send_alu(
+            lt_opcode,
+            Word::extend_var::<AB>(trap_cols.a_lt_b),
+            local.op_a_value,
+            local.op_b_value,
+            local.is_teq + local.is_tge + local.is_tgeu + local.is_tlt + local.is_tltu,
+        );
+        builder.send_alu(
+            Opcode::SLTU.as_field::<AB::F>(),
+            Word::extend_var::<AB>(trap_cols.a_gt_b),
+            local.op_b_value,
+            local.op_a_value,
+            local.is_teq,
+        );
+
+        builder.when(local.is_teq).assert_one(trap_cols.a_lt_b + trap_cols.a_gt_b);
+        builder.when(local.is_tne).assert_word_eq(local.op_a_value, local.op_b_value);
+        builder.when(local.is_tge + local.is_tgeu).assert_one(trap_cols.a_lt_b);
+        builder.when(local.is_tlt + local.is_tltu).assert_zero(trap_cols.a_lt_b);
+    }
+
     pub(crate) fn eval_ins<AB: ZKMAirBuilder>(
         &self,
         builder: &mut AB,
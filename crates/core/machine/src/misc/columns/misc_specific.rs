@@ -1,4 +1,4 @@
-use crate::misc::columns::{ExtCols, InsCols, MaddsubCols, MovcondCols, SextCols};
+use crate::misc::columns::{ExtCols, InsCols, MaddsubCols, MovcondCols, SextCols, TrapCols};
 use std::{
     fmt::{Debug, Formatter},
     mem::{size_of, transmute},
@@ -17,6 +17,7 @@ pub union MiscSpecificCols<T: Copy> {
     sext: SextCols<T>,
     ext: ExtCols<T>,
     ins: InsCols<T>,
+    trap: TrapCols<T>,
 }
 
 impl<T: Copy + Default> Default for MiscSpecificCols<T> {
@@ -68,4 +69,10 @@ impl<T: Copy> MiscSpecificCols<T> {
     pub fn ins_mut(&mut self) -> &mut InsCols<T> {
         unsafe { &mut self.ins }
     }
+    pub fn trap(&self) -> &TrapCols<T> {
+        unsafe { &self.trap }
+    }
+    pub fn trap_mut(&mut self) -> &mut TrapCols<T> {
+        unsafe { &mut self.trap }
+    }
 }
@@ -4,6 +4,7 @@ mod maddsub;
 mod misc_specific;
 mod movcond;
 mod sext;
+mod trap;
 
 pub use ext::*;
 pub use ins::*;
@@ -11,6 +12,7 @@ pub use maddsub::*;
 pub use misc_specific::*;
 pub use movcond::*;
 pub use sext::*;
+pub use trap::*;
 
 use std::mem::size_of;
 use zkm_derive::AlignedBorrow;
@@ -47,7 +49,14 @@ pub struct MiscInstrColumns<T: Copy> {
     pub is_ext: T,
     pub is_maddu: T,
     pub is_msubu: T,
+    pub is_madd: T,
+    pub is_msub: T,
     pub is_meq: T,
     pub is_mne: T,
     pub is_teq: T,
+    pub is_tne: T,
+    pub is_tge: T,
+    pub is_tgeu: T,
+    pub is_tlt: T,
+    pub is_tltu: T,
 }
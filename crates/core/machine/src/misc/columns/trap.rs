@@ -0,0 +1,14 @@
+use std::mem::size_of;
+use zkm_derive::AlignedBorrow;
+
+pub const NUM_TRAP_COLS: usize = size_of::<TrapCols<u8>>();
+
+/// The column layout for traps.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct TrapCols<T> {
+    /// Whether a is less than b, signed for tge/tlt and unsigned otherwise.
+    pub a_lt_b: T,
+    /// Whether a is greater than b, unsigned. Only used by teq.
+    pub a_gt_b: T,
+}
@@ -59,6 +59,24 @@ mod tests {
             Instruction::new(Opcode::TEQ, 28, 29, 0, false, true),
             Instruction::new(Opcode::TEQ, 28, 0, 0, false, true),
             Instruction::new(Opcode::TEQ, 0, 28, 0, false, true),
+            Instruction::new(Opcode::MADD, 32, 31, 31, false, false),
+            Instruction::new(Opcode::MADD, 32, 29, 31, false, false),
+            Instruction::new(Opcode::MADD, 32, 29, 0, false, false),
+            Instruction::new(Opcode::MSUB, 32, 31, 31, false, false),
+            Instruction::new(Opcode::MSUB, 32, 29, 31, false, false),
+            Instruction::new(Opcode::MSUB, 32, 29, 0, false, false),
+            Instruction::new(Opcode::ADD, 27, 0, 0xffff_ff00, false, true),
+            Instruction::new(Opcode::TEQ, 27, 5, 0, true, true),
+            Instruction::new(Opcode::TNE, 28, 28, 0, false, true),
+            Instruction::new(Opcode::TNE, 0, 0, 0, true, true),
+            Instruction::new(Opcode::TGE, 29, 28, 0, false, true),
+            Instruction::new(Opcode::TGE, 27, 1, 0, true, true),
+            Instruction::new(Opcode::TGEU, 29, 28, 0, false, true),
+            Instruction::new(Opcode::TGEU, 1, 27, 0, false, true),
+            Instruction::new(Opcode::TLT, 28, 29, 0, false, true),
+            Instruction::new(Opcode::TLT, 29, 0xffff_ffff, 0, true, true),
+            Instruction::new(Opcode::TLTU, 28, 29, 0, false, true),
+            Instruction::new(Opcode::TLTU, 27, 1, 0, true, true),
         ];
         let program = Program::new(instructions, 0, 0);
         run_test::<CpuProver<_, _>>(program).unwrap();
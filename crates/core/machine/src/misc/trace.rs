@@ -95,12 +95,20 @@ impl MiscInstrsChip {
         cols.is_ins = F::from_bool(matches!(event.opcode, Opcode::INS));
         cols.is_maddu = F::from_bool(matches!(event.opcode, Opcode::MADDU));
         cols.is_msubu = F::from_bool(matches!(event.opcode, Opcode::MSUBU));
+        cols.is_madd = F::from_bool(matches!(event.opcode, Opcode::MADD));
+        cols.is_msub = F::from_bool(matches!(event.opcode, Opcode::MSUB));
         cols.is_meq = F::from_bool(matches!(event.opcode, Opcode::MEQ));
         cols.is_mne = F::from_bool(matches!(event.opcode, Opcode::MNE));
         cols.is_teq = F::from_bool(matches!(event.opcode, Opcode::TEQ));
+        cols.is_tne = F::from_bool(matches!(event.opcode, Opcode::TNE));
+        cols.is_tge = F::from_bool(matches!(event.opcode, Opcode::TGE));
+        cols.is_tgeu = F::from_bool(matches!(event.opcode, Opcode::TGEU));
+        cols.is_tlt = F::from_bool(matches!(event.opcode, Opcode::TLT));
+        cols.is_tltu = F::from_bool(matches!(event.opcode, Opcode::TLTU));
 
         self.populate_sext(cols, event, blu);
         self.populate_movcond(cols, event, blu);
+        self.populate_trap(cols, event, blu);
         self.populate_maddsub(cols, event, blu);
         self.populate_ext(cols, event, blu);
         self.populate_ins(cols, event, blu);
@@ -141,7 +149,7 @@ impl MiscInstrsChip {
         event: &MiscEvent,
         _blu: &mut impl ByteRecord,
     ) {
-        if !matches!(event.opcode, Opcode::MNE | Opcode::MEQ | Opcode::TEQ) {
+        if !matches!(event.opcode, Opcode::MNE | Opcode::MEQ) {
             return;
         }
         let movcond_cols = cols.misc_specific_columns.movcond_mut();
@@ -149,23 +157,44 @@ impl MiscInstrsChip {
         movcond_cols.c_eq_0 = F::from_bool(event.c == 0);
     }
 
+    fn populate_trap<F: PrimeField32>(
+        &self,
+        cols: &mut MiscInstrColumns<F>,
+        event: &MiscEvent,
+        _blu: &mut impl ByteRecord,
+    ) {
+        let a_lt_b = match event.opcode {
+            Opcode::TGE | Opcode::TLT => (event.a as i32) < (event.b as i32),
+            Opcode::TEQ | Opcode::TNE | Opcode::TGEU | Opcode::TLTU => event.a < event.b,
+            _ => return,
+        };
+        let trap_cols = cols.misc_specific_columns.trap_mut();
+        trap_cols.a_lt_b = F::from_bool(a_lt_b);
+        trap_cols.a_gt_b = F::from_bool(event.a > event.b);
+    }
+
     fn populate_maddsub<F: PrimeField32>(
         &self,
         cols: &mut MiscInstrColumns<F>,
         event: &MiscEvent,
         blu: &mut impl ByteRecord,
     ) {
-        if !matches!(event.opcode, Opcode::MADDU | Opcode::MSUBU) {
+        if !matches!(event.opcode, Opcode::MADDU | Opcode::MSUBU | Opcode::MADD | Opcode::MSUB) {
             return;
         }
         let maddsub_cols = cols.misc_specific_columns.maddsub_mut();
-        let multiply = event.b as u64 * event.c as u64;
+        let multiply = match event.opcode {
+            Opcode::MADD | Opcode::MSUB => {
+                ((event.b as i32) as i64 * (event.c as i32) as i64) as u64
+            }
+            _ => event.b as u64 * event.c as u64,
+        };
         let mul_hi = (multiply >> 32) as u32;
         let mul_lo = multiply as u32;
         maddsub_cols.mul_hi = Word::from(mul_hi);
         maddsub_cols.mul_lo = Word::from(mul_lo);
 
-        let is_add = event.opcode == Opcode::MADDU;
+        let is_add = matches!(event.opcode, Opcode::MADDU | Opcode::MADD);
         let src2_lo = if is_add { event.prev_a } else { event.a };
         let src2_hi = if is_add { event.hi_record.prev_value } else { event.hi_record.value };
         let _ = maddsub_cols.add_operation.populate(
@@ -176,8 +205,8 @@ impl MiscInstrsChip {
         maddsub_cols.src2_lo = Word::from(src2_lo);
         maddsub_cols.src2_hi = Word::from(src2_hi);
 
-        // For maddu/msubu instructions, pass in a dummy byte lookup vector.
-        // This maddu/msubu instruction chip also has a op_hi_access field that will be
+        // For maddu/msubu/madd/msub instructions, pass in a dummy byte lookup vector.
+        // This maddsub instruction chip also has a op_hi_access field that will be
         // populated and that will contribute to the byte lookup dependencies.
         maddsub_cols.op_hi_access.populate(MemoryRecordEnum::Write(event.hi_record), blu);
     }
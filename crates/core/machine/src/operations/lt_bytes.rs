@@ -0,0 +1,132 @@
+use itertools::izip;
+
+use p3_air::AirBuilder;
+use p3_field::{FieldAlgebra, PrimeField32};
+
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord},
+    ByteOpcode,
+};
+use zkm_derive::AlignedBorrow;
+use zkm_stark::air::{BaseAirBuilder, ZKMAirBuilder};
+
+/// Operation columns for computing whether a little-endian byte array is smaller than another.
+///
+/// Unlike [`super::AssertLtColsBytes`], the comparison may go either way and the result is a
+/// boolean column.
+#[derive(Debug, Clone, Copy, AlignedBorrow)]
+#[repr(C)]
+pub struct LtBytesOperation<T, const N: usize> {
+    /// Boolean flags to indicate the most significant byte in which the inputs differ. All flags
+    /// are zero if the inputs are equal.
+    pub byte_flags: [T; N],
+
+    pub a_comparison_byte: T,
+    pub b_comparison_byte: T,
+
+    /// Whether `a < b`.
+    pub result: T,
+}
+
+impl<F: Default + Copy, const N: usize> Default for LtBytesOperation<F, N> {
+    fn default() -> Self {
+        Self {
+            byte_flags: [F::default(); N],
+            a_comparison_byte: F::default(),
+            b_comparison_byte: F::default(),
+            result: F::default(),
+        }
+    }
+}
+
+impl<F: PrimeField32, const N: usize> LtBytesOperation<F, N> {
+    pub fn populate(&mut self, record: &mut impl ByteRecord, a: &[u8], b: &[u8]) -> bool {
+        *self = Self::default();
+        for (a_byte, b_byte, flag) in
+            izip!(a.iter().rev(), b.iter().rev(), self.byte_flags.iter_mut().rev())
+        {
+            if a_byte != b_byte {
+                let result = a_byte < b_byte;
+                *flag = F::ONE;
+                self.a_comparison_byte = F::from_canonical_u8(*a_byte);
+                self.b_comparison_byte = F::from_canonical_u8(*b_byte);
+                self.result = F::from_bool(result);
+                record.add_byte_lookup_event(ByteLookupEvent::new(
+                    ByteOpcode::LTU,
+                    result as u16,
+                    0,
+                    *a_byte,
+                    *b_byte,
+                ));
+                record.add_byte_lookup_event(ByteLookupEvent::new(
+                    ByteOpcode::LTU,
+                    !result as u16,
+                    0,
+                    *b_byte,
+                    *a_byte,
+                ));
+                return result;
+            }
+        }
+        false
+    }
+}
+
+impl<V: Copy, const N: usize> LtBytesOperation<V, N> {
+    /// Evaluate the comparison of `a` and `b`, whose bytes may be expressions of degree at most 2.
+    ///
+    /// The bytes of `a` and `b` are not range checked.
+    pub fn eval<AB: ZKMAirBuilder<Var = V>>(
+        &self,
+        builder: &mut AB,
+        a: &[AB::Expr],
+        b: &[AB::Expr],
+        is_real: impl Into<AB::Expr>,
+    ) where
+        V: Into<AB::Expr>,
+    {
+        let is_real: AB::Expr = is_real.into();
+
+        // At most one flag is set, and only on real rows.
+        let mut sum_flags = AB::Expr::ZERO;
+        for &flag in self.byte_flags.iter() {
+            builder.assert_bool(flag);
+            sum_flags = sum_flags.clone() + flag.into();
+        }
+        builder.assert_bool(sum_flags.clone());
+        builder.when_not(is_real.clone()).assert_zero(sum_flags.clone());
+
+        // All bytes above the flagged one are equal, and so are all bytes if no flag is set.
+        let mut is_inequality_visited = AB::Expr::ZERO;
+        for (a_byte, b_byte, &flag) in
+            izip!(a.iter().rev(), b.iter().rev(), self.byte_flags.iter().rev())
+        {
+            is_inequality_visited = is_inequality_visited.clone() + flag.into();
+            builder
+                .when(is_real.clone() - is_inequality_visited.clone())
+                .assert_eq(a_byte.clone(), b_byte.clone());
+            builder.when(flag).assert_eq(self.a_comparison_byte, a_byte.clone());
+            builder.when(flag).assert_eq(self.b_comparison_byte, b_byte.clone());
+        }
+
+        // Equal inputs are not smaller.
+        builder.assert_bool(self.result);
+        builder.when_not(sum_flags.clone()).assert_zero(self.result);
+
+        // The flagged bytes differ, and `result` tells which one is smaller.
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            self.result,
+            self.a_comparison_byte,
+            self.b_comparison_byte,
+            sum_flags.clone(),
+        );
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            AB::Expr::ONE - self.result,
+            self.b_comparison_byte,
+            self.a_comparison_byte,
+            sum_flags,
+        );
+    }
+}
@@ -20,6 +20,7 @@ mod is_zero_word;
 mod koala_bear_range;
 mod koala_bear_word;
 mod lt;
+mod lt_bytes;
 mod not;
 mod or;
 pub mod poseidon2;
@@ -40,6 +41,7 @@ pub use is_zero_word::*;
 pub use koala_bear_range::*;
 pub use koala_bear_word::*;
 pub use lt::*;
+pub use lt_bytes::*;
 pub use not::*;
 pub use or::*;
 pub use xor::*;
@@ -579,6 +579,24 @@ fn derive_cluster_from_maximal_shape(shape: &Shape<MipsAirId>) -> ShapeCluster<M
     let misc_log_height = shape.log2_height(&MipsAirId::MiscInstrs);
     maybe_log2_heights.insert(MipsAirId::MiscInstrs, heuristic(misc_log_height, 0));
 
+    // Most programs don't use floating-point instructions, so shards without them must not pay
+    // for padding the floating-point chips.
+    let optional = |log2_heights: Vec<Option<usize>>| {
+        std::iter::once(None)
+            .chain(log2_heights.into_iter().filter(Option::is_some))
+            .collect::<Vec<_>>()
+    };
+
+    let fpu_arith_log_height = shape.log2_height(&MipsAirId::FpuArith);
+    maybe_log2_heights.insert(MipsAirId::FpuArith, optional(heuristic(fpu_arith_log_height, 0)));
+
+    let fpu_compare_log_height = shape.log2_height(&MipsAirId::FpuCompare);
+    maybe_log2_heights
+        .insert(MipsAirId::FpuCompare, optional(heuristic(fpu_compare_log_height, 0)));
+
+    let fpu_move_log_height = shape.log2_height(&MipsAirId::FpuMove);
+    maybe_log2_heights.insert(MipsAirId::FpuMove, optional(heuristic(fpu_move_log_height, 0)));
+
     let syscall_core_log_height = shape.log2_height(&MipsAirId::SyscallCore);
     maybe_log2_heights.insert(MipsAirId::SyscallCore, heuristic(syscall_core_log_height, 0));
 
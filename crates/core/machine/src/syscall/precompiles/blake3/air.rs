@@ -0,0 +1,234 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::FieldAlgebra;
+use p3_matrix::Matrix;
+use zkm_core_executor::{
+    events::{G_INDEX, MSG_SCHEDULE, OPERATION_COUNT, ROUND_COUNT},
+    syscalls::SyscallCode,
+};
+use zkm_stark::air::{LookupScope, ZKMAirBuilder};
+
+use super::{
+    columns::{Blake3CompressInnerCols, NUM_BLAKE3_COMPRESS_INNER_COLS},
+    g::GOperation,
+    Blake3CompressInnerChip,
+};
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::MemoryCols,
+};
+
+impl<F> BaseAir<F> for Blake3CompressInnerChip {
+    fn width(&self) -> usize {
+        NUM_BLAKE3_COMPRESS_INNER_COLS
+    }
+}
+
+impl<AB> Air<AB> for Blake3CompressInnerChip
+where
+    AB: ZKMAirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Blake3CompressInnerCols<AB::Var> = (*local).borrow();
+        let next: &Blake3CompressInnerCols<AB::Var> = (*next).borrow();
+
+        self.eval_control_flow_flags(builder, local, next);
+
+        self.eval_memory(builder, local);
+
+        self.eval_g(builder, local);
+
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::BLAKE3_COMPRESS_INNER.syscall_id()),
+            local.state_ptr,
+            local.message_ptr,
+            local.is_syscall,
+            LookupScope::Local,
+        );
+    }
+}
+
+impl Blake3CompressInnerChip {
+    fn eval_control_flow_flags<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake3CompressInnerCols<AB::Var>,
+        next: &Blake3CompressInnerCols<AB::Var>,
+    ) {
+        // Assert that is_real is a bool.
+        builder.assert_bool(local.is_real);
+
+        // Verify that the selectors are bool, and that exactly one round and one operation is
+        // selected on real rows, and none on padding rows.
+        let mut round_sum = AB::Expr::ZERO;
+        for i in 0..ROUND_COUNT {
+            builder.assert_bool(local.is_round_index_n[i]);
+            round_sum = round_sum.clone() + local.is_round_index_n[i].into();
+        }
+        builder.assert_eq(round_sum, local.is_real);
+
+        let mut operation_sum = AB::Expr::ZERO;
+        for i in 0..OPERATION_COUNT {
+            builder.assert_bool(local.is_operation_index_n[i]);
+            operation_sum = operation_sum.clone() + local.is_operation_index_n[i].into();
+        }
+        builder.assert_eq(operation_sum, local.is_real);
+
+        // The first `G` function of the first round receives the syscall, and the last `G`
+        // function of the last round ends it.
+        builder
+            .assert_eq(local.is_syscall, local.is_round_index_n[0] * local.is_operation_index_n[0]);
+        builder.assert_eq(
+            local.is_last_call,
+            local.is_round_index_n[ROUND_COUNT - 1]
+                * local.is_operation_index_n[OPERATION_COUNT - 1],
+        );
+
+        // The first row of the table must be the start of a syscall if it is real.
+        builder.when_first_row().assert_eq(local.is_syscall, local.is_real);
+
+        // If this row is real and not the last call, then next row should also be real.
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_call)
+            .assert_one(next.is_real);
+
+        // Once the is_real flag is changed to false, it should not be changed back.
+        builder.when_transition().when_not(local.is_real).assert_zero(next.is_real);
+
+        // Whenever the next row is real, the operation index is incremented modulo the number of
+        // operations.
+        for i in 0..OPERATION_COUNT {
+            builder.when_transition().when(next.is_real).assert_eq(
+                local.is_operation_index_n[i],
+                next.is_operation_index_n[(i + 1) % OPERATION_COUNT],
+            );
+        }
+
+        // The round index is incremented after the last operation of a round, and stays the same
+        // otherwise. After the last call, the next row starts a new syscall.
+        let last_operation = local.is_operation_index_n[OPERATION_COUNT - 1];
+        for i in 0..ROUND_COUNT {
+            builder
+                .when_transition()
+                .when(next.is_real)
+                .when_not(last_operation)
+                .assert_eq(local.is_round_index_n[i], next.is_round_index_n[i]);
+        }
+        for i in 0..ROUND_COUNT - 1 {
+            builder
+                .when_transition()
+                .when(next.is_real)
+                .when(last_operation)
+                .assert_eq(local.is_round_index_n[i], next.is_round_index_n[i + 1]);
+        }
+        builder
+            .when_transition()
+            .when(next.is_real)
+            .when(local.is_last_call)
+            .assert_one(next.is_syscall);
+
+        // If this row is real and not the last call, then the next row belongs to the same syscall
+        // and is one clock cycle later.
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_call)
+            .assert_eq(local.shard, next.shard);
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_call)
+            .assert_eq(local.clk + AB::Expr::ONE, next.clk);
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_call)
+            .assert_eq(local.state_ptr, next.state_ptr);
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_call)
+            .assert_eq(local.message_ptr, next.message_ptr);
+
+        // Assert that the table ends in nonreal columns. Since each syscall is 56 rows and the
+        // table is padded to a power of 2, the last row of the table should always be padding.
+        builder.when_last_row().assert_zero(local.is_real);
+    }
+
+    /// Constrains the memory addresses of the state and message words, and that the message is
+    /// read only.
+    fn eval_memory<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake3CompressInnerCols<AB::Var>,
+    ) {
+        // The state indices only depend on the operation.
+        for (i, state_index) in local.state_index.iter().enumerate() {
+            let mut expected = AB::Expr::ZERO;
+            for (operation, g_index) in G_INDEX.iter().enumerate() {
+                expected = expected.clone()
+                    + local.is_operation_index_n[operation]
+                        * AB::F::from_canonical_usize(g_index[i]);
+            }
+            builder.assert_eq(*state_index, expected);
+        }
+
+        // The message indices depend on both the round and the operation.
+        for (i, msg_schedule) in local.msg_schedule.iter().enumerate() {
+            let mut expected = AB::Expr::ZERO;
+            for (round, schedule) in MSG_SCHEDULE.iter().enumerate() {
+                for operation in 0..OPERATION_COUNT {
+                    expected = expected.clone()
+                        + local.is_round_index_n[round]
+                            * local.is_operation_index_n[operation]
+                            * AB::F::from_canonical_usize(schedule[2 * operation + i]);
+                }
+            }
+            builder.assert_eq(*msg_schedule, expected);
+        }
+
+        for (state_index, state_write) in local.state_index.iter().zip(local.state_writes.iter()) {
+            builder.eval_memory_access(
+                local.shard,
+                local.clk,
+                local.state_ptr + *state_index * AB::F::from_canonical_u32(4),
+                state_write,
+                local.is_real,
+            );
+        }
+
+        for (msg_schedule, message_read) in
+            local.msg_schedule.iter().zip(local.message_reads.iter())
+        {
+            builder.eval_memory_access(
+                local.shard,
+                local.clk,
+                local.message_ptr + *msg_schedule * AB::F::from_canonical_u32(4),
+                message_read,
+                local.is_real,
+            );
+        }
+    }
+
+    /// Constrains that the written state words are the output of `G` on the previous ones.
+    fn eval_g<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake3CompressInnerCols<AB::Var>,
+    ) {
+        let input = local.state_writes.map(|state_write| *state_write.prev_value());
+        let msg = local.message_reads.map(|message_read| *message_read.value());
+        let output = GOperation::<AB::F>::eval(builder, input, msg, local.g, local.is_real);
+
+        for (state_write, value) in local.state_writes.iter().zip(output) {
+            builder.when(local.is_real).assert_word_eq(*state_write.value(), value);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::mem::size_of;
+
+use zkm_core_executor::events::{
+    NUM_MSG_WORDS_PER_CALL, NUM_STATE_WORDS_PER_CALL, OPERATION_COUNT, ROUND_COUNT,
+};
+use zkm_derive::AlignedBorrow;
+
+use super::g::GOperation;
+use crate::memory::{MemoryReadCols, MemoryWriteCols};
+
+pub const NUM_BLAKE3_COMPRESS_INNER_COLS: usize = size_of::<Blake3CompressInnerCols<u8>>();
+
+/// A set of columns needed to compute the rounds of the BLAKE3 compression function.
+///
+/// Each compress inner syscall is processed over 56 rows, one for every `G` function of its 7
+/// rounds. Each row reads its two message words, and reads and writes back its four state words.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Blake3CompressInnerCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub clk: T,
+    pub state_ptr: T,
+    pub message_ptr: T,
+
+    /// Selects the round being processed.
+    pub is_round_index_n: [T; ROUND_COUNT],
+
+    /// Selects the `G` function of the round being processed.
+    pub is_operation_index_n: [T; OPERATION_COUNT],
+
+    /// Whether this row is the first of a syscall, i.e. the one receiving it.
+    pub is_syscall: T,
+
+    /// Whether this row is the last of a syscall.
+    pub is_last_call: T,
+
+    /// The indices of the state words this `G` function operates on.
+    pub state_index: [T; NUM_STATE_WORDS_PER_CALL],
+
+    /// The indices of the message words this `G` function mixes in.
+    pub msg_schedule: [T; NUM_MSG_WORDS_PER_CALL],
+
+    /// The state words, read and written back with the output of `G`.
+    pub state_writes: [MemoryWriteCols<T>; NUM_STATE_WORDS_PER_CALL],
+
+    /// The message words.
+    pub message_reads: [MemoryReadCols<T>; NUM_MSG_WORDS_PER_CALL],
+
+    pub g: GOperation<T>,
+
+    pub is_real: T,
+}
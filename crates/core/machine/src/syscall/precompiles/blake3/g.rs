@@ -0,0 +1,142 @@
+use p3_field::Field;
+use zkm_core_executor::events::ByteRecord;
+use zkm_derive::AlignedBorrow;
+use zkm_stark::{air::ZKMAirBuilder, Word};
+
+use crate::operations::{AddOperation, FixedRotateRightOperation, XorOperation};
+
+/// A set of columns needed to compute the BLAKE3 `G` function.
+///
+/// The rotations by 16 and 8 bits are byte permutations, so they are applied directly to the
+/// output of the preceding xor and need no columns of their own.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct GOperation<T> {
+    /// `a + b`.
+    pub a_plus_b: AddOperation<T>,
+    /// `a := a + b + x`.
+    pub a_plus_b_plus_x: AddOperation<T>,
+    /// `d ^ a`, whose right rotation by 16 is the new `d`.
+    pub d_xor_a: XorOperation<T>,
+    /// `c := c + d`.
+    pub c_plus_d: AddOperation<T>,
+    /// `b ^ c`.
+    pub b_xor_c: XorOperation<T>,
+    /// `b := (b ^ c) >>> 12`.
+    pub b_xor_c_rotate_right_12: FixedRotateRightOperation<T>,
+
+    /// `a + b`.
+    pub a_plus_b_2: AddOperation<T>,
+    /// `a := a + b + y`.
+    pub a_plus_b_2_plus_y: AddOperation<T>,
+    /// `d ^ a`, whose right rotation by 8 is the new `d`.
+    pub d_xor_a_2: XorOperation<T>,
+    /// `c := c + d`.
+    pub c_plus_d_2: AddOperation<T>,
+    /// `b ^ c`.
+    pub b_xor_c_2: XorOperation<T>,
+    /// `b := (b ^ c) >>> 7`.
+    pub b_xor_c_2_rotate_right_7: FixedRotateRightOperation<T>,
+}
+
+/// Rotates a word right by 16 bits, which only permutes its bytes.
+fn rotate_right_16<T: Copy>(x: Word<T>) -> Word<T> {
+    Word([x[2], x[3], x[0], x[1]])
+}
+
+/// Rotates a word right by 8 bits, which only permutes its bytes.
+fn rotate_right_8<T: Copy>(x: Word<T>) -> Word<T> {
+    Word([x[1], x[2], x[3], x[0]])
+}
+
+impl<F: Field> GOperation<F> {
+    pub fn populate(
+        &mut self,
+        record: &mut impl ByteRecord,
+        input: [u32; 4],
+        msg: [u32; 2],
+    ) -> [u32; 4] {
+        let [mut a, mut b, mut c, mut d] = input;
+        let [x, y] = msg;
+
+        // First half of the mixing, with the message word `x`.
+        a = self.a_plus_b.populate(record, a, b);
+        a = self.a_plus_b_plus_x.populate(record, a, x);
+        d = self.d_xor_a.populate(record, d, a).rotate_right(16);
+        c = self.c_plus_d.populate(record, c, d);
+        b = self.b_xor_c.populate(record, b, c);
+        b = self.b_xor_c_rotate_right_12.populate(record, b, 12);
+
+        // Second half of the mixing, with the message word `y`.
+        a = self.a_plus_b_2.populate(record, a, b);
+        a = self.a_plus_b_2_plus_y.populate(record, a, y);
+        d = self.d_xor_a_2.populate(record, d, a).rotate_right(8);
+        c = self.c_plus_d_2.populate(record, c, d);
+        b = self.b_xor_c_2.populate(record, b, c);
+        b = self.b_xor_c_2_rotate_right_7.populate(record, b, 7);
+
+        [a, b, c, d]
+    }
+
+    /// Evaluates the `G` function and returns the resulting `[a, b, c, d]` words.
+    pub fn eval<AB: ZKMAirBuilder>(
+        builder: &mut AB,
+        input: [Word<AB::Var>; 4],
+        msg: [Word<AB::Var>; 2],
+        cols: GOperation<AB::Var>,
+        is_real: AB::Var,
+    ) -> [Word<AB::Var>; 4] {
+        let [a, b, c, d] = input;
+        let [x, y] = msg;
+
+        // First half of the mixing, with the message word `x`.
+        AddOperation::<AB::F>::eval(builder, a, b, cols.a_plus_b, is_real.into());
+        AddOperation::<AB::F>::eval(
+            builder,
+            cols.a_plus_b.value,
+            x,
+            cols.a_plus_b_plus_x,
+            is_real.into(),
+        );
+        let a = cols.a_plus_b_plus_x.value;
+        XorOperation::<AB::F>::eval(builder, d, a, cols.d_xor_a, is_real);
+        let d = rotate_right_16(cols.d_xor_a.value);
+        AddOperation::<AB::F>::eval(builder, c, d, cols.c_plus_d, is_real.into());
+        let c = cols.c_plus_d.value;
+        XorOperation::<AB::F>::eval(builder, b, c, cols.b_xor_c, is_real);
+        FixedRotateRightOperation::<AB::F>::eval(
+            builder,
+            cols.b_xor_c.value,
+            12,
+            cols.b_xor_c_rotate_right_12,
+            is_real,
+        );
+        let b = cols.b_xor_c_rotate_right_12.value;
+
+        // Second half of the mixing, with the message word `y`.
+        AddOperation::<AB::F>::eval(builder, a, b, cols.a_plus_b_2, is_real.into());
+        AddOperation::<AB::F>::eval(
+            builder,
+            cols.a_plus_b_2.value,
+            y,
+            cols.a_plus_b_2_plus_y,
+            is_real.into(),
+        );
+        let a = cols.a_plus_b_2_plus_y.value;
+        XorOperation::<AB::F>::eval(builder, d, a, cols.d_xor_a_2, is_real);
+        let d = rotate_right_8(cols.d_xor_a_2.value);
+        AddOperation::<AB::F>::eval(builder, c, d, cols.c_plus_d_2, is_real.into());
+        let c = cols.c_plus_d_2.value;
+        XorOperation::<AB::F>::eval(builder, b, c, cols.b_xor_c_2, is_real);
+        FixedRotateRightOperation::<AB::F>::eval(
+            builder,
+            cols.b_xor_c_2.value,
+            7,
+            cols.b_xor_c_2_rotate_right_7,
+            is_real,
+        );
+        let b = cols.b_xor_c_2_rotate_right_7.value;
+
+        [a, b, c, d]
+    }
+}
@@ -0,0 +1,36 @@
+mod air;
+mod columns;
+mod g;
+mod trace;
+
+/// Implements the rounds of the BLAKE3 compression function. The inputs to the syscall are a
+/// pointer to the 16 word state, which is updated in place, and a pointer to the 16 word message
+/// block.
+///
+/// In the AIR, each syscall takes up 56 rows, one for each of the 8 `G` functions of the 7 rounds.
+/// Each row reads the four state words and two message words of its `G` function, and writes the
+/// mixed state words back to memory.
+#[derive(Default)]
+pub struct Blake3CompressInnerChip;
+
+impl Blake3CompressInnerChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_artifacts::BLAKE3_ELF;
+    use zkm_core_executor::Program;
+    use zkm_stark::CpuProver;
+
+    use crate::utils::{run_test, setup_logger};
+
+    #[test]
+    fn test_blake3_compress_inner_program() {
+        setup_logger();
+        let program = Program::from(BLAKE3_ELF).unwrap();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use zkm_core_executor::{
+    events::{
+        Blake3CompressInnerEvent, ByteLookupEvent, ByteRecord, PrecompileEvent, G_INDEX,
+        MSG_SCHEDULE, OPERATION_COUNT, ROUND_COUNT,
+    },
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use zkm_stark::air::MachineAir;
+
+use super::{
+    columns::{Blake3CompressInnerCols, NUM_BLAKE3_COMPRESS_INNER_COLS},
+    Blake3CompressInnerChip,
+};
+use crate::utils::pad_rows_fixed;
+
+impl<F: PrimeField32> MachineAir<F> for Blake3CompressInnerChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Blake3CompressInner".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Some(Vec::new());
+        for (_, event) in input.get_precompile_events(SyscallCode::BLAKE3_COMPRESS_INNER) {
+            let event = if let PrecompileEvent::Blake3CompressInner(event) = event {
+                event
+            } else {
+                unreachable!()
+            };
+            self.event_to_rows(event, &mut rows, &mut Vec::new());
+        }
+        let mut rows = rows.unwrap();
+
+        pad_rows_fixed(
+            &mut rows,
+            || [F::ZERO; NUM_BLAKE3_COMPRESS_INNER_COLS],
+            input.fixed_log2_rows::<F, _>(self),
+        );
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_BLAKE3_COMPRESS_INNER_COLS,
+        )
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let events = input.get_precompile_events(SyscallCode::BLAKE3_COMPRESS_INNER);
+        let chunk_size = std::cmp::max(events.len() / num_cpus::get(), 1);
+
+        let blu_batches = events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<ByteLookupEvent, usize> = HashMap::new();
+                events.iter().for_each(|(_, event)| {
+                    let event = if let PrecompileEvent::Blake3CompressInner(event) = event {
+                        event
+                    } else {
+                        unreachable!()
+                    };
+                    self.event_to_rows::<F>(event, &mut None, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_byte_lookup_events_from_maps(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(SyscallCode::BLAKE3_COMPRESS_INNER).is_empty()
+        }
+    }
+}
+
+impl Blake3CompressInnerChip {
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &Blake3CompressInnerEvent,
+        rows: &mut Option<Vec<[F; NUM_BLAKE3_COMPRESS_INNER_COLS]>>,
+        blu: &mut impl ByteRecord,
+    ) {
+        for round in 0..ROUND_COUNT {
+            for operation in 0..OPERATION_COUNT {
+                let call = round * OPERATION_COUNT + operation;
+                let mut row = [F::ZERO; NUM_BLAKE3_COMPRESS_INNER_COLS];
+                let cols: &mut Blake3CompressInnerCols<F> = row.as_mut_slice().borrow_mut();
+
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.clk = F::from_canonical_u32(event.clk + call as u32);
+                cols.state_ptr = F::from_canonical_u32(event.state_ptr);
+                cols.message_ptr = F::from_canonical_u32(event.message_ptr);
+
+                cols.is_round_index_n[round] = F::ONE;
+                cols.is_operation_index_n[operation] = F::ONE;
+                cols.is_syscall = F::from_bool(call == 0);
+                cols.is_last_call = F::from_bool(call == ROUND_COUNT * OPERATION_COUNT - 1);
+
+                cols.state_index = G_INDEX[operation].map(F::from_canonical_usize);
+                cols.msg_schedule = core::array::from_fn(|i| {
+                    F::from_canonical_usize(MSG_SCHEDULE[round][2 * operation + i])
+                });
+
+                let state_writes = &event.state_writes[call];
+                let message_reads = &event.message_reads[call];
+                for (access, record) in cols.state_writes.iter_mut().zip(state_writes.iter()) {
+                    access.populate(*record, blu);
+                }
+                for (access, record) in cols.message_reads.iter_mut().zip(message_reads.iter()) {
+                    access.populate(*record, blu);
+                }
+
+                let input = state_writes.map(|record| record.prev_value);
+                let msg = message_reads.map(|record| record.value);
+                let output = cols.g.populate(blu, input, msg);
+                debug_assert_eq!(output, state_writes.map(|record| record.value));
+
+                cols.is_real = F::ONE;
+
+                if rows.as_ref().is_some() {
+                    rows.as_mut().unwrap().push(row);
+                }
+            }
+        }
+    }
+}
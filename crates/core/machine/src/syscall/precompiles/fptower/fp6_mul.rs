@@ -0,0 +1,586 @@
+use std::{
+    borrow::{Borrow, BorrowMut},
+    marker::PhantomData,
+};
+
+use crate::{air::MemoryAirBuilder, utils::zeroed_f_vec};
+use generic_array::GenericArray;
+use itertools::Itertools;
+use num::BigUint;
+use p3_air::{Air, BaseAir};
+use p3_field::{FieldAlgebra, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use std::mem::size_of;
+use typenum::Unsigned;
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, FieldOperation, PrecompileEvent},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use zkm_curves::{
+    params::{FieldParameters, Limbs, NumLimbs, NumWords},
+    weierstrass::{FieldType, FpOpField},
+};
+use zkm_derive::AlignedBorrow;
+use zkm_stark::air::{BaseAirBuilder, LookupScope, MachineAir, Polynomial, ZKMAirBuilder};
+
+use crate::{
+    memory::{value_as_limbs, MemoryReadCols, MemoryWriteCols},
+    operations::field::field_op::FieldOpCols,
+    utils::{limbs_from_prev_access, pad_rows_fixed, words_to_bytes_le_vec},
+};
+
+pub const fn num_fp6_mul_cols<P: FieldParameters + NumWords>() -> usize {
+    size_of::<Fp6MulAssignCols<u8, P>>()
+}
+
+/// A set of columns for a multiplication in `Fp2 = Fp[u] / (u^2 + 1)`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp2MulCols<T, P: FieldParameters + NumWords> {
+    pub(crate) a0_mul_b0: FieldOpCols<T, P>,
+    pub(crate) a1_mul_b1: FieldOpCols<T, P>,
+    pub(crate) a0_mul_b1: FieldOpCols<T, P>,
+    pub(crate) a1_mul_b0: FieldOpCols<T, P>,
+    pub(crate) c0: FieldOpCols<T, P>,
+    pub(crate) c1: FieldOpCols<T, P>,
+}
+
+/// A set of columns for an addition in `Fp2`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp2AddCols<T, P: FieldParameters + NumWords> {
+    pub(crate) c0: FieldOpCols<T, P>,
+    pub(crate) c1: FieldOpCols<T, P>,
+}
+
+/// A set of columns for a multiplication by the sextic non-residue `xi = xi0 + u` in `Fp2`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp2MulByNonResidueCols<T, P: FieldParameters + NumWords> {
+    pub(crate) xi_mul_c0: FieldOpCols<T, P>,
+    pub(crate) xi_mul_c1: FieldOpCols<T, P>,
+    pub(crate) c0: FieldOpCols<T, P>,
+    pub(crate) c1: FieldOpCols<T, P>,
+}
+
+/// A set of columns for the Fp6Mul operation.
+///
+/// An element of `Fp6 = Fp2[v] / (v^3 - xi)` is laid out in memory as its three `Fp2`
+/// coefficients, each of which is laid out like an operand of the Fp2Mul operation.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp6MulAssignCols<T, P: FieldParameters + NumWords> {
+    pub is_real: T,
+    pub shard: T,
+    pub clk: T,
+    pub x_ptr: T,
+    pub y_ptr: T,
+    pub x_access: [GenericArray<MemoryWriteCols<T>, P::WordsCurvePoint>; 3],
+    pub y_access: [GenericArray<MemoryReadCols<T>, P::WordsCurvePoint>; 3],
+    /// The products `a_i * b_j` of the coefficients of the operands.
+    pub(crate) products: [[Fp2MulCols<T, P>; 3]; 3],
+    /// `c0 = a0 * b0 + xi * (a1 * b2 + a2 * b1)`.
+    pub(crate) c0_sum: Fp2AddCols<T, P>,
+    pub(crate) c0_xi: Fp2MulByNonResidueCols<T, P>,
+    pub(crate) c0: Fp2AddCols<T, P>,
+    /// `c1 = a0 * b1 + a1 * b0 + xi * a2 * b2`.
+    pub(crate) c1_sum: Fp2AddCols<T, P>,
+    pub(crate) c1_xi: Fp2MulByNonResidueCols<T, P>,
+    pub(crate) c1: Fp2AddCols<T, P>,
+    /// `c2 = a0 * b2 + a1 * b1 + a2 * b0`.
+    pub(crate) c2_sum: Fp2AddCols<T, P>,
+    pub(crate) c2: Fp2AddCols<T, P>,
+}
+
+type Fp2<T> = [T; 2];
+
+impl<F: PrimeField32, P: FpOpField> Fp2MulCols<F, P> {
+    fn populate(
+        &mut self,
+        blu_events: &mut Vec<ByteLookupEvent>,
+        a: &Fp2<BigUint>,
+        b: &Fp2<BigUint>,
+        modulus: &BigUint,
+    ) -> Fp2<BigUint> {
+        let a0_mul_b0 = self.a0_mul_b0.populate_with_modulus(
+            blu_events,
+            &a[0],
+            &b[0],
+            modulus,
+            FieldOperation::Mul,
+        );
+        let a1_mul_b1 = self.a1_mul_b1.populate_with_modulus(
+            blu_events,
+            &a[1],
+            &b[1],
+            modulus,
+            FieldOperation::Mul,
+        );
+        let a0_mul_b1 = self.a0_mul_b1.populate_with_modulus(
+            blu_events,
+            &a[0],
+            &b[1],
+            modulus,
+            FieldOperation::Mul,
+        );
+        let a1_mul_b0 = self.a1_mul_b0.populate_with_modulus(
+            blu_events,
+            &a[1],
+            &b[0],
+            modulus,
+            FieldOperation::Mul,
+        );
+        let c0 = self.c0.populate_with_modulus(
+            blu_events,
+            &a0_mul_b0,
+            &a1_mul_b1,
+            modulus,
+            FieldOperation::Sub,
+        );
+        let c1 = self.c1.populate_with_modulus(
+            blu_events,
+            &a0_mul_b1,
+            &a1_mul_b0,
+            modulus,
+            FieldOperation::Add,
+        );
+        [c0, c1]
+    }
+}
+
+impl<F: PrimeField32, P: FpOpField> Fp2AddCols<F, P> {
+    fn populate(
+        &mut self,
+        blu_events: &mut Vec<ByteLookupEvent>,
+        a: &Fp2<BigUint>,
+        b: &Fp2<BigUint>,
+        modulus: &BigUint,
+    ) -> Fp2<BigUint> {
+        let c0 =
+            self.c0.populate_with_modulus(blu_events, &a[0], &b[0], modulus, FieldOperation::Add);
+        let c1 =
+            self.c1.populate_with_modulus(blu_events, &a[1], &b[1], modulus, FieldOperation::Add);
+        [c0, c1]
+    }
+}
+
+impl<F: PrimeField32, P: FpOpField> Fp2MulByNonResidueCols<F, P> {
+    fn populate(
+        &mut self,
+        blu_events: &mut Vec<ByteLookupEvent>,
+        a: &Fp2<BigUint>,
+        modulus: &BigUint,
+    ) -> Fp2<BigUint> {
+        let xi0 = BigUint::from(P::FP6_NON_RESIDUE_C0);
+        let xi_mul_c0 = self.xi_mul_c0.populate_with_modulus(
+            blu_events,
+            &a[0],
+            &xi0,
+            modulus,
+            FieldOperation::Mul,
+        );
+        let xi_mul_c1 = self.xi_mul_c1.populate_with_modulus(
+            blu_events,
+            &a[1],
+            &xi0,
+            modulus,
+            FieldOperation::Mul,
+        );
+        let c0 = self.c0.populate_with_modulus(
+            blu_events,
+            &xi_mul_c0,
+            &a[1],
+            modulus,
+            FieldOperation::Sub,
+        );
+        let c1 = self.c1.populate_with_modulus(
+            blu_events,
+            &xi_mul_c1,
+            &a[0],
+            modulus,
+            FieldOperation::Add,
+        );
+        [c0, c1]
+    }
+}
+
+impl<V: Copy, P: FpOpField> Fp2MulCols<V, P> {
+    fn eval<AB: ZKMAirBuilder<Var = V>>(
+        &self,
+        builder: &mut AB,
+        a: &Fp2<Limbs<V, P::Limbs>>,
+        b: &Fp2<Limbs<V, P::Limbs>>,
+        modulus: &Polynomial<AB::Expr>,
+        is_real: V,
+    ) -> Fp2<Limbs<V, P::Limbs>>
+    where
+        V: Into<AB::Expr>,
+        Limbs<V, P::Limbs>: Copy,
+    {
+        self.a0_mul_b0.eval_with_modulus(
+            builder,
+            &a[0],
+            &b[0],
+            modulus,
+            FieldOperation::Mul,
+            is_real,
+        );
+        self.a1_mul_b1.eval_with_modulus(
+            builder,
+            &a[1],
+            &b[1],
+            modulus,
+            FieldOperation::Mul,
+            is_real,
+        );
+        self.a0_mul_b1.eval_with_modulus(
+            builder,
+            &a[0],
+            &b[1],
+            modulus,
+            FieldOperation::Mul,
+            is_real,
+        );
+        self.a1_mul_b0.eval_with_modulus(
+            builder,
+            &a[1],
+            &b[0],
+            modulus,
+            FieldOperation::Mul,
+            is_real,
+        );
+        self.c0.eval_with_modulus(
+            builder,
+            &self.a0_mul_b0.result,
+            &self.a1_mul_b1.result,
+            modulus,
+            FieldOperation::Sub,
+            is_real,
+        );
+        self.c1.eval_with_modulus(
+            builder,
+            &self.a0_mul_b1.result,
+            &self.a1_mul_b0.result,
+            modulus,
+            FieldOperation::Add,
+            is_real,
+        );
+        [self.c0.result, self.c1.result]
+    }
+}
+
+impl<V: Copy, P: FpOpField> Fp2AddCols<V, P> {
+    fn eval<AB: ZKMAirBuilder<Var = V>>(
+        &self,
+        builder: &mut AB,
+        a: &Fp2<Limbs<V, P::Limbs>>,
+        b: &Fp2<Limbs<V, P::Limbs>>,
+        modulus: &Polynomial<AB::Expr>,
+        is_real: V,
+    ) -> Fp2<Limbs<V, P::Limbs>>
+    where
+        V: Into<AB::Expr>,
+        Limbs<V, P::Limbs>: Copy,
+    {
+        self.c0.eval_with_modulus(builder, &a[0], &b[0], modulus, FieldOperation::Add, is_real);
+        self.c1.eval_with_modulus(builder, &a[1], &b[1], modulus, FieldOperation::Add, is_real);
+        [self.c0.result, self.c1.result]
+    }
+}
+
+impl<V: Copy, P: FpOpField> Fp2MulByNonResidueCols<V, P> {
+    fn eval<AB: ZKMAirBuilder<Var = V>>(
+        &self,
+        builder: &mut AB,
+        a: &Fp2<Limbs<V, P::Limbs>>,
+        modulus: &Polynomial<AB::Expr>,
+        is_real: V,
+    ) -> Fp2<Limbs<V, P::Limbs>>
+    where
+        V: Into<AB::Expr>,
+        Limbs<V, P::Limbs>: Copy,
+    {
+        let mut xi0_coeffs = vec![AB::Expr::ZERO; P::Limbs::USIZE];
+        xi0_coeffs[0] = AB::Expr::from_canonical_u32(P::FP6_NON_RESIDUE_C0);
+        let xi0 = Polynomial::from_coefficients(&xi0_coeffs);
+
+        self.xi_mul_c0.eval_with_modulus(
+            builder,
+            &a[0],
+            &xi0,
+            modulus,
+            FieldOperation::Mul,
+            is_real,
+        );
+        self.xi_mul_c1.eval_with_modulus(
+            builder,
+            &a[1],
+            &xi0,
+            modulus,
+            FieldOperation::Mul,
+            is_real,
+        );
+        self.c0.eval_with_modulus(
+            builder,
+            &self.xi_mul_c0.result,
+            &a[1],
+            modulus,
+            FieldOperation::Sub,
+            is_real,
+        );
+        self.c1.eval_with_modulus(
+            builder,
+            &self.xi_mul_c1.result,
+            &a[0],
+            modulus,
+            FieldOperation::Add,
+            is_real,
+        );
+        [self.c0.result, self.c1.result]
+    }
+}
+
+#[derive(Default)]
+pub struct Fp6MulAssignChip<P> {
+    _marker: PhantomData<P>,
+}
+
+impl<P: FpOpField> Fp6MulAssignChip<P> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+
+    fn populate_field_ops<F: PrimeField32>(
+        blu_events: &mut Vec<ByteLookupEvent>,
+        cols: &mut Fp6MulAssignCols<F, P>,
+        a: &[Fp2<BigUint>; 3],
+        b: &[Fp2<BigUint>; 3],
+    ) {
+        let modulus = &BigUint::from_bytes_le(P::MODULUS);
+
+        let mut p: [[Fp2<BigUint>; 3]; 3] = Default::default();
+        for i in 0..3 {
+            for j in 0..3 {
+                p[i][j] = cols.products[i][j].populate(blu_events, &a[i], &b[j], modulus);
+            }
+        }
+
+        let c0_sum = cols.c0_sum.populate(blu_events, &p[1][2], &p[2][1], modulus);
+        let c0_xi = cols.c0_xi.populate(blu_events, &c0_sum, modulus);
+        cols.c0.populate(blu_events, &p[0][0], &c0_xi, modulus);
+
+        let c1_sum = cols.c1_sum.populate(blu_events, &p[0][1], &p[1][0], modulus);
+        let c1_xi = cols.c1_xi.populate(blu_events, &p[2][2], modulus);
+        cols.c1.populate(blu_events, &c1_sum, &c1_xi, modulus);
+
+        let c2_sum = cols.c2_sum.populate(blu_events, &p[0][2], &p[1][1], modulus);
+        cols.c2.populate(blu_events, &c2_sum, &p[2][0], modulus);
+    }
+}
+
+impl<F: PrimeField32, P: FpOpField> MachineAir<F> for Fp6MulAssignChip<P> {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => "Bn254Fp6MulAssign".to_string(),
+            FieldType::Bls12381 => "Bls12381Fp6MulAssign".to_string(),
+        }
+    }
+
+    fn generate_trace(&self, input: &Self::Record, output: &mut Self::Record) -> RowMajorMatrix<F> {
+        let events = match P::FIELD_TYPE {
+            FieldType::Bn254 => input.get_precompile_events(SyscallCode::BN254_FP6_MUL),
+            FieldType::Bls12381 => input.get_precompile_events(SyscallCode::BLS12381_FP6_MUL),
+        };
+
+        let num_words_fp2 = <P as NumWords>::WordsCurvePoint::USIZE;
+        let num_words_field_element = <P as NumWords>::WordsFieldElement::USIZE;
+        let to_fp6 = |words: &[u32]| -> [Fp2<BigUint>; 3] {
+            let mut coeffs = words
+                .chunks(num_words_field_element)
+                .map(|limbs| BigUint::from_bytes_le(&words_to_bytes_le_vec(limbs)));
+            core::array::from_fn(|_| core::array::from_fn(|_| coeffs.next().unwrap()))
+        };
+
+        let mut rows = Vec::new();
+        let mut new_byte_lookup_events = Vec::new();
+
+        for (_, event) in events {
+            let event = match (P::FIELD_TYPE, event) {
+                (FieldType::Bn254, PrecompileEvent::Bn254Fp6Mul(event)) => event,
+                (FieldType::Bls12381, PrecompileEvent::Bls12381Fp6Mul(event)) => event,
+                _ => unreachable!(),
+            };
+
+            let mut row = zeroed_f_vec(num_fp6_mul_cols::<P>());
+            let cols: &mut Fp6MulAssignCols<F, P> = row.as_mut_slice().borrow_mut();
+
+            cols.is_real = F::ONE;
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+            cols.y_ptr = F::from_canonical_u32(event.y_ptr);
+
+            Self::populate_field_ops(
+                &mut new_byte_lookup_events,
+                cols,
+                &to_fp6(&event.x),
+                &to_fp6(&event.y),
+            );
+
+            // Populate the memory access columns.
+            for i in 0..3 {
+                for j in 0..num_words_fp2 {
+                    cols.y_access[i][j].populate(
+                        event.y_memory_records[i * num_words_fp2 + j],
+                        &mut new_byte_lookup_events,
+                    );
+                    cols.x_access[i][j].populate(
+                        event.x_memory_records[i * num_words_fp2 + j],
+                        &mut new_byte_lookup_events,
+                    );
+                }
+            }
+            rows.push(row);
+        }
+
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows_fixed(
+            &mut rows,
+            || {
+                let mut row = zeroed_f_vec(num_fp6_mul_cols::<P>());
+                let cols: &mut Fp6MulAssignCols<F, P> = row.as_mut_slice().borrow_mut();
+                let zero: [Fp2<BigUint>; 3] = Default::default();
+                Self::populate_field_ops(&mut vec![], cols, &zero, &zero);
+                row
+            },
+            input.fixed_log2_rows::<F, _>(self),
+        );
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), num_fp6_mul_cols::<P>())
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            match P::FIELD_TYPE {
+                FieldType::Bn254 => {
+                    !shard.get_precompile_events(SyscallCode::BN254_FP6_MUL).is_empty()
+                }
+                FieldType::Bls12381 => {
+                    !shard.get_precompile_events(SyscallCode::BLS12381_FP6_MUL).is_empty()
+                }
+            }
+        }
+    }
+
+    fn local_only(&self) -> bool {
+        true
+    }
+}
+
+impl<F, P: FpOpField> BaseAir<F> for Fp6MulAssignChip<P> {
+    fn width(&self) -> usize {
+        num_fp6_mul_cols::<P>()
+    }
+}
+
+impl<AB, P: FpOpField> Air<AB> for Fp6MulAssignChip<P>
+where
+    AB: ZKMAirBuilder,
+    Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &Fp6MulAssignCols<AB::Var, P> = (*local).borrow();
+
+        let num_words_field_element = <P as NumLimbs>::Limbs::USIZE / 4;
+        let a: [Fp2<Limbs<AB::Var, P::Limbs>>; 3] = core::array::from_fn(|i| {
+            [
+                limbs_from_prev_access(&local.x_access[i][0..num_words_field_element]),
+                limbs_from_prev_access(&local.x_access[i][num_words_field_element..]),
+            ]
+        });
+        let b: [Fp2<Limbs<AB::Var, P::Limbs>>; 3] = core::array::from_fn(|i| {
+            [
+                limbs_from_prev_access(&local.y_access[i][0..num_words_field_element]),
+                limbs_from_prev_access(&local.y_access[i][num_words_field_element..]),
+            ]
+        });
+
+        let modulus_coeffs =
+            P::MODULUS.iter().map(|&limbs| AB::Expr::from_canonical_u8(limbs)).collect_vec();
+        let p_modulus = Polynomial::from_coefficients(&modulus_coeffs);
+
+        let p: [[Fp2<Limbs<AB::Var, P::Limbs>>; 3]; 3] = core::array::from_fn(|i| {
+            core::array::from_fn(|j| {
+                local.products[i][j].eval(builder, &a[i], &b[j], &p_modulus, local.is_real)
+            })
+        });
+
+        let c0_sum = local.c0_sum.eval(builder, &p[1][2], &p[2][1], &p_modulus, local.is_real);
+        let c0_xi = local.c0_xi.eval(builder, &c0_sum, &p_modulus, local.is_real);
+        let c0 = local.c0.eval(builder, &p[0][0], &c0_xi, &p_modulus, local.is_real);
+
+        let c1_sum = local.c1_sum.eval(builder, &p[0][1], &p[1][0], &p_modulus, local.is_real);
+        let c1_xi = local.c1_xi.eval(builder, &p[2][2], &p_modulus, local.is_real);
+        let c1 = local.c1.eval(builder, &c1_sum, &c1_xi, &p_modulus, local.is_real);
+
+        let c2_sum = local.c2_sum.eval(builder, &p[0][2], &p[1][1], &p_modulus, local.is_real);
+        let c2 = local.c2.eval(builder, &c2_sum, &p[2][0], &p_modulus, local.is_real);
+
+        for (c, x_access) in [c0, c1, c2].iter().zip(local.x_access.iter()) {
+            builder
+                .when(local.is_real)
+                .assert_all_eq(c[0], value_as_limbs(&x_access[0..num_words_field_element]));
+            builder
+                .when(local.is_real)
+                .assert_all_eq(c[1], value_as_limbs(&x_access[num_words_field_element..]));
+        }
+
+        let num_bytes_fp2 = <P as NumWords>::WordsCurvePoint::USIZE * 4;
+        for i in 0..3 {
+            let offset = AB::F::from_canonical_usize(i * num_bytes_fp2);
+            builder.eval_memory_access_slice(
+                local.shard,
+                local.clk.into(),
+                local.y_ptr + offset,
+                &local.y_access[i],
+                local.is_real,
+            );
+            builder.eval_memory_access_slice(
+                local.shard,
+                local.clk + AB::F::from_canonical_u32(1), /* We read p at +1 since p, q could be
+                                                           * the same. */
+                local.x_ptr + offset,
+                &local.x_access[i],
+                local.is_real,
+            );
+        }
+
+        let syscall_id_felt = match P::FIELD_TYPE {
+            FieldType::Bn254 => AB::F::from_canonical_u32(SyscallCode::BN254_FP6_MUL.syscall_id()),
+            FieldType::Bls12381 => {
+                AB::F::from_canonical_u32(SyscallCode::BLS12381_FP6_MUL.syscall_id())
+            }
+        };
+
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            syscall_id_felt,
+            local.x_ptr,
+            local.y_ptr,
+            local.is_real,
+            LookupScope::Local,
+        );
+    }
+}
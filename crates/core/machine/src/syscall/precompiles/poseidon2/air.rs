@@ -0,0 +1,230 @@
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::MemoryWriteCols,
+    operations::{
+        poseidon2::{
+            air::{eval_external_round, eval_internal_rounds},
+            permutation::Poseidon2Cols,
+            trace::populate_perm_deg3,
+            Poseidon2Operation, NUM_EXTERNAL_ROUNDS,
+        },
+        KoalaBearWordRangeChecker,
+    },
+    utils::pad_rows_fixed,
+};
+
+use p3_air::{Air, BaseAir, PairBuilder};
+use p3_field::{FieldAlgebra, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use std::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+use zkm_core_executor::{
+    events::{ByteRecord, PrecompileEvent, POSEIDON2_WIDTH},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use zkm_derive::AlignedBorrow;
+use zkm_stark::air::{LookupScope, MachineAir, ZKMAirBuilder};
+
+/// The number of columns in the Poseidon2PermuteCols.
+const NUM_COLS: usize = size_of::<Poseidon2PermuteCols<u8>>();
+
+#[derive(Default)]
+pub struct Poseidon2PermuteChip;
+
+impl Poseidon2PermuteChip {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// A set of columns for the Poseidon2 permute operation.
+#[derive(AlignedBorrow, Clone, Copy)]
+#[repr(C)]
+pub struct Poseidon2PermuteCols<T: Copy> {
+    /// The shard number of the syscall.
+    pub shard: T,
+
+    /// The clock cycle of the syscall.
+    pub clk: T,
+
+    /// The pointer to the state.
+    pub state_ptr: T,
+
+    // The state is read from and written back to the same location, which is why it is of type
+    // MemoryWriteCols. The input is stored in the "prev_value" of the memory columns.
+    pub state_memory: [MemoryWriteCols<T>; POSEIDON2_WIDTH],
+
+    /// Range checks that the input words are canonical KoalaBear elements.
+    pub input_range_checkers: [KoalaBearWordRangeChecker<T>; POSEIDON2_WIDTH],
+
+    /// Range checks that the output words are canonical KoalaBear elements.
+    pub output_range_checkers: [KoalaBearWordRangeChecker<T>; POSEIDON2_WIDTH],
+
+    /// The columns of the permutation.
+    pub permutation: Poseidon2Operation<T>,
+
+    pub is_real: T,
+}
+
+impl<F: PrimeField32> MachineAir<F> for Poseidon2PermuteChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Poseidon2Permute".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut new_byte_lookup_events = Vec::new();
+
+        let mut rows = input
+            .get_precompile_events(SyscallCode::POSEIDON2_PERMUTE)
+            .iter()
+            .map(|(_, event)| {
+                let event = if let PrecompileEvent::Poseidon2Permute(event) = event {
+                    event
+                } else {
+                    unreachable!()
+                };
+                let mut row: [F; NUM_COLS] = [F::ZERO; NUM_COLS];
+                let cols: &mut Poseidon2PermuteCols<F> = row.as_mut_slice().borrow_mut();
+
+                // Assign basic values to the columns.
+                cols.is_real = F::ONE;
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.state_ptr = F::from_canonical_u32(event.state_ptr);
+
+                // Populate memory and range check columns.
+                for i in 0..POSEIDON2_WIDTH {
+                    let record = event.state_memory_records[i];
+                    cols.state_memory[i].populate(record, &mut new_byte_lookup_events);
+                    cols.input_range_checkers[i].populate(record.prev_value);
+                    cols.output_range_checkers[i].populate(record.value);
+                    new_byte_lookup_events.add_u8_range_checks(&record.value.to_le_bytes());
+                }
+
+                // Populate the permutation.
+                let state: [F; POSEIDON2_WIDTH] =
+                    core::array::from_fn(|i| F::from_canonical_u32(event.state[i]));
+                cols.permutation = populate_perm_deg3(state, None);
+
+                row
+            })
+            .collect::<Vec<_>>();
+
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows_fixed(
+            &mut rows,
+            || {
+                let mut row: [F; NUM_COLS] = [F::ZERO; NUM_COLS];
+                let cols: &mut Poseidon2PermuteCols<F> = row.as_mut_slice().borrow_mut();
+
+                // The permutation constraints are not gated by is_real, so padding rows must
+                // hold a valid permutation.
+                cols.permutation = populate_perm_deg3([F::ZERO; POSEIDON2_WIDTH], None);
+
+                row
+            },
+            input.fixed_log2_rows::<F, _>(self),
+        );
+
+        // Convert the trace to a row major matrix.
+        RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), NUM_COLS)
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(SyscallCode::POSEIDON2_PERMUTE).is_empty()
+        }
+    }
+
+    fn local_only(&self) -> bool {
+        true
+    }
+}
+
+impl<F> BaseAir<F> for Poseidon2PermuteChip {
+    fn width(&self) -> usize {
+        NUM_COLS
+    }
+}
+
+impl<AB> Air<AB> for Poseidon2PermuteChip
+where
+    AB: ZKMAirBuilder + PairBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &Poseidon2PermuteCols<AB::Var> = (*local).borrow();
+
+        // Evaluate the permutation.
+        for r in 0..NUM_EXTERNAL_ROUNDS {
+            eval_external_round(builder, &local.permutation.permutation, r);
+        }
+        eval_internal_rounds(builder, &local.permutation.permutation);
+
+        let perm_input = local.permutation.permutation.external_rounds_state()[0];
+        let perm_output = local.permutation.permutation.perm_output();
+
+        for i in 0..POSEIDON2_WIDTH {
+            let memory = &local.state_memory[i];
+
+            // The input words must be canonical, so that they reduce to the permutation input.
+            KoalaBearWordRangeChecker::<AB::F>::range_check(
+                builder,
+                memory.prev_value,
+                local.input_range_checkers[i],
+                local.is_real.into(),
+            );
+            builder.when(local.is_real).assert_eq(memory.prev_value.reduce::<AB>(), perm_input[i]);
+
+            // The output words must be canonical, so that they are the unique encoding of the
+            // permutation output.
+            builder.slice_range_check_u8(&memory.access.value.0, local.is_real);
+            KoalaBearWordRangeChecker::<AB::F>::range_check(
+                builder,
+                memory.access.value,
+                local.output_range_checkers[i],
+                local.is_real.into(),
+            );
+            builder
+                .when(local.is_real)
+                .assert_eq(memory.access.value.reduce::<AB>(), perm_output[i]);
+        }
+
+        // Read and write the state.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into() + AB::Expr::ONE,
+            local.state_ptr,
+            &local.state_memory,
+            local.is_real,
+        );
+
+        // Receive the arguments.
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::POSEIDON2_PERMUTE.syscall_id()),
+            local.state_ptr,
+            AB::Expr::ZERO,
+            local.is_real,
+            LookupScope::Local,
+        );
+
+        // Assert that is_real is a boolean.
+        builder.assert_bool(local.is_real);
+    }
+}
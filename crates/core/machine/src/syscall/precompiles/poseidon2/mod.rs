@@ -0,0 +1,44 @@
+mod air;
+
+pub use air::*;
+
+#[cfg(test)]
+mod tests {
+
+    use p3_field::{FieldAlgebra, PrimeField32};
+    use p3_koala_bear::KoalaBear;
+    use test_artifacts::POSEIDON2_ELF;
+    use zkm_core_executor::Program;
+    use zkm_primitives::poseidon2_permute;
+    use zkm_stark::CpuProver;
+
+    use crate::{
+        io::ZKMStdin,
+        utils::{self, run_test_io},
+    };
+
+    #[test]
+    fn test_poseidon2_permute() {
+        utils::setup_logger();
+        let inputs: Vec<[u32; 16]> = vec![
+            [0; 16],
+            core::array::from_fn(|i| i as u32),
+            [KoalaBear::ORDER_U32 - 1; 16],
+            core::array::from_fn(|i| (i as u32 + 1) * 0x0123_4567 % KoalaBear::ORDER_U32),
+        ];
+
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&(inputs.len() as u32));
+        for input in inputs.iter() {
+            stdin.write(input);
+        }
+
+        let program = Program::from(POSEIDON2_ELF).unwrap();
+        let mut public_values = run_test_io::<CpuProver<_, _>>(program, stdin).unwrap();
+        for input in inputs {
+            let expected = poseidon2_permute(input.map(KoalaBear::from_canonical_u32))
+                .map(|x| x.as_canonical_u32());
+            assert_eq!(public_values.read::<[u32; 16]>(), expected);
+        }
+    }
+}
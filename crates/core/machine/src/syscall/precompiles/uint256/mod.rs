@@ -5,7 +5,7 @@ pub use air::*;
 #[cfg(test)]
 mod tests {
 
-    use test_artifacts::UINT256_MUL_ELF;
+    use test_artifacts::{MODEXP_ELF, UINT256_MUL_ELF};
     use zkm_core_executor::Program;
     use zkm_curves::{params::FieldParameters, uint256::U256Field, utils::biguint_from_limbs};
     use zkm_stark::CpuProver;
@@ -22,6 +22,13 @@ mod tests {
         run_test_io::<CpuProver<_, _>>(program, ZKMStdin::new()).unwrap();
     }
 
+    #[test]
+    fn test_modexp() {
+        utils::setup_logger();
+        let program = Program::from(MODEXP_ELF).unwrap();
+        run_test_io::<CpuProver<_, _>>(program, ZKMStdin::new()).unwrap();
+    }
+
     #[test]
     fn test_uint256_modulus() {
         assert_eq!(biguint_from_limbs(U256Field::MODULUS), U256Field::modulus());
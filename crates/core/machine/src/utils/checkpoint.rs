@@ -0,0 +1,215 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tiny_keccak::{Hasher, Keccak};
+use zkm_core_executor::{ExecutionRecord, ExecutionState, Program};
+use zkm_stark::{air::PublicValues, ShardProof, StarkGenericConfig};
+
+use crate::{io::ZKMStdin, ZKM_CIRCUIT_VERSION};
+
+use super::ZKMCoreProverError;
+
+/// Options for writing [`ProvingCheckpoint`]s while proving.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointOpts {
+    /// The directory the checkpoints are written to.
+    pub dir: PathBuf,
+    /// The minimum number of execution shards between two checkpoints.
+    pub interval: u32,
+}
+
+impl CheckpointOpts {
+    /// Create new [`CheckpointOpts`] writing a checkpoint to `dir` every `interval` shards.
+    pub fn new(dir: impl Into<PathBuf>, interval: u32) -> Self {
+        Self { dir: dir.into(), interval: interval.max(1) }
+    }
+
+    /// The path of the checkpoint starting at the execution batch `batch_index`.
+    #[must_use]
+    pub fn path(&self, batch_index: usize) -> PathBuf {
+        self.dir.join(format!("checkpoint-{batch_index}.bin"))
+    }
+}
+
+/// Identifies the program, input and prover a [`ProvingCheckpoint`] was created with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointHeader {
+    /// The circuit version of the prover which created the checkpoint.
+    pub version: String,
+    /// The hash of the program, see [`program_hash`].
+    pub program_hash: [u8; 32],
+    /// The hash of the input and the deferred proofs, see [`input_hash`].
+    pub input_hash: [u8; 32],
+    /// The index of the execution batch the checkpoint starts at.
+    pub batch_index: usize,
+    /// The execution shard the checkpoint starts at.
+    pub execution_shard: u32,
+}
+
+/// A snapshot of the core proving pipeline at the start of an execution batch, from which proving
+/// can be resumed later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvingCheckpoint {
+    /// The header identifying the program.
+    pub header: CheckpointHeader,
+    /// The full execution state, including all of memory.
+    pub state: ExecutionState,
+    /// The public values of the last shard proven before the checkpoint.
+    pub public_values: PublicValues<u32, u32>,
+    /// The deferred events which were not yet split into shards.
+    pub deferred: ExecutionRecord,
+    /// The serialized proofs of the shards proven before the checkpoint, see
+    /// [`Self::shard_proofs`].
+    pub proofs: Vec<u8>,
+}
+
+impl ProvingCheckpoint {
+    /// Save the checkpoint to the file at `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ZKMCoreProverError> {
+        let mut writer = BufWriter::new(File::create(path).map_err(ZKMCoreProverError::IoError)?);
+        bincode::serialize_into(&mut writer, self)
+            .map_err(ZKMCoreProverError::SerializationError)?;
+        writer.flush().map_err(ZKMCoreProverError::IoError)
+    }
+
+    /// Load a checkpoint from the file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ZKMCoreProverError> {
+        let reader = BufReader::new(File::open(path).map_err(ZKMCoreProverError::IoError)?);
+        bincode::deserialize_from(reader).map_err(ZKMCoreProverError::SerializationError)
+    }
+
+    /// Decode the proofs of the shards proven before the checkpoint, which a resumed proof starts
+    /// with.
+    pub fn shard_proofs<SC: StarkGenericConfig>(
+        &self,
+    ) -> Result<Vec<ShardProof<SC>>, ZKMCoreProverError> {
+        bincode::deserialize(&self.proofs).map_err(ZKMCoreProverError::SerializationError)
+    }
+
+    /// Check that the checkpoint was created for `program` and `stdin` by a prover with the same
+    /// circuit version.
+    pub fn validate(&self, program: &Program, stdin: &ZKMStdin) -> Result<(), ZKMCoreProverError> {
+        if self.header.version != ZKM_CIRCUIT_VERSION {
+            return Err(ZKMCoreProverError::InvalidCheckpoint(format!(
+                "checkpoint was created with version {}, expected {}",
+                self.header.version, ZKM_CIRCUIT_VERSION
+            )));
+        }
+        if self.header.program_hash != program_hash(program) {
+            return Err(ZKMCoreProverError::InvalidCheckpoint(
+                "checkpoint was created for a different program".to_string(),
+            ));
+        }
+        if self.header.input_hash != input_hash(stdin) {
+            return Err(ZKMCoreProverError::InvalidCheckpoint(
+                "checkpoint was created for a different input".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Hash the instructions, entrypoint and initial memory image of a program.
+#[must_use]
+pub fn program_hash(program: &Program) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut update = |bytes: Vec<u8>| hasher.update(&bytes);
+    update(bincode::serialize(&program.instructions).unwrap());
+    update(bincode::serialize(&(program.pc_start, program.pc_base, program.next_pc)).unwrap());
+    update(bincode::serialize(&program.image).unwrap());
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// Hash the input of a program and its deferred proofs.
+#[must_use]
+pub fn input_hash(stdin: &ZKMStdin) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(&bincode::serialize(&stdin.buffer).unwrap());
+    hasher.update(&bincode::serialize(&stdin.proofs).unwrap());
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// Write a checkpoint at the start of the execution batch `batch_index`, together with the proofs
+/// of all of the shards before it, logging any failure instead of aborting the proof.
+pub(crate) fn write_checkpoint<SC: StarkGenericConfig>(
+    opts: &CheckpointOpts,
+    program: &Program,
+    input_hash: [u8; 32],
+    batch_index: usize,
+    state: ExecutionState,
+    public_values: PublicValues<u32, u32>,
+    deferred: ExecutionRecord,
+    shard_proofs: &[ShardProof<SC>],
+) {
+    let header = CheckpointHeader {
+        version: ZKM_CIRCUIT_VERSION.to_string(),
+        program_hash: program_hash(program),
+        input_hash,
+        batch_index,
+        execution_shard: state.current_shard,
+    };
+    let path = opts.path(batch_index);
+    let result = bincode::serialize(shard_proofs)
+        .map_err(ZKMCoreProverError::SerializationError)
+        .map(|proofs| ProvingCheckpoint { header, state, public_values, deferred, proofs })
+        .and_then(|checkpoint| {
+            std::fs::create_dir_all(&opts.dir).map_err(ZKMCoreProverError::IoError)?;
+            checkpoint.save(&path)
+        });
+    match result {
+        Ok(()) => tracing::info!("wrote checkpoint to {}", path.display()),
+        Err(e) => tracing::warn!("failed to write checkpoint to {}: {}", path.display(), e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zkm_core_executor::{ExecutionRecord, ExecutionState};
+    use zkm_stark::{air::PublicValues, koala_bear_poseidon2::KoalaBearPoseidon2};
+
+    use crate::{io::ZKMStdin, programs::tests::simple_program};
+
+    use super::{input_hash, program_hash, CheckpointOpts, ProvingCheckpoint};
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        let program = simple_program();
+        let dir = tempfile::tempdir().unwrap();
+        let opts = CheckpointOpts::new(dir.path(), 4);
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&42u32);
+
+        super::write_checkpoint::<KoalaBearPoseidon2>(
+            &opts,
+            &program,
+            input_hash(&stdin),
+            3,
+            ExecutionState::new(program.pc_start, program.next_pc),
+            PublicValues::<u32, u32>::default(),
+            ExecutionRecord::default(),
+            &[],
+        );
+
+        let checkpoint = ProvingCheckpoint::load(opts.path(3)).unwrap();
+        assert_eq!(checkpoint.header.batch_index, 3);
+        assert!(checkpoint.shard_proofs::<KoalaBearPoseidon2>().unwrap().is_empty());
+        assert_eq!(checkpoint.header.program_hash, program_hash(&program));
+        checkpoint.validate(&program, &stdin).unwrap();
+
+        let mut other = simple_program();
+        other.pc_start += 4;
+        assert!(checkpoint.validate(&other, &stdin).is_err());
+
+        let mut other_stdin = ZKMStdin::new();
+        other_stdin.write(&43u32);
+        assert!(checkpoint.validate(&program, &other_stdin).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use std::{collections::BTreeMap, str::FromStr, time::Duration};
+
+use hashbrown::HashMap;
+use p3_field::PrimeField32;
+use zkm_core_executor::{
+    ExecutionRecord, ExecutionReport, Executor, MipsAirId, Program, ZKMContext,
+};
+use zkm_stark::{air::MachineAir, shape::Shape, MachineRecord, ZKMCoreOpts};
+
+use crate::{
+    io::ZKMStdin, memory::NUM_LOCAL_MEMORY_ENTRIES_PER_ROW, mips::MipsAir, shape::CoreShapeConfig,
+};
+
+use super::ZKMCoreProverError;
+
+/// The default throughput of the core prover, in LDE cells per second.
+///
+/// This is a rough calibration of the CPU prover on a 64 core machine and should be replaced by a
+/// measurement of the actual hardware with [`ExecutionEstimate::prover_time`].
+pub const DEFAULT_LDE_CELLS_PER_SECOND: u64 = 1 << 26;
+
+/// The predicted trace heights and shape of a single shard.
+#[derive(Debug, Clone)]
+pub struct ShardEstimate {
+    /// The shard number.
+    pub shard: u32,
+    /// The number of rows of each air in the shard, before padding.
+    pub heights: BTreeMap<MipsAirId, usize>,
+    /// The shape the shard is padded to, if the shapes are fixed.
+    pub shape: Option<Shape<MipsAirId>>,
+    /// The number of cells in the low-degree extensions of the padded traces of the shard.
+    pub lde_cells: u64,
+    /// The size of the low-degree extensions of the padded traces of the shard, in bytes.
+    pub lde_size: u64,
+}
+
+/// The predicted cost of proving an execution, computed without generating any traces.
+#[derive(Debug, Clone)]
+pub struct ExecutionEstimate {
+    /// The number of cycles of the execution.
+    pub cycles: u64,
+    /// The estimates of every shard, in proving order.
+    pub shards: Vec<ShardEstimate>,
+    /// The estimated peak memory usage of the core prover, in bytes.
+    pub peak_memory: u64,
+    /// The execution report.
+    pub report: ExecutionReport,
+}
+
+impl ExecutionEstimate {
+    /// The number of shards of the core proof.
+    #[must_use]
+    pub fn num_shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// The total number of cells in the low-degree extensions of all shards.
+    #[must_use]
+    pub fn total_lde_cells(&self) -> u64 {
+        self.shards.iter().map(|shard| shard.lde_cells).sum()
+    }
+
+    /// The estimated time to generate the core proof with a prover committing to
+    /// `lde_cells_per_second` cells per second.
+    #[must_use]
+    pub fn prover_time(&self, lde_cells_per_second: u64) -> Duration {
+        Duration::from_secs_f64(self.total_lde_cells() as f64 / lde_cells_per_second as f64)
+    }
+
+    /// The estimated time to generate the core proof, assuming [`DEFAULT_LDE_CELLS_PER_SECOND`].
+    #[must_use]
+    pub fn estimated_prover_time(&self) -> Duration {
+        self.prover_time(DEFAULT_LDE_CELLS_PER_SECOND)
+    }
+}
+
+/// Estimate the cost of proving the execution of a program with the core prover.
+///
+/// The program is executed and its events are split into shards, deferred and fitted to a shape
+/// exactly as in [`super::prove_with_context`], but no traces are generated. If `shape_config` is
+/// provided, the preprocessed shape of `program` must already be fixed.
+pub fn estimate_with_context<F: PrimeField32>(
+    program: Program,
+    stdin: &ZKMStdin,
+    opts: ZKMCoreOpts,
+    context: ZKMContext,
+    shape_config: Option<&CoreShapeConfig<F>>,
+) -> Result<ExecutionEstimate, ZKMCoreProverError> {
+    let (airs, costs) = MipsAir::<F>::get_airs_and_costs();
+    let precompile_airs = MipsAir::<F>::precompile_airs_with_memory_events_per_row();
+    let preprocessed_heights = MipsAir::<F>::preprocessed_heights(&program);
+
+    // Setup the runtime.
+    let mut runtime = Executor::with_context(program.clone(), opts, context);
+    runtime.maximal_shapes = shape_config.map(|config| {
+        config.maximal_core_shapes(opts.shard_size.ilog2() as usize).into_iter().collect()
+    });
+    runtime.write_vecs(&stdin.buffer);
+    for (proof, vk) in stdin.proofs.iter() {
+        runtime.write_proof(proof.clone(), vk.clone());
+    }
+
+    let mut shards = Vec::new();
+    let mut deferred = ExecutionRecord::new(program.into());
+    loop {
+        let (mut records, done) =
+            runtime.execute_record(true).map_err(ZKMCoreProverError::ExecutionError)?;
+
+        // Defer the events that are too expensive to include in every shard.
+        for record in records.iter_mut() {
+            deferred.append(&mut record.defer());
+        }
+        records.append(&mut deferred.split(done, opts.split_opts));
+
+        for mut record in records {
+            // Generate the dependencies.
+            for air in airs.iter() {
+                let mut output = ExecutionRecord::default();
+                air.generate_dependencies(&record, &mut output);
+                record.append(&mut output);
+            }
+
+            // Collect the heights of the airs the shard contains.
+            let mut heights = BTreeMap::new();
+            let mut add_heights = |airs: Vec<(MipsAirId, usize)>| {
+                for (air, height) in airs {
+                    *heights.entry(air).or_insert(0) += height;
+                }
+            };
+            add_heights(preprocessed_heights.clone());
+            if record.contains_cpu() {
+                add_heights(MipsAir::<F>::core_heights(&record));
+            }
+            add_heights(MipsAir::<F>::memory_heights(&record));
+            for (air, _) in precompile_airs.iter() {
+                if let Some((height, num_memory_local_events, num_global_events)) =
+                    air.precompile_heights(&record)
+                {
+                    add_heights(vec![
+                        (MipsAirId::from_str(&air.name()).unwrap(), height),
+                        (
+                            MipsAirId::SyscallPrecompile,
+                            height.div_ceil(air.rows_per_event(Some(&record)).max(1)),
+                        ),
+                        (
+                            MipsAirId::MemoryLocal,
+                            num_memory_local_events.div_ceil(NUM_LOCAL_MEMORY_ENTRIES_PER_ROW),
+                        ),
+                        (MipsAirId::Global, num_global_events),
+                    ]);
+                }
+            }
+            heights.retain(|_, height| *height > 0);
+
+            // Fit the shard to a shape.
+            if let Some(shape_config) = shape_config {
+                shape_config.fix_shape(&mut record).map_err(ZKMCoreProverError::ShapeError)?;
+            }
+
+            let lde_cells = match &record.shape {
+                Some(shape) => {
+                    shape.iter().map(|(air, log2_height)| cost(&costs, air) << *log2_height).sum()
+                }
+                None => heights
+                    .iter()
+                    .map(|(air, height)| cost(&costs, air) * height.next_power_of_two() as u64)
+                    .sum(),
+            };
+            shards.push(ShardEstimate {
+                shard: shards.len() as u32 + 1,
+                heights,
+                shape: record.shape,
+                lde_cells,
+                lde_size: lde_cells * ((core::mem::size_of::<F>() << 1) as u64),
+            });
+        }
+
+        if done {
+            break;
+        }
+    }
+
+    // The prover keeps the traces of a batch of shards in memory at once.
+    let peak_memory = shards
+        .windows(opts.shard_batch_size.clamp(1, shards.len().max(1)))
+        .map(|batch| batch.iter().map(|shard| shard.lde_size).sum())
+        .max()
+        .unwrap_or_default();
+
+    Ok(ExecutionEstimate {
+        cycles: runtime.state.global_clk,
+        shards,
+        peak_memory,
+        report: runtime.report,
+    })
+}
+
+fn cost(costs: &HashMap<String, u64>, air: &MipsAirId) -> u64 {
+    costs.get(air.as_str()).copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_koala_bear::KoalaBear;
+    use zkm_core_executor::{MipsAirId, Program};
+    use zkm_stark::ZKMCoreOpts;
+
+    use super::estimate_with_context;
+    use crate::{io::ZKMStdin, programs::tests::fibonacci_program, shape::CoreShapeConfig};
+    use test_artifacts::KECCAK_SPONGE_ELF;
+
+    fn estimate(mut program: Program) -> super::ExecutionEstimate {
+        let shape_config = CoreShapeConfig::<KoalaBear>::default();
+        shape_config.fix_preprocessed_shape(&mut program).unwrap();
+        estimate_with_context(
+            program,
+            &ZKMStdin::new(),
+            ZKMCoreOpts::default(),
+            Default::default(),
+            Some(&shape_config),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_estimate_fibonacci() {
+        let estimate = estimate(fibonacci_program());
+        assert!(estimate.num_shards() > 0);
+        assert!(estimate.cycles > 0);
+        assert!(estimate.shards[0].heights[&MipsAirId::Cpu] > 0);
+
+        for shard in estimate.shards.iter() {
+            let shape = shard.shape.as_ref().unwrap();
+            for (air, height) in shard.heights.iter() {
+                assert!(*height <= 1 << shape.log2_height(air).unwrap());
+            }
+        }
+        assert!(estimate.peak_memory >= estimate.shards.iter().map(|s| s.lde_size).max().unwrap());
+        assert!(estimate.estimated_prover_time() > std::time::Duration::ZERO);
+    }
+
+    #[test]
+    fn test_estimate_precompile_shard() {
+        let estimate = estimate(Program::from(KECCAK_SPONGE_ELF).unwrap());
+        assert!(estimate
+            .shards
+            .iter()
+            .any(|shard| shard.heights.contains_key(&MipsAirId::KeccakSponge)
+                && !shard.heights.contains_key(&MipsAirId::Cpu)));
+    }
+}
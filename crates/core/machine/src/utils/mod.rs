@@ -1,11 +1,17 @@
+mod checkpoint;
 pub mod concurrency;
+mod estimate;
 mod logger;
+mod progress;
 mod prove;
 mod span;
 mod tracer;
 
+pub use checkpoint::*;
+pub use estimate::*;
 pub use logger::*;
 use p3_field::Field;
+pub use progress::*;
 pub use prove::*;
 pub use span::*;
 pub use tracer::*;
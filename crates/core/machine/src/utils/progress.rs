@@ -0,0 +1,101 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// A stage of proof generation reported to the listener of a [`ProverMonitor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofProgress {
+    /// The program was fully executed, and the number of shards to prove is known.
+    ExecutionDone { num_shards: usize },
+    /// A core shard was proven. The total is known once the execution is done.
+    ShardProven { proven: usize, total: Option<usize> },
+    /// A recursion proof of the given layer of the compress tree was proven.
+    CompressProven { layer: usize, num_layers: usize, proven: usize, total: usize },
+    /// The compressed proof was shrunk.
+    Shrunk,
+    /// The shrunk proof was wrapped into a proof over the BN254 field.
+    Wrapped,
+    /// The wrapped proof was proven with a SNARK.
+    SnarkProven,
+}
+
+/// A token to cancel a running proof generation.
+///
+/// Cancelling is cooperative: the prover stops at its next check, which happens at least between
+/// two batches of shards and two recursion proofs.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a new, not yet cancelled, token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the proof generation.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the proof generation was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Observes and controls a proof generation: reports its [`ProofProgress`] to a listener and
+/// stops it once its [`CancellationToken`] is cancelled.
+#[derive(Clone, Default)]
+pub struct ProverMonitor {
+    listener: Option<Arc<dyn Fn(ProofProgress) + Send + Sync>>,
+    cancellation: CancellationToken,
+}
+
+impl ProverMonitor {
+    /// Create a monitor without a listener, which is never cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report the progress to `listener`, which is called from the prover threads.
+    #[must_use]
+    pub fn with_listener(
+        mut self,
+        listener: impl Fn(ProofProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.listener = Some(Arc::new(listener));
+        self
+    }
+
+    /// Stop the proof generation once `cancellation` is cancelled.
+    #[must_use]
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Report progress to the listener, if any.
+    pub fn report(&self, progress: ProofProgress) {
+        if let Some(listener) = &self.listener {
+            listener(progress);
+        }
+    }
+
+    /// Whether the proof generation was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+}
+
+impl fmt::Debug for ProverMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProverMonitor")
+            .field("listener", &self.listener.is_some())
+            .field("cancellation", &self.cancellation)
+            .finish()
+    }
+}
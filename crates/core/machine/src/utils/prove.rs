@@ -3,7 +3,11 @@ use std::{
     io::{
         Seek, {self},
     },
-    sync::{mpsc::sync_channel, Arc, Mutex},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::sync_channel,
+        Arc, Mutex, OnceLock,
+    },
 };
 use web_time::Instant;
 
@@ -21,16 +25,19 @@ use zkm_stark::{
 use p3_field::PrimeField32;
 use p3_koala_bear::KoalaBear;
 
-use crate::shape::CoreShapeConfig;
+use crate::shape::{CoreShapeConfig, CoreShapeError};
 use crate::{
     io::ZKMStdin,
-    utils::{chunk_vec, concurrency::TurnBasedSync},
+    utils::{
+        chunk_vec, concurrency::TurnBasedSync, input_hash, write_checkpoint, CheckpointOpts,
+        ProofProgress, ProverMonitor, ProvingCheckpoint,
+    },
 };
 use zkm_core_executor::{
     events::{format_table_line, sorted_table_lines},
     subproof::NoOpSubproofVerifier,
-    ExecutionError, ExecutionRecord, ExecutionReport, ExecutionState, Executor, Program,
-    ZKMContext,
+    DeferredProofVerification, ExecutionError, ExecutionRecord, ExecutionReport, ExecutionState,
+    Executor, Program, ZKMContext,
 };
 use zkm_primitives::io::ZKMPublicValues;
 
@@ -42,6 +49,10 @@ use zkm_stark::{
     ZKMCoreOpts,
 };
 
+/// A snapshot of the execution state at the start of a batch, together with the state of the
+/// pipeline, to be persisted once all of the shards before the batch are proven.
+type PendingCheckpoint = (usize, ExecutionState, PublicValues<u32, u32>, ExecutionRecord);
+
 #[derive(Error, Debug)]
 pub enum ZKMCoreProverError {
     #[error("failed to execute program: {0}")]
@@ -50,6 +61,14 @@ pub enum ZKMCoreProverError {
     IoError(io::Error),
     #[error("serialization error: {0}")]
     SerializationError(bincode::Error),
+    #[error("invalid checkpoint: {0}")]
+    InvalidCheckpoint(String),
+    #[error("failed to fix the shape of a shard: {0}")]
+    ShapeError(CoreShapeError),
+    #[error("the proof generation was cancelled")]
+    Cancelled,
+    #[error("executions with Linux syscalls cannot be proven yet")]
+    LinuxSyscallsUnsupported,
 }
 
 pub fn prove_simple<SC: StarkGenericConfig, P: MachineProver<SC, MipsAir<SC::Val>>>(
@@ -138,20 +157,109 @@ where
     Com<SC>: Send + Sync,
     PcsProverData<SC>: Send + Sync,
 {
+    prove_with_checkpoints::<SC, P>(
+        prover,
+        pk,
+        program,
+        stdin,
+        opts,
+        context,
+        shape_config,
+        None,
+        None,
+        &ProverMonitor::default(),
+    )
+}
+
+/// Prove the execution of a program, optionally writing [`ProvingCheckpoint`]s according to
+/// `checkpoint_opts` and resuming from the checkpoint `resume`.
+///
+/// When resuming, the returned proof starts with the proofs of the shards before the checkpoint,
+/// which are stored in it, so it proves the whole execution. The same `stdin` must be passed,
+/// since the checkpoint is rejected if its input or deferred proofs differ.
+///
+/// The progress is reported to `monitor`, and the proof generation stops with
+/// [`ZKMCoreProverError::Cancelled`] once it is cancelled.
+#[allow(clippy::too_many_arguments)]
+pub fn prove_with_checkpoints<SC: StarkGenericConfig, P: MachineProver<SC, MipsAir<SC::Val>>>(
+    prover: &P,
+    pk: &P::DeviceProvingKey,
+    program: Program,
+    stdin: &ZKMStdin,
+    opts: ZKMCoreOpts,
+    context: ZKMContext,
+    shape_config: Option<&CoreShapeConfig<SC::Val>>,
+    checkpoint_opts: Option<&CheckpointOpts>,
+    resume: Option<ProvingCheckpoint>,
+    monitor: &ProverMonitor,
+) -> Result<(MachineProof<SC>, Vec<u8>, u64), ZKMCoreProverError>
+where
+    SC::Val: PrimeField32,
+    SC::Challenger: 'static + Clone + Send,
+    OpeningProof<SC>: Send,
+    Com<SC>: Send + Sync,
+    PcsProverData<SC>: Send + Sync,
+{
+    // No chip constrains the Linux syscalls, so these executions can only be run, not proven.
+    if context.preimage_oracle.is_some() {
+        return Err(ZKMCoreProverError::LinuxSyscallsUnsupported);
+    }
+
+    let input_hash = input_hash(stdin);
+    if let Some(checkpoint) = &resume {
+        checkpoint.validate(&program, stdin)?;
+    }
+
     // Setup the runtime.
     let mut runtime = Executor::with_context(program.clone(), opts, context);
     runtime.maximal_shapes = shape_config.map(|config| {
         config.maximal_core_shapes(opts.shard_size.ilog2() as usize).into_iter().collect()
     });
-    runtime.write_vecs(&stdin.buffer);
-    for proof in stdin.proofs.iter() {
-        let (proof, vk) = proof.clone();
-        runtime.write_proof(proof, vk);
-    }
+
+    // Setup the state of the pipeline, either from scratch or from the checkpoint.
+    let (first_index, initial_public_values, initial_deferred, initial_proofs) = match resume {
+        Some(checkpoint) => {
+            tracing::info!(
+                "resuming from checkpoint at execution shard {}",
+                checkpoint.header.execution_shard
+            );
+            let shard_proofs = checkpoint.shard_proofs::<SC>()?;
+            runtime.state = checkpoint.state;
+            // The deferred proofs were already verified before the checkpoint was created.
+            runtime.deferred_proof_verification = DeferredProofVerification::Disabled;
+            (
+                checkpoint.header.batch_index,
+                checkpoint.public_values,
+                checkpoint.deferred,
+                shard_proofs,
+            )
+        }
+        None => {
+            runtime.write_vecs(&stdin.buffer);
+            for proof in stdin.proofs.iter() {
+                let (proof, vk) = proof.clone();
+                runtime.write_proof(proof, vk);
+            }
+            (
+                0,
+                PublicValues::<u32, u32>::default().reset(),
+                ExecutionRecord::new(program.clone().into()),
+                Vec::new(),
+            )
+        }
+    };
 
     #[cfg(feature = "debug")]
     let (all_records_tx, all_records_rx) = std::sync::mpsc::channel::<Vec<ExecutionRecord>>();
 
+    // The number of shards to prove, known once the execution is done.
+    let initial_shard = initial_public_values.shard;
+    let num_initial_proofs = initial_proofs.len();
+    let num_shards = OnceLock::new();
+    let num_shards = &num_shards;
+    let num_proven = AtomicUsize::new(num_initial_proofs);
+    let num_proven = &num_proven;
+
     // Record the start of the process.
     let proving_start = Instant::now();
     let span = tracing::Span::current().clone();
@@ -161,17 +269,41 @@ where
         // Spawn the checkpoint generator thread.
         let checkpoint_generator_span = tracing::Span::current().clone();
         let (checkpoints_tx, checkpoints_rx) =
-            sync_channel::<(usize, File, bool)>(opts.checkpoints_channel_capacity);
+            sync_channel::<(usize, File, bool, Option<ExecutionState>)>(
+                opts.checkpoints_channel_capacity,
+            );
         let checkpoint_generator_handle: ScopedJoinHandle<Result<_, ZKMCoreProverError>> =
             s.spawn(move || {
                 let _span = checkpoint_generator_span.enter();
                 tracing::debug_span!("checkpoint generator").in_scope(|| {
-                    let mut index = 0;
+                    let mut index = first_index;
+                    let mut next_snapshot_shard = checkpoint_opts.map(|checkpoint_opts| {
+                        runtime.state.current_shard + checkpoint_opts.interval
+                    });
                     loop {
                         // Enter the span.
                         let span = tracing::debug_span!("batch");
                         let _span = span.enter();
 
+                        // Stop executing once the proof generation is cancelled. The trace
+                        // workers drain the checkpoints which were already sent.
+                        if monitor.is_cancelled() {
+                            break Err(ZKMCoreProverError::Cancelled);
+                        }
+
+                        // Snapshot the full execution state if a checkpoint is due, so that it can
+                        // be persisted together with the state of the pipeline.
+                        let snapshot = match (checkpoint_opts, next_snapshot_shard) {
+                            (Some(checkpoint_opts), Some(shard))
+                                if runtime.state.current_shard >= shard =>
+                            {
+                                next_snapshot_shard =
+                                    Some(runtime.state.current_shard + checkpoint_opts.interval);
+                                Some(runtime.state.clone())
+                            }
+                            _ => None,
+                        };
+
                         // Execute the runtime until we reach a checkpoint.
                         let (checkpoint, done) = runtime
                             .execute_state(false)
@@ -185,7 +317,7 @@ where
                             .map_err(ZKMCoreProverError::IoError)?;
 
                         // Send the checkpoint.
-                        checkpoints_tx.send((index, checkpoint_file, done)).unwrap();
+                        checkpoints_tx.send((index, checkpoint_file, done, snapshot)).unwrap();
 
                         // If we've reached the final checkpoint, break out of the loop.
                         if done {
@@ -207,14 +339,16 @@ where
         let p2_trace_gen_sync = Arc::new(TurnBasedSync::new());
         let checkpoints_rx = Arc::new(Mutex::new(checkpoints_rx));
         let (p2_records_and_traces_tx, p2_records_and_traces_rx) =
-            sync_channel::<(Vec<ExecutionRecord>, Vec<Vec<(String, RowMajorMatrix<Val<SC>>)>>)>(
-                opts.records_and_traces_channel_capacity,
-            );
+            sync_channel::<(
+                Option<PendingCheckpoint>,
+                Vec<ExecutionRecord>,
+                Vec<Vec<(String, RowMajorMatrix<Val<SC>>)>>,
+            )>(opts.records_and_traces_channel_capacity);
         let p2_records_and_traces_tx = Arc::new(Mutex::new(p2_records_and_traces_tx));
 
         let report_aggregate = Arc::new(Mutex::new(ExecutionReport::default()));
-        let state = Arc::new(Mutex::new(PublicValues::<u32, u32>::default().reset()));
-        let deferred = Arc::new(Mutex::new(ExecutionRecord::new(program.clone().into())));
+        let state = Arc::new(Mutex::new(initial_public_values));
+        let deferred = Arc::new(Mutex::new(initial_deferred));
         let mut p2_record_and_trace_gen_handles = Vec::new();
         for _ in 0..opts.trace_gen_workers {
             let record_gen_sync = Arc::clone(&p2_record_gen_sync);
@@ -238,7 +372,17 @@ where
                     loop {
                         // Receive the latest checkpoint.
                         let received = { checkpoints_rx.lock().unwrap().recv() };
-                        if let Ok((index, mut checkpoint, done)) = received {
+                        if let Ok((index, mut checkpoint, done, snapshot)) = received {
+                            // Skip the checkpoint once cancelled, but still take our turns so
+                            // that the other workers are not blocked.
+                            if monitor.is_cancelled() {
+                                record_gen_sync.wait_for_turn(index - first_index);
+                                record_gen_sync.advance_turn();
+                                trace_gen_sync.wait_for_turn(index - first_index);
+                                trace_gen_sync.advance_turn();
+                                continue;
+                            }
+
                             // Trace the checkpoint and reconstruct the execution records.
                             let (mut records, report) = tracing::debug_span!("trace checkpoint")
                                 .in_scope(|| {
@@ -254,22 +398,24 @@ where
                             reset_seek(&mut checkpoint);
 
                             // Wait for our turn to update the state.
-                            record_gen_sync.wait_for_turn(index);
+                            record_gen_sync.wait_for_turn(index - first_index);
+
+                            // Capture the snapshot together with the state of the pipeline, which
+                            // now reflects all of the batches before this one. It is persisted by
+                            // the prover once the shards of these batches are proven.
+                            let pending = snapshot.map(|snapshot| {
+                                let public_values = *state.lock().unwrap();
+                                let deferred = deferred.lock().unwrap().clone();
+                                (index, snapshot, public_values, deferred)
+                            });
 
                             // Update the public values & prover state for the shards which contain
                             // "cpu events".
                             let mut state = state.lock().unwrap();
-                            for record in records.iter_mut() {
-                                state.shard += 1;
-                                state.execution_shard = record.public_values.execution_shard;
-                                state.start_pc = record.public_values.start_pc;
-                                state.next_pc = record.public_values.next_pc;
-                                state.committed_value_digest =
-                                    record.public_values.committed_value_digest;
-                                state.deferred_proofs_digest =
-                                    record.public_values.deferred_proofs_digest;
-                                record.public_values = *state;
-                            }
+                            assign_cpu_public_values(
+                                &mut state,
+                                records.iter_mut().map(|record| &mut record.public_values),
+                            );
 
                             // Defer events that are too expensive to include in every shard.
                             let mut deferred = deferred.lock().unwrap();
@@ -278,28 +424,18 @@ where
                             }
 
                             // See if any deferred shards are ready to be committed to.
-                            let mut deferred = deferred.split(done, opts.split_opts);
+                            let mut deferred =
+                                split_deferred_records(&mut state, &mut deferred, done, opts);
                             log::debug!("deferred {} records", deferred.len());
+                            records.append(&mut deferred);
 
-                            // Update the public values & prover state for the shards which do not
-                            // contain "cpu events" before committing to them.
-                            if !done {
-                                state.execution_shard += 1;
-                            }
-                            for record in deferred.iter_mut() {
-                                state.shard += 1;
-                                state.previous_init_addr_bits =
-                                    record.public_values.previous_init_addr_bits;
-                                state.last_init_addr_bits =
-                                    record.public_values.last_init_addr_bits;
-                                state.previous_finalize_addr_bits =
-                                    record.public_values.previous_finalize_addr_bits;
-                                state.last_finalize_addr_bits =
-                                    record.public_values.last_finalize_addr_bits;
-                                state.start_pc = state.next_pc;
-                                record.public_values = *state;
+                            // All the shards are known once the last checkpoint is split.
+                            if done {
+                                let total =
+                                    (state.shard - initial_shard) as usize + num_initial_proofs;
+                                num_shards.set(total).unwrap();
+                                monitor.report(ProofProgress::ExecutionDone { num_shards: total });
                             }
-                            records.append(&mut deferred);
 
                             // Generate the dependencies.
                             tracing::debug_span!("generate dependencies", index).in_scope(|| {
@@ -327,9 +463,16 @@ where
                                     .collect::<Vec<_>>();
                             });
 
-                            trace_gen_sync.wait_for_turn(index);
+                            trace_gen_sync.wait_for_turn(index - first_index);
 
-                            // Send the records to the phase 2 prover.
+                            // Send the pending checkpoint and the records to the phase 2 prover.
+                            if pending.is_some() {
+                                records_and_traces_tx
+                                    .lock()
+                                    .unwrap()
+                                    .send((pending, Vec::new(), Vec::new()))
+                                    .unwrap();
+                            }
                             let chunked_records = chunk_vec(records, opts.shard_batch_size);
                             let chunked_main_traces = chunk_vec(main_traces, opts.shard_batch_size);
                             chunked_records
@@ -339,7 +482,7 @@ where
                                     records_and_traces_tx
                                         .lock()
                                         .unwrap()
-                                        .send((records, main_traces))
+                                        .send((None, records, main_traces))
                                         .unwrap();
                                 });
 
@@ -358,11 +501,36 @@ where
 
         // Spawn the phase 2 prover thread.
         let p2_prover_span = tracing::Span::current().clone();
+        let program = program.clone();
         let p2_prover_handle = s.spawn(move || {
             let _span = p2_prover_span.enter();
-            let mut shard_proofs = Vec::new();
+            let mut shard_proofs = initial_proofs;
             tracing::debug_span!("phase 2 prover").in_scope(|| {
-                for (records, traces) in p2_records_and_traces_rx.into_iter() {
+                for (pending, records, traces) in p2_records_and_traces_rx.into_iter() {
+                    // Drain the remaining batches once cancelled.
+                    if monitor.is_cancelled() {
+                        continue;
+                    }
+
+                    // The batches arrive in order, so all of the shards before a pending
+                    // checkpoint are proven by now.
+                    if let (
+                        Some(checkpoint_opts),
+                        Some((index, snapshot, public_values, deferred)),
+                    ) = (checkpoint_opts, pending)
+                    {
+                        write_checkpoint(
+                            checkpoint_opts,
+                            &program,
+                            input_hash,
+                            index,
+                            snapshot,
+                            public_values,
+                            deferred,
+                            &shard_proofs,
+                        );
+                    }
+
                     tracing::debug_span!("batch").in_scope(|| {
                         let span = tracing::Span::current().clone();
                         shard_proofs.par_extend(
@@ -396,6 +564,12 @@ where
                                         drop(record);
                                     });
 
+                                    let proven = num_proven.fetch_add(1, Ordering::Relaxed) + 1;
+                                    monitor.report(ProofProgress::ShardProven {
+                                        proven,
+                                        total: num_shards.get().copied(),
+                                    });
+
                                     proof
                                 },
                             ),
@@ -407,7 +581,7 @@ where
         });
 
         // Wait until the checkpoint generator handle has fully finished.
-        let public_values_stream = checkpoint_generator_handle.join().unwrap().unwrap();
+        let public_values_stream = checkpoint_generator_handle.join().unwrap();
 
         // Wait until the records and traces have been fully generated for phase 2.
         p2_record_and_trace_gen_handles.into_iter().for_each(|handle| handle.join().unwrap());
@@ -415,6 +589,12 @@ where
         // Wait until the phase 2 prover has finished.
         let shard_proofs = p2_prover_handle.join().unwrap();
 
+        // Only fail once all the threads are done, so that none of them outlives the call.
+        let public_values_stream = public_values_stream?;
+        if monitor.is_cancelled() {
+            return Err(ZKMCoreProverError::Cancelled);
+        }
+
         // Log some of the `ExecutionReport` information.
         let report_aggregate = report_aggregate.lock().unwrap();
         tracing::info!(
@@ -610,11 +790,28 @@ fn trace_checkpoint<SC: StarkGenericConfig>(
 where
     <SC as StarkGenericConfig>::Val: PrimeField32,
 {
-    let noop = NoOpSubproofVerifier;
-
     let mut reader = std::io::BufReader::new(file);
     let state: ExecutionState =
         bincode::deserialize_from(&mut reader).expect("failed to deserialize state");
+    trace_state::<SC>(program, state, opts, shape_config)
+}
+
+/// Re-execute the batch of shards starting at the checkpointed `state` and reconstruct their
+/// execution records.
+///
+/// The public values of the records still have to be assigned with [`assign_cpu_public_values`]
+/// and their expensive events deferred before they can be proven.
+pub fn trace_state<SC: StarkGenericConfig>(
+    program: Program,
+    state: ExecutionState,
+    opts: ZKMCoreOpts,
+    shape_config: Option<&CoreShapeConfig<SC::Val>>,
+) -> (Vec<ExecutionRecord>, ExecutionReport)
+where
+    <SC as StarkGenericConfig>::Val: PrimeField32,
+{
+    let noop = NoOpSubproofVerifier;
+
     let mut runtime = Executor::recover(program, state, opts);
     runtime.maximal_shapes = shape_config.map(|config| {
         config.maximal_core_shapes(opts.shard_size.ilog2() as usize).into_iter().collect()
@@ -630,6 +827,52 @@ where
     (records, runtime.report)
 }
 
+/// Update the public values & prover state for the shards which contain "cpu events".
+///
+/// The public values of the shards must be visited in execution order, starting from the state
+/// left by the previous batch.
+pub fn assign_cpu_public_values<'a>(
+    state: &mut PublicValues<u32, u32>,
+    public_values: impl IntoIterator<Item = &'a mut PublicValues<u32, u32>>,
+) {
+    for public_values in public_values {
+        state.shard += 1;
+        state.execution_shard = public_values.execution_shard;
+        state.start_pc = public_values.start_pc;
+        state.next_pc = public_values.next_pc;
+        state.committed_value_digest = public_values.committed_value_digest;
+        state.deferred_proofs_digest = public_values.deferred_proofs_digest;
+        *public_values = *state;
+    }
+}
+
+/// Split off the deferred shards which are ready to be committed to, and update the public values
+/// & prover state for them since they do not contain "cpu events".
+///
+/// This must be called once per batch, after [`assign_cpu_public_values`], with `done` set for the
+/// last batch of the execution.
+pub fn split_deferred_records(
+    state: &mut PublicValues<u32, u32>,
+    deferred: &mut ExecutionRecord,
+    done: bool,
+    opts: ZKMCoreOpts,
+) -> Vec<ExecutionRecord> {
+    let mut records = deferred.split(done, opts.split_opts);
+    if !done {
+        state.execution_shard += 1;
+    }
+    for record in records.iter_mut() {
+        state.shard += 1;
+        state.previous_init_addr_bits = record.public_values.previous_init_addr_bits;
+        state.last_init_addr_bits = record.public_values.last_init_addr_bits;
+        state.previous_finalize_addr_bits = record.public_values.previous_finalize_addr_bits;
+        state.last_finalize_addr_bits = record.public_values.last_finalize_addr_bits;
+        state.start_pc = state.next_pc;
+        record.public_values = *state;
+    }
+    records
+}
+
 fn reset_seek(file: &mut File) {
     file.seek(std::io::SeekFrom::Start(0)).expect("failed to seek to start of tempfile");
 }
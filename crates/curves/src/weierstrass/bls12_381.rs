@@ -53,6 +53,7 @@ impl FieldParameters for Bls12381BaseField {
 
 impl FpOpField for Bls12381BaseField {
     const FIELD_TYPE: FieldType = FieldType::Bls12381;
+    const FP6_NON_RESIDUE_C0: u32 = 1;
 }
 
 impl NumLimbs for Bls12381BaseField {
@@ -287,6 +287,10 @@ pub enum FieldType {
 
 pub trait FpOpField: FieldParameters + NumWords {
     const FIELD_TYPE: FieldType;
+
+    /// The constant coefficient `c0` of the non-residue `c0 + u` defining the sextic extension
+    /// `Fp6 = Fp2[v] / (v^3 - (c0 + u))`.
+    const FP6_NON_RESIDUE_C0: u32;
 }
 
 #[cfg(test)]
@@ -56,6 +56,32 @@ pub mod fd {
         /// The file descriptor through which to access `hook_bls12_381_inverse`.
         pub const FD_BLS12_381_INVERSE: u32 = 10;
 
+        /// The file descriptor through which to read the environment variables set by the host.
+        pub const FD_GETENV: u32 = 11;
+
+        /// The file descriptor through which to read the random number generator seed set by the
+        /// host.
+        pub const FD_RNG_SEED: u32 = 12;
+    }
+
+    /// The lowest file descriptor of the typed hooks, see [`typed_hook_fd`].
+    pub const TYPED_HOOK_FD_START: u32 = 1 << 31;
+
+    /// Returns the file descriptor through which to access the typed hook registered under `name`.
+    ///
+    /// The file descriptor is the 32-bit FNV-1a hash of the name with the highest bit set, so that
+    /// the host and the guest agree on it without a shared table, and it never collides with the
+    /// fixed file descriptors above.
+    pub const fn typed_hook_fd(name: &str) -> u32 {
+        let bytes = name.as_bytes();
+        let mut hash: u32 = 0x811c_9dc5;
+        let mut i = 0;
+        while i < bytes.len() {
+            hash ^= bytes[i] as u32;
+            hash = hash.wrapping_mul(0x0100_0193);
+            i += 1;
+        }
+        hash | TYPED_HOOK_FD_START
     }
 }
 
@@ -1120,7 +1120,7 @@ pub fn poseidon2_init() -> Poseidon2KoalaBear<16> {
     Poseidon2::new(external_round_constants, internal_round_constants)
 }
 
-use p3_symmetric::{CryptographicHasher, PaddingFreeSponge};
+use p3_symmetric::{CryptographicHasher, PaddingFreeSponge, Permutation};
 
 pub fn poseidon2_hash(input: Vec<KoalaBear>) -> [KoalaBear; 8] {
     POSEIDON2_HASHER.hash_iter(input)
@@ -1134,6 +1134,12 @@ pub fn poseidon2_hasher() -> PaddingFreeSponge<Poseidon2KoalaBear<16>, 16, 8, 8>
 lazy_static! {
     pub static ref POSEIDON2_HASHER: PaddingFreeSponge::<Poseidon2KoalaBear<16>, 16, 8, 8> =
         poseidon2_hasher();
+    pub static ref POSEIDON2_PERM: Poseidon2KoalaBear<16> = poseidon2_init();
+}
+
+/// Apply the width 16 Poseidon2 permutation used by the recursion stack to `state`.
+pub fn poseidon2_permute(state: [KoalaBear; 16]) -> [KoalaBear; 16] {
+    POSEIDON2_PERM.permute(state)
 }
 
 /// Append a single deferred proof to a hash chain of deferred proofs.
@@ -63,3 +63,42 @@ impl Default for Buffer {
         Self::new()
     }
 }
+
+/// A proof that a vkey digest belongs to a vkey registry, the Merkle tree over the vkey digests of
+/// the programs whose proofs are accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VkeyMembershipProof {
+    /// The index of the vkey digest in the registry.
+    pub index: usize,
+    /// The digests of the siblings on the path from the leaf to the root, as canonical KoalaBear
+    /// elements.
+    pub path: Vec<[u32; 8]>,
+}
+
+impl VkeyMembershipProof {
+    /// Computes the root of the registry from the vkey digest, with `compress` compressing two
+    /// nodes into their parent.
+    ///
+    /// The leaves are stored in bit-reversed order, like in the Merkle trees of the recursion
+    /// stack.
+    pub fn root(
+        &self,
+        vk_digest: &[u32; 8],
+        compress: impl Fn(&[u32; 8], &[u32; 8]) -> [u32; 8],
+    ) -> [u32; 8] {
+        let height = self.path.len();
+        assert!(height < usize::BITS as usize && self.index >> height == 0, "index out of bounds");
+        let mut index = if height == 0 {
+            0
+        } else {
+            self.index.reverse_bits() >> (usize::BITS as usize - height)
+        };
+
+        let mut node = *vk_digest;
+        for sibling in &self.path {
+            node = if index % 2 == 0 { compress(&node, sibling) } else { compress(sibling, &node) };
+            index >>= 1;
+        }
+        node
+    }
+}
@@ -80,6 +80,9 @@ fi
 cp ./build/groth16/groth16_vk.bin ../verifier/bn254-vk/groth16_vk.bin
 cp ./build/plonk/plonk_vk.bin ../verifier/bn254-vk/plonk_vk.bin
 
+# Build the compress machine description into the compressed verifier crate
+cargo run --release --bin build_compress_machine -- --build-dir ../compressed-verifier/compress-machine
+
 echo "Successfully uploaded build artifacts to S3:"
 echo "- s3://$S3_BUCKET/$GROTH16_ARCHIVE"
 echo "- s3://$S3_BUCKET/$PLONK_ARCHIVE"
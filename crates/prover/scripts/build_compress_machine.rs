@@ -0,0 +1,21 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use zkm_core_machine::utils::setup_logger;
+use zkm_prover::{build::build_compress_machine_to_file, ZKMProver};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(short, long)]
+    build_dir: PathBuf,
+}
+
+pub fn main() {
+    setup_logger();
+    let args = Args::parse();
+    // The description pins the allowed recursion verifying keys of `vk_map.bin`.
+    std::env::set_var("VERIFY_VK", "true");
+    let prover = ZKMProver::new();
+    build_compress_machine_to_file(&prover, args.build_dir);
+}
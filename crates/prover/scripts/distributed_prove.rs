@@ -0,0 +1,76 @@
+use std::{net::TcpListener, path::PathBuf, process::Command};
+
+use clap::Parser;
+use zkm_core_executor::ZKMContext;
+use zkm_core_machine::{io::ZKMStdin, utils::setup_logger};
+use zkm_prover::{components::DefaultProverComponents, distributed::ProverCoordinator, ZKMProver};
+use zkm_stark::ZKMProverOpts;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// The program to prove.
+    #[clap(short, long)]
+    elf: PathBuf,
+    /// The bincode-serialized `ZKMStdin` of the program.
+    #[clap(short, long)]
+    stdin: Option<PathBuf>,
+    /// The address to listen on for workers.
+    #[clap(short, long, default_value = "127.0.0.1:7878")]
+    addr: String,
+    /// The number of workers to wait for.
+    #[clap(short, long, default_value_t = 2)]
+    workers: usize,
+    /// Spawn the workers as child processes of the `prover_worker` binary next to this one,
+    /// instead of waiting for them to be started separately.
+    #[clap(long, default_value_t = false)]
+    spawn_workers: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    setup_logger();
+    let args = Args::parse();
+
+    let elf = std::fs::read(&args.elf)?;
+    let stdin = match &args.stdin {
+        Some(path) => bincode::deserialize(&std::fs::read(path)?)?,
+        None => ZKMStdin::default(),
+    };
+
+    let listener = TcpListener::bind(&args.addr)?;
+    let mut children = Vec::new();
+    if args.spawn_workers {
+        let worker_bin = std::env::current_exe()?.with_file_name("prover_worker");
+        for _ in 0..args.workers {
+            children.push(
+                Command::new(&worker_bin)
+                    .args(["--coordinator", &listener.local_addr()?.to_string()])
+                    .spawn()?,
+            );
+        }
+    }
+
+    let prover = ZKMProver::<DefaultProverComponents>::new();
+    let opts = ZKMProverOpts::default();
+    let (pk, vk) = prover.setup(&elf);
+
+    tracing::info!("waiting for {} workers on {}", args.workers, listener.local_addr()?);
+    let coordinator = ProverCoordinator::accept(&listener, args.workers)?;
+    coordinator.setup(&pk, opts)?;
+
+    tracing::info!("prove core");
+    let core_proof = coordinator.prove_core(&prover, &pk, &stdin, opts, ZKMContext::default())?;
+    prover.verify(&core_proof.proof, &vk)?;
+
+    tracing::info!("compress");
+    let compressed_proof = coordinator.compress(&prover, &vk, core_proof, vec![], opts)?;
+    prover.verify_compressed(&compressed_proof, &vk)?;
+
+    coordinator.shutdown()?;
+    for mut child in children {
+        child.wait()?;
+    }
+
+    tracing::info!("the compressed proof is valid");
+    Ok(())
+}
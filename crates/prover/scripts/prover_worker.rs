@@ -0,0 +1,38 @@
+use std::{thread, time::Duration};
+
+use clap::Parser;
+use zkm_core_machine::utils::setup_logger;
+use zkm_prover::{
+    components::DefaultProverComponents,
+    distributed::{DistributedProverError, ProverWorker},
+    ZKMProver,
+};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// The address of the coordinator.
+    #[clap(short, long, default_value = "127.0.0.1:7878")]
+    coordinator: String,
+    /// How many times to retry connecting while the coordinator is not listening yet.
+    #[clap(short, long, default_value_t = 30)]
+    retries: usize,
+}
+
+fn main() -> Result<(), DistributedProverError> {
+    setup_logger();
+    let args = Args::parse();
+
+    let worker = ProverWorker::new(ZKMProver::<DefaultProverComponents>::new());
+    let mut retries = args.retries;
+    loop {
+        match worker.connect(&args.coordinator) {
+            Err(DistributedProverError::IoError(e)) if retries > 0 => {
+                tracing::warn!("failed to connect to {}: {}", args.coordinator, e);
+                retries -= 1;
+                thread::sleep(Duration::from_secs(1));
+            }
+            result => return result,
+        }
+    }
+}
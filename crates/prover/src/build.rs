@@ -1,8 +1,12 @@
-use std::{borrow::Borrow, path::PathBuf};
+use std::{borrow::Borrow, collections::HashMap, path::PathBuf};
 
+use p3_air::{BaseAir, VirtualPairCol};
+use p3_field::{Field, FieldAlgebra};
 use p3_koala_bear::KoalaBear;
+use p3_uni_stark::{get_symbolic_constraints, Entry, SymbolicExpression};
 use zkm_core_executor::ZKMContext;
 use zkm_core_machine::io::ZKMStdin;
+use zkm_primitives::RC_16_30;
 use zkm_recursion_circuit::{
     hash::FieldHasherVariable,
     machine::{ZKMCompressWitnessValues, ZKMWrapVerifier},
@@ -18,12 +22,21 @@ pub use zkm_recursion_core::stark::zkm_dev_mode;
 
 pub use zkm_recursion_circuit::witness::{OuterWitness, Witnessable};
 
+use zkm_compressed_verifier::{
+    ChipDescription, CompressMachine, ConstraintNode, FriParameters, LinearColumn,
+    LookupDescription,
+};
 use zkm_recursion_gnark_ffi::{Groth16Bn254Prover, PlonkBn254Prover};
-use zkm_stark::{ShardProof, StarkVerifyingKey, ZKMProverOpts};
+use zkm_stark::{
+    air::{LookupScope, MachineAir},
+    koala_bear_poseidon2::default_fri_config,
+    Chip, Lookup, MachineProver, ShardProof, StarkVerifyingKey, ZKMProverOpts, PROOF_MAX_NUM_PVS,
+};
 
 use crate::{
+    components::ZKMProverComponents,
     utils::{koalabear_bytes_to_bn254, koalabears_to_bn254, words_to_bytes},
-    OuterSC, WrapAir, ZKMProver,
+    CompressAir, OuterSC, WrapAir, ZKMProver,
 };
 
 /// Tries to build the PLONK artifacts inside the development directory.
@@ -208,3 +221,151 @@ fn build_outer_circuit(template_input: &ZKMCompressWitnessValues<OuterSC>) -> Ve
 
     operations
 }
+
+/// Builds the description of the compress machine, which the compressed proof verifier of
+/// `zkm-compressed-verifier` checks proofs against.
+///
+/// The description pins the allowed recursion verifying keys, so the prover must verify them,
+/// i.e. be created with `VERIFY_VK=true`.
+pub fn build_compress_machine<C: ZKMProverComponents>(prover: &ZKMProver<C>) -> CompressMachine {
+    assert!(
+        prover.vk_verification,
+        "the compress machine must be built by a prover with VERIFY_VK=true"
+    );
+    let machine = prover.compress_prover.machine();
+    let fri_config = default_fri_config();
+    CompressMachine {
+        poseidon2_round_constants: RC_16_30.to_vec(),
+        fri: FriParameters {
+            log_blowup: fri_config.log_blowup,
+            num_queries: fri_config.num_queries,
+            proof_of_work_bits: fri_config.proof_of_work_bits,
+        },
+        num_pv_elts: machine.num_pv_elts(),
+        vk_root: prover.recursion_vk_root,
+        allowed_vk_digests: prover.recursion_vk_map.keys().copied().collect(),
+        chips: machine.chips().iter().map(describe_chip).collect(),
+    }
+}
+
+/// Builds the description of the compress machine into `build_dir/compress_machine.bin`.
+pub fn build_compress_machine_to_file<C: ZKMProverComponents>(
+    prover: &ZKMProver<C>,
+    build_dir: impl Into<PathBuf>,
+) {
+    let build_dir = build_dir.into();
+    std::fs::create_dir_all(&build_dir).expect("failed to create build directory");
+    let machine = build_compress_machine(prover);
+    let bytes = bincode::serialize(&machine).expect("failed to serialize compress machine");
+    std::fs::write(build_dir.join("compress_machine.bin"), bytes)
+        .expect("failed to write compress machine");
+}
+
+fn describe_chip(chip: &Chip<KoalaBear, CompressAir<KoalaBear>>) -> ChipDescription {
+    // The compressed proof verifier only supports local lookups.
+    assert_eq!(chip.commit_scope(), LookupScope::Local, "chip {} is global", chip.name());
+    assert!(
+        chip.sends().iter().chain(chip.receives()).all(|lookup| lookup.scope == LookupScope::Local),
+        "chip {} has global lookups",
+        chip.name()
+    );
+
+    let describe_lookup = |lookup: &Lookup<KoalaBear>| LookupDescription {
+        argument_index: lookup.argument_index(),
+        values: lookup.values.iter().map(|value| describe_column(chip, value)).collect(),
+        multiplicity: describe_column(chip, &lookup.multiplicity),
+    };
+
+    // Flatten the constraints into a list of nodes, sharing the common subexpressions.
+    let constraints =
+        get_symbolic_constraints(&chip.air, chip.preprocessed_width(), PROOF_MAX_NUM_PVS);
+    let mut nodes = Vec::new();
+    let mut node_indices = HashMap::new();
+    let constraints = constraints
+        .iter()
+        .map(|constraint| describe_node(constraint, &mut nodes, &mut node_indices))
+        .collect();
+
+    ChipDescription {
+        name: chip.name(),
+        preprocessed_width: chip.preprocessed_width(),
+        width: chip.width(),
+        log_quotient_degree: chip.log_quotient_degree(),
+        local_only: chip.local_only(),
+        num_sent_byte_lookups: chip.num_sent_byte_lookups(),
+        sends: chip.sends().iter().map(describe_lookup).collect(),
+        receives: chip.receives().iter().map(describe_lookup).collect(),
+        nodes,
+        constraints,
+    }
+}
+
+/// Recovers the weights of the affine column by evaluating it on the unit rows.
+fn describe_column(
+    chip: &Chip<KoalaBear, CompressAir<KoalaBear>>,
+    column: &VirtualPairCol<KoalaBear>,
+) -> LinearColumn {
+    let preprocessed = vec![KoalaBear::ZERO; chip.preprocessed_width()];
+    let main = vec![KoalaBear::ZERO; chip.width()];
+    let constant = column.apply::<KoalaBear, KoalaBear>(&preprocessed, &main);
+
+    let weights = |row: &[KoalaBear], eval: &dyn Fn(&[KoalaBear]) -> KoalaBear| {
+        (0..row.len())
+            .filter_map(|i| {
+                let mut unit = row.to_vec();
+                unit[i] = KoalaBear::ONE;
+                let weight = eval(&unit) - constant;
+                (!weight.is_zero()).then_some((i, weight))
+            })
+            .collect()
+    };
+    LinearColumn {
+        preprocessed: weights(&preprocessed, &|row| column.apply(row, &main)),
+        main: weights(&main, &|row| column.apply(&preprocessed, row)),
+        constant,
+    }
+}
+
+fn describe_node(
+    expression: &SymbolicExpression<KoalaBear>,
+    nodes: &mut Vec<ConstraintNode>,
+    node_indices: &mut HashMap<*const SymbolicExpression<KoalaBear>, usize>,
+) -> usize {
+    let key = expression as *const _;
+    if let Some(index) = node_indices.get(&key) {
+        return *index;
+    }
+
+    let node = match expression {
+        SymbolicExpression::Variable(v) => match v.entry {
+            Entry::Preprocessed { offset } => {
+                ConstraintNode::Preprocessed { offset, index: v.index }
+            }
+            Entry::Main { offset } => ConstraintNode::Main { offset, index: v.index },
+            Entry::Public => ConstraintNode::Public(v.index),
+            entry => panic!("unexpected variable {entry:?} in the constraints"),
+        },
+        SymbolicExpression::IsFirstRow => ConstraintNode::IsFirstRow,
+        SymbolicExpression::IsLastRow => ConstraintNode::IsLastRow,
+        SymbolicExpression::IsTransition => ConstraintNode::IsTransition,
+        SymbolicExpression::Constant(c) => ConstraintNode::Constant(*c),
+        SymbolicExpression::Add { x, y, .. } => ConstraintNode::Add(
+            describe_node(x, nodes, node_indices),
+            describe_node(y, nodes, node_indices),
+        ),
+        SymbolicExpression::Sub { x, y, .. } => ConstraintNode::Sub(
+            describe_node(x, nodes, node_indices),
+            describe_node(y, nodes, node_indices),
+        ),
+        SymbolicExpression::Neg { x, .. } => {
+            ConstraintNode::Neg(describe_node(x, nodes, node_indices))
+        }
+        SymbolicExpression::Mul { x, y, .. } => ConstraintNode::Mul(
+            describe_node(x, nodes, node_indices),
+            describe_node(y, nodes, node_indices),
+        ),
+    };
+    nodes.push(node);
+    node_indices.insert(key, nodes.len() - 1);
+    nodes.len() - 1
+}
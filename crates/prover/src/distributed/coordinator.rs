@@ -0,0 +1,361 @@
+use std::{
+    collections::BTreeMap,
+    net::TcpListener,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::sync_channel,
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use tracing::instrument;
+use zkm_core_executor::{ExecutionRecord, ExecutionReport, ExecutionState, Executor, ZKMContext};
+use zkm_core_machine::{
+    io::ZKMStdin,
+    reduce::ZKMReduceProof,
+    utils::{assign_cpu_public_values, concurrency::TurnBasedSync, split_deferred_records},
+};
+use zkm_primitives::io::ZKMPublicValues;
+use zkm_recursion_circuit::machine::ZKMCompressWitnessValues;
+use zkm_stark::{air::PublicValues, ShardProof, StarkVerifyingKey, ZKMProverOpts};
+
+use super::{
+    attach_program, detach_program, Connection, DistributedProverError, WorkerRequest,
+    WorkerResponse,
+};
+use crate::{
+    components::ZKMProverComponents, CoreSC, InnerSC, ZKMCircuitWitness, ZKMCoreProof,
+    ZKMCoreProofData, ZKMProver, ZKMProvingKey, ZKMVerifyingKey, REDUCE_BATCH_SIZE,
+};
+
+/// A connection to a [`ProverWorker`](super::ProverWorker).
+struct WorkerConnection(Connection);
+
+impl WorkerConnection {
+    /// Send a request and wait for the response of the worker.
+    fn request(
+        &mut self,
+        request: &WorkerRequest,
+    ) -> Result<WorkerResponse, DistributedProverError> {
+        self.0.send(request)?;
+        match self.0.recv()? {
+            WorkerResponse::Error(e) => Err(DistributedProverError::WorkerError(e)),
+            response => Ok(response),
+        }
+    }
+}
+
+/// Coordinates the proving of a program across the workers connected to it.
+pub struct ProverCoordinator {
+    workers: Vec<Mutex<WorkerConnection>>,
+}
+
+impl ProverCoordinator {
+    /// Wait for `num_workers` workers to connect to `listener`.
+    pub fn accept(
+        listener: &TcpListener,
+        num_workers: usize,
+    ) -> Result<Self, DistributedProverError> {
+        let mut workers = Vec::with_capacity(num_workers);
+        while workers.len() < num_workers {
+            let (stream, addr) = listener.accept()?;
+            tracing::info!("worker {} connected from {}", workers.len(), addr);
+            workers.push(Mutex::new(WorkerConnection(Connection::new(stream)?)));
+        }
+        Ok(Self { workers })
+    }
+
+    /// The number of connected workers.
+    pub fn num_workers(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Set up the program of `pk` on every worker. Must be called before [`Self::prove_core`].
+    #[instrument(name = "setup workers", level = "info", skip_all)]
+    pub fn setup(
+        &self,
+        pk: &ZKMProvingKey,
+        opts: ZKMProverOpts,
+    ) -> Result<(), DistributedProverError> {
+        let request = WorkerRequest::Setup { elf: pk.elf.clone(), opts };
+        thread::scope(|s| {
+            let handles = self
+                .workers
+                .iter()
+                .map(|worker| s.spawn(|| worker.lock().unwrap().request(&request)))
+                .collect::<Vec<_>>();
+            for handle in handles {
+                match handle.join().unwrap()? {
+                    WorkerResponse::Ready => {}
+                    response => return Err(unexpected(&response)),
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Generate the shard proofs of a program like [`ZKMProver::prove_core`], with the shards
+    /// proven by the workers.
+    ///
+    /// The checkpoints are re-executed by the workers, which do not have access to the preimage
+    /// oracle of `context`, so the Linux syscall mode is not supported.
+    #[instrument(name = "prove_core", level = "info", skip_all)]
+    pub fn prove_core<'a, C: ZKMProverComponents>(
+        &self,
+        prover: &'a ZKMProver<C>,
+        pk: &ZKMProvingKey,
+        stdin: &ZKMStdin,
+        opts: ZKMProverOpts,
+        mut context: ZKMContext<'a>,
+    ) -> Result<ZKMCoreProof, DistributedProverError> {
+        if context.preimage_oracle.is_some() {
+            return Err(DistributedProverError::Unsupported("preimage oracles"));
+        }
+        context.subproof_verifier = Some(prover);
+
+        let program = prover
+            .get_program(&pk.elf)
+            .map_err(|e| DistributedProverError::WorkerError(e.to_string()))?;
+        let program = Arc::new(program);
+        let core_opts = opts.core_opts;
+
+        // Setup the runtime.
+        let mut runtime = Executor::with_context(program.as_ref().clone(), core_opts, context);
+        runtime.maximal_shapes = prover.core_shape_config.as_ref().map(|config| {
+            config.maximal_core_shapes(core_opts.shard_size.ilog2() as usize).into_iter().collect()
+        });
+        runtime.write_vecs(&stdin.buffer);
+        for (proof, vk) in stdin.proofs.iter() {
+            runtime.write_proof(proof.clone(), vk.clone());
+        }
+
+        let failed = AtomicBool::new(false);
+        let sync = TurnBasedSync::new();
+        let state = Mutex::new(PublicValues::<u32, u32>::default().reset());
+        let deferred = Mutex::new(ExecutionRecord::new(program.clone()));
+        let report_aggregate = Mutex::new(ExecutionReport::default());
+        let shard_proofs = Mutex::new(BTreeMap::<usize, Vec<ShardProof<CoreSC>>>::new());
+
+        let (checkpoints_tx, checkpoints_rx) =
+            sync_channel::<(usize, ExecutionState, bool)>(core_opts.checkpoints_channel_capacity);
+        let checkpoints_rx = Mutex::new(checkpoints_rx);
+
+        let public_values_stream = thread::scope(|s| {
+            // Spawn the checkpoint generator thread.
+            let failed = &failed;
+            let generator = s.spawn(move || -> Result<_, DistributedProverError> {
+                let mut index = 0;
+                loop {
+                    if failed.load(Ordering::Relaxed) {
+                        return Ok(Vec::new());
+                    }
+                    let (checkpoint, done) = runtime.execute_state(false)?;
+                    checkpoints_tx.send((index, checkpoint, done)).unwrap();
+                    if done {
+                        return Ok(runtime.state.public_values_stream);
+                    }
+                    index += 1;
+                }
+            });
+
+            // Spawn a thread per worker, which proves one batch of shards at a time.
+            let handles = self
+                .workers
+                .iter()
+                .map(|worker| {
+                    let (sync, state, deferred) = (&sync, &state, &deferred);
+                    let (report_aggregate, shard_proofs) = (&report_aggregate, &shard_proofs);
+                    let (checkpoints_rx, program) = (&checkpoints_rx, &program);
+                    s.spawn(move || -> Result<(), DistributedProverError> {
+                        let mut worker = worker.lock().unwrap();
+                        let mut result = Ok(());
+                        loop {
+                            let received = { checkpoints_rx.lock().unwrap().recv() };
+                            let Ok((index, checkpoint, done)) = received else { break };
+
+                            // Keep taking turns after a failure, so that the other workers do
+                            // not wait for this one forever.
+                            let traced = if failed.load(Ordering::Relaxed) {
+                                None
+                            } else {
+                                match worker.request(&WorkerRequest::Trace { state: checkpoint }) {
+                                    Ok(WorkerResponse::Traced {
+                                        public_values,
+                                        deferred,
+                                        report,
+                                    }) => Some((public_values, deferred, report)),
+                                    Ok(response) => {
+                                        result = Err(unexpected(&response));
+                                        None
+                                    }
+                                    Err(e) => {
+                                        result = Err(e);
+                                        None
+                                    }
+                                }
+                            };
+                            if traced.is_none() {
+                                failed.store(true, Ordering::Relaxed);
+                            }
+
+                            // Wait for our turn to update the state.
+                            sync.wait_for_turn(index);
+                            let request =
+                                traced.map(|(mut public_values, batch_deferred, report)| {
+                                    *report_aggregate.lock().unwrap() += report;
+
+                                    let mut state = state.lock().unwrap();
+                                    assign_cpu_public_values(&mut state, public_values.iter_mut());
+
+                                    let mut deferred = deferred.lock().unwrap();
+                                    deferred.append(&mut attach_program(batch_deferred, program));
+                                    let deferred = split_deferred_records(
+                                        &mut state,
+                                        &mut deferred,
+                                        done,
+                                        core_opts,
+                                    )
+                                    .into_iter()
+                                    .map(detach_program)
+                                    .collect();
+                                    WorkerRequest::Prove { public_values, deferred }
+                                });
+                            sync.advance_turn();
+
+                            let Some(request) = request else { continue };
+                            match worker.request(&request) {
+                                Ok(WorkerResponse::Proved(proofs)) => {
+                                    tracing::debug!(
+                                        "proved {} shards of batch {}",
+                                        proofs.len(),
+                                        index
+                                    );
+                                    shard_proofs.lock().unwrap().insert(index, proofs);
+                                }
+                                Ok(response) => {
+                                    failed.store(true, Ordering::Relaxed);
+                                    result = Err(unexpected(&response));
+                                }
+                                Err(e) => {
+                                    failed.store(true, Ordering::Relaxed);
+                                    result = Err(e);
+                                }
+                            }
+                        }
+                        result
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let public_values_stream = generator.join().unwrap();
+            if public_values_stream.is_err() {
+                failed.store(true, Ordering::Relaxed);
+            }
+            for handle in handles {
+                handle.join().unwrap()?;
+            }
+            public_values_stream
+        })?;
+
+        let shard_proofs = shard_proofs.into_inner().unwrap().into_values().flatten().collect();
+        let cycles = report_aggregate.into_inner().unwrap().total_instruction_count();
+        tracing::info!("summary: cycles={}", cycles);
+
+        Ok(ZKMCoreProof {
+            proof: ZKMCoreProofData(shard_proofs),
+            stdin: stdin.clone(),
+            public_values: ZKMPublicValues::from(&public_values_stream),
+            cycles,
+        })
+    }
+
+    /// Reduce shard proofs to a single shard proof like [`ZKMProver::compress`], with the nodes
+    /// of the compress tree proven by the workers.
+    ///
+    /// The tree is proven layer by layer, so a layer only starts once the previous one is done.
+    #[instrument(name = "compress", level = "info", skip_all)]
+    pub fn compress<C: ZKMProverComponents>(
+        &self,
+        prover: &ZKMProver<C>,
+        vk: &ZKMVerifyingKey,
+        proof: ZKMCoreProof,
+        deferred_proofs: Vec<ZKMReduceProof<InnerSC>>,
+        opts: ZKMProverOpts,
+    ) -> Result<ZKMReduceProof<InnerSC>, DistributedProverError> {
+        let first_layer_inputs =
+            prover.get_first_layer_inputs(vk, &proof.proof.0, &deferred_proofs, 1);
+
+        let mut proofs = self.prove_layer(first_layer_inputs, opts)?;
+        let mut height = 0;
+        while proofs.len() > 1 {
+            let is_complete = proofs.len() <= REDUCE_BATCH_SIZE;
+            let inputs = proofs
+                .chunks(REDUCE_BATCH_SIZE)
+                .map(|vks_and_proofs| {
+                    ZKMCircuitWitness::Compress(ZKMCompressWitnessValues {
+                        vks_and_proofs: vks_and_proofs.to_vec(),
+                        is_complete,
+                    })
+                })
+                .collect();
+            proofs = self.prove_layer(inputs, opts)?;
+            height += 1;
+            tracing::debug!("proved layer {} of the compress tree", height);
+        }
+
+        let (vk, proof) = proofs.pop().unwrap();
+        Ok(ZKMReduceProof { vk, proof })
+    }
+
+    /// Prove the nodes of a layer of the compress tree, returning the proofs in order.
+    fn prove_layer(
+        &self,
+        inputs: Vec<ZKMCircuitWitness>,
+        opts: ZKMProverOpts,
+    ) -> Result<Vec<(StarkVerifyingKey<InnerSC>, ShardProof<InnerSC>)>, DistributedProverError>
+    {
+        let num_inputs = inputs.len();
+        let inputs = Mutex::new(inputs.into_iter().enumerate());
+        let proofs = Mutex::new(BTreeMap::new());
+        thread::scope(|s| {
+            let handles = self
+                .workers
+                .iter()
+                .map(|worker| {
+                    let (inputs, proofs) = (&inputs, &proofs);
+                    s.spawn(move || -> Result<(), DistributedProverError> {
+                        let mut worker = worker.lock().unwrap();
+                        loop {
+                            let next = { inputs.lock().unwrap().next() };
+                            let Some((index, input)) = next else { return Ok(()) };
+                            match worker.request(&WorkerRequest::Compress { input, opts })? {
+                                WorkerResponse::Compressed { vk, proof } => {
+                                    proofs.lock().unwrap().insert(index, (vk, proof));
+                                }
+                                response => return Err(unexpected(&response)),
+                            }
+                        }
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles.into_iter().try_for_each(|handle| handle.join().unwrap())
+        })?;
+
+        let proofs = proofs.into_inner().unwrap().into_values().collect::<Vec<_>>();
+        assert_eq!(proofs.len(), num_inputs);
+        Ok(proofs)
+    }
+
+    /// Shut the workers down and close the connections.
+    pub fn shutdown(self) -> Result<(), DistributedProverError> {
+        for worker in self.workers {
+            worker.into_inner().unwrap().0.send(&WorkerRequest::Shutdown)?;
+        }
+        Ok(())
+    }
+}
+
+fn unexpected(response: &WorkerResponse) -> DistributedProverError {
+    DistributedProverError::UnexpectedMessage(response.name().to_string())
+}
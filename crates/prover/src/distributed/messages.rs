@@ -0,0 +1,102 @@
+use std::{
+    io::{BufReader, BufWriter, Write},
+    net::TcpStream,
+    sync::Arc,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use zkm_core_executor::{ExecutionRecord, ExecutionReport, ExecutionState, Program};
+use zkm_stark::{air::PublicValues, ShardProof, StarkVerifyingKey, ZKMProverOpts};
+
+use super::DistributedProverError;
+use crate::{CoreSC, InnerSC, ZKMCircuitWitness};
+
+/// A request sent by the coordinator to a worker.
+#[derive(Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+pub enum WorkerRequest {
+    /// Set up the program to prove. Must precede any [`WorkerRequest::Trace`].
+    Setup { elf: Vec<u8>, opts: ZKMProverOpts },
+    /// Re-execute a checkpoint and trace the records of its batch of shards.
+    Trace { state: ExecutionState },
+    /// Prove the batch of the last traced checkpoint, given the public values of its shards and
+    /// the deferred shards split off after it.
+    Prove { public_values: Vec<PublicValues<u32, u32>>, deferred: Vec<ExecutionRecord> },
+    /// Prove a node of the compress tree.
+    Compress { input: ZKMCircuitWitness, opts: ZKMProverOpts },
+    /// Close the connection.
+    Shutdown,
+}
+
+/// A response sent by a worker to the coordinator.
+#[derive(Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+pub enum WorkerResponse {
+    /// The program is set up.
+    Ready,
+    /// The public values of the traced shards, and the events they defer.
+    Traced {
+        public_values: Vec<PublicValues<u32, u32>>,
+        deferred: ExecutionRecord,
+        report: ExecutionReport,
+    },
+    /// The proofs of the shards of the batch, in order.
+    Proved(Vec<ShardProof<CoreSC>>),
+    /// The proof of a node of the compress tree.
+    Compressed { vk: StarkVerifyingKey<InnerSC>, proof: ShardProof<InnerSC> },
+    /// The request failed.
+    Error(String),
+}
+
+impl WorkerResponse {
+    /// The name of the response, for error messages.
+    pub fn name(&self) -> &'static str {
+        match self {
+            WorkerResponse::Ready => "Ready",
+            WorkerResponse::Traced { .. } => "Traced",
+            WorkerResponse::Proved(_) => "Proved",
+            WorkerResponse::Compressed { .. } => "Compressed",
+            WorkerResponse::Error(_) => "Error",
+        }
+    }
+}
+
+/// A bidirectional stream of bincode-encoded messages.
+pub struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: BufWriter<TcpStream>,
+}
+
+impl Connection {
+    pub fn new(stream: TcpStream) -> Result<Self, DistributedProverError> {
+        stream.set_nodelay(true)?;
+        let reader = BufReader::new(stream.try_clone()?);
+        let writer = BufWriter::new(stream);
+        Ok(Self { reader, writer })
+    }
+
+    pub fn send<T: Serialize>(&mut self, message: &T) -> Result<(), DistributedProverError> {
+        bincode::serialize_into(&mut self.writer, message)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    pub fn recv<T: DeserializeOwned>(&mut self) -> Result<T, DistributedProverError> {
+        Ok(bincode::deserialize_from(&mut self.reader)?)
+    }
+}
+
+/// Replace the program of a record before sending it, since both ends already hold the program.
+pub(crate) fn detach_program(mut record: ExecutionRecord) -> ExecutionRecord {
+    record.program = Arc::new(Program::default());
+    record
+}
+
+/// Restore the program of a record received with [`detach_program`].
+pub(crate) fn attach_program(
+    mut record: ExecutionRecord,
+    program: &Arc<Program>,
+) -> ExecutionRecord {
+    record.program = program.clone();
+    record
+}
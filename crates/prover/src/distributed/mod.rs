@@ -0,0 +1,179 @@
+//! Distributed proving across several worker processes.
+//!
+//! A [`ProverCoordinator`] executes the program and hands the shards and the nodes of the
+//! compress tree out to [`ProverWorker`]s, which connect to it over TCP. The coordinator only
+//! runs the cheap, sequential parts of the pipeline:
+//!
+//! 1. It executes the program to produce the checkpoints ([`ExecutionState`]s) of the batches of
+//!    shards, and sends each checkpoint to a worker, which re-executes it to trace its records.
+//! 2. In the order of the checkpoints, it assigns the public values of the traced shards and
+//!    splits the events deferred to their own shards, and sends both back to the worker, which
+//!    proves the shards of the batch.
+//! 3. It dispatches the nodes of the compress tree layer by layer, in batches of
+//!    [`REDUCE_BATCH_SIZE`](crate::REDUCE_BATCH_SIZE) proofs, and returns the root as a
+//!    [`ZKMReduceProof`](zkm_core_machine::reduce::ZKMReduceProof).
+//!
+//! The proofs are identical to the ones of [`ZKMProver::prove_core`](crate::ZKMProver::prove_core)
+//! and [`ZKMProver::compress`](crate::ZKMProver::compress), so they are verified the same way.
+//!
+//! [`ExecutionState`]: zkm_core_executor::ExecutionState
+
+mod coordinator;
+mod messages;
+mod worker;
+
+pub use coordinator::*;
+pub use messages::*;
+pub use worker::*;
+
+use std::io;
+
+use thiserror::Error;
+use zkm_core_executor::ExecutionError;
+
+#[derive(Error, Debug)]
+pub enum DistributedProverError {
+    #[error("io error: {0}")]
+    IoError(#[from] io::Error),
+    #[error("serialization error: {0}")]
+    SerializationError(#[from] bincode::Error),
+    #[error("failed to execute program: {0}")]
+    ExecutionError(#[from] ExecutionError),
+    #[error("worker error: {0}")]
+    WorkerError(String),
+    #[error("unexpected message: {0}")]
+    UnexpectedMessage(String),
+    #[error("unsupported: {0}")]
+    Unsupported(&'static str),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env,
+        net::TcpListener,
+        process::{Command, Stdio},
+        thread,
+    };
+
+    use serial_test::serial;
+    use zkm_core_executor::ZKMContext;
+    use zkm_core_machine::{io::ZKMStdin, utils::setup_logger};
+    use zkm_stark::ZKMProverOpts;
+
+    use super::*;
+    use crate::{components::DefaultProverComponents, ZKMProver};
+
+    /// Proves Fibonacci with a coordinator and two workers connected over the loopback interface,
+    /// and checks that the proofs verify like the ones of a single prover.
+    ///
+    /// The workers run on threads of the test process, but each holds its own [`ZKMProver`] and
+    /// only communicates with the coordinator through its socket, as a separate process would.
+    #[test]
+    #[serial]
+    #[ignore]
+    fn test_distributed_prover() -> anyhow::Result<()> {
+        setup_logger();
+        let elf = test_artifacts::FIBONACCI_ELF;
+        let opts = ZKMProverOpts::default();
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let workers = (0..2)
+            .map(|_| {
+                thread::spawn(move || {
+                    ProverWorker::new(ZKMProver::<DefaultProverComponents>::new()).connect(addr)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let prover = ZKMProver::<DefaultProverComponents>::new();
+        let coordinator = ProverCoordinator::accept(&listener, 2)?;
+        let (pk, vk) = prover.setup(elf);
+        coordinator.setup(&pk, opts)?;
+
+        let core_proof = coordinator.prove_core(
+            &prover,
+            &pk,
+            &ZKMStdin::default(),
+            opts,
+            ZKMContext::default(),
+        )?;
+        prover.verify(&core_proof.proof, &vk)?;
+
+        let compressed_proof = coordinator.compress(&prover, &vk, core_proof, vec![], opts)?;
+        prover.verify_compressed(&compressed_proof, &vk)?;
+
+        coordinator.shutdown()?;
+        for worker in workers {
+            worker.join().unwrap()?;
+        }
+
+        Ok(())
+    }
+
+    /// The environment variable giving the worker processes the address of the coordinator.
+    const COORDINATOR_ENV: &str = "ZKM_TEST_COORDINATOR";
+
+    /// Proves and compresses Fibonacci with a coordinator and two workers running in separate
+    /// processes, and checks that the proofs verify like the ones of a single prover.
+    ///
+    /// The worker processes run [`worker_process`] from the test binary itself.
+    #[test]
+    #[serial]
+    fn test_distributed_prover_processes() -> anyhow::Result<()> {
+        setup_logger();
+        let elf = test_artifacts::FIBONACCI_ELF;
+        let opts = ZKMProverOpts::default();
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let test_exe = env::current_exe()?;
+        let mut workers = (0..2)
+            .map(|_| {
+                Command::new(&test_exe)
+                    .args(["distributed::tests::worker_process", "--exact", "--ignored"])
+                    .env(COORDINATOR_ENV, addr.to_string())
+                    .stdout(Stdio::null())
+                    .spawn()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let prover = ZKMProver::<DefaultProverComponents>::new();
+        let coordinator = ProverCoordinator::accept(&listener, 2)?;
+        let (pk, vk) = prover.setup(elf);
+        coordinator.setup(&pk, opts)?;
+
+        let core_proof = coordinator.prove_core(
+            &prover,
+            &pk,
+            &ZKMStdin::default(),
+            opts,
+            ZKMContext::default(),
+        )?;
+        prover.verify(&core_proof.proof, &vk)?;
+
+        let compressed_proof = coordinator.compress(&prover, &vk, core_proof, vec![], opts)?;
+        prover.verify_compressed(&compressed_proof, &vk)?;
+
+        coordinator.shutdown()?;
+        for worker in workers.iter_mut() {
+            assert!(worker.wait()?.success());
+        }
+
+        Ok(())
+    }
+
+    /// The worker process of [`test_distributed_prover_processes`], which connects to the
+    /// coordinator given by [`COORDINATOR_ENV`].
+    #[test]
+    #[ignore]
+    fn worker_process() -> anyhow::Result<()> {
+        let Ok(addr) = env::var(COORDINATOR_ENV) else {
+            return Ok(());
+        };
+        setup_logger();
+        ProverWorker::new(ZKMProver::<DefaultProverComponents>::new()).connect(addr)?;
+        Ok(())
+    }
+}
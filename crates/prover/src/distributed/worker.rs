@@ -0,0 +1,189 @@
+use std::{
+    net::{TcpStream, ToSocketAddrs},
+    sync::Arc,
+};
+
+use p3_koala_bear::KoalaBear;
+use rayon::prelude::*;
+use zkm_core_executor::{ExecutionRecord, ExecutionState, Program};
+use zkm_core_machine::{mips::MipsAir, utils::trace_state};
+use zkm_stark::{air::PublicValues, MachineProver, MachineProvingKey, ShardProof, ZKMProverOpts};
+
+use super::{
+    attach_program, detach_program, Connection, DistributedProverError, WorkerRequest,
+    WorkerResponse,
+};
+use crate::{components::ZKMProverComponents, CoreSC, ZKMCircuitWitness, ZKMProver};
+
+type CoreProvingKey<C> = <<C as ZKMProverComponents>::CoreProver as MachineProver<
+    CoreSC,
+    MipsAir<KoalaBear>,
+>>::DeviceProvingKey;
+
+/// The program a worker proves the shards of.
+struct WorkerSetup<C: ZKMProverComponents> {
+    program: Arc<Program>,
+    pk: CoreProvingKey<C>,
+    opts: ZKMProverOpts,
+    /// The records of the last traced checkpoint, which wait for their public values.
+    traced: Option<Vec<ExecutionRecord>>,
+}
+
+/// A worker which proves shards and compress tree nodes on behalf of a
+/// [`ProverCoordinator`](super::ProverCoordinator).
+pub struct ProverWorker<C: ZKMProverComponents> {
+    prover: ZKMProver<C>,
+}
+
+impl<C: ZKMProverComponents> ProverWorker<C> {
+    pub fn new(prover: ZKMProver<C>) -> Self {
+        Self { prover }
+    }
+
+    /// Connect to the coordinator at `addr` and serve its requests until it shuts the worker
+    /// down.
+    pub fn connect(&self, addr: impl ToSocketAddrs) -> Result<(), DistributedProverError> {
+        let mut connection = Connection::new(TcpStream::connect(addr)?)?;
+        tracing::info!("connected to coordinator");
+
+        let mut setup = None;
+        loop {
+            let request = connection.recv::<WorkerRequest>()?;
+            let response = match request {
+                WorkerRequest::Shutdown => break,
+                request => self.handle(request, &mut setup),
+            };
+            let response = response.unwrap_or_else(|e| {
+                tracing::error!("request failed: {}", e);
+                WorkerResponse::Error(e.to_string())
+            });
+            connection.send(&response)?;
+        }
+
+        tracing::info!("shut down by coordinator");
+        Ok(())
+    }
+
+    fn handle(
+        &self,
+        request: WorkerRequest,
+        setup: &mut Option<WorkerSetup<C>>,
+    ) -> Result<WorkerResponse, DistributedProverError> {
+        match request {
+            WorkerRequest::Setup { elf, opts } => {
+                let program = self
+                    .prover
+                    .get_program(&elf)
+                    .map_err(|e| DistributedProverError::WorkerError(e.to_string()))?;
+                let (pk, _) = tracing::info_span!("setup")
+                    .in_scope(|| self.prover.core_prover.setup(&program));
+                *setup = Some(WorkerSetup { program: Arc::new(program), pk, opts, traced: None });
+                Ok(WorkerResponse::Ready)
+            }
+            WorkerRequest::Trace { state } => {
+                let setup = setup.as_mut().ok_or(DistributedProverError::WorkerError(
+                    "the worker is not set up".to_string(),
+                ))?;
+                Ok(self.trace(setup, state))
+            }
+            WorkerRequest::Prove { public_values, deferred } => {
+                let setup = setup.as_mut().ok_or(DistributedProverError::WorkerError(
+                    "the worker is not set up".to_string(),
+                ))?;
+                self.prove(setup, public_values, deferred).map(WorkerResponse::Proved)
+            }
+            WorkerRequest::Compress { input, opts } => self.compress(input, opts),
+            WorkerRequest::Shutdown => unreachable!(),
+        }
+    }
+
+    /// Trace the records of a checkpoint and defer their expensive events.
+    fn trace(&self, setup: &mut WorkerSetup<C>, state: ExecutionState) -> WorkerResponse {
+        let (mut records, report) = tracing::info_span!("trace checkpoint").in_scope(|| {
+            trace_state::<CoreSC>(
+                setup.program.as_ref().clone(),
+                state,
+                setup.opts.core_opts,
+                self.prover.core_shape_config.as_ref(),
+            )
+        });
+
+        let mut deferred = ExecutionRecord::new(setup.program.clone());
+        for record in records.iter_mut() {
+            deferred.append(&mut record.defer());
+        }
+        let public_values = records.iter().map(|record| record.public_values).collect();
+        setup.traced = Some(records);
+
+        WorkerResponse::Traced { public_values, deferred: detach_program(deferred), report }
+    }
+
+    /// Prove the shards of the last traced checkpoint, followed by the deferred shards.
+    fn prove(
+        &self,
+        setup: &mut WorkerSetup<C>,
+        public_values: Vec<PublicValues<u32, u32>>,
+        deferred: Vec<ExecutionRecord>,
+    ) -> Result<Vec<ShardProof<CoreSC>>, DistributedProverError> {
+        let mut records = setup
+            .traced
+            .take()
+            .ok_or(DistributedProverError::WorkerError("no checkpoint was traced".to_string()))?;
+        if records.len() != public_values.len() {
+            return Err(DistributedProverError::WorkerError(format!(
+                "expected public values for {} shards, got {}",
+                records.len(),
+                public_values.len()
+            )));
+        }
+        for (record, public_values) in records.iter_mut().zip(public_values) {
+            record.public_values = public_values;
+        }
+        records.extend(deferred.into_iter().map(|record| attach_program(record, &setup.program)));
+
+        let core_prover = &self.prover.core_prover;
+        tracing::info_span!("generate dependencies").in_scope(|| {
+            core_prover.machine().generate_dependencies(&mut records, &setup.opts.core_opts, None)
+        });
+
+        // Fix the shape of the records.
+        if let Some(shape_config) = &self.prover.core_shape_config {
+            for record in records.iter_mut() {
+                shape_config
+                    .fix_shape(record)
+                    .map_err(|e| DistributedProverError::WorkerError(e.to_string()))?;
+            }
+        }
+
+        // Create the challenger and observe the verifying key.
+        let mut challenger = core_prover.config().challenger();
+        setup.pk.observe_into(&mut challenger);
+
+        tracing::info_span!("prove shards", shards = records.len()).in_scope(|| {
+            records
+                .into_par_iter()
+                .map(|record| {
+                    let traces = core_prover.generate_traces(&record);
+                    let data = core_prover.commit(&record, traces);
+                    core_prover
+                        .open(&setup.pk, data, &mut challenger.clone())
+                        .map_err(|e| DistributedProverError::WorkerError(e.to_string()))
+                })
+                .collect()
+        })
+    }
+
+    /// Prove a node of the compress tree.
+    fn compress(
+        &self,
+        input: ZKMCircuitWitness,
+        opts: ZKMProverOpts,
+    ) -> Result<WorkerResponse, DistributedProverError> {
+        let (program, record, traces) = tracing::info_span!("generate records and traces")
+            .in_scope(|| self.prover.generate_recursion_record_and_traces(input, &opts))
+            .map_err(|e| DistributedProverError::WorkerError(e.to_string()))?;
+        let (vk, proof) = tracing::info_span!("prove compress")
+            .in_scope(|| self.prover.prove_recursion_record(&program, record, traces));
+        Ok(WorkerResponse::Compressed { vk, proof })
+    }
+}
@@ -13,6 +13,8 @@
 
 pub mod build;
 pub mod components;
+pub mod distributed;
+pub mod registry;
 pub mod shapes;
 pub mod types;
 pub mod utils;
@@ -26,10 +28,11 @@ use std::{
     path::Path,
     sync::{
         atomic::{AtomicUsize, Ordering},
-        mpsc::sync_channel,
+        mpsc::{sync_channel, RecvTimeoutError},
         Arc, Mutex, OnceLock,
     },
     thread,
+    time::Duration,
 };
 
 use lru::LruCache;
@@ -44,7 +47,10 @@ use zkm_core_machine::{
     mips::MipsAir,
     reduce::ZKMReduceProof,
     shape::CoreShapeConfig,
-    utils::{concurrency::TurnBasedSync, ZKMCoreProverError},
+    utils::{
+        concurrency::TurnBasedSync, CheckpointOpts, ExecutionEstimate, ProofProgress,
+        ProverMonitor, ProvingCheckpoint, ZKMCoreProverError,
+    },
 };
 use zkm_primitives::{hash_deferred_proof, io::ZKMPublicValues};
 use zkm_recursion_circuit::{
@@ -73,8 +79,11 @@ use zkm_recursion_core::{
     stark::KoalaBearPoseidon2Outer,
     RecursionProgram, Runtime as RecursionRuntime,
 };
-pub use zkm_recursion_gnark_ffi::proof::{Groth16Bn254Proof, PlonkBn254Proof};
 use zkm_recursion_gnark_ffi::{groth16_bn254::Groth16Bn254Prover, plonk_bn254::PlonkBn254Prover};
+pub use zkm_recursion_gnark_ffi::{
+    proof::{Groth16Bn254Proof, PlonkBn254Proof},
+    IZKM_VERIFIER_SOL,
+};
 use zkm_stark::{
     air::PublicValues, koala_bear_poseidon2::KoalaBearPoseidon2, Challenge, MachineProver,
     ShardProof, StarkGenericConfig, StarkVerifyingKey, Val, Word, ZKMCoreOpts, ZKMProverOpts,
@@ -105,6 +114,9 @@ const WRAP_DEGREE: usize = 9;
 const CORE_CACHE_SIZE: usize = 5;
 pub const REDUCE_BATCH_SIZE: usize = 2;
 
+/// How often the compress pipeline checks for cancellation while waiting for a proof.
+const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 // TODO: FIX
 //
 // const SHAPES_URL_PREFIX: &str = "https://zkm-circuits.s3.us-east-2.amazonaws.com/shapes";
@@ -304,29 +316,79 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         let program = self.get_program(elf).unwrap();
         let opts = ZKMCoreOpts::default();
         let mut runtime = Executor::with_context(program, opts, context);
+        runtime.maybe_setup_profiler(elf);
         runtime.write_vecs(&stdin.buffer);
         for (proof, vkey) in stdin.proofs.iter() {
             runtime.write_proof(proof.clone(), vkey.clone());
         }
-        runtime.run_fast()?;
+        runtime.run_fast().map_err(|err| err.symbolize(elf))?;
         Ok((ZKMPublicValues::from(&runtime.state.public_values_stream), runtime.report))
     }
 
+    /// Execute a program and estimate the cost of proving it with the core prover and the options
+    /// `opts`, without generating any traces. Uses the provided context.
+    #[instrument(name = "estimate", level = "info", skip_all)]
+    pub fn estimate<'a>(
+        &'a self,
+        elf: &[u8],
+        stdin: &ZKMStdin,
+        opts: ZKMProverOpts,
+        mut context: ZKMContext<'a>,
+    ) -> Result<ExecutionEstimate, ZKMCoreProverError> {
+        context.subproof_verifier = Some(self);
+        let program = self.get_program(elf).unwrap();
+        zkm_core_machine::utils::estimate_with_context(
+            program,
+            stdin,
+            opts.core_opts,
+            context,
+            self.core_shape_config.as_ref(),
+        )
+    }
+
     /// Generate shard proofs which split up and prove the valid execution of a MIPS program with
     /// the core prover. Uses the provided context.
     #[instrument(name = "prove_core", level = "info", skip_all)]
     pub fn prove_core<'a>(
+        &'a self,
+        pk: &ZKMProvingKey,
+        stdin: &ZKMStdin,
+        opts: ZKMProverOpts,
+        context: ZKMContext<'a>,
+    ) -> Result<ZKMCoreProof, ZKMCoreProverError> {
+        self.prove_core_with_checkpoints(
+            pk,
+            stdin,
+            opts,
+            context,
+            None,
+            None,
+            &ProverMonitor::default(),
+        )
+    }
+
+    /// Generate shard proofs like [`Self::prove_core`], writing checkpoints according to
+    /// `checkpoint_opts` and resuming from the checkpoint `resume` if provided.
+    ///
+    /// When resuming, only the shards after the checkpoint are proven. The progress is reported to
+    /// `monitor`, which can also cancel the proof generation.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_core_with_checkpoints<'a>(
         &'a self,
         pk: &ZKMProvingKey,
         stdin: &ZKMStdin,
         opts: ZKMProverOpts,
         mut context: ZKMContext<'a>,
+        checkpoint_opts: Option<&CheckpointOpts>,
+        resume: Option<ProvingCheckpoint>,
+        monitor: &ProverMonitor,
     ) -> Result<ZKMCoreProof, ZKMCoreProverError> {
         context.subproof_verifier = Some(self);
-        let program = self.get_program(&pk.elf).unwrap();
+        let elf = &pk.elf;
+        let program = self.get_program(elf).unwrap();
         let pk = self.core_prover.pk_to_device(&pk.pk);
         let (proof, public_values_stream, cycles) =
-            zkm_core_machine::utils::prove_with_context::<_, C::CoreProver>(
+            zkm_core_machine::utils::prove_with_checkpoints::<_, C::CoreProver>(
                 &self.core_prover,
                 &pk,
                 program,
@@ -334,7 +396,16 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                 opts.core_opts,
                 context,
                 self.core_shape_config.as_ref(),
-            )?;
+                checkpoint_opts,
+                resume,
+                monitor,
+            )
+            .map_err(|err| match err {
+                ZKMCoreProverError::ExecutionError(err) => {
+                    ZKMCoreProverError::ExecutionError(err.symbolize(elf))
+                }
+                err => err,
+            })?;
         Self::check_for_high_cycles(cycles);
         let public_values = ZKMPublicValues::from(&public_values_stream);
         Ok(ZKMCoreProof {
@@ -592,13 +663,26 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
     }
 
     /// Reduce shards proofs to a single shard proof using the recursion prover.
-    #[instrument(name = "compress", level = "info", skip_all)]
     pub fn compress(
         &self,
         vk: &ZKMVerifyingKey,
         proof: ZKMCoreProof,
         deferred_proofs: Vec<ZKMReduceProof<InnerSC>>,
         opts: ZKMProverOpts,
+    ) -> Result<ZKMReduceProof<InnerSC>, ZKMRecursionProverError> {
+        self.compress_with_monitor(vk, proof, deferred_proofs, opts, &ProverMonitor::default())
+    }
+
+    /// Reduce shards proofs like [`Self::compress`], reporting the proofs of each layer of the
+    /// compress tree to `monitor`, which can also cancel the proof generation.
+    #[instrument(name = "compress", level = "info", skip_all)]
+    pub fn compress_with_monitor(
+        &self,
+        vk: &ZKMVerifyingKey,
+        proof: ZKMCoreProof,
+        deferred_proofs: Vec<ZKMReduceProof<InnerSC>>,
+        opts: ZKMProverOpts,
+        monitor: &ProverMonitor,
     ) -> Result<ZKMReduceProof<InnerSC>, ZKMRecursionProverError> {
         // The batch size for reducing two layers of recursion.
         let batch_size = REDUCE_BATCH_SIZE;
@@ -619,9 +703,17 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             expected_height += 1;
         }
 
+        // Every layer halves the number of proofs, up to the root of the tree.
+        let mut layer_sizes = vec![num_first_layer_inputs];
+        while layer_sizes.len() <= expected_height {
+            layer_sizes.push(layer_sizes.last().unwrap().div_ceil(2));
+        }
+        let layer_proven = layer_sizes.iter().map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+        let (layer_sizes, layer_proven) = (&layer_sizes, &layer_proven);
+
         // Generate the proofs.
         let span = tracing::Span::current().clone();
-        let (vk, proof) = thread::scope(|s| {
+        let result = thread::scope(|s| {
             let _span = span.enter();
 
             // Spawn a worker that sends the first layer inputs to a bounded channel.
@@ -636,7 +728,11 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                 s.spawn(move || {
                     for (index, input) in first_layer_inputs.into_iter().enumerate() {
                         input_sync.wait_for_turn(index);
-                        input_tx.lock().unwrap().send((index, 0, input)).unwrap();
+                        // Keep taking turns once cancelled, so that the next layer generator is
+                        // not blocked.
+                        if !monitor.is_cancelled() {
+                            input_tx.lock().unwrap().send((index, 0, input)).unwrap();
+                        }
                         input_sync.advance_turn();
                     }
                 });
@@ -665,76 +761,24 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                     loop {
                         let received = { input_rx.lock().unwrap().recv() };
                         if let Ok((index, height, input)) = received {
-                            // Get the program and witness stream.
-                            let (program, witness_stream) = tracing::debug_span!(
-                                "get program and witness stream"
-                            )
-                            .in_scope(|| match input {
-                                ZKMCircuitWitness::Core(input) => {
-                                    let mut witness_stream = Vec::new();
-                                    Witnessable::<InnerConfig>::write(&input, &mut witness_stream);
-                                    (self.recursion_program(&input), witness_stream)
-                                }
-                                ZKMCircuitWitness::Deferred(input) => {
-                                    let mut witness_stream = Vec::new();
-                                    Witnessable::<InnerConfig>::write(&input, &mut witness_stream);
-                                    (self.deferred_program(&input), witness_stream)
-                                }
-                                ZKMCircuitWitness::Compress(input) => {
-                                    let mut witness_stream = Vec::new();
-
-                                    let input_with_merkle = self.make_merkle_proofs(input);
-
-                                    Witnessable::<InnerConfig>::write(
-                                        &input_with_merkle,
-                                        &mut witness_stream,
-                                    );
-
-                                    (self.compress_program(&input_with_merkle), witness_stream)
-                                }
-                            });
-
-                            // Execute the runtime.
-                            let record = tracing::debug_span!("execute runtime").in_scope(|| {
-                                let mut runtime =
-                                    RecursionRuntime::<Val<InnerSC>, Challenge<InnerSC>, _>::new(
-                                        program.clone(),
-                                        self.compress_prover.config().perm.clone(),
-                                    );
-                                runtime.witness_stream = witness_stream.into();
-                                runtime
-                                    .run()
-                                    .map_err(|e| {
-                                        ZKMRecursionProverError::RuntimeError(e.to_string())
-                                    })
-                                    .unwrap();
-                                runtime.record
-                            });
-
-                            // Generate the dependencies.
-                            let mut records = vec![record];
-                            tracing::debug_span!("generate dependencies").in_scope(|| {
-                                self.compress_prover.machine().generate_dependencies(
-                                    &mut records,
-                                    &opts.recursion_opts,
-                                    None,
-                                )
+                            let generated = (!monitor.is_cancelled()).then(|| {
+                                self.generate_recursion_record_and_traces(input, &opts).unwrap()
                             });
 
-                            // Generate the traces.
-                            let record = records.into_iter().next().unwrap();
-                            let traces = tracing::debug_span!("generate traces")
-                                .in_scope(|| self.compress_prover.generate_traces(&record));
-
                             // Wait for our turn to update the state.
                             record_and_trace_sync.wait_for_turn(index);
 
-                            // Send the record and traces to the worker.
-                            record_and_trace_tx
-                                .lock()
-                                .unwrap()
-                                .send((index, height, program, record, traces))
-                                .unwrap();
+                            // Send the record and traces to the worker, unless cancelled. Since
+                            // this is checked during our turn, the sent indices stay contiguous.
+                            if let (false, Some((program, record, traces))) =
+                                (monitor.is_cancelled(), generated)
+                            {
+                                record_and_trace_tx
+                                    .lock()
+                                    .unwrap()
+                                    .send((index, height, program, record, traces))
+                                    .unwrap();
+                            }
 
                             // Advance the turn.
                             record_and_trace_sync.advance_turn();
@@ -765,50 +809,29 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                         let received = { record_and_trace_rx.lock().unwrap().recv() };
                         if let Ok((index, height, program, record, traces)) = received {
                             tracing::debug_span!("batch").in_scope(|| {
-                                // Get the keys.
-                                let (pk, vk) = tracing::debug_span!("Setup compress program")
-                                    .in_scope(|| self.compress_prover.setup(&program));
-
-                                // Observe the proving key.
-                                let mut challenger = self.compress_prover.config().challenger();
-                                tracing::debug_span!("observe proving key").in_scope(|| {
-                                    pk.observe_into(&mut challenger);
-                                });
-
-                                #[cfg(feature = "debug")]
-                                self.compress_prover.debug_constraints(
-                                    &self.compress_prover.pk_to_host(&pk),
-                                    vec![record.clone()],
-                                    &mut challenger.clone(),
-                                );
-
-                                // Commit to the record and traces.
-                                let data = tracing::debug_span!("commit")
-                                    .in_scope(|| self.compress_prover.commit(&record, traces));
-
-                                // Generate the proof.
-                                let proof = tracing::debug_span!("open").in_scope(|| {
-                                    self.compress_prover.open(&pk, data, &mut challenger).unwrap()
-                                });
-
-                                // Verify the proof.
-                                #[cfg(feature = "debug")]
-                                self.compress_prover
-                                    .machine()
-                                    .verify(
-                                        &vk,
-                                        &zkm_stark::MachineProof {
-                                            shard_proofs: vec![proof.clone()],
-                                        },
-                                        &mut self.compress_prover.config().challenger(),
-                                    )
-                                    .unwrap();
+                                let proved = (!monitor.is_cancelled())
+                                    .then(|| self.prove_recursion_record(&program, record, traces));
 
                                 // Wait for our turn to update the state.
                                 prover_sync.wait_for_turn(index);
 
-                                // Send the proof.
-                                proofs_tx.lock().unwrap().send((index, height, vk, proof)).unwrap();
+                                // Send the proof, unless cancelled.
+                                if let (false, Some((vk, proof))) = (monitor.is_cancelled(), proved)
+                                {
+                                    proofs_tx
+                                        .lock()
+                                        .unwrap()
+                                        .send((index, height, vk, proof))
+                                        .unwrap();
+                                    let proven =
+                                        layer_proven[height].fetch_add(1, Ordering::Relaxed) + 1;
+                                    monitor.report(ProofProgress::CompressProven {
+                                        layer: height,
+                                        num_layers: layer_sizes.len(),
+                                        proven,
+                                        total: layer_sizes[height],
+                                    });
+                                }
 
                                 // Advance the turn.
                                 prover_sync.advance_turn();
@@ -839,7 +862,17 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                         if expected_height == 0 {
                             break;
                         }
-                        let received = { proofs_rx.lock().unwrap().recv() };
+                        // Poll for the next proof, since none may come once cancelled.
+                        let received = loop {
+                            match proofs_rx.lock().unwrap().recv_timeout(CANCELLATION_POLL_INTERVAL)
+                            {
+                                Err(RecvTimeoutError::Timeout) if !monitor.is_cancelled() => {}
+                                received => break received,
+                            }
+                        };
+                        if monitor.is_cancelled() {
+                            break;
+                        }
                         if let Ok((index, height, vk, proof)) = received {
                             batch.push((index, height, vk, proof));
 
@@ -909,13 +942,131 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             }
             handle.join().unwrap();
 
+            if monitor.is_cancelled() {
+                return Err(ZKMRecursionProverError::Cancelled);
+            }
             let (_, _, vk, proof) = proofs_rx.lock().unwrap().recv().unwrap();
-            (vk, proof)
+            Ok((vk, proof))
         });
 
+        let (vk, proof) = result?;
         Ok(ZKMReduceProof { vk, proof })
     }
 
+    /// Execute the recursion program verifying a node of the compress tree and generate the record
+    /// and traces of its execution.
+    pub fn generate_recursion_record_and_traces(
+        &self,
+        input: ZKMCircuitWitness,
+        opts: &ZKMProverOpts,
+    ) -> Result<
+        (
+            Arc<RecursionProgram<KoalaBear>>,
+            ExecutionRecord<KoalaBear>,
+            Vec<(String, RowMajorMatrix<KoalaBear>)>,
+        ),
+        ZKMRecursionProverError,
+    > {
+        // Get the program and witness stream.
+        let (program, witness_stream) = tracing::debug_span!("get program and witness stream")
+            .in_scope(|| match input {
+                ZKMCircuitWitness::Core(input) => {
+                    let mut witness_stream = Vec::new();
+                    Witnessable::<InnerConfig>::write(&input, &mut witness_stream);
+                    (self.recursion_program(&input), witness_stream)
+                }
+                ZKMCircuitWitness::Deferred(input) => {
+                    let mut witness_stream = Vec::new();
+                    Witnessable::<InnerConfig>::write(&input, &mut witness_stream);
+                    (self.deferred_program(&input), witness_stream)
+                }
+                ZKMCircuitWitness::Compress(input) => {
+                    let mut witness_stream = Vec::new();
+
+                    let input_with_merkle = self.make_merkle_proofs(input);
+
+                    Witnessable::<InnerConfig>::write(&input_with_merkle, &mut witness_stream);
+
+                    (self.compress_program(&input_with_merkle), witness_stream)
+                }
+            });
+
+        // Execute the runtime.
+        let record = tracing::debug_span!("execute runtime").in_scope(|| {
+            let mut runtime = RecursionRuntime::<Val<InnerSC>, Challenge<InnerSC>, _>::new(
+                program.clone(),
+                self.compress_prover.config().perm.clone(),
+            );
+            runtime.witness_stream = witness_stream.into();
+            runtime.run().map_err(|e| ZKMRecursionProverError::RuntimeError(e.to_string()))?;
+            Ok(runtime.record)
+        })?;
+
+        // Generate the dependencies.
+        let mut records = vec![record];
+        tracing::debug_span!("generate dependencies").in_scope(|| {
+            self.compress_prover.machine().generate_dependencies(
+                &mut records,
+                &opts.recursion_opts,
+                None,
+            )
+        });
+
+        // Generate the traces.
+        let record = records.into_iter().next().unwrap();
+        let traces = tracing::debug_span!("generate traces")
+            .in_scope(|| self.compress_prover.generate_traces(&record));
+
+        Ok((program, record, traces))
+    }
+
+    /// Prove the execution of a recursion program from the record and traces generated by
+    /// [`Self::generate_recursion_record_and_traces`].
+    pub fn prove_recursion_record(
+        &self,
+        program: &RecursionProgram<KoalaBear>,
+        record: ExecutionRecord<KoalaBear>,
+        traces: Vec<(String, RowMajorMatrix<KoalaBear>)>,
+    ) -> (StarkVerifyingKey<InnerSC>, ShardProof<InnerSC>) {
+        // Get the keys.
+        let (pk, vk) = tracing::debug_span!("Setup compress program")
+            .in_scope(|| self.compress_prover.setup(program));
+
+        // Observe the proving key.
+        let mut challenger = self.compress_prover.config().challenger();
+        tracing::debug_span!("observe proving key").in_scope(|| {
+            pk.observe_into(&mut challenger);
+        });
+
+        #[cfg(feature = "debug")]
+        self.compress_prover.debug_constraints(
+            &self.compress_prover.pk_to_host(&pk),
+            vec![record.clone()],
+            &mut challenger.clone(),
+        );
+
+        // Commit to the record and traces.
+        let data = tracing::debug_span!("commit")
+            .in_scope(|| self.compress_prover.commit(&record, traces));
+
+        // Generate the proof.
+        let proof = tracing::debug_span!("open")
+            .in_scope(|| self.compress_prover.open(&pk, data, &mut challenger).unwrap());
+
+        // Verify the proof.
+        #[cfg(feature = "debug")]
+        self.compress_prover
+            .machine()
+            .verify(
+                &vk,
+                &zkm_stark::MachineProof { shard_proofs: vec![proof.clone()] },
+                &mut self.compress_prover.config().challenger(),
+            )
+            .unwrap();
+
+        (vk, proof)
+    }
+
     /// Wrap a reduce proof into a STARK proven over a SNARK-friendly field.
     #[instrument(name = "shrink", level = "info", skip_all)]
     pub fn shrink(
@@ -1542,4 +1693,45 @@ pub mod tests {
         setup_logger();
         test_e2e_with_deferred_proofs_prover::<DefaultProverComponents>(ZKMProverOpts::default())
     }
+
+    /// Tests verifying deferred proofs of any program of a vkey registry.
+    #[test]
+    #[serial]
+    #[ignore]
+    fn test_e2e_with_vkey_registry() -> Result<()> {
+        setup_logger();
+        let opts = ZKMProverOpts::default();
+        let prover = ZKMProver::<DefaultProverComponents>::new();
+
+        // The registry holds the programs whose proofs are verified.
+        let (keccak_pk, keccak_vk) = prover.setup(test_artifacts::KECCAK_SPONGE_ELF);
+        let (_, fibonacci_vk) = prover.setup(test_artifacts::FIBONACCI_ELF);
+        let registry = crate::registry::VkeyRegistry::new([&fibonacci_vk, &keccak_vk]);
+        let (verify_pk, verify_vk) = prover.setup(test_artifacts::VERIFY_PROOF_REGISTRY_ELF);
+
+        tracing::info!("prove subproof");
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&1usize);
+        stdin.write(&vec![0u8, 1, 2]);
+        let deferred_proof = prover.prove_core(&keccak_pk, &stdin, opts, Default::default())?;
+        let pv = deferred_proof.public_values.to_vec();
+        let deferred_reduce = prover.compress(&keccak_vk, deferred_proof, vec![], opts)?;
+
+        tracing::info!("prove verify program");
+        let membership_proof = registry.prove(&keccak_vk).unwrap();
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&registry.root());
+        stdin.write(&vec![(keccak_vk.hash_u32(), membership_proof, pv)]);
+        stdin.write_proof(deferred_reduce.clone(), keccak_vk.vk.clone());
+        let verify_proof = prover.prove_core(&verify_pk, &stdin, opts, Default::default())?;
+        let mut public_values = verify_proof.public_values.clone();
+        assert_eq!(public_values.read::<[u32; 8]>(), registry.root());
+
+        tracing::info!("compress verify program");
+        let verify_reduce =
+            prover.compress(&verify_vk, verify_proof, vec![deferred_reduce], opts)?;
+        prover.verify_compressed(&verify_reduce, &verify_vk)?;
+
+        Ok(())
+    }
 }
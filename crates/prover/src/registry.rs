@@ -0,0 +1,97 @@
+//! A registry of the verifying keys of several programs, which lets a program verify proofs of any
+//! of them with `zkm_lib::verify::verify_zkm_proof_in_registry`, by checking a membership proof
+//! against the registry root instead of hardcoding a vkey digest.
+
+use p3_field::{FieldAlgebra, PrimeField32};
+use p3_koala_bear::KoalaBear;
+use serde::{Deserialize, Serialize};
+use zkm_primitives::types::VkeyMembershipProof;
+use zkm_recursion_circuit::{hash::FieldHasher, merkle_tree::MerkleTree};
+use zkm_stark::DIGEST_SIZE;
+
+use crate::{HashableKey, InnerSC, ZKMVerifyingKey};
+
+/// A Merkle tree over the vkey digests of a set of programs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VkeyRegistry {
+    /// The vkey digests, in the order of the leaves.
+    digests: Vec<[u32; DIGEST_SIZE]>,
+    root: [KoalaBear; DIGEST_SIZE],
+    tree: MerkleTree<KoalaBear, InnerSC>,
+}
+
+impl VkeyRegistry {
+    /// Creates the registry of the programs with the given verifying keys.
+    pub fn new<'a>(vks: impl IntoIterator<Item = &'a ZKMVerifyingKey>) -> Self {
+        Self::from_digests(vks.into_iter().map(|vk| vk.hash_u32()).collect())
+    }
+
+    /// Creates the registry of the vkey digests, as returned by [HashableKey::hash_u32].
+    pub fn from_digests(digests: Vec<[u32; DIGEST_SIZE]>) -> Self {
+        assert!(!digests.is_empty(), "the registry must contain at least one vkey");
+        let mut leaves = digests.iter().map(to_koalabears).collect::<Vec<_>>();
+        // The tree needs at least two leaves, and pads the others with the zero digest.
+        if leaves.len() == 1 {
+            leaves.push(Default::default());
+        }
+        let (root, tree) = MerkleTree::commit(leaves);
+        Self { digests, root, tree }
+    }
+
+    /// The root of the registry, which the verifying program checks the membership proofs against.
+    pub fn root(&self) -> [u32; DIGEST_SIZE] {
+        self.root.map(|x| x.as_canonical_u32())
+    }
+
+    /// The vkey digests of the registry.
+    pub fn digests(&self) -> &[[u32; DIGEST_SIZE]] {
+        &self.digests
+    }
+
+    /// Returns the proof that the program with the verifying key is in the registry, or `None` if
+    /// it is not.
+    pub fn prove(&self, vk: &ZKMVerifyingKey) -> Option<VkeyMembershipProof> {
+        self.prove_digest(&vk.hash_u32())
+    }
+
+    /// Returns the proof that the vkey digest is in the registry, or `None` if it is not.
+    pub fn prove_digest(&self, vk_digest: &[u32; DIGEST_SIZE]) -> Option<VkeyMembershipProof> {
+        let index = self.digests.iter().position(|digest| digest == vk_digest)?;
+        let (_, proof) = self.tree.open(index);
+        let path = proof.path.iter().map(|node| node.map(|x| x.as_canonical_u32())).collect();
+        Some(VkeyMembershipProof { index, path })
+    }
+}
+
+fn to_koalabears(digest: &[u32; DIGEST_SIZE]) -> [KoalaBear; DIGEST_SIZE] {
+    digest.map(KoalaBear::from_canonical_u32)
+}
+
+/// Compresses two nodes of a registry, like `zkm_lib::poseidon2::compress` in the zkVM.
+pub fn compress(left: &[u32; DIGEST_SIZE], right: &[u32; DIGEST_SIZE]) -> [u32; DIGEST_SIZE] {
+    InnerSC::constant_compress([to_koalabears(left), to_koalabears(right)])
+        .map(|x| x.as_canonical_u32())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vkey_registry() {
+        for num_vkeys in 1..10u32 {
+            let digests = (0..num_vkeys).map(|i| [i + 1; DIGEST_SIZE]).collect::<Vec<_>>();
+            let registry = VkeyRegistry::from_digests(digests.clone());
+
+            for digest in &digests {
+                let proof = registry.prove_digest(digest).unwrap();
+                assert_eq!(proof.root(digest, compress), registry.root());
+
+                // The proof does not hold for another vkey.
+                let other = [0; DIGEST_SIZE];
+                assert_ne!(proof.root(&other, compress), registry.root());
+            }
+            assert!(registry.prove_digest(&[num_vkeys + 1; DIGEST_SIZE]).is_none());
+        }
+    }
+}
@@ -222,8 +222,11 @@ pub enum ZKMReduceProofWrapper {
 pub enum ZKMRecursionProverError {
     #[error("Runtime error: {0}")]
     RuntimeError(String),
+    #[error("Proof generation was cancelled")]
+    Cancelled,
 }
 
+#[derive(Serialize, Deserialize)]
 #[allow(clippy::large_enum_variant)]
 pub enum ZKMCircuitWitness {
     Core(ZKMRecursionWitnessValues<CoreSC>),
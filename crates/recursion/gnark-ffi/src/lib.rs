@@ -10,3 +10,6 @@ pub use groth16_bn254::*;
 pub use plonk_bn254::*;
 pub use proof::*;
 pub use witness::*;
+
+/// The Solidity interface implemented by the Groth16 and Plonk verifier contracts.
+pub const IZKM_VERIFIER_SOL: &str = include_str!("../assets/IZKMVerifier.sol");
@@ -5,5 +5,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .protoc_arg("--experimental_allow_proto3_optional")
         .compile(&["src/proto/stage.proto"], &["src/proto"])?;
 
+    // The aggregation program is only built on demand, as it requires the Ziren toolchain.
+    if std::env::var_os("CARGO_FEATURE_AGGREGATION").is_some() {
+        zkm_build::build_program(concat!(env!("CARGO_MANIFEST_DIR"), "/guests/aggregation"));
+    }
+
     Ok(())
 }
@@ -0,0 +1,31 @@
+//! The program used by `ProverClient::aggregate` to aggregate the compressed proofs of any
+//! programs into a single proof.
+//!
+//! The public values commit to the verified proofs, as the concatenation of the vkey hash (8 little
+//! endian words) and the sha256 digest of the public values of every proof.
+#![no_std]
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+extern crate alloc;
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+use zkm_zkvm::lib::verify::verify_zkm_proof;
+
+pub fn main() {
+    let vkeys = zkm_zkvm::io::read::<Vec<[u32; 8]>>();
+    let public_values = zkm_zkvm::io::read::<Vec<Vec<u8>>>();
+    assert_eq!(vkeys.len(), public_values.len());
+
+    let mut commitment = Vec::with_capacity(vkeys.len() * 64);
+    for (vkey, public_values) in vkeys.iter().zip(public_values.iter()) {
+        let public_values_digest: [u8; 32] = Sha256::digest(public_values).into();
+        verify_zkm_proof(vkey, &public_values_digest);
+
+        for word in vkey {
+            commitment.extend_from_slice(&word.to_le_bytes());
+        }
+        commitment.extend_from_slice(&public_values_digest);
+    }
+    zkm_zkvm::io::commit_slice(&commitment);
+}
@@ -1,10 +1,23 @@
-use zkm_core_executor::{ExecutionReport, HookEnv, ZKMContextBuilder};
-use zkm_core_machine::io::ZKMStdin;
+use zkm_core_executor::{ExecutionReport, HookEnv, MemoryChecks, ZKMContextBuilder};
+use zkm_core_machine::{
+    io::ZKMStdin,
+    utils::{CancellationToken, CheckpointOpts, ExecutionEstimate, ProofProgress, ProverMonitor},
+};
 use zkm_primitives::io::ZKMPublicValues;
 use zkm_prover::{components::DefaultProverComponents, ZKMProvingKey};
 
-use anyhow::{Ok, Result};
-use std::time::Duration;
+use anyhow::{anyhow, Ok, Result};
+use futures::channel::{mpsc, oneshot};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::PathBuf,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
 use zkm_stark::{ZKMCoreOpts, ZKMProverOpts};
 
 use crate::{provers::ProofOpts, Prover, ZKMProofKind, ZKMProofWithPublicValues};
@@ -38,6 +51,20 @@ impl<'a> Execute<'a> {
         Ok(prover.zkm_prover().execute(elf, &stdin, context)?)
     }
 
+    /// Execute the program on the input and estimate the cost of proving it, consuming the built
+    /// action `self`.
+    ///
+    /// The returned [ExecutionEstimate] contains the predicted heights and shape of every shard,
+    /// the number of shards, the estimated peak memory and the estimated proving time of the core
+    /// proof with the default options. No traces are generated.
+    ///
+    /// Use [Prove::estimate] to estimate the cost with the options of a proof.
+    pub fn estimate(self) -> Result<ExecutionEstimate> {
+        let Self { prover, elf, stdin, mut context_builder } = self;
+        let context = context_builder.build();
+        Ok(prover.zkm_prover().estimate(elf, &stdin, ZKMProverOpts::default(), context)?)
+    }
+
     /// Add a runtime [Hook](super::Hook) into the context.
     ///
     /// Hooks may be invoked from within Ziren by writing to the specified file descriptor `fd`
@@ -52,6 +79,20 @@ impl<'a> Execute<'a> {
         self
     }
 
+    /// Add a typed runtime hook into the context, registered under `name`.
+    ///
+    /// Typed hooks may be invoked from within Ziren with
+    /// `zkm_zkvm::hook::call::<Req, Resp>(name, &req)`, which sends the request to `f` and
+    /// returns its response.
+    pub fn with_typed_hook<Req: DeserializeOwned, Resp: Serialize>(
+        mut self,
+        name: &str,
+        f: impl Fn(Req) -> Resp + Send + Sync + 'a,
+    ) -> Self {
+        self.context_builder.typed_hook(name, f);
+        self
+    }
+
     /// Avoid registering the default hooks in the runtime.
     ///
     /// It is not necessary to call this to override hooks --- instead, simply
@@ -75,6 +116,37 @@ impl<'a> Execute<'a> {
         self.context_builder.set_skip_deferred_proof_verification(value);
         self
     }
+
+    /// Set the environment variable `key` of the program to `value`, read with `std::env::var`
+    /// in the zkVM.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.context_builder.env(key, value);
+        self
+    }
+
+    /// Set the seed of the random number generator of the program.
+    pub fn rng_seed(mut self, seed: [u8; 32]) -> Self {
+        self.context_builder.rng_seed(seed);
+        self
+    }
+
+    /// Enable the memory-safety checks of the program, which report the loads from the null page,
+    /// the stores to read-only or executable segments, and the stack overflows as an
+    /// [`zkm_core_executor::ExecutionError::MemoryViolation`].
+    pub fn memory_checks(mut self, checks: MemoryChecks) -> Self {
+        self.context_builder.memory_checks(checks);
+        self
+    }
+
+    /// Attach a backtrace of the guest to the execution errors, which are then wrapped in
+    /// [`zkm_core_executor::ExecutionError::WithBacktrace`]; match on
+    /// [`zkm_core_executor::ExecutionError::inner`] to get the original error.
+    ///
+    /// Keep some debug info in the guest's release profile to symbolize the backtraces.
+    pub fn backtraces(mut self) -> Self {
+        self.context_builder.backtraces();
+        self
+    }
 }
 
 /// Builder to prepare and configure proving execution of a program on an input.
@@ -88,6 +160,10 @@ pub struct Prove<'a> {
     core_opts: ZKMCoreOpts,
     recursion_opts: ZKMCoreOpts,
     timeout: Option<Duration>,
+    checkpoint_opts: Option<CheckpointOpts>,
+    resume_from: Option<PathBuf>,
+    progress_listener: Option<Arc<dyn Fn(ProofProgress) + Send + Sync>>,
+    cancellation: CancellationToken,
 }
 
 impl<'a> Prove<'a> {
@@ -109,6 +185,10 @@ impl<'a> Prove<'a> {
             core_opts: ZKMCoreOpts::default(),
             recursion_opts: ZKMCoreOpts::recursion(),
             timeout: None,
+            checkpoint_opts: None,
+            resume_from: None,
+            progress_listener: None,
+            cancellation: CancellationToken::new(),
         }
     }
 
@@ -123,9 +203,18 @@ impl<'a> Prove<'a> {
             core_opts,
             recursion_opts,
             timeout,
+            checkpoint_opts,
+            resume_from,
+            progress_listener,
+            cancellation,
         } = self;
         let opts = ZKMProverOpts { core_opts, recursion_opts };
-        let proof_opts = ProofOpts { zkm_prover_opts: opts, timeout };
+        let mut monitor = ProverMonitor::new().with_cancellation(cancellation);
+        if let Some(listener) = progress_listener {
+            monitor = monitor.with_listener(move |progress| listener(progress));
+        }
+        let proof_opts =
+            ProofOpts { zkm_prover_opts: opts, timeout, checkpoint_opts, resume_from, monitor };
         let context = context_builder.build();
 
         // Dump the program and stdin to files for debugging if `ZKM_DUMP` is set.
@@ -134,6 +223,17 @@ impl<'a> Prove<'a> {
         prover.prove_impl(pk, stdin, proof_opts, context, kind)
     }
 
+    /// Execute the program on the input and estimate the cost of proving it with the options of
+    /// the proof, such as its shard size, consuming the built action `self`.
+    ///
+    /// See [Execute::estimate] for more documentation.
+    pub fn estimate(self) -> Result<ExecutionEstimate> {
+        let Self { prover, pk, stdin, mut context_builder, core_opts, recursion_opts, .. } = self;
+        let opts = ZKMProverOpts { core_opts, recursion_opts };
+        let context = context_builder.build();
+        Ok(prover.zkm_prover().estimate(&pk.elf, &stdin, opts, context)?)
+    }
+
     /// Set the proof kind to the core mode. This is the default.
     pub fn core(mut self) -> Self {
         self.kind = ZKMProofKind::Core;
@@ -178,6 +278,20 @@ impl<'a> Prove<'a> {
         self
     }
 
+    /// Add a typed runtime hook into the context, registered under `name`.
+    ///
+    /// Typed hooks may be invoked from within Ziren with
+    /// `zkm_zkvm::hook::call::<Req, Resp>(name, &req)`, which sends the request to `f` and
+    /// returns its response.
+    pub fn with_typed_hook<Req: DeserializeOwned, Resp: Serialize>(
+        mut self,
+        name: &str,
+        f: impl Fn(Req) -> Resp + Send + Sync + 'a,
+    ) -> Self {
+        self.context_builder.typed_hook(name, f);
+        self
+    }
+
     /// Avoid registering the default hooks in the runtime.
     ///
     /// It is not necessary to call this to override hooks --- instead, simply
@@ -227,4 +341,199 @@ impl<'a> Prove<'a> {
         self.context_builder.set_skip_deferred_proof_verification(value);
         self
     }
+
+    /// Set the environment variable `key` of the program to `value`, read with `std::env::var`
+    /// in the zkVM.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.context_builder.env(key, value);
+        self
+    }
+
+    /// Set the seed of the random number generator of the program.
+    pub fn rng_seed(mut self, seed: [u8; 32]) -> Self {
+        self.context_builder.rng_seed(seed);
+        self
+    }
+
+    /// Attach a backtrace of the guest to the execution errors, which are then wrapped in
+    /// [`zkm_core_executor::ExecutionError::WithBacktrace`]; match on
+    /// [`zkm_core_executor::ExecutionError::inner`] to get the original error.
+    ///
+    /// When resuming from a checkpoint, the backtrace only covers the calls made after it.
+    pub fn backtraces(mut self) -> Self {
+        self.context_builder.backtraces();
+        self
+    }
+
+    /// Write a checkpoint to `dir` every `interval_shards` execution shards while proving.
+    ///
+    /// A checkpoint may be passed to [`Self::resume_from_checkpoint`] to continue an interrupted
+    /// proof. This parameter is only used by the cpu prover.
+    pub fn checkpoints(mut self, dir: impl Into<PathBuf>, interval_shards: u32) -> Self {
+        self.checkpoint_opts = Some(CheckpointOpts::new(dir, interval_shards));
+        self
+    }
+
+    /// Resume proving from the checkpoint at `path`, previously written while proving the same
+    /// program.
+    ///
+    /// Only the shards after the checkpoint are proven, while the proofs of the shards before it
+    /// are taken from the checkpoint. The same `stdin` must be passed, since the checkpoint is
+    /// rejected if its input or deferred proofs differ. This parameter is only used by the cpu
+    /// prover.
+    pub fn resume_from_checkpoint(mut self, path: impl Into<PathBuf>) -> Self {
+        self.resume_from = Some(path.into());
+        self
+    }
+
+    /// Call `f` with the progress of the proof generation, from the prover threads.
+    ///
+    /// This parameter is only used by the cpu prover.
+    pub fn on_progress(mut self, f: impl Fn(ProofProgress) + Send + Sync + 'static) -> Self {
+        self.progress_listener = Some(Arc::new(f));
+        self
+    }
+
+    /// Stop the proof generation once `token` is cancelled, in which case it fails with a
+    /// `Cancelled` error.
+    ///
+    /// This parameter is only used by the cpu prover.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+}
+
+/// Builder to prepare and configure the aggregation of proofs into a single proof.
+/// May be run with [Self::run].
+#[cfg(feature = "aggregation")]
+pub struct Aggregate<'a> {
+    prover: &'a dyn Prover<DefaultProverComponents>,
+    kind: ZKMProofKind,
+    stdin: ZKMStdin,
+}
+
+#[cfg(feature = "aggregation")]
+impl<'a> Aggregate<'a> {
+    /// Prepare to aggregate the compressed `proofs` of the programs with the corresponding `vks`.
+    ///
+    /// Prefer using [ProverClient::aggregate](super::ProverClient::aggregate).
+    /// See there for more documentation.
+    pub fn new(
+        prover: &'a dyn Prover<DefaultProverComponents>,
+        proofs: &[ZKMProofWithPublicValues],
+        vks: &[zkm_prover::ZKMVerifyingKey],
+    ) -> Result<Self> {
+        let stdin = crate::aggregation::aggregation_stdin(proofs, vks)?;
+        Ok(Self { prover, kind: ZKMProofKind::Compressed, stdin })
+    }
+
+    /// Prove the aggregation of the proofs, consuming the built action `self`.
+    pub fn run(self) -> Result<ZKMProofWithPublicValues> {
+        let Self { prover, kind, stdin } = self;
+        let (pk, _) = prover.setup(crate::aggregation::AGGREGATION_ELF);
+        let mut prove = Prove::new(prover, &pk, stdin);
+        prove.kind = kind;
+        prove.run()
+    }
+
+    /// Set the proof kind to the compressed mode. This is the default.
+    pub fn compressed(mut self) -> Self {
+        self.kind = ZKMProofKind::Compressed;
+        self
+    }
+
+    /// Set the proof mode to the plonk bn254 mode.
+    pub fn plonk(mut self) -> Self {
+        self.kind = ZKMProofKind::Plonk;
+        self
+    }
+
+    /// Set the proof mode to the groth16 bn254 mode.
+    pub fn groth16(mut self) -> Self {
+        self.kind = ZKMProofKind::Groth16;
+        self
+    }
+}
+
+impl Prove<'static> {
+    /// Prove the execution of the program on the input in a background thread, consuming the
+    /// built action `self`.
+    ///
+    /// The returned [`ProofTask`] resolves to the proof, streams the progress of the proof
+    /// generation, and cancels it when dropped before completion. Since the proof is generated in
+    /// a separate thread, the client and the proving key must outlive it, e.g. by storing them in
+    /// a `static`.
+    pub fn run_async(mut self) -> ProofTask {
+        let (progress_tx, progress_rx) = mpsc::unbounded();
+        let listener = self.progress_listener.take();
+        self.progress_listener = Some(Arc::new(move |progress: ProofProgress| {
+            if let Some(listener) = &listener {
+                listener(progress.clone());
+            }
+            // The receiver may have been dropped, which is fine.
+            let _ = progress_tx.unbounded_send(progress);
+        }));
+        let cancellation = self.cancellation.clone();
+
+        let (result_tx, result_rx) = oneshot::channel();
+        std::thread::spawn(move || {
+            let result = catch_unwind(AssertUnwindSafe(|| self.run()))
+                .unwrap_or_else(|_| Err(anyhow!("the prover panicked")));
+            let _ = result_tx.send(result);
+        });
+
+        ProofTask { result: result_rx, progress: Some(progress_rx), cancellation, done: false }
+    }
+}
+
+/// A proof being generated in the background by [`Prove::run_async`].
+///
+/// Resolves to the proof once it is generated. Dropping the task before then cancels the proof
+/// generation.
+pub struct ProofTask {
+    result: oneshot::Receiver<Result<ZKMProofWithPublicValues>>,
+    progress: Option<mpsc::UnboundedReceiver<ProofProgress>>,
+    cancellation: CancellationToken,
+    done: bool,
+}
+
+impl ProofTask {
+    /// Take the stream of the progress of the proof generation, which ends once the proof
+    /// generation does. Returns `None` if it was already taken.
+    pub fn take_progress(&mut self) -> Option<mpsc::UnboundedReceiver<ProofProgress>> {
+        self.progress.take()
+    }
+
+    /// A token which cancels the proof generation, in which case the task resolves to a
+    /// `Cancelled` error.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
+
+    /// Cancel the proof generation.
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+}
+
+impl Future for ProofTask {
+    type Output = Result<ZKMProofWithPublicValues>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let result = match Pin::new(&mut self.result).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        self.done = true;
+        Poll::Ready(result.unwrap_or_else(|_| Err(anyhow!("the prover thread stopped"))))
+    }
+}
+
+impl Drop for ProofTask {
+    fn drop(&mut self) {
+        if !self.done {
+            self.cancellation.cancel();
+        }
+    }
 }
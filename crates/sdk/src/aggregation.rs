@@ -0,0 +1,63 @@
+//! # Ziren Aggregation
+//!
+//! Aggregation of the compressed proofs of any programs into a single proof, with the aggregation
+//! program built into the SDK.
+
+use anyhow::{bail, ensure, Result};
+use zkm_primitives::io::ZKMPublicValues;
+use zkm_prover::{HashableKey, ZKMVerifyingKey};
+
+use crate::{include_elf, ZKMProof, ZKMProofWithPublicValues, ZKMStdin};
+
+/// The program aggregating the proofs, whose public values are decoded by [AggregatedProof].
+pub const AGGREGATION_ELF: &[u8] = include_elf!("zkm-aggregation");
+
+/// A proof verified by the aggregation program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AggregatedProof {
+    /// The hash of the vkey of the program, as returned by [HashableKey::hash_u32].
+    pub vkey_hash: [u32; 8],
+    /// The sha256 digest of the public values of the proof.
+    pub public_values_digest: [u8; 32],
+}
+
+impl AggregatedProof {
+    /// Decodes the proofs committed to by the public values of an aggregation proof.
+    pub fn decode_all(public_values: &ZKMPublicValues) -> Result<Vec<Self>> {
+        let bytes = public_values.as_slice();
+        ensure!(bytes.len() % 64 == 0, "invalid aggregation public values");
+        Ok(bytes
+            .chunks_exact(64)
+            .map(|chunk| {
+                let mut vkey_hash = [0u32; 8];
+                for (word, bytes) in vkey_hash.iter_mut().zip(chunk[..32].chunks_exact(4)) {
+                    *word = u32::from_le_bytes(bytes.try_into().unwrap());
+                }
+                Self { vkey_hash, public_values_digest: chunk[32..].try_into().unwrap() }
+            })
+            .collect())
+    }
+}
+
+/// Writes the input of the aggregation program, verifying the compressed `proofs` of the programs
+/// with the corresponding `vks`.
+pub(crate) fn aggregation_stdin(
+    proofs: &[ZKMProofWithPublicValues],
+    vks: &[ZKMVerifyingKey],
+) -> Result<ZKMStdin> {
+    ensure!(proofs.len() == vks.len(), "expected one verifying key per proof");
+
+    let mut stdin = ZKMStdin::new();
+    stdin.write(&vks.iter().map(|vk| vk.hash_u32()).collect::<Vec<_>>());
+    stdin.write(&proofs.iter().map(|proof| proof.public_values.to_vec()).collect::<Vec<_>>());
+
+    // The proofs are not read by the program, but witnessed by the prover when recursively
+    // verifying them.
+    for (proof, vk) in proofs.iter().zip(vks) {
+        let ZKMProof::Compressed(reduce_proof) = &proof.proof else {
+            bail!("only compressed proofs can be aggregated");
+        };
+        stdin.write_proof(*reduce_proof.clone(), vk.vk.clone());
+    }
+    Ok(stdin)
+}
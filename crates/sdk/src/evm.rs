@@ -0,0 +1,316 @@
+//! # Ziren EVM
+//!
+//! Utilities for verifying Ziren Groth16 and Plonk proofs onchain: exporting the Solidity verifier
+//! contracts and encoding proofs into the calldata they expect.
+
+use std::{fs, path::Path};
+
+use anyhow::{bail, Context, Result};
+use ethers::abi::{encode, short_signature, ParamType, Token};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use zkm_prover::IZKM_VERIFIER_SOL;
+
+use crate::{
+    install::try_install_circuit_artifacts, ZKMProof, ZKMProofKind, ZKMProofWithPublicValues,
+    ZKM_CIRCUIT_VERSION,
+};
+
+/// The name of the function of `IZKMVerifier` which verifies a proof.
+const VERIFY_PROOF: &str = "verifyProof";
+
+/// The file name of the fixture written next to the verifier contracts.
+pub const EVM_FIXTURE_FILE: &str = "fixture.json";
+
+/// Returns the ABI encoded calldata of `IZKMVerifier.verifyProof(programVKey, publicValues,
+/// proofBytes)`.
+pub fn verify_proof_calldata(
+    program_vkey: [u8; 32],
+    public_values: &[u8],
+    proof: &[u8],
+) -> Vec<u8> {
+    let selector = short_signature(
+        VERIFY_PROOF,
+        &[ParamType::FixedBytes(32), ParamType::Bytes, ParamType::Bytes],
+    );
+    let args = encode(&[
+        Token::FixedBytes(program_vkey.to_vec()),
+        Token::Bytes(public_values.to_vec()),
+        Token::Bytes(proof.to_vec()),
+    ]);
+    [selector.to_vec(), args].concat()
+}
+
+/// Returns the program vkey committed to by a Plonk or Groth16 proof, as the `bytes32` expected by
+/// the onchain verifier.
+///
+/// Returns `None` for other proofs, and for proofs whose first public input is not an integer of
+/// at most 32 bytes.
+pub fn program_vkey(proof: &ZKMProof) -> Option<[u8; 32]> {
+    let public_inputs = match proof {
+        ZKMProof::Plonk(proof) => &proof.public_inputs,
+        ZKMProof::Groth16(proof) => &proof.public_inputs,
+        _ => return None,
+    };
+    let vkey = public_inputs[0].parse::<BigUint>().ok()?.to_bytes_be();
+    let offset = 32usize.checked_sub(vkey.len())?;
+    let mut program_vkey = [0u8; 32];
+    program_vkey[offset..].copy_from_slice(&vkey);
+    Some(program_vkey)
+}
+
+/// The inputs of an onchain verification of a proof, used to test the verifier contracts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmFixture {
+    /// The program vkey, as a hex encoded `bytes32`.
+    pub vkey: String,
+    /// The hex encoded public values.
+    pub public_values: String,
+    /// The hex encoded proof bytes.
+    pub proof: String,
+    /// The hex encoded calldata of `IZKMVerifier.verifyProof`.
+    pub calldata: String,
+}
+
+impl EvmFixture {
+    /// Creates the fixture of a Plonk or Groth16 proof.
+    pub fn new(proof: &ZKMProofWithPublicValues) -> Result<Self> {
+        let Some(vkey) = program_vkey(&proof.proof) else {
+            bail!("only Plonk and Groth16 proofs are verifiable onchain");
+        };
+        Ok(Self {
+            vkey: format!("0x{}", hex::encode(vkey)),
+            public_values: format!("0x{}", hex::encode(proof.public_values.as_slice())),
+            proof: format!("0x{}", hex::encode(proof.bytes()?)),
+            calldata: format!("0x{}", hex::encode(proof.evm_calldata()?)),
+        })
+    }
+
+    /// Writes the fixture as JSON to `dir/fixture.json`.
+    pub fn save(&self, dir: impl AsRef<Path>) -> Result<()> {
+        let path = dir.as_ref().join(EVM_FIXTURE_FILE);
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+/// Writes the Solidity contracts verifying proofs of kind `kind` for the program with the given
+/// vkey to `dir`.
+///
+/// The contracts are laid out as follows:
+/// - `IZKMVerifier.sol`: the interface of the verifiers.
+/// - `<circuit version>/ZKMVerifier{Groth16,Plonk}.sol`: the verifier of all Ziren proofs of the
+///   circuit version, along with the gnark verifier it inherits from.
+/// - `ZKMProgramVerifier.sol`: a verifier bound to the program vkey, which forwards to the Ziren
+///   verifier it is deployed with.
+///
+/// The gnark verifiers are taken from the circuit artifacts, which are downloaded if missing.
+pub fn write_solidity_verifiers(
+    kind: ZKMProofKind,
+    program_vkey: [u8; 32],
+    dir: impl AsRef<Path>,
+) -> Result<()> {
+    let (artifacts_type, proof_system, contracts) = match kind {
+        ZKMProofKind::Groth16 => {
+            ("groth16", "Groth16", ["ZKMVerifierGroth16.sol", "Groth16Verifier.sol"])
+        }
+        ZKMProofKind::Plonk => ("plonk", "Plonk", ["ZKMVerifierPlonk.sol", "PlonkVerifier.sol"]),
+        _ => bail!("only Plonk and Groth16 proofs are verifiable onchain"),
+    };
+    let artifacts_dir = if zkm_prover::build::zkm_dev_mode() {
+        match kind {
+            ZKMProofKind::Groth16 => zkm_prover::build::groth16_bn254_artifacts_dev_dir(),
+            _ => zkm_prover::build::plonk_bn254_artifacts_dev_dir(),
+        }
+    } else {
+        try_install_circuit_artifacts(artifacts_type)
+    };
+
+    let dir = dir.as_ref();
+    let version_dir = dir.join(ZKM_CIRCUIT_VERSION);
+    fs::create_dir_all(&version_dir)
+        .with_context(|| format!("failed to create {}", version_dir.display()))?;
+    for contract in contracts {
+        fs::copy(artifacts_dir.join(contract), version_dir.join(contract))
+            .with_context(|| format!("failed to copy {contract} from the circuit artifacts"))?;
+    }
+    fs::write(dir.join("IZKMVerifier.sol"), IZKM_VERIFIER_SOL)?;
+
+    let program_verifier = include_str!("../assets/ZKMProgramVerifier.txt")
+        .replace("{PROGRAM_VKEY}", &format!("0x{}", hex::encode(program_vkey)))
+        .replace("{PROOF_SYSTEM}", proof_system);
+    fs::write(dir.join("ZKMProgramVerifier.sol"), program_verifier)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use ethers::abi::decode;
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        primitives::{Address, ExecutionResult, Output, TxKind},
+        Evm,
+    };
+    use zkm_prover::Groth16Bn254Proof;
+
+    use super::*;
+    use crate::{utils, HashableKey, ProverClient, ZKMStdin};
+
+    /// The creation bytecode of `ZKMVerifierGroth16.sol`, compiled by
+    /// [`test_verify_groth16_in_evm`].
+    const GROTH16_VERIFIER_BYTECODE: &[u8] = include_bytes!("../assets/evm/ZKMVerifierGroth16.bin");
+
+    /// The fixture of a Groth16 proof of Fibonacci, written by [`test_verify_groth16_in_evm`].
+    const GROTH16_FIXTURE: &str = include_str!("../assets/evm/fixture.json");
+
+    #[test]
+    fn test_evm_calldata() {
+        utils::setup_logger();
+        let client = ProverClient::mock();
+        let elf = test_artifacts::FIBONACCI_ELF;
+        let (pk, vk) = client.setup(elf);
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&10usize);
+        let proof = client.prove(&pk, stdin).groth16().run().unwrap();
+
+        let calldata = proof.evm_calldata().unwrap();
+        assert_eq!(calldata[..4], short_signature(VERIFY_PROOF, &verify_proof_params()));
+        let args = decode(&verify_proof_params(), &calldata[4..]).unwrap();
+        assert_eq!(
+            args,
+            vec![
+                Token::FixedBytes(hex::decode(&vk.bytes32()[2..]).unwrap()),
+                Token::Bytes(proof.public_values.to_vec()),
+                Token::Bytes(proof.bytes().unwrap()),
+            ]
+        );
+    }
+
+    fn verify_proof_params() -> [ParamType; 3] {
+        [ParamType::FixedBytes(32), ParamType::Bytes, ParamType::Bytes]
+    }
+
+    #[test]
+    fn test_program_vkey_invalid_public_inputs() {
+        let proof = |vkey: String| {
+            ZKMProof::Groth16(Groth16Bn254Proof {
+                public_inputs: [vkey, "0".to_string()],
+                ..Default::default()
+            })
+        };
+        assert_eq!(program_vkey(&proof("vkey".to_string())), None);
+        assert_eq!(program_vkey(&proof(BigUint::from(2u8).pow(256).to_string())), None);
+
+        let mut expected = [0u8; 32];
+        expected[31] = 7;
+        assert_eq!(program_vkey(&proof("7".to_string())), Some(expected));
+    }
+
+    /// Deploys the contract with the given creation bytecode and returns its address.
+    fn deploy(evm: &mut Evm<'_, (), CacheDB<EmptyDB>>, bytecode: Vec<u8>) -> Address {
+        evm.tx_mut().transact_to = TxKind::Create;
+        evm.tx_mut().data = bytecode.into();
+        match evm.transact_commit().unwrap() {
+            ExecutionResult::Success { output: Output::Create(_, Some(address)), .. } => address,
+            result => panic!("failed to deploy the verifier: {result:?}"),
+        }
+    }
+
+    /// Verifies the committed Groth16 fixture with the committed verifier bytecode, so that the
+    /// onchain verification is tested without `solc` or the circuit artifacts.
+    #[test]
+    fn test_verify_groth16_fixture_in_evm() {
+        assert!(
+            !GROTH16_VERIFIER_BYTECODE.is_empty(),
+            "the verifier bytecode is missing, regenerate it with `test_verify_groth16_in_evm`"
+        );
+        let fixture: EvmFixture = serde_json::from_str(GROTH16_FIXTURE).unwrap();
+        let decode_hex = |s: &str| hex::decode(s.trim_start_matches("0x")).unwrap();
+
+        let mut evm = Evm::builder().with_db(CacheDB::new(EmptyDB::default())).build();
+        let address = deploy(&mut evm, GROTH16_VERIFIER_BYTECODE.to_vec());
+
+        // Verify the proof.
+        evm.tx_mut().transact_to = TxKind::Call(address);
+        evm.tx_mut().data = decode_hex(&fixture.calldata).into();
+        let result = evm.transact_commit().unwrap();
+        assert!(result.is_success(), "proof rejected: {result:?}");
+
+        // The verification must fail for other public values.
+        let program_vkey = decode_hex(&fixture.vkey).try_into().unwrap();
+        let calldata = verify_proof_calldata(program_vkey, b"other", &decode_hex(&fixture.proof));
+        evm.tx_mut().data = calldata.into();
+        let result = evm.transact_commit().unwrap();
+        assert!(!result.is_success());
+    }
+
+    /// Compiles the contract `name` of the Solidity file at `path` with `solc`.
+    fn compile(dir: &Path, path: &Path, name: &str) -> Vec<u8> {
+        let output = Command::new("solc")
+            .args(["--combined-json", "bin", "--base-path"])
+            .arg(dir)
+            .arg(path)
+            .output()
+            .expect("failed to run solc");
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let output: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let (_, contract) = output["contracts"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .find(|(id, _)| id.ends_with(&format!(":{name}")))
+            .expect("contract not found");
+        hex::decode(contract["bin"].as_str().unwrap()).unwrap()
+    }
+
+    /// Proves Fibonacci with Groth16, verifies the proof with the exported verifier, and writes the
+    /// verifier bytecode and the fixture checked by [`test_verify_groth16_fixture_in_evm`] to
+    /// `assets/evm`.
+    // Requires `solc` (>= 0.8.20) in the `PATH`.
+    // RUST_LOG=debug cargo test -r test_verify_groth16_in_evm -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn test_verify_groth16_in_evm() {
+        utils::setup_logger();
+        let client = ProverClient::cpu();
+        let elf = test_artifacts::FIBONACCI_ELF;
+        let (pk, _) = client.setup(elf);
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&10usize);
+        let proof = client.prove(&pk, stdin).groth16().run().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let program_vkey = program_vkey(&proof.proof).unwrap();
+        write_solidity_verifiers(ZKMProofKind::Groth16, program_vkey, dir.path()).unwrap();
+        let bytecode = compile(
+            dir.path(),
+            &dir.path().join(ZKM_CIRCUIT_VERSION).join("ZKMVerifierGroth16.sol"),
+            "ZKMVerifier",
+        );
+
+        // Deploy the verifier.
+        let mut evm = Evm::builder().with_db(CacheDB::new(EmptyDB::default())).build();
+        let address = deploy(&mut evm, bytecode.clone());
+
+        // Verify the proof.
+        evm.tx_mut().transact_to = TxKind::Call(address);
+        evm.tx_mut().data = proof.evm_calldata().unwrap().into();
+        let result = evm.transact_commit().unwrap();
+        assert!(result.is_success(), "proof rejected: {result:?}");
+
+        // The verification must fail for other public values.
+        let calldata = verify_proof_calldata(program_vkey, b"other", &proof.bytes().unwrap());
+        evm.tx_mut().data = calldata.into();
+        let result = evm.transact_commit().unwrap();
+        assert!(!result.is_success());
+
+        // Update the assets of `test_verify_groth16_fixture_in_evm`.
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/evm");
+        fs::write(assets.join("ZKMVerifierGroth16.bin"), bytecode).unwrap();
+        EvmFixture::new(&proof).unwrap().save(assets).unwrap();
+    }
+}
@@ -3,6 +3,9 @@
 //! A library for interacting with the Ziren zkVM.
 
 pub mod action;
+#[cfg(feature = "aggregation")]
+pub mod aggregation;
+pub mod evm;
 // pub mod artifacts;
 pub mod install;
 
@@ -26,12 +29,18 @@ use zkm_prover::components::DefaultProverComponents;
 pub use provers::{CpuProver, MockProver, Prover};
 
 pub use zkm_build::include_elf;
-pub use zkm_core_executor::{ExecutionReport, HookEnv, ZKMContext, ZKMContextBuilder};
-pub use zkm_core_machine::{io::ZKMStdin, ZKM_CIRCUIT_VERSION};
-pub use zkm_primitives::io::ZKMPublicValues;
+pub use zkm_core_executor::{
+    ExecutionReport, HookEnv, MemoryChecks, ZKMContext, ZKMContextBuilder,
+};
+pub use zkm_core_machine::{
+    io::ZKMStdin,
+    utils::{CancellationToken, ExecutionEstimate, ProofProgress, ShardEstimate},
+    ZKM_CIRCUIT_VERSION,
+};
+pub use zkm_primitives::{io::ZKMPublicValues, types::VkeyMembershipProof};
 pub use zkm_prover::{
-    CoreSC, HashableKey, InnerSC, OuterSC, PlonkBn254Proof, ProverMode, ZKMProver, ZKMProvingKey,
-    ZKMVerifyingKey,
+    registry::VkeyRegistry, CoreSC, HashableKey, InnerSC, OuterSC, PlonkBn254Proof, ProverMode,
+    ZKMProver, ZKMProvingKey, ZKMVerifyingKey,
 };
 
 // Re-export the utilities.
@@ -180,6 +189,7 @@ impl ProverClient {
     ///
     /// To execute, call [action::Execute::run], which returns
     /// the public values and execution report of the program after it has been executed.
+    /// To estimate the cost of proving the program instead, call [action::Execute::estimate].
     ///
     /// ### Examples
     /// ```no_run
@@ -235,6 +245,47 @@ impl ProverClient {
         action::Prove::new(self.prover.as_ref(), pk, stdin)
     }
 
+    /// Prepare to aggregate the compressed proofs of any programs into a single proof.
+    ///
+    /// Each proof is recursively verified against the verifying key at the same index in `vks` by
+    /// the [aggregation program](aggregation::AGGREGATION_ELF), whose public values commit to the
+    /// list of (vkey hash, public values digest) pairs of the proofs, decoded with
+    /// [aggregation::AggregatedProof::decode_all]. The aggregation proof is compressed by default,
+    /// and verified with the verifying key of the aggregation program.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use zkm_sdk::{aggregation::AGGREGATION_ELF, ProverClient, ZKMStdin};
+    ///
+    /// let elf = test_artifacts::FIBONACCI_ELF;
+    /// let client = ProverClient::new();
+    /// let (pk, vk) = client.setup(elf);
+    ///
+    /// // Generate the compressed proofs.
+    /// let proofs = [10usize, 20]
+    ///     .map(|n| {
+    ///         let mut stdin = ZKMStdin::new();
+    ///         stdin.write(&n);
+    ///         client.prove(&pk, stdin).compressed().run().unwrap()
+    ///     })
+    ///     .to_vec();
+    ///
+    /// // Aggregate them into a single Groth16 proof.
+    /// let proof = client.aggregate(&proofs, &[vk.clone(), vk]).unwrap().groth16().run().unwrap();
+    ///
+    /// // Verify the aggregation proof.
+    /// let (_, aggregation_vk) = client.setup(AGGREGATION_ELF);
+    /// client.verify(&proof, &aggregation_vk).unwrap();
+    /// ```
+    #[cfg(feature = "aggregation")]
+    pub fn aggregate<'a>(
+        &'a self,
+        proofs: &[ZKMProofWithPublicValues],
+        vks: &[ZKMVerifyingKey],
+    ) -> anyhow::Result<action::Aggregate<'a>> {
+        action::Aggregate::new(self.prover.as_ref(), proofs, vks)
+    }
+
     /// Verifies that the given proof is valid and matches the given verification key produced by
     /// [Self::setup].
     ///
@@ -407,7 +458,8 @@ mod tests {
     use crate::utils::compute_groth16_public_values;
     use crate::ZKMProof;
     use crate::ZKMProof::Groth16;
-    use crate::{utils, ProverClient, ZKMStdin};
+    use crate::{utils, CancellationToken, ProofProgress, ProverClient, ZKMProvingKey, ZKMStdin};
+    use futures::StreamExt;
     use zkm_primitives::io::ZKMPublicValues;
 
     #[test]
@@ -421,6 +473,36 @@ mod tests {
         // tracing::info!("gas = {}", report.estimate_gas());
     }
 
+    #[test]
+    fn test_estimate() {
+        utils::setup_logger();
+        let client = ProverClient::cpu();
+        let elf = test_artifacts::FIBONACCI_ELF;
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&10usize);
+        let estimate = client.execute(elf, stdin).estimate().unwrap();
+        assert!(estimate.num_shards() > 0);
+        assert!(estimate.shards.iter().all(|shard| shard.shape.is_some()));
+        assert!(estimate.peak_memory > 0);
+    }
+
+    #[test]
+    fn test_estimate_with_shard_size() {
+        utils::setup_logger();
+        let client = ProverClient::cpu();
+        let elf = test_artifacts::FIBONACCI_ELF;
+        let (pk, _) = client.setup(elf);
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&10usize);
+        let estimate = client.execute(elf, stdin.clone()).estimate().unwrap();
+        assert_eq!(estimate.num_shards(), 1);
+
+        // Half of the cycles rounded down to a power of two, so the execution spans two shards.
+        let shard_size = 1 << (estimate.cycles.ilog2() - 1);
+        let estimate = client.prove(&pk, stdin).shard_size(shard_size).estimate().unwrap();
+        assert!(estimate.num_shards() > 1);
+    }
+
     #[test]
     #[should_panic]
     fn test_execute_panic() {
@@ -463,6 +545,97 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "aggregation")]
+    #[test]
+    fn test_e2e_aggregate() {
+        use crate::aggregation::{AggregatedProof, AGGREGATION_ELF};
+        use crate::HashableKey;
+
+        utils::setup_logger();
+        let client = ProverClient::cpu();
+        let elf = test_artifacts::FIBONACCI_ELF;
+        let (pk, vk) = client.setup(elf);
+        let proofs = [10usize, 20]
+            .map(|n| {
+                let mut stdin = ZKMStdin::new();
+                stdin.write(&n);
+                client.prove(&pk, stdin).compressed().run().unwrap()
+            })
+            .to_vec();
+
+        // Aggregate the proofs & verify.
+        let proof = client.aggregate(&proofs, &[vk.clone(), vk.clone()]).unwrap().run().unwrap();
+        let (_, aggregation_vk) = client.setup(AGGREGATION_ELF);
+        client.verify(&proof, &aggregation_vk).unwrap();
+
+        // The public values commit to the aggregated proofs.
+        let aggregated = AggregatedProof::decode_all(&proof.public_values).unwrap();
+        let expected = proofs
+            .iter()
+            .map(|proof| AggregatedProof {
+                vkey_hash: vk.hash_u32(),
+                public_values_digest: proof.public_values.hash().try_into().unwrap(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(aggregated, expected);
+
+        // Core proofs cannot be aggregated.
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&10usize);
+        let core_proof = client.prove(&pk, stdin).run().unwrap();
+        assert!(client.aggregate(&[core_proof], &[vk]).is_err());
+    }
+
+    #[test]
+    fn test_e2e_compressed_async() {
+        utils::setup_logger();
+        let client: &'static ProverClient = Box::leak(Box::new(ProverClient::cpu()));
+        let elf = test_artifacts::FIBONACCI_ELF;
+        let (pk, vk) = client.setup(elf);
+        let pk: &'static ZKMProvingKey = Box::leak(Box::new(pk));
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&10usize);
+
+        // Generate proof & verify, collecting the progress.
+        let mut task = client.prove(pk, stdin).compressed().run_async();
+        let progress = task.take_progress().unwrap();
+        let proof = futures::executor::block_on(task).unwrap();
+        client.verify(&proof, &vk).unwrap();
+
+        let progress = futures::executor::block_on(progress.collect::<Vec<_>>());
+        let num_shards = match progress.first() {
+            Some(ProofProgress::ExecutionDone { num_shards }) => *num_shards,
+            _ => panic!("expected the execution to be reported first"),
+        };
+        let shards_proven = progress
+            .iter()
+            .filter(|progress| matches!(progress, ProofProgress::ShardProven { .. }))
+            .count();
+        assert_eq!(shards_proven, num_shards);
+        assert!(matches!(
+            progress.last(),
+            Some(ProofProgress::CompressProven { layer, num_layers, proven: 1, total: 1 })
+                if layer + 1 == *num_layers
+        ));
+    }
+
+    #[test]
+    fn test_cancel_async() {
+        utils::setup_logger();
+        let client: &'static ProverClient = Box::leak(Box::new(ProverClient::cpu()));
+        let elf = test_artifacts::FIBONACCI_ELF;
+        let (pk, _) = client.setup(elf);
+        let pk: &'static ZKMProvingKey = Box::leak(Box::new(pk));
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&10usize);
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let task = client.prove(pk, stdin).compressed().with_cancellation(token).run_async();
+        let err = futures::executor::block_on(task).unwrap_err();
+        assert!(err.to_string().contains("cancelled"));
+    }
+
     #[test]
     fn test_e2e_compressed() {
         utils::setup_logger();
@@ -61,7 +61,10 @@ impl NetworkProver {
         );
         let ssl_cert_path = env::var("SSL_CERT_PATH").ok();
         let ssl_key_path = env::var("SSL_KEY_PATH").ok();
-        let ssl_config = if ca_cert_path.as_ref().is_none() {
+        let endpoint_para = endpoint.to_owned().expect("ENDPOINT must be set");
+        // A plaintext endpoint, e.g. a local stage server, does not need the certificates.
+        let ssl_config = if ca_cert_path.as_ref().is_none() || endpoint_para.starts_with("http://")
+        {
             None
         } else {
             let (ca_cert, identity) = get_cert_and_identity(
@@ -72,7 +75,6 @@ impl NetworkProver {
             Some(Config { ca_cert, identity })
         };
 
-        let endpoint_para = endpoint.to_owned().expect("ENDPOINT must be set");
         let endpoint = match ssl_config {
             Some(config) => {
                 let mut tls_config = ClientTlsConfig::new()
@@ -116,6 +118,10 @@ impl NetworkProver {
     }
 
     pub async fn download_file(url: &str) -> Result<Vec<u8>> {
+        // A stage server storing the proofs on its local file system returns `file://` URLs.
+        if let Some(path) = url.strip_prefix("file://") {
+            return Ok(fs::read(path)?);
+        }
         let response = reqwest::get(url).await?;
         let content = response.bytes().await?;
         Ok(content.to_vec())
@@ -1,6 +1,6 @@
 use std::{fmt::Debug, fs::File, path::Path};
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 use strum_macros::{EnumDiscriminants, EnumTryAs};
 use zkm_core_executor::ZKMReduceProof;
@@ -66,33 +66,58 @@ impl ZKMProofWithPublicValues {
     /// For Plonk or Groth16 proofs, returns the proof in a byte encoding the onchain verifier
     /// accepts. The bytes consist of the first four bytes of Plonk vkey hash followed by the
     /// encoded proof, in a form optimized for onchain verification.
-    pub fn bytes(&self) -> Vec<u8> {
+    ///
+    /// For compressed proofs, returns the bincode encoded proof accepted by the compressed proof
+    /// verifier of `zkm-verifier`.
+    ///
+    /// Returns an error for core proofs, which have no byte encoding any verifier accepts.
+    pub fn bytes(&self) -> Result<Vec<u8>> {
         match &self.proof {
             ZKMProof::Plonk(plonk_proof) => {
                 if plonk_proof.encoded_proof.is_empty() {
                     // If the proof is empty, then this is a mock proof. The mock Ziren verifier
                     // expects an empty byte array for verification, so return an empty byte array.
-                    return Vec::new();
+                    return Ok(Vec::new());
                 }
 
                 let proof_bytes =
-                    hex::decode(&plonk_proof.encoded_proof).expect("Invalid Plonk proof");
-                [plonk_proof.plonk_vkey_hash[..4].to_vec(), proof_bytes].concat()
+                    hex::decode(&plonk_proof.encoded_proof).context("invalid Plonk proof")?;
+                Ok([plonk_proof.plonk_vkey_hash[..4].to_vec(), proof_bytes].concat())
             }
             ZKMProof::Groth16(groth16_proof) => {
                 if groth16_proof.encoded_proof.is_empty() {
                     // If the proof is empty, then this is a mock proof. The mock Ziren verifier
                     // expects an empty byte array for verification, so return an empty byte array.
-                    return Vec::new();
+                    return Ok(Vec::new());
                 }
 
                 let proof_bytes =
-                    hex::decode(&groth16_proof.encoded_proof).expect("Invalid Groth16 proof");
-                [groth16_proof.groth16_vkey_hash[..4].to_vec(), proof_bytes].concat()
+                    hex::decode(&groth16_proof.encoded_proof).context("invalid Groth16 proof")?;
+                Ok([groth16_proof.groth16_vkey_hash[..4].to_vec(), proof_bytes].concat())
             }
-            _ => unimplemented!("only Plonk and Groth16 proofs are verifiable onchain"),
+            ZKMProof::Compressed(proof) => {
+                // The compressed proof verifier of `zkm-verifier` expects the bincode encoded
+                // reduce proof, which contains the verifying key of the compress program.
+                bincode::serialize(&**proof).context("failed to serialize compressed proof")
+            }
+            _ => bail!("only Plonk, Groth16 and compressed proofs are verifiable"),
         }
     }
+
+    /// For Plonk or Groth16 proofs, returns the ABI encoded calldata of a call to
+    /// `IZKMVerifier.verifyProof` of the onchain verifier, verifying this proof.
+    ///
+    /// Returns an error for other proofs, which are not verifiable onchain.
+    pub fn evm_calldata(&self) -> Result<Vec<u8>> {
+        let Some(program_vkey) = crate::evm::program_vkey(&self.proof) else {
+            bail!("only Plonk and Groth16 proofs are verifiable onchain");
+        };
+        Ok(crate::evm::verify_proof_calldata(
+            program_vkey,
+            self.public_values.as_slice(),
+            &self.bytes()?,
+        ))
+    }
 }
 
 pub type ZKMCoreProofVerificationError = MachineVerificationError<CoreSC>;
@@ -117,7 +142,7 @@ mod tests {
             zkm_version: "".to_string(),
         };
         let expected_bytes = [vec![0, 0, 0, 0], hex::decode("ab").unwrap()].concat();
-        assert_eq!(plonk_proof.bytes(), expected_bytes);
+        assert_eq!(plonk_proof.bytes().unwrap(), expected_bytes);
     }
 
     #[test]
@@ -134,7 +159,7 @@ mod tests {
             zkm_version: "".to_string(),
         };
         let expected_bytes = [vec![0, 0, 0, 0], hex::decode("ab").unwrap()].concat();
-        assert_eq!(groth16_proof.bytes(), expected_bytes);
+        assert_eq!(groth16_proof.bytes().unwrap(), expected_bytes);
     }
 
     #[test]
@@ -150,7 +175,7 @@ mod tests {
             public_values: ZKMPublicValues::new(),
             zkm_version: "".to_string(),
         };
-        assert_eq!(mock_plonk_proof.bytes(), Vec::<u8>::new());
+        assert_eq!(mock_plonk_proof.bytes().unwrap(), Vec::<u8>::new());
     }
 
     #[test]
@@ -166,18 +191,18 @@ mod tests {
             public_values: ZKMPublicValues::new(),
             zkm_version: "".to_string(),
         };
-        assert_eq!(mock_groth16_proof.bytes(), Vec::<u8>::new());
+        assert_eq!(mock_groth16_proof.bytes().unwrap(), Vec::<u8>::new());
     }
 
     #[test]
-    #[should_panic(expected = "only Plonk and Groth16 proofs are verifiable onchain")]
-    fn test_core_proof_bytes_unimplemented() {
+    fn test_core_proof_bytes_unsupported() {
         let core_proof = ZKMProofWithPublicValues {
             proof: ZKMProof::Core(vec![]),
             stdin: ZKMStdin::new(),
             public_values: ZKMPublicValues::new(),
             zkm_version: "".to_string(),
         };
-        core_proof.bytes();
+        assert!(core_proof.bytes().is_err());
+        assert!(core_proof.evm_calldata().is_err());
     }
 }
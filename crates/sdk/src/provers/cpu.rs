@@ -1,7 +1,10 @@
 use anyhow::Result;
 use zkm_core_executor::ZKMContext;
-use zkm_core_machine::io::ZKMStdin;
-use zkm_prover::{components::DefaultProverComponents, ZKMProver};
+use zkm_core_machine::{
+    io::ZKMStdin,
+    utils::{ProofProgress, ProvingCheckpoint},
+};
+use zkm_prover::{components::DefaultProverComponents, ZKMProver, ZKMRecursionProverError};
 
 use crate::install::try_install_circuit_artifacts;
 use crate::{
@@ -41,9 +44,13 @@ impl CpuProver {
 
         // Generate the shrink proof.
         let shrink_proof = self.prover.shrink(proof, opts.zkm_prover_opts)?;
+        opts.monitor.report(ProofProgress::Shrunk);
+        check_cancelled(&opts)?;
 
         // Genenerate the wrap proof.
         let outer_proof = self.prover.wrap_bn254(shrink_proof, opts.zkm_prover_opts)?;
+        opts.monitor.report(ProofProgress::Wrapped);
+        check_cancelled(&opts)?;
 
         let groth16_bn254_artifacts = if zkm_prover::build::zkm_dev_mode() {
             zkm_prover::build::try_build_groth16_bn254_artifacts_dev(
@@ -55,6 +62,7 @@ impl CpuProver {
         };
 
         let proof = self.prover.wrap_groth16_bn254(outer_proof, &groth16_bn254_artifacts);
+        opts.monitor.report(ProofProgress::SnarkProven);
         Ok(ZKMProofWithPublicValues {
             proof: ZKMProof::Groth16(proof),
             stdin,
@@ -89,9 +97,20 @@ impl Prover<DefaultProverComponents> for CpuProver {
             return self.compress_to_groth16(stdin, opts);
         }
 
+        // Load the checkpoint to resume from, if any.
+        let resume = opts.resume_from.as_ref().map(ProvingCheckpoint::load).transpose()?;
+
         // Generate the core proof.
         let proof: zkm_prover::ZKMProofWithMetadata<zkm_prover::ZKMCoreProofData> =
-            self.prover.prove_core(pk, &stdin, opts.zkm_prover_opts, context)?;
+            self.prover.prove_core_with_checkpoints(
+                pk,
+                &stdin,
+                opts.zkm_prover_opts,
+                context,
+                opts.checkpoint_opts.as_ref(),
+                resume,
+                &opts.monitor,
+            )?;
         if kind == ZKMProofKind::Core {
             return Ok(ZKMProofWithPublicValues {
                 proof: ZKMProof::Core(proof.proof.0),
@@ -106,8 +125,13 @@ impl Prover<DefaultProverComponents> for CpuProver {
         let public_values = proof.public_values.clone();
 
         // Generate the compressed proof.
-        let reduce_proof =
-            self.prover.compress(&pk.vk, proof, deferred_proofs, opts.zkm_prover_opts)?;
+        let reduce_proof = self.prover.compress_with_monitor(
+            &pk.vk,
+            proof,
+            deferred_proofs,
+            opts.zkm_prover_opts,
+            &opts.monitor,
+        )?;
         if kind == ZKMProofKind::Compressed {
             return Ok(ZKMProofWithPublicValues {
                 proof: ZKMProof::Compressed(Box::new(reduce_proof)),
@@ -119,9 +143,13 @@ impl Prover<DefaultProverComponents> for CpuProver {
 
         // Generate the shrink proof.
         let compress_proof = self.prover.shrink(reduce_proof, opts.zkm_prover_opts)?;
+        opts.monitor.report(ProofProgress::Shrunk);
+        check_cancelled(&opts)?;
 
         // Genenerate the wrap proof.
         let outer_proof = self.prover.wrap_bn254(compress_proof, opts.zkm_prover_opts)?;
+        opts.monitor.report(ProofProgress::Wrapped);
+        check_cancelled(&opts)?;
 
         if kind == ZKMProofKind::Plonk {
             let plonk_bn254_artifacts = if zkm_prover::build::zkm_dev_mode() {
@@ -133,6 +161,7 @@ impl Prover<DefaultProverComponents> for CpuProver {
                 try_install_circuit_artifacts("plonk")
             };
             let proof = self.prover.wrap_plonk_bn254(outer_proof, &plonk_bn254_artifacts);
+            opts.monitor.report(ProofProgress::SnarkProven);
 
             return Ok(ZKMProofWithPublicValues {
                 proof: ZKMProof::Plonk(proof),
@@ -151,6 +180,7 @@ impl Prover<DefaultProverComponents> for CpuProver {
             };
 
             let proof = self.prover.wrap_groth16_bn254(outer_proof, &groth16_bn254_artifacts);
+            opts.monitor.report(ProofProgress::SnarkProven);
             return Ok(ZKMProofWithPublicValues {
                 proof: ZKMProof::Groth16(proof),
                 stdin,
@@ -163,6 +193,14 @@ impl Prover<DefaultProverComponents> for CpuProver {
     }
 }
 
+/// Stop between two stages of the proof generation once it is cancelled.
+fn check_cancelled(opts: &ProofOpts) -> Result<()> {
+    if opts.monitor.is_cancelled() {
+        return Err(ZKMRecursionProverError::Cancelled.into());
+    }
+    Ok(())
+}
+
 impl Default for CpuProver {
     fn default() -> Self {
         Self::new()
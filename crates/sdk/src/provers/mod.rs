@@ -11,6 +11,7 @@ pub use mock::MockProver;
 use itertools::Itertools;
 use p3_field::PrimeField32;
 use std::borrow::Borrow;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use anyhow::Result;
@@ -18,7 +19,11 @@ use strum_macros::EnumString;
 use thiserror::Error;
 use zkm_core_executor::ExecutionReport;
 use zkm_core_executor::ZKMContext;
-use zkm_core_machine::{io::ZKMStdin, ZKM_CIRCUIT_VERSION};
+use zkm_core_machine::{
+    io::ZKMStdin,
+    utils::{CheckpointOpts, ProverMonitor},
+    ZKM_CIRCUIT_VERSION,
+};
 use zkm_primitives::io::ZKMPublicValues;
 use zkm_prover::{
     components::{DefaultProverComponents, ZKMProverComponents},
@@ -46,6 +51,13 @@ pub struct ProofOpts {
     pub zkm_prover_opts: ZKMProverOpts,
     /// Optional timeout duration for proof generation.
     pub timeout: Option<Duration>,
+    /// Optional options to periodically write proving checkpoints.
+    pub checkpoint_opts: Option<CheckpointOpts>,
+    /// Optional path of a checkpoint to resume proving from.
+    pub resume_from: Option<PathBuf>,
+    /// Receives the progress of the proof generation and can cancel it. Only used by the CPU
+    /// prover.
+    pub monitor: ProverMonitor,
 }
 
 #[derive(Error, Debug)]
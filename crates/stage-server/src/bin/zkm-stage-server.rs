@@ -0,0 +1,70 @@
+use std::{net::SocketAddr, path::PathBuf};
+
+use clap::Parser;
+use ethers::types::Address;
+use tonic::transport::{Certificate, Identity, Server, ServerTlsConfig};
+use zkm_core_machine::utils::setup_logger;
+use zkm_prover::{components::DefaultProverComponents, ZKMProver};
+use zkm_stage_server::{JobQueue, ProofStorage, StageServer};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// The address to listen on.
+    #[clap(long, env = "STAGE_SERVER_ADDR", default_value = "127.0.0.1:50000")]
+    addr: SocketAddr,
+    /// The directory to store the proofs in.
+    #[clap(long, env = "STAGE_SERVER_STORAGE", default_value = "proofs")]
+    storage: PathBuf,
+    /// The URL under which the storage directory is served, if not `file://<storage>`.
+    #[clap(long, env = "STAGE_SERVER_BASE_URL")]
+    base_url: Option<String>,
+    /// The maximum number of pending jobs.
+    #[clap(long, default_value_t = 16)]
+    queue_capacity: usize,
+    /// Only accept requests signed by these addresses.
+    #[clap(long, value_delimiter = ',')]
+    allowed_signers: Option<Vec<Address>>,
+    /// The certificate and key of the server, to serve over TLS.
+    #[clap(long, requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+    #[clap(long, requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+    /// The CA certificate of the clients, to require client authentication.
+    #[clap(long, requires = "tls_cert")]
+    client_ca_cert: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    setup_logger();
+    let args = Args::parse();
+
+    let storage = ProofStorage::new(&args.storage, args.base_url)?;
+    let queue = JobQueue::start(
+        ZKMProver::<DefaultProverComponents>::new(),
+        storage.clone(),
+        args.queue_capacity,
+    );
+    let server = StageServer::new(queue, storage, args.allowed_signers);
+
+    let mut builder = Server::builder();
+    if let (Some(cert), Some(key)) = (&args.tls_cert, &args.tls_key) {
+        let mut tls_config = ServerTlsConfig::new()
+            .identity(Identity::from_pem(std::fs::read(cert)?, std::fs::read(key)?));
+        if let Some(ca_cert) = &args.client_ca_cert {
+            tls_config = tls_config.client_ca_root(Certificate::from_pem(std::fs::read(ca_cert)?));
+        }
+        builder = builder.tls_config(tls_config)?;
+    }
+
+    tracing::info!("listening on {}", args.addr);
+    builder
+        .add_service(server.into_service())
+        .serve_with_shutdown(args.addr, async {
+            tokio::signal::ctrl_c().await.ok();
+        })
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,308 @@
+use std::{
+    collections::HashMap,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        mpsc::{sync_channel, SyncSender, TrySendError},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use thiserror::Error;
+use zkm_core_executor::{Program, ZKMContext};
+use zkm_core_machine::{io::ZKMStdin, reduce::ZKMReduceProof};
+use zkm_primitives::io::ZKMPublicValues;
+use zkm_prover::{components::DefaultProverComponents, InnerSC, ZKMProver};
+use zkm_sdk::{
+    install::try_install_circuit_artifacts,
+    network::prover::stage_service::{GenerateProofRequest, Status, Step},
+    ZKMProof, ZKMProofKind,
+};
+use zkm_stark::ZKMProverOpts;
+
+use crate::storage::{ProofMetadata, ProofStorage};
+
+/// A validated proof request.
+pub struct ProofJob {
+    pub proof_id: String,
+    pub elf: Vec<u8>,
+    pub stdin: ZKMStdin,
+    pub kind: ZKMProofKind,
+    pub opts: ZKMProverOpts,
+}
+
+impl ProofJob {
+    /// Decode a [`GenerateProofRequest`] the way the SDK's `NetworkProver` encodes it.
+    pub fn from_request(request: GenerateProofRequest) -> Result<Self, String> {
+        let proof_id = if request.proof_id.is_empty() {
+            uuid::Uuid::new_v4().to_string()
+        } else {
+            request.proof_id
+        };
+        if !ProofStorage::is_valid_id(&proof_id) {
+            return Err(format!("invalid proof id {proof_id:?}"));
+        }
+
+        let step = |step: Option<i32>| match step {
+            Some(step) => Step::from_i32(step).map(Some).ok_or(format!("invalid step {step}")),
+            None => Ok(None),
+        };
+        let kind = match (step(request.from_step)?, step(request.target_step)?) {
+            (None | Some(Step::Init), Some(Step::InProve)) => ZKMProofKind::Core,
+            (None | Some(Step::Init), Some(Step::InAgg)) => ZKMProofKind::Compressed,
+            (None | Some(Step::Init), None | Some(Step::InSnark)) => ZKMProofKind::Groth16,
+            (Some(Step::InAgg), None | Some(Step::InSnark)) => ZKMProofKind::CompressToGroth16,
+            (from, target) => {
+                return Err(format!("unsupported steps from {from:?} to {target:?}"));
+            }
+        };
+
+        let mut stdin = ZKMStdin::new();
+        if !request.private_input_stream.is_empty() {
+            stdin.buffer = bincode::deserialize(&request.private_input_stream)
+                .map_err(|e| format!("invalid private input stream: {e}"))?;
+        }
+        for receipt in request.receipt_inputs.iter() {
+            let proof =
+                bincode::deserialize(receipt).map_err(|e| format!("invalid receipt input: {e}"))?;
+            stdin.proofs.push(proof);
+        }
+        if kind == ZKMProofKind::CompressToGroth16 {
+            if stdin.buffer.len() != 1 || stdin.proofs.len() != 1 {
+                return Err("expected the public values and the compressed proof".to_string());
+            }
+        } else if let Err(e) = Program::from(&request.elf_data) {
+            return Err(format!("invalid elf: {e}"));
+        }
+
+        let mut opts = ZKMProverOpts::default();
+        if request.seg_size != 0 {
+            if !request.seg_size.is_power_of_two() {
+                return Err(format!("segment size {} is not a power of two", request.seg_size));
+            }
+            opts.core_opts.shard_size = request.seg_size as usize;
+        }
+
+        Ok(Self { proof_id, elf: request.elf_data, stdin, kind, opts })
+    }
+}
+
+/// The state of a job.
+#[derive(Debug, Clone)]
+pub struct JobStatus {
+    pub status: Status,
+    pub step: Step,
+    pub error: Option<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum SubmitError {
+    #[error("a proof with id {0} already exists")]
+    DuplicateId(String),
+    #[error("the job queue is full")]
+    QueueFull,
+}
+
+/// A queue of proof jobs, which are proven one at a time on a dedicated thread.
+///
+/// Each job uses all the cores of the machine, so running several at once would only slow them
+/// all down.
+#[derive(Clone)]
+pub struct JobQueue {
+    jobs: Arc<Mutex<HashMap<String, JobStatus>>>,
+    tx: SyncSender<ProofJob>,
+    storage: ProofStorage,
+}
+
+impl JobQueue {
+    /// Start proving the jobs submitted to the queue, which holds at most `capacity` pending jobs.
+    pub fn start(
+        prover: ZKMProver<DefaultProverComponents>,
+        storage: ProofStorage,
+        capacity: usize,
+    ) -> Self {
+        let jobs = Arc::new(Mutex::new(HashMap::new()));
+        let (tx, rx) = sync_channel::<ProofJob>(capacity);
+        {
+            let jobs = Arc::clone(&jobs);
+            let storage = storage.clone();
+            thread::spawn(move || {
+                for job in rx {
+                    let proof_id = job.proof_id.clone();
+                    tracing::info!("proving {} ({:?})", proof_id, job.kind);
+                    let set_step = |step: Step| {
+                        tracing::info!("{}: {}", proof_id, step);
+                        let mut jobs = jobs.lock().unwrap();
+                        jobs.get_mut(&proof_id).unwrap().step = step;
+                    };
+                    let result =
+                        catch_unwind(AssertUnwindSafe(|| prove(&prover, &storage, job, set_step)))
+                            .unwrap_or_else(|_| Err("the prover panicked".to_string()));
+
+                    let mut jobs = jobs.lock().unwrap();
+                    let state = jobs.get_mut(&proof_id).unwrap();
+                    match result {
+                        Ok(()) => {
+                            state.status = Status::Success;
+                            state.step = Step::End;
+                        }
+                        Err(e) => {
+                            tracing::error!("{} failed at {:?}: {}", proof_id, state.step, e);
+                            state.status = match state.step {
+                                Step::Init | Step::InSplit => Status::SplitError,
+                                Step::InProve => Status::ProveError,
+                                Step::InAgg => Status::AggError,
+                                Step::InSnark => Status::SnarkError,
+                                Step::End => Status::InternalError,
+                            };
+                            state.error = Some(e);
+                        }
+                    }
+                }
+            });
+        }
+        Self { jobs, tx, storage }
+    }
+
+    /// Queue a job.
+    pub fn submit(&self, job: ProofJob) -> Result<(), SubmitError> {
+        let mut jobs = self.jobs.lock().unwrap();
+        if jobs.contains_key(&job.proof_id) || self.storage.contains(&job.proof_id) {
+            return Err(SubmitError::DuplicateId(job.proof_id));
+        }
+        let proof_id = job.proof_id.clone();
+        match self.tx.try_send(job) {
+            Ok(()) => {
+                let status = JobStatus { status: Status::Computing, step: Step::Init, error: None };
+                jobs.insert(proof_id, status);
+                Ok(())
+            }
+            Err(TrySendError::Full(_)) => Err(SubmitError::QueueFull),
+            Err(TrySendError::Disconnected(_)) => panic!("the prover thread stopped"),
+        }
+    }
+
+    /// The state of a job submitted since the server started.
+    pub fn status(&self, proof_id: &str) -> Option<JobStatus> {
+        self.jobs.lock().unwrap().get(proof_id).cloned()
+    }
+}
+
+/// Prove a job up to its target step and store the proof.
+fn prove(
+    prover: &ZKMProver<DefaultProverComponents>,
+    storage: &ProofStorage,
+    job: ProofJob,
+    set_step: impl Fn(Step),
+) -> Result<(), String> {
+    let ProofJob { proof_id, elf, mut stdin, kind, opts } = job;
+
+    let (proof, public_values, cycles) = if kind == ZKMProofKind::CompressToGroth16 {
+        set_step(Step::InSnark);
+        let public_values: ZKMPublicValues =
+            bincode::deserialize(&stdin.buffer[0]).map_err(|e| e.to_string())?;
+        let (compressed_proof, _) = stdin.proofs.pop().unwrap();
+        (wrap_groth16(prover, compressed_proof, opts)?, public_values, 0)
+    } else {
+        // Execute the program first, so that invalid inputs are reported before proving.
+        set_step(Step::InSplit);
+        let (pk, vk) = prover.setup(&elf);
+        prover.execute(&elf, &stdin, ZKMContext::default()).map_err(|e| e.to_string())?;
+
+        set_step(Step::InProve);
+        let core_proof = prover
+            .prove_core(&pk, &stdin, opts, ZKMContext::default())
+            .map_err(|e| e.to_string())?;
+        let public_values = core_proof.public_values.clone();
+        let cycles = core_proof.cycles;
+
+        if kind == ZKMProofKind::Core {
+            (ZKMProof::Core(core_proof.proof.0), public_values, cycles)
+        } else {
+            set_step(Step::InAgg);
+            let deferred_proofs = stdin.proofs.iter().map(|(proof, _)| proof.clone()).collect();
+            let compressed_proof = prover
+                .compress(&vk, core_proof, deferred_proofs, opts)
+                .map_err(|e| e.to_string())?;
+
+            if kind == ZKMProofKind::Compressed {
+                (ZKMProof::Compressed(Box::new(compressed_proof)), public_values, cycles)
+            } else {
+                set_step(Step::InSnark);
+                (wrap_groth16(prover, compressed_proof, opts)?, public_values, cycles)
+            }
+        }
+    };
+
+    let metadata = ProofMetadata { cycles, snark: matches!(proof, ZKMProof::Groth16(_)) };
+    storage
+        .store(&proof_id, &proof, public_values.as_slice(), &metadata)
+        .map_err(|e| format!("failed to store the proof: {e}"))
+}
+
+/// Shrink and wrap a compressed proof into a Groth16 proof.
+fn wrap_groth16(
+    prover: &ZKMProver<DefaultProverComponents>,
+    compressed_proof: ZKMReduceProof<InnerSC>,
+    opts: ZKMProverOpts,
+) -> Result<ZKMProof, String> {
+    let shrink_proof = prover.shrink(compressed_proof, opts).map_err(|e| e.to_string())?;
+    let outer_proof = prover.wrap_bn254(shrink_proof, opts).map_err(|e| e.to_string())?;
+    let groth16_bn254_artifacts = if zkm_prover::build::zkm_dev_mode() {
+        zkm_prover::build::try_build_groth16_bn254_artifacts_dev(
+            &outer_proof.vk,
+            &outer_proof.proof,
+        )
+    } else {
+        try_install_circuit_artifacts("groth16")
+    };
+    Ok(ZKMProof::Groth16(prover.wrap_groth16_bn254(outer_proof, &groth16_bn254_artifacts)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(from_step: Option<Step>, target_step: Option<Step>) -> GenerateProofRequest {
+        GenerateProofRequest {
+            proof_id: "proof".to_string(),
+            elf_data: test_artifacts::FIBONACCI_ELF.to_vec(),
+            private_input_stream: bincode::serialize(&vec![vec![1u8, 2, 3]]).unwrap(),
+            from_step: from_step.map(Into::into),
+            target_step: target_step.map(Into::into),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_proof_kinds() {
+        let kind = |from_step, target_step| {
+            ProofJob::from_request(request(from_step, target_step)).map(|job| job.kind)
+        };
+        assert_eq!(kind(None, Some(Step::InProve)), Ok(ZKMProofKind::Core));
+        assert_eq!(kind(None, Some(Step::InAgg)), Ok(ZKMProofKind::Compressed));
+        assert_eq!(kind(None, Some(Step::InSnark)), Ok(ZKMProofKind::Groth16));
+        assert_eq!(kind(None, None), Ok(ZKMProofKind::Groth16));
+        assert!(kind(Some(Step::InProve), Some(Step::InAgg)).is_err());
+        // A compressed proof to wrap is required.
+        assert!(kind(Some(Step::InAgg), Some(Step::InSnark)).is_err());
+    }
+
+    #[test]
+    fn test_invalid_requests() {
+        let job = ProofJob::from_request(request(None, Some(Step::InAgg))).unwrap();
+        assert_eq!(job.stdin.buffer, vec![vec![1, 2, 3]]);
+
+        let mut invalid = request(None, Some(Step::InAgg));
+        invalid.seg_size = 3;
+        assert!(ProofJob::from_request(invalid).is_err());
+
+        let mut invalid = request(None, Some(Step::InAgg));
+        invalid.proof_id = "../proof".to_string();
+        assert!(ProofJob::from_request(invalid).is_err());
+
+        let mut invalid = request(None, Some(Step::InAgg));
+        invalid.elf_data = vec![0; 16];
+        assert!(ProofJob::from_request(invalid).is_err());
+    }
+}
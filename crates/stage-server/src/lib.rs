@@ -0,0 +1,61 @@
+//! A self-hostable implementation of the `stage.v1.StageService` spoken by the SDK's
+//! `NetworkProver`.
+//!
+//! Proof requests are queued and proven one at a time with a local [`ZKMProver`], going through
+//! the steps of the service: executing the program (`InSplit`), proving its shards (`InProve`),
+//! compressing them (`InAgg`) and wrapping the result into a Groth16 proof (`InSnark`), depending
+//! on the requested target step. The proofs are stored in a local directory.
+//!
+//! [`ZKMProver`]: zkm_prover::ZKMProver
+
+pub mod job;
+pub mod service;
+pub mod storage;
+
+pub use job::*;
+pub use service::*;
+pub use storage::*;
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use ethers::signers::LocalWallet;
+    use tonic::transport::{Endpoint, Server};
+    use zkm_core_machine::utils::setup_logger;
+    use zkm_prover::{components::DefaultProverComponents, ZKMProver};
+    use zkm_sdk::{CpuProver, NetworkProver, Prover, ZKMProofKind, ZKMStdin};
+
+    use super::*;
+
+    /// Proves Fibonacci through the SDK's `NetworkProver` against a local server.
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore]
+    async fn test_network_prover() {
+        setup_logger();
+        let elf = test_artifacts::FIBONACCI_ELF;
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = ProofStorage::new(dir.path(), None).unwrap();
+        let queue =
+            JobQueue::start(ZKMProver::<DefaultProverComponents>::new(), storage.clone(), 1);
+        let server = StageServer::new(queue, storage, None);
+
+        let addr = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        tokio::spawn(Server::builder().add_service(server.into_service()).serve(addr));
+
+        let network_prover = NetworkProver {
+            endpoint: Endpoint::from_shared(format!("http://{addr}")).unwrap(),
+            wallet: LocalWallet::new(&mut ethers::core::rand::thread_rng()),
+            local_prover: CpuProver::new(),
+            poll_interval: 1,
+        };
+        let (_, vk) = network_prover.setup(elf);
+        let (proof, cycles) = network_prover
+            .prove_with_cycles(elf, ZKMStdin::new(), ZKMProofKind::Compressed, None)
+            .await
+            .unwrap();
+        assert!(cycles > 0);
+        network_prover.verify(&proof, &vk).unwrap();
+    }
+}
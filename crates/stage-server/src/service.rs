@@ -0,0 +1,139 @@
+use ethers::types::{Address, Signature};
+use tonic::{Request, Response};
+use zkm_sdk::network::prover::stage_service::{
+    stage_service_server::{StageService, StageServiceServer},
+    GenerateProofRequest, GenerateProofResponse, GetStatusRequest, GetStatusResponse, Status, Step,
+};
+
+use crate::{
+    job::{JobQueue, ProofJob, SubmitError},
+    storage::ProofStorage,
+};
+
+/// The `stage.v1.StageService` backed by a local [`JobQueue`].
+pub struct StageServer {
+    queue: JobQueue,
+    storage: ProofStorage,
+    allowed_signers: Option<Vec<Address>>,
+}
+
+impl StageServer {
+    /// Create a server for the jobs of `queue`, whose proofs are stored in `storage`.
+    ///
+    /// If `allowed_signers` is set, only requests signed by one of these addresses are accepted.
+    pub fn new(
+        queue: JobQueue,
+        storage: ProofStorage,
+        allowed_signers: Option<Vec<Address>>,
+    ) -> Self {
+        Self { queue, storage, allowed_signers }
+    }
+
+    pub fn into_service(self) -> StageServiceServer<Self> {
+        StageServiceServer::new(self)
+    }
+
+    /// Check the signature of a request, which covers the same fields as the one made by the
+    /// SDK's `NetworkProver::sign_ecdsa`.
+    fn verify_signature(&self, request: &GenerateProofRequest) -> Result<(), String> {
+        let Some(allowed_signers) = &self.allowed_signers else { return Ok(()) };
+        let message = match request.block_no {
+            Some(block_no) => format!("{}&{}&{}", request.proof_id, block_no, request.seg_size),
+            None => format!("{}&{}", request.proof_id, request.seg_size),
+        };
+        let signature: Signature =
+            request.signature.parse().map_err(|e| format!("invalid signature: {e}"))?;
+        let signer = signature.recover(message).map_err(|e| format!("invalid signature: {e}"))?;
+        if !allowed_signers.contains(&signer) {
+            return Err(format!("signer {signer:?} is not allowed"));
+        }
+        Ok(())
+    }
+}
+
+#[tonic::async_trait]
+impl StageService for StageServer {
+    async fn generate_proof(
+        &self,
+        request: Request<GenerateProofRequest>,
+    ) -> Result<Response<GenerateProofResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let proof_id = request.proof_id.clone();
+        let rejected = |status: Status, error_message: String| {
+            tracing::warn!("rejected proof request {:?}: {}", proof_id, error_message);
+            Ok(Response::new(GenerateProofResponse {
+                status: status.into(),
+                error_message,
+                proof_id: proof_id.clone(),
+                ..Default::default()
+            }))
+        };
+
+        if let Err(e) = self.verify_signature(&request) {
+            return rejected(Status::InvalidParameter, e);
+        }
+        let job = match ProofJob::from_request(request) {
+            Ok(job) => job,
+            Err(e) => return rejected(Status::InvalidParameter, e),
+        };
+        let proof_id = job.proof_id.clone();
+        match self.queue.submit(job) {
+            Ok(()) => {}
+            Err(e @ SubmitError::DuplicateId(_)) => {
+                return rejected(Status::InvalidParameter, e.to_string())
+            }
+            Err(e @ SubmitError::QueueFull) => {
+                return rejected(Status::InternalError, e.to_string())
+            }
+        }
+        tracing::info!("queued proof request {}", proof_id);
+
+        Ok(Response::new(GenerateProofResponse {
+            status: Status::Computing.into(),
+            proof_id,
+            ..Default::default()
+        }))
+    }
+
+    async fn get_status(
+        &self,
+        request: Request<GetStatusRequest>,
+    ) -> Result<Response<GetStatusResponse>, tonic::Status> {
+        let proof_id = request.into_inner().proof_id;
+        let mut response = GetStatusResponse { proof_id: proof_id.clone(), ..Default::default() };
+
+        // Jobs of a previous run of the server are only known through the storage.
+        let job = self.queue.status(&proof_id);
+        let completed = match &job {
+            Some(job) => job.status == Status::Success,
+            None => ProofStorage::is_valid_id(&proof_id) && self.storage.contains(&proof_id),
+        };
+        if !completed {
+            let (status, step) = match job {
+                Some(job) => (job.status, job.step),
+                None => (Status::InvalidParameter, Step::Init),
+            };
+            response.status = status.into();
+            response.step = step.into();
+            return Ok(Response::new(response));
+        }
+
+        let stored = self
+            .storage
+            .load(&proof_id)
+            .map_err(|e| tonic::Status::internal(format!("failed to load the proof: {e}")))?
+            .ok_or_else(|| tonic::Status::internal("the proof is missing from the storage"))?;
+        response.status = Status::Success.into();
+        response.step = Step::End.into();
+        response.proof_with_public_inputs = stored.proof;
+        response.output_stream = stored.public_values;
+        response.public_values_url = self.storage.public_values_url(&proof_id);
+        if stored.metadata.snark {
+            response.snark_proof_url = self.storage.proof_url(&proof_id);
+        } else {
+            response.stark_proof_url = self.storage.proof_url(&proof_id);
+        }
+        response.total_steps = stored.metadata.cycles;
+        Ok(Response::new(response))
+    }
+}
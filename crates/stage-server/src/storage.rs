@@ -0,0 +1,151 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use zkm_sdk::ZKMProof;
+
+const PROOF_FILE: &str = "proof.json";
+const PUBLIC_VALUES_FILE: &str = "public_values.bin";
+const METADATA_FILE: &str = "metadata.json";
+
+/// What is known about a stored proof besides the proof itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofMetadata {
+    /// The number of cycles of the proven execution.
+    pub cycles: u64,
+    /// Whether the proof is a SNARK.
+    pub snark: bool,
+}
+
+/// A proof read back from the [`ProofStorage`].
+pub struct StoredProof {
+    /// The JSON encoding of the [`ZKMProof`].
+    pub proof: Vec<u8>,
+    pub public_values: Vec<u8>,
+    pub metadata: ProofMetadata,
+}
+
+/// Stores the proofs of the completed jobs on the local file system, in one directory per job:
+///
+/// ```text
+/// <root>/<proof_id>/proof.json
+/// <root>/<proof_id>/public_values.bin
+/// <root>/<proof_id>/metadata.json
+/// ```
+///
+/// The files are referenced in the responses by URL, so that clients can download them. By
+/// default these are `file://` URLs, which are only useful to clients on the same machine; with a
+/// base URL, the directory can be served by any static file server instead.
+#[derive(Debug, Clone)]
+pub struct ProofStorage {
+    root: PathBuf,
+    base_url: String,
+}
+
+impl ProofStorage {
+    /// Create a storage in the directory `root`, creating it if needed.
+    pub fn new(root: impl AsRef<Path>, base_url: Option<String>) -> io::Result<Self> {
+        fs::create_dir_all(&root)?;
+        let root = fs::canonicalize(root)?;
+        let base_url = base_url
+            .unwrap_or_else(|| format!("file://{}", root.display()))
+            .trim_end_matches('/')
+            .to_string();
+        Ok(Self { root, base_url })
+    }
+
+    /// Whether a job could use `proof_id`, which must be a single path component.
+    pub fn is_valid_id(proof_id: &str) -> bool {
+        !proof_id.is_empty()
+            && proof_id.len() <= 128
+            && proof_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    fn dir(&self, proof_id: &str) -> PathBuf {
+        self.root.join(proof_id)
+    }
+
+    /// Store the proof of a completed job.
+    pub fn store(
+        &self,
+        proof_id: &str,
+        proof: &ZKMProof,
+        public_values: &[u8],
+        metadata: &ProofMetadata,
+    ) -> io::Result<()> {
+        let dir = self.dir(proof_id);
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(PUBLIC_VALUES_FILE), public_values)?;
+        fs::write(dir.join(PROOF_FILE), serde_json::to_vec(proof)?)?;
+        // The metadata is written last, since its presence marks the proof as complete.
+        fs::write(dir.join(METADATA_FILE), serde_json::to_vec(metadata)?)?;
+        Ok(())
+    }
+
+    /// Load the proof of a completed job, if it was stored.
+    pub fn load(&self, proof_id: &str) -> io::Result<Option<StoredProof>> {
+        let dir = self.dir(proof_id);
+        if !dir.join(METADATA_FILE).is_file() {
+            return Ok(None);
+        }
+        let metadata = serde_json::from_slice(&fs::read(dir.join(METADATA_FILE))?)?;
+        Ok(Some(StoredProof {
+            proof: fs::read(dir.join(PROOF_FILE))?,
+            public_values: fs::read(dir.join(PUBLIC_VALUES_FILE))?,
+            metadata,
+        }))
+    }
+
+    /// Whether a proof was stored for `proof_id`.
+    pub fn contains(&self, proof_id: &str) -> bool {
+        self.dir(proof_id).join(METADATA_FILE).is_file()
+    }
+
+    /// The URL of the proof of a job.
+    pub fn proof_url(&self, proof_id: &str) -> String {
+        format!("{}/{}/{}", self.base_url, proof_id, PROOF_FILE)
+    }
+
+    /// The URL of the public values of a job.
+    pub fn public_values_url(&self, proof_id: &str) -> String {
+        format!("{}/{}/{}", self.base_url, proof_id, PUBLIC_VALUES_FILE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = ProofStorage::new(dir.path(), None).unwrap();
+        assert!(!storage.contains("proof-1"));
+        assert!(storage.load("proof-1").unwrap().is_none());
+
+        let proof = ZKMProof::Core(vec![]);
+        let metadata = ProofMetadata { cycles: 42, snark: false };
+        storage.store("proof-1", &proof, &[1, 2, 3], &metadata).unwrap();
+
+        let stored = storage.load("proof-1").unwrap().unwrap();
+        assert!(matches!(serde_json::from_slice(&stored.proof).unwrap(), ZKMProof::Core(_)));
+        assert_eq!(stored.public_values, vec![1, 2, 3]);
+        assert_eq!(stored.metadata.cycles, 42);
+
+        let root = fs::canonicalize(dir.path()).unwrap();
+        assert_eq!(
+            storage.public_values_url("proof-1"),
+            format!("file://{}/proof-1/public_values.bin", root.display())
+        );
+    }
+
+    #[test]
+    fn test_proof_ids() {
+        assert!(ProofStorage::is_valid_id("0b3c7e1a-5d2f-4c8e-9a61-1f2d3e4c5b6a"));
+        assert!(!ProofStorage::is_valid_id(""));
+        assert!(!ProofStorage::is_valid_id("../proof"));
+        assert!(!ProofStorage::is_valid_id("a/b"));
+    }
+}
@@ -68,6 +68,7 @@ impl ZKMProverOpts {
         opts.core_opts.split_opts.keccak /= divisor;
         opts.core_opts.split_opts.sha_extend /= divisor;
         opts.core_opts.split_opts.sha_compress /= divisor;
+        opts.core_opts.split_opts.blake3_compress_inner /= divisor;
         opts.core_opts.split_opts.memory /= divisor;
 
         opts.recursion_opts.shard_batch_size = 2;
@@ -140,6 +141,7 @@ impl Default for ZKMCoreOpts {
         opts.split_opts.keccak /= divisor;
         opts.split_opts.sha_extend /= divisor;
         opts.split_opts.sha_compress /= divisor;
+        opts.split_opts.blake3_compress_inner /= divisor;
         opts.split_opts.memory /= divisor;
 
         opts
@@ -204,6 +206,8 @@ pub struct SplitOpts {
     pub sha_extend: usize,
     /// The threshold for sha compress events.
     pub sha_compress: usize,
+    /// The threshold for blake3 compress inner events.
+    pub blake3_compress_inner: usize,
     /// The threshold for memory events.
     pub memory: usize,
 }
@@ -217,6 +221,7 @@ impl SplitOpts {
             keccak: 8 * deferred_split_threshold / 24,
             sha_extend: 32 * deferred_split_threshold / 48,
             sha_compress: 32 * deferred_split_threshold / 80,
+            blake3_compress_inner: 32 * deferred_split_threshold / 56,
             memory: 64 * deferred_split_threshold,
         }
     }
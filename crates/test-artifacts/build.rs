@@ -16,5 +16,14 @@ fn main() -> Result<()> {
         Default::default(),
     );
 
+    // The program using the embedded allocator is its own workspace, see its manifest.
+    let embedded_allocator_path = tests_path.join("embedded-allocator");
+    build_program_with_args(
+        embedded_allocator_path.to_str().ok_or_else(|| {
+            Error::other(format!("expected {embedded_allocator_path:?} to be valid UTF-8"))
+        })?,
+        Default::default(),
+    );
+
     Ok(())
 }
@@ -0,0 +1,107 @@
+# A minimal program for the Cannon-compatible Linux syscall mode.
+#
+# It sends a hint, requests the preimage of the local key 0x01..01, reads it into a page returned by
+# `mmap`, checks its length prefix, echoes it to stdout and exits with `exit_group`. It exits with
+# code 1 if the length prefix doesn't match the number of bytes read.
+#
+# Rebuild `preimage.elf` with:
+#
+#   llvm-mc -arch=mipsel -mcpu=mips32r2 -filetype=obj preimage.s -o preimage.o
+#   rust-lld -flavor gnu -m elf32ltsmip --entry=_start -Ttext=0x20000 -z max-page-size=4096 preimage.o -o preimage.elf
+
+    .set noreorder
+    .set noat
+
+    .text
+    .globl _start
+    .ent _start
+_start:
+    # mmap(NULL, 4096): the buffer of the preimage.
+    li      $v0, 4090
+    move    $a0, $zero
+    li      $a1, 4096
+    syscall
+    move    $s0, $v0
+
+    # Send the hint, which is prefixed with its big-endian length.
+    li      $v0, 4004
+    li      $a0, 4
+    la      $a1, hint
+    li      $a2, 9
+    syscall
+
+    # Write the key, at most up to the end of a word at a time.
+    la      $s1, key
+    li      $s2, 32
+write_key:
+    li      $v0, 4004
+    li      $a0, 6
+    move    $a1, $s1
+    move    $a2, $s2
+    syscall
+    addu    $s1, $s1, $v0
+    subu    $s2, $s2, $v0
+    bnez    $s2, write_key
+    nop
+
+    # Read the length-prefixed preimage until the end of the stream.
+    move    $s1, $zero
+read_preimage:
+    li      $v0, 4003
+    li      $a0, 5
+    addu    $a1, $s0, $s1
+    li      $a2, 4096
+    subu    $a2, $a2, $s1
+    syscall
+    bnez    $a3, fail
+    nop
+    bnez    $v0, read_preimage
+    addu    $s1, $s1, $v0
+
+    # Check the big-endian length prefix against the number of bytes read.
+    move    $t0, $zero
+    move    $t1, $zero
+decode_len:
+    addu    $t2, $s0, $t1
+    lbu     $t2, 0($t2)
+    sll     $t0, $t0, 8
+    or      $t0, $t0, $t2
+    addiu   $t1, $t1, 1
+    li      $t3, 8
+    bne     $t1, $t3, decode_len
+    nop
+    addiu   $s2, $s1, -8
+    bne     $t0, $s2, fail
+    nop
+
+    # Echo the preimage to stdout.
+    li      $v0, 4004
+    li      $a0, 1
+    addiu   $a1, $s0, 8
+    move    $a2, $s2
+    syscall
+
+    # exit_group(0)
+    li      $v0, 4246
+    move    $a0, $zero
+    syscall
+    nop
+
+fail:
+    # exit_group(1)
+    li      $v0, 4246
+    li      $a0, 1
+    syscall
+    nop
+    .end _start
+
+    .data
+    .align 2
+hint:
+    .byte   0, 0, 0, 5
+    .ascii  "hello"
+    .align 2
+key:
+    .byte   1
+    .space  30
+    .byte   1
@@ -0,0 +1,39 @@
+#![no_std]
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use zkm_zkvm::lib::blake3::hash;
+
+/// Digests of the official BLAKE3 test vectors, whose inputs are `i % 251` for `i` in
+/// `0..len`. They cover empty input, partial and full blocks, and multi-chunk trees.
+const TEST_VECTORS: [(usize, &str); 8] = [
+    (0, "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"),
+    (3, "e1be4d7a8ab5560aa4199eea339849ba8e293d55ca0a81006726d184519e647f"),
+    (64, "4eed7141ea4a5cd4b788606bd23f46e212af9cacebacdc7d1f4c6dc7f2511b98"),
+    (65, "de1e5fa0be70df6d2be8fffd0e99ceaa8eb6e8c93a63f2d8d1c30ecb6b263dee"),
+    (1024, "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7"),
+    (1025, "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444"),
+    (2048, "e776b6028c7cd22a4d0ba182a8bf62205d2ef576467e838ed6f2529b85fba24a"),
+    (5000, "ee78d92070de3df1c57c37002abf0a6b1a6589acdeef4d8ffac7cf3d9e8f2836"),
+];
+
+fn decode_hex(hex: &str) -> [u8; 32] {
+    let nibble = |c: u8| (c as char).to_digit(16).unwrap() as u8;
+    let hex = hex.as_bytes();
+    core::array::from_fn(|i| (nibble(hex[2 * i]) << 4) | nibble(hex[2 * i + 1]))
+}
+
+pub fn main() {
+    assert_eq!(
+        hash(b"hello world"),
+        decode_hex("d74981efa70a0c880b8d8c1985d075dbcbf679b99a5f9914e5aaf96b831a9e24")
+    );
+
+    let mut input = [0u8; 5000];
+    for (i, byte) in input.iter_mut().enumerate() {
+        *byte = (i % 251) as u8;
+    }
+    for (len, expected) in TEST_VECTORS {
+        assert_eq!(hash(&input[..len]), decode_hex(expected));
+    }
+}
@@ -0,0 +1,82 @@
+#![no_std]
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use core::str::FromStr;
+use num_bigint::BigUint;
+use zkm_zkvm::syscalls::syscall_bls12381_fp6_mulmod;
+
+const MODULUS: &str =
+    "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559787";
+
+/// The constant coefficient of the non-residue `xi = xi0 + u`.
+const XI0: u32 = 1;
+
+const NUM_LIMBS: usize = 12;
+
+type Fp2 = [BigUint; 2];
+
+fn random_fp(seed: &mut u32, modulus: &BigUint) -> BigUint {
+    let mut limbs = [0u32; NUM_LIMBS];
+    for limb in limbs.iter_mut() {
+        // xorshift32
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 17;
+        *seed ^= *seed << 5;
+        *limb = *seed;
+    }
+    BigUint::from_slice(&limbs) % modulus
+}
+
+fn fp2_add(a: &Fp2, b: &Fp2, modulus: &BigUint) -> Fp2 {
+    [(&a[0] + &b[0]) % modulus, (&a[1] + &b[1]) % modulus]
+}
+
+fn fp2_mul(a: &Fp2, b: &Fp2, modulus: &BigUint) -> Fp2 {
+    [
+        (modulus + (&a[0] * &b[0]) % modulus - (&a[1] * &b[1]) % modulus) % modulus,
+        (&a[0] * &b[1] + &a[1] * &b[0]) % modulus,
+    ]
+}
+
+fn fp6_mul(a: &[Fp2; 3], b: &[Fp2; 3], modulus: &BigUint) -> [Fp2; 3] {
+    let xi = [BigUint::from(XI0), BigUint::from(1u32)];
+    let p = |i: usize, j: usize| fp2_mul(&a[i], &b[j], modulus);
+    let c0 =
+        fp2_add(&p(0, 0), &fp2_mul(&xi, &fp2_add(&p(1, 2), &p(2, 1), modulus), modulus), modulus);
+    let c1 =
+        fp2_add(&fp2_add(&p(0, 1), &p(1, 0), modulus), &fp2_mul(&xi, &p(2, 2), modulus), modulus);
+    let c2 = fp2_add(&fp2_add(&p(0, 2), &p(1, 1), modulus), &p(2, 0), modulus);
+    [c0, c1, c2]
+}
+
+fn to_limbs(a: &[Fp2; 3]) -> [u32; 6 * NUM_LIMBS] {
+    let mut limbs = [0u32; 6 * NUM_LIMBS];
+    for (chunk, coeff) in limbs.chunks_mut(NUM_LIMBS).zip(a.iter().flatten()) {
+        let digits = coeff.to_u32_digits();
+        chunk[..digits.len()].copy_from_slice(&digits);
+    }
+    limbs
+}
+
+pub fn main() {
+    let modulus = BigUint::from_str(MODULUS).unwrap();
+    let mut seed = 0x2545f491u32;
+
+    for _ in 0..10 {
+        let a: [Fp2; 3] = core::array::from_fn(|_| {
+            [random_fp(&mut seed, &modulus), random_fp(&mut seed, &modulus)]
+        });
+        let b: [Fp2; 3] = core::array::from_fn(|_| {
+            [random_fp(&mut seed, &modulus), random_fp(&mut seed, &modulus)]
+        });
+
+        let mut x = to_limbs(&a);
+        let y = to_limbs(&b);
+        unsafe {
+            syscall_bls12381_fp6_mulmod(x.as_mut_ptr(), y.as_ptr());
+        }
+
+        assert_eq!(x, to_limbs(&fp6_mul(&a, &b, &modulus)));
+    }
+}
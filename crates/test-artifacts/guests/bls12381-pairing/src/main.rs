@@ -0,0 +1,74 @@
+#![no_std]
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use zkm_lib::bls12381::{pairing_check, Bls12381Point, FP_N};
+use zkm_lib::utils::AffinePoint;
+
+/// The generator of G1, as the limbs of `x || y`.
+const G1: [u32; 2 * FP_N] = [
+    0xdb22c6bb, 0xfb3af00a, 0xf97a1aef, 0x6c55e83f, 0x171bac58, 0xa14e3a3f, 0x9774b905, 0xc3688c4f,
+    0x4fa9ac0f, 0x2695638c, 0x3197d794, 0x17f1d3a7, 0x46c5e7e1, 0x0caa2329, 0xa2888ae4, 0xd03cc744,
+    0x2c04b3ed, 0x00db18cb, 0xd5d00af6, 0xfcf5e095, 0x741d8ae4, 0xa09e30ed, 0xe3aaa0f1, 0x08b3f481,
+];
+
+/// The negation of the generator of G1.
+const NEG_G1: [u32; 2 * FP_N] = [
+    0xdb22c6bb, 0xfb3af00a, 0xf97a1aef, 0x6c55e83f, 0x171bac58, 0xa14e3a3f, 0x9774b905, 0xc3688c4f,
+    0x4fa9ac0f, 0x2695638c, 0x3197d794, 0x17f1d3a7, 0xb939c2ca, 0xad54dcd6, 0x0ecb751b, 0x4e6f38ba,
+    0xcaac4236, 0x6655b9d5, 0x1db507c9, 0x67816aef, 0xcf2e21f2, 0xaa7d76c8, 0x55d545a8, 0x114d1d68,
+];
+
+/// The generator of G2 on the twist, as the limbs of `x.c0 || x.c1 || y.c0 || y.c1`.
+const G2: [u32; 4 * FP_N] = [
+    0xc121bdb8, 0xd48056c8, 0xa805bbef, 0x0bac0326, 0x7ae3d177, 0xb4510b64, 0xfa403b02, 0xc6e47ad4,
+    0x2dc51051, 0x26080527, 0xf08f0a91, 0x024aa2b2, 0x5d042b7e, 0xe5ac7d05, 0x13945d57, 0x334cf112,
+    0xdc7f5049, 0xb5da61bb, 0x9920b61a, 0x596bd0d0, 0x88274f65, 0x7dacd3a0, 0x52719f60, 0x13e02b60,
+    0x08b82801, 0xe1935486, 0x3baca289, 0x923ac9cc, 0x5160d12c, 0x6d429a69, 0x8cbdd3a7, 0xadfd9baa,
+    0xda2e351a, 0x8cc9cdc6, 0x727d6e11, 0x0ce5d527, 0xf05f79be, 0xaaa9075f, 0x5cec1da1, 0x3f370d27,
+    0x572e99ab, 0x267492ab, 0x85a763af, 0xcb3e287e, 0x2bc28b99, 0x32acd2b0, 0x2ea734cc, 0x0606c4a0,
+];
+
+/// A point on the curve which is not in G1, as the limbs of `x || y`.
+const NOT_G1: [u32; 2 * FP_N] = [
+    0x00000004, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x4abe706c, 0x5ea93e35, 0x00e1de5d, 0x6346b8ed,
+    0x92848344, 0xda9dd85e, 0xc9926b26, 0xc760f988, 0xf3763e9b, 0xb33cffc3, 0xd40d6212, 0x0a989bad,
+];
+
+/// The negation of `NOT_G1`.
+const NEG_NOT_G1: [u32; 2 * FP_N] = [
+    0x00000004, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0xb5413a3f, 0x5b55c1ca, 0xb07221a2, 0xbb654711,
+    0x642c72df, 0x8c92fa42, 0x29f2a798, 0x9d1651fc, 0x4fd56e3b, 0x97dea7f2, 0x65728487, 0x0f68763c,
+];
+
+pub fn main() {
+    // e(P, Q) * e(-P, Q) = 1.
+    assert!(pairing_check(&[(G1, G2), (NEG_G1, G2)]));
+
+    // e(2P, Q) * e(-P, Q)^2 = 1.
+    let mut g1_double = Bls12381Point::new(G1);
+    g1_double.double();
+    let g1_double = *g1_double.limbs_ref();
+    assert!(pairing_check(&[(g1_double, G2), (NEG_G1, G2), (NEG_G1, G2)]));
+
+    // The pairing is non-degenerate.
+    assert!(!pairing_check(&[(G1, G2)]));
+    assert!(!pairing_check(&[(g1_double, G2), (NEG_G1, G2)]));
+
+    // Invalid points fail the check, even though their Miller loops cancel out.
+    let mut off_curve = G1;
+    off_curve[FP_N..].copy_from_slice(&[0; FP_N]);
+    off_curve[FP_N] = 1;
+    let mut neg_off_curve = G1;
+    neg_off_curve[FP_N..].copy_from_slice(&[
+        0xffffaaaa, 0xb9feffff, 0xb153ffff, 0x1eabfffe, 0xf6b0f624, 0x6730d2a0, 0xf38512bf,
+        0x64774b84, 0x434bacd7, 0x4b1ba7b6, 0x397fe69a, 0x1a0111ea,
+    ]);
+    assert!(!pairing_check(&[(off_curve, G2), (neg_off_curve, G2)]));
+    assert!(!pairing_check(&[(NOT_G1, G2), (NEG_NOT_G1, G2)]));
+
+    // Pairs containing the point at infinity are skipped.
+    assert!(pairing_check(&[([0; 2 * FP_N], G2), (G1, [0; 4 * FP_N])]));
+}
@@ -0,0 +1,60 @@
+#![no_std]
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use zkm_lib::bn254::{pairing_check, Bn254Point, FP_N};
+use zkm_lib::utils::AffinePoint;
+
+/// The generator of G1, as the limbs of `x || y`.
+const G1: [u32; 2 * FP_N] = [1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0];
+
+/// The negation of the generator of G1.
+const NEG_G1: [u32; 2 * FP_N] = [
+    1, 0, 0, 0, 0, 0, 0, 0, 0xd87cfd45, 0x3c208c16, 0x6871ca8d, 0x97816a91, 0x8181585d, 0xb85045b6,
+    0xe131a029, 0x30644e72,
+];
+
+/// The generator of G2 on the twist, as the limbs of `x.c0 || x.c1 || y.c0 || y.c1`.
+const G2: [u32; 4 * FP_N] = [
+    0xd992f6ed, 0x46debd5c, 0xf75edadd, 0x674322d4, 0x5e5c4479, 0x426a0066, 0x121f1e76, 0x1800deef,
+    0xaef312c2, 0x97e485b7, 0x35a9e712, 0xf1aa4933, 0x31fb5d25, 0x7260bfb7, 0x920d483a, 0x198e9393,
+    0x66fa7daa, 0x4ce6cc01, 0x0c43d37b, 0xe3d1e769, 0x8dcb408f, 0x4aab7180, 0xdb8c6deb, 0x12c85ea5,
+    0xd122975b, 0x55acdadc, 0x70b38ef3, 0xbc4b3133, 0x690c3395, 0xec9e99ad, 0x585ff075, 0x090689d0,
+];
+
+/// A point on the twist which is not in G2.
+const NOT_G2: [u32; 4 * FP_N] = [
+    0x00000007, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000003, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x5cfaff25, 0xbde1603f, 0xf1f90026, 0xe9bc57ed, 0x07524ba0, 0xc493d6a5, 0xdff657b3, 0x2d195734,
+    0x9f74b2d1, 0x7a6518f4, 0x14321ead, 0x392b353f, 0x11c30a86, 0x5bd9166d, 0x6e29a284, 0x28c41d8c,
+];
+
+pub fn main() {
+    // e(P, Q) * e(-P, Q) = 1.
+    assert!(pairing_check(&[(G1, G2), (NEG_G1, G2)]));
+
+    // e(2P, Q) * e(-P, Q)^2 = 1.
+    let mut g1_double = Bn254Point::new(G1);
+    g1_double.double();
+    let g1_double = *g1_double.limbs_ref();
+    assert!(pairing_check(&[(g1_double, G2), (NEG_G1, G2), (NEG_G1, G2)]));
+
+    // The pairing is non-degenerate.
+    assert!(!pairing_check(&[(G1, G2)]));
+    assert!(!pairing_check(&[(g1_double, G2), (NEG_G1, G2)]));
+
+    // Invalid points fail the check, even though their Miller loops cancel out.
+    let off_curve = [1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0];
+    let mut neg_off_curve = NEG_G1;
+    neg_off_curve[FP_N] += 1;
+    assert!(!pairing_check(&[(off_curve, G2), (neg_off_curve, G2)]));
+    assert!(!pairing_check(&[(G1, NOT_G2), (NEG_G1, NOT_G2)]));
+    let mut non_canonical = G1;
+    non_canonical[..FP_N].copy_from_slice(&NEG_G1[FP_N..]);
+    non_canonical[0] += 3;
+    assert!(!pairing_check(&[(non_canonical, G2), (NEG_G1, G2)]));
+
+    // Pairs containing the point at infinity are skipped.
+    assert!(pairing_check(&[([0; 2 * FP_N], G2), (G1, [0; 4 * FP_N])]));
+}
@@ -0,0 +1,22 @@
+#![no_main]
+
+zkm_zkvm::entrypoint!(main);
+
+pub fn main() {
+    // The freed memory is reused by the next allocation.
+    let first = Box::new([1u32; 16]);
+    let addr = first.as_ptr() as usize;
+    drop(first);
+    let second = Box::new([2u32; 16]);
+    assert_eq!(second.as_ptr() as usize, addr);
+
+    // The buffers add up to more than the whole heap (1.625 GiB above the stack), so this only
+    // succeeds if they are freed.
+    let mut sum = 0u32;
+    for i in 0..2048u32 {
+        let mut buffer = Vec::<u8>::with_capacity(1 << 20);
+        buffer.push(i as u8);
+        sum += u32::from(buffer[0]);
+    }
+    zkm_zkvm::io::commit(&sum);
+}
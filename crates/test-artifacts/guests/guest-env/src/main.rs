@@ -0,0 +1,16 @@
+#![no_main]
+
+use rand::Rng;
+
+zkm_zkvm::entrypoint!(main);
+
+pub fn main() {
+    zkm_zkvm::env::commit_rng_seed();
+
+    let greeting = std::env::var("GREETING").unwrap_or_default();
+    zkm_zkvm::io::commit(&greeting);
+    zkm_zkvm::io::commit(&std::env::var("MISSING").is_err());
+
+    let random: [u8; 16] = rand::thread_rng().gen();
+    zkm_zkvm::io::commit(&random);
+}
@@ -0,0 +1,89 @@
+//! Runs the COP1 instructions emitted by a hard-float compiler, and checks them against the
+//! soft-float results of the same operations.
+//!
+//! The toolchain and its standard library are built soft-float, so the FPU instructions are
+//! enabled in the inline assembly only. The soft-float code never uses the FPU registers.
+
+#![no_std]
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use core::arch::asm;
+
+/// The results of the double precision operations on `x` and `y`.
+#[derive(Debug, Default, PartialEq)]
+struct Results {
+    sum: f64,
+    product: f64,
+    quotient: f64,
+    root: f64,
+    single: f32,
+    truncated: i32,
+    less: u32,
+}
+
+fn hard_float(x: f64, y: f64) -> Results {
+    let mut results = Results::default();
+    let less: u32;
+    let truncated: i32;
+    unsafe {
+        asm!(
+            ".set push",
+            ".set hardfloat",
+            "ldc1 $f0, 0({x})",
+            "ldc1 $f2, 0({y})",
+            "add.d $f4, $f0, $f2",
+            "sdc1 $f4, 0({sum})",
+            "mul.d $f4, $f0, $f2",
+            "sdc1 $f4, 0({product})",
+            "div.d $f4, $f0, $f2",
+            "sdc1 $f4, 0({quotient})",
+            "sqrt.d $f4, $f0",
+            "sdc1 $f4, 0({root})",
+            "cvt.s.d $f6, $f0",
+            "swc1 $f6, 0({single})",
+            "trunc.w.d $f6, $f0",
+            "mfc1 {truncated}, $f6",
+            "c.olt.d $fcc0, $f0, $f2",
+            "addiu {less}, $zero, 0",
+            "addiu {one}, $zero, 1",
+            "movt {less}, {one}, $fcc0",
+            ".set pop",
+            x = in(reg) &x,
+            y = in(reg) &y,
+            sum = in(reg) &mut results.sum,
+            product = in(reg) &mut results.product,
+            quotient = in(reg) &mut results.quotient,
+            root = in(reg) &mut results.root,
+            single = in(reg) &mut results.single,
+            truncated = out(reg) truncated,
+            less = out(reg) less,
+            one = out(reg) _,
+        );
+    }
+    results.truncated = truncated;
+    results.less = less;
+    results
+}
+
+/// The soft-float results of the operations, given the square root of `x`.
+fn soft_float(x: f64, y: f64, root: f64) -> Results {
+    Results {
+        sum: x + y,
+        product: x * y,
+        quotient: x / y,
+        root,
+        single: x as f32,
+        truncated: x as i32,
+        less: (x < y) as u32,
+    }
+}
+
+pub fn main() {
+    // The operands, and the square root of the first one.
+    let operands = [(6.25, 3.0, 2.5), (1e10, -7.5, 1e5), (0.25, 0.1, 0.5), (-0.0, 2.0, -0.0)];
+    for (x, y, root) in operands {
+        assert_eq!(hard_float(x, y), soft_float(x, y, root));
+    }
+    zkm_zkvm::io::commit(&operands.len());
+}
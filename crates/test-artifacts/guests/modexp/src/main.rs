@@ -0,0 +1,99 @@
+#![no_std]
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+extern crate alloc;
+
+use alloc::{vec, vec::Vec};
+use zkm_zkvm::lib::modexp::modexp;
+
+const RSA_MODULUS: &str = concat!(
+    "98f135d25f557203301850c5a38fd547923a736994e3bf911a61dbe22e44158bae97ba94d0eda82f8f6d0558",
+    "4ef8aa38922766581e27a1c08a6a63ec24ede6a46b4cb2424a23d5962217beaddbc496cb8e81973e0becd7b0",
+    "3898d190f9ebdacc0cb1e29c658cda1495e60af593bd04cf0fd630f1f29d0da9953f48f1a09f76b5a170b338",
+    "39263059f28c105d1fb17c2390c192cfd3ac94af0f21ddb66cad4a268d116ece1738f7d93d9c172411e20b8f",
+    "6b0d549b6f03675a1600a35a099950d836f675cc81e74ef5e8e25d940ed904759531985d5d9dc9f81818e811",
+    "892f902bd23f0824128b2f330c5c7fd0a6a3a4506513270e269e0d37f2a74de452e6b439",
+);
+
+const RSA_SIGNATURE: &str = concat!(
+    "7f261498289fcd59a54a7bb1fee08f571242425051c1ccd17f9acae01f5057ca02135e92b1d3f28ede0d7ac3",
+    "baea9e13deef86ab1031d0f646e1f40a097c976bf46c697d2caf82eeeacbe226e875555790f82ec1d3fcff2a",
+    "3af4d46b0a18e8830e07bc1e398f1012bd4acefaecbd389be4bcfc49b64a0872e6cc3ababced2057ee05cde0",
+    "0902c77ebff206867347214cdd2055930d6eaf14f4733f3e7d1bfbc7a2ea20b2f14c942e05319acb5c74273f",
+    "98e2774cbd87ad5c90a9587403e430ec66a78795e761d17731af10506bf2efc6f877186d76b07e881ed162ae",
+    "2eb1547f15052434b9b5df9e7769b10f4205b4907a70c31012f037b64ce4228c38fb29",
+);
+
+const RSA_MESSAGE: &str = concat!(
+    "46b9ab7c62969cf99632798a497c6b5f95d4c4465d304e77584368b4f5dfd02b4b6951025fd0cf0dd0fa0f71",
+    "5f2596ac78de2892f409518529bc23d3c7a60709cb27796d05bd67d6cac70032bd9dc9f3cd434a0845011d36",
+    "fe5f5f7846ef3710099a69decdfd4a0a2792cf2d881c82aa57507a6b7f8f37d843f62876597a5ba297e14d0c",
+    "89f906bd3ef9131db380c89a008844807405f1045844950ac16bf3f05288d2f775784d30cb4a143ade2fd048",
+    "dd09615beac08b604d46015addc3496e98473a0d83eaad9b2d4345958500ab42221d5b5687abd00682d41e21",
+    "d1000f126e0a736531bbb4274b332c7859b90f4a9c7bbef528de90acb051071b472d7a72",
+);
+
+const EVEN_MODULUS: &str = concat!(
+    "daffb2297631a992f0ce583505c6af0758d5563dab2cd31ee315128862c33a4fb774eb5248db40af72158370",
+    "d269a9a5ae658f33fe3b890b93f448b3a5aa3c814f426dcbb394fb36bb2d420f0f88080b10a3d6b2aa05e11a",
+    "b2715945795e8229451abd81f1d69ed617f5e837d70820fe119a72d174c9df6acc011cdd94740320",
+);
+
+const EVEN_BASE: &str = concat!(
+    "e25aec6f0245bd86d40fc891b4a6a50df4db4d66a3a47469a4d8cdb305fdd2e16096e36aab0d1bc52d9230d9",
+    "77ee22571594720771f8ca8181166d2287672fdf2022a96fb1a14a0f9e77f1b103cdf1582b0eab477d264154",
+    "79c65dc9f503f63af83bd0561e6211c70cf49952399c4aaeac137dc76fb0f17a3007e62aa0a1df9fd789c653",
+    "9382b0537e6",
+);
+
+const EVEN_EXPONENT: &str = concat!("262d1c9af0153e7c2a26a2c0bd3b1287fff52ddf5d616499c9",);
+
+const EVEN_RESULT: &str = concat!(
+    "20dbf1720ef33733e635217c911a4be0e44c6542c78a31291646760e727ca8a78b01443acda99cf46e369e29",
+    "9ede53a8a7084738a37340930d69c43d774198dc177dffbd19643e25c8ea91e819b037460c00661d822dab87",
+    "8683c100994a609101a9a4335e0fbdb6e570fd6ce57cb2dd26506d142d318adf6221a283fe56ac80",
+);
+
+/// The modulus of the secp256k1 base field.
+const SECP256K1_P: &str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+
+fn decode_hex(hex: &str) -> Vec<u8> {
+    let nibble = |c: u8| (c as char).to_digit(16).unwrap() as u8;
+    hex.as_bytes().chunks(2).map(|pair| (nibble(pair[0]) << 4) | nibble(pair[1])).collect()
+}
+
+pub fn main() {
+    // Verifies an RSA-2048 signature with the public exponent 65537.
+    assert_eq!(
+        modexp(&decode_hex(RSA_SIGNATURE), &[0x01, 0x00, 0x01], &decode_hex(RSA_MODULUS)),
+        decode_hex(RSA_MESSAGE)
+    );
+
+    // An even 1024-bit modulus, with a base wider than the modulus.
+    assert_eq!(
+        modexp(&decode_hex(EVEN_BASE), &decode_hex(EVEN_EXPONENT), &decode_hex(EVEN_MODULUS)),
+        decode_hex(EVEN_RESULT)
+    );
+
+    // The first EIP-198 example: Fermat's little theorem for the secp256k1 base field.
+    let p = decode_hex(SECP256K1_P);
+    let mut p_minus_one = p.clone();
+    p_minus_one[31] -= 1;
+    let mut one = vec![0u8; 32];
+    one[31] = 1;
+    assert_eq!(modexp(&[3], &p_minus_one, &p), one);
+
+    // A 4096-bit modulus `2^4096 - 1`, for which `2^4097 = 2` and `(-1)^3 = -1`.
+    let modulus = vec![0xff; 512];
+    let mut two = vec![0u8; 512];
+    two[511] = 2;
+    assert_eq!(modexp(&[2], &[0x10, 0x01], &modulus), two);
+    let mut minus_one = modulus.clone();
+    minus_one[511] -= 1;
+    assert_eq!(modexp(&minus_one, &[3], &modulus), minus_one);
+
+    // The EIP-198 edge cases: a zero modulus, and `0^0 = 1`.
+    assert_eq!(modexp(&[3], &[5], &[0, 0]), vec![0, 0]);
+    assert_eq!(modexp(&[], &[], &[0, 5]), vec![0, 1]);
+}
@@ -0,0 +1,23 @@
+#![no_std]
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use zkm_zkvm::lib::poseidon2::{compress, permute};
+
+pub fn main() {
+    let num_cases = zkm_zkvm::io::read::<u32>();
+    for _ in 0..num_cases {
+        let mut state = zkm_zkvm::io::read::<[u32; 16]>();
+        permute(&mut state);
+        zkm_zkvm::io::commit(&state);
+    }
+
+    // Compressing two digests is a permutation of their concatenation, truncated to a digest.
+    let left = [1u32, 2, 3, 4, 5, 6, 7, 8];
+    let right = [9u32, 10, 11, 12, 13, 14, 15, 16];
+    let mut state = [0u32; 16];
+    state[..8].copy_from_slice(&left);
+    state[8..].copy_from_slice(&right);
+    permute(&mut state);
+    assert_eq!(compress(&left, &right), state[..8]);
+}
@@ -0,0 +1,12 @@
+#![no_main]
+
+zkm_zkvm::entrypoint!(main);
+
+pub fn main() {
+    let n: u64 = zkm_zkvm::io::read();
+
+    // The host computes the square root, and the guest only checks it.
+    let (root, remainder): (u64, u64) = zkm_zkvm::hook::call("isqrt", &n);
+    assert!(root * root + remainder == n && remainder <= 2 * root);
+    zkm_zkvm::io::commit(&root);
+}
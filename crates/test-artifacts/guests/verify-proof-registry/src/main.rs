@@ -0,0 +1,22 @@
+//! This is a test program that takes in a vkey registry root and a list of (vkey, membership
+//! proof, input) tuples, and then verifies the Ziren proof for each of them.
+#![no_std]
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use sha2::{Digest, Sha256};
+extern crate alloc;
+use alloc::vec::Vec;
+use zkm_zkvm::lib::verify::{verify_zkm_proof_in_registry, VkeyMembershipProof};
+
+pub fn main() {
+    let registry_root = zkm_zkvm::io::read::<[u32; 8]>();
+    let proofs = zkm_zkvm::io::read::<Vec<([u32; 8], VkeyMembershipProof, Vec<u8>)>>();
+    for (vkey, membership_proof, input) in proofs.iter() {
+        let pv_digest = Sha256::digest(input);
+        verify_zkm_proof_in_registry(&registry_root, vkey, membership_proof, &pv_digest.into());
+    }
+
+    // Only the registry root is committed to, so the programs of the registry can be upgraded.
+    zkm_zkvm::io::commit(&registry_root);
+}
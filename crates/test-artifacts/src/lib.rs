@@ -54,20 +54,47 @@ pub const BLS12381_DECOMPRESS_ELF: &[u8] = include_elf!("bls-decompress-test");
 
 pub const VERIFY_PROOF_ELF: &[u8] = include_elf!("verify-proof");
 
+pub const VERIFY_PROOF_REGISTRY_ELF: &[u8] = include_elf!("verify-proof-registry");
+
 pub const BLS12381_FP_ELF: &[u8] = include_elf!("bls12381-fp-test");
 
 pub const BLS12381_FP2_MUL_ELF: &[u8] = include_elf!("bls12381-fp2-mul-test");
 
 pub const BLS12381_FP2_ADDSUB_ELF: &[u8] = include_elf!("bls12381-fp2-addsub-test");
 
+pub const BLS12381_FP6_MUL_ELF: &[u8] = include_elf!("bls12381-fp6-mul-test");
+
+pub const BLS12381_PAIRING_ELF: &[u8] = include_elf!("bls12381-pairing-test");
+
 pub const BN254_FP_ELF: &[u8] = include_elf!("bn254-fp-test");
 
 pub const BN254_FP2_ADDSUB_ELF: &[u8] = include_elf!("bn254-fp2-addsub-test");
 
 pub const BN254_FP2_MUL_ELF: &[u8] = include_elf!("bn254-fp2-mul-test");
 
+pub const BN254_FP6_MUL_ELF: &[u8] = include_elf!("bn254-fp6-mul-test");
+
+pub const BN254_PAIRING_ELF: &[u8] = include_elf!("bn254-pairing-test");
+
 //pub const TENDERMINT_BENCHMARK_ELF: &[u8] = include_elf!("tendermint-benchmark-program");
 
 pub const U256XU2048_MUL_ELF: &[u8] = include_elf!("u256x2048-mul");
 
+pub const POSEIDON2_ELF: &[u8] = include_elf!("poseidon2-test");
+
+pub const BLAKE3_ELF: &[u8] = include_elf!("blake3-test");
+
+pub const MODEXP_ELF: &[u8] = include_elf!("modexp-test");
+
 pub const UNCONSTRAINED_ELF: &[u8] = include_elf!("unconstrained");
+
+pub const GUEST_ENV_ELF: &[u8] = include_elf!("guest-env");
+
+pub const HARD_FLOAT_ELF: &[u8] = include_elf!("hard-float-test");
+
+pub const TYPED_HOOK_ELF: &[u8] = include_elf!("typed-hook");
+
+pub const EMBEDDED_ALLOCATOR_ELF: &[u8] = include_elf!("embedded-allocator-test");
+
+/// A program for the Cannon-compatible Linux syscall mode, assembled from `cannon/preimage.s`.
+pub const CANNON_PREIMAGE_ELF: &[u8] = include_bytes!("../cannon/preimage.elf");
@@ -55,7 +55,7 @@ pub fn convert_ark(
     vkey_hash: &str,
     groth16_vk: &[u8],
 ) -> Result<ArkProof, ArkGroth16Error> {
-    let proof = proof_with_pub_values.bytes();
+    let proof = proof_with_pub_values.bytes().map_err(|_| ArkGroth16Error::InvalidInput)?;
     let public_inputs = proof_with_pub_values.public_values.to_vec();
 
     // Hash the vk and get the first 4 bytes.
@@ -1,5 +1,8 @@
 //! This crate provides verifiers for Ziren Groth16 and Plonk BN254 proofs in a no-std environment.
 //! It is patched for efficient verification within the Ziren zkVM context.
+//!
+//! With the `compressed` feature, it also re-exports the verifier for compressed proofs of
+//! `zkm-compressed-verifier`, which only depends on the Plonky3 verifier.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
@@ -34,5 +37,11 @@ pub use plonk::error::PlonkError;
 pub use plonk::PlonkVerifier;
 mod plonk;
 
+#[cfg(feature = "compressed")]
+pub use zkm_compressed_verifier::{
+    ChipDescription, CompressMachine, CompressedError, CompressedVerifier, ConstraintNode,
+    FriParameters, LinearColumn, LookupDescription, COMPRESS_MACHINE_BYTES,
+};
+
 #[cfg(test)]
 mod tests;
@@ -16,7 +16,7 @@ fn test_verify_groth16() {
     let zkm_proof_with_public_values = client.prove(&pk, ZKMStdin::new()).groth16().run().unwrap();
 
     // Extract the proof and public inputs.
-    let proof = zkm_proof_with_public_values.bytes();
+    let proof = zkm_proof_with_public_values.bytes().unwrap();
     let public_inputs = zkm_proof_with_public_values.public_values.to_vec();
 
     // Get the vkey hash.
@@ -47,7 +47,7 @@ fn test_verify_plonk() {
     let zkm_proof_with_public_values = client.prove(&pk, ZKMStdin::new()).plonk().run().unwrap();
 
     // Extract the proof and public inputs.
-    let proof = zkm_proof_with_public_values.bytes();
+    let proof = zkm_proof_with_public_values.bytes().unwrap();
     let public_inputs = zkm_proof_with_public_values.public_values.to_vec();
 
     // Get the vkey hash.
@@ -73,7 +73,7 @@ fn test_e2e_verify_groth16() {
     // zkm_proof_with_public_values.save("test_binaries/hello-world-groth16.bin").expect("saving proof failed");
 
     // Extract the proof and public inputs.
-    let proof = zkm_proof_with_public_values.bytes();
+    let proof = zkm_proof_with_public_values.bytes().unwrap();
     let public_inputs = zkm_proof_with_public_values.public_values.to_vec();
 
     // Get the vkey hash.
@@ -0,0 +1,30 @@
+use std::{env, fs, path::Path};
+
+/// The default size of the stack of the guest, in bytes.
+const DEFAULT_STACK_SIZE: usize = 0x80_0000;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=ZKM_STACK_SIZE");
+
+    let stack_size = match env::var("ZKM_STACK_SIZE") {
+        Ok(size) => {
+            let parsed = match size.strip_prefix("0x") {
+                Some(hex) => usize::from_str_radix(hex, 16),
+                None => size.parse(),
+            };
+            parsed.unwrap_or_else(|_| panic!("invalid ZKM_STACK_SIZE: {size}"))
+        }
+        Err(_) => DEFAULT_STACK_SIZE,
+    };
+    assert!(stack_size % 8 == 0, "ZKM_STACK_SIZE must be a multiple of 8");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(
+        Path::new(&out_dir).join("stack_size.rs"),
+        format!(
+            "/// The size of the stack of the guest, set by `ZKM_STACK_SIZE`.\n\
+             pub const STACK_SIZE: usize = {stack_size:#x};\n"
+        ),
+    )
+    .unwrap();
+}
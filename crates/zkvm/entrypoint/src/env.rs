@@ -0,0 +1,35 @@
+//! The environment of the program, set by the host with `ZKMContextBuilder`.
+//!
+//! The environment variables are read with `std::env::var`, and the seed of the random number
+//! generator backs `sys_rand` and `getrandom`. Neither is constrained, so a program whose output
+//! depends on them should commit them to its public values.
+
+use lazy_static::lazy_static;
+use zkm_primitives::consts::fd::FD_RNG_SEED;
+
+use crate::syscalls::sys_write;
+
+lazy_static! {
+    /// The seed set by the host, read on first use.
+    static ref RNG_SEED: Option<[u8; 32]> = {
+        sys_write(FD_RNG_SEED, core::ptr::null(), 0);
+        let seed = crate::io::read_vec();
+        (!seed.is_empty()).then(|| seed.try_into().expect("invalid RNG seed"))
+    };
+}
+
+/// Returns the seed of the random number generator set by the host, if any.
+pub fn rng_seed() -> Option<[u8; 32]> {
+    *RNG_SEED
+}
+
+/// Commits the seed of the random number generator set by the host to the public values, so that
+/// the verifier knows the randomness the program used.
+///
+/// # Panics
+///
+/// Panics if the host did not set a seed.
+pub fn commit_rng_seed() {
+    let seed = rng_seed().expect("no RNG seed set by the host");
+    crate::io::commit_slice(&seed);
+}
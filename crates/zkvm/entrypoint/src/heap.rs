@@ -1,18 +1,27 @@
-//! Ported from Entrypoint for Ziren zkVM.
+//! The global allocators of the zkVM.
+//!
+//! By default, the heap is a bump allocator which never frees memory. With the `embedded` feature,
+//! it is a linked list allocator which reuses the freed memory, for programs allocating many
+//! temporary buffers.
 
-use core::alloc::{GlobalAlloc, Layout};
+mod simple;
+pub use simple::SimpleAlloc;
 
-use crate::syscalls::sys_alloc_aligned;
+#[cfg(feature = "embedded")]
+mod embedded;
+#[cfg(feature = "embedded")]
+pub use embedded::EmbeddedAlloc;
 
-/// A simple heap allocator.
-///
-/// Allocates memory from left to right, without any deallocation.
-pub struct SimpleAlloc;
+/// The global allocator registered by the `entrypoint!` macro.
+#[cfg(not(feature = "embedded"))]
+pub type ZkvmAlloc = SimpleAlloc;
 
-unsafe impl GlobalAlloc for SimpleAlloc {
-    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        sys_alloc_aligned(layout.size(), layout.align())
-    }
+/// The global allocator registered by the `entrypoint!` macro.
+#[cfg(feature = "embedded")]
+pub type ZkvmAlloc = EmbeddedAlloc;
 
-    unsafe fn dealloc(&self, _: *mut u8, _: Layout) {}
+/// Initializes the heap, before the program starts.
+pub fn init() {
+    #[cfg(feature = "embedded")]
+    embedded::init();
 }
@@ -0,0 +1,42 @@
+use core::alloc::{GlobalAlloc, Layout};
+
+use critical_section::RawRestoreState;
+use embedded_alloc::LlffHeap as Heap;
+
+use crate::syscalls::{is_hint, MAX_MEMORY, UPPER_MEMORY_START};
+
+/// The heap shared by all instances of [EmbeddedAlloc].
+static INNER_HEAP: Heap = Heap::empty();
+
+/// A linked list heap allocator, which reuses the freed memory.
+///
+/// The heap spans the memory above the stack, while the hint buffers take the memory below it.
+pub struct EmbeddedAlloc;
+
+unsafe impl GlobalAlloc for EmbeddedAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INNER_HEAP.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // The hint buffers are not allocated from the heap, and are never reused.
+        if !is_hint(ptr) {
+            INNER_HEAP.dealloc(ptr, layout)
+        }
+    }
+}
+
+/// Gives the heap the memory above the stack.
+pub(crate) fn init() {
+    unsafe { INNER_HEAP.init(UPPER_MEMORY_START, MAX_MEMORY - UPPER_MEMORY_START) };
+}
+
+/// The zkVM is single threaded, so critical sections need no locking.
+struct SingleThreadedCriticalSection;
+critical_section::set_impl!(SingleThreadedCriticalSection);
+
+unsafe impl critical_section::Impl for SingleThreadedCriticalSection {
+    unsafe fn acquire() -> RawRestoreState {}
+
+    unsafe fn release(_: RawRestoreState) {}
+}
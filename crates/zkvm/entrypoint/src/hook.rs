@@ -0,0 +1,27 @@
+//! Typed hooks, registered by the host with `ZKMContextBuilder::typed_hook`.
+//!
+//! A hook is addressed by its name, from which both sides derive the same file descriptor with
+//! [`typed_hook_fd`]. The request and the response are encoded with `bincode`. Like any hint,
+//! the response is not constrained, so the program must check it before relying on it.
+
+use serde::{de::DeserializeOwned, Serialize};
+pub use zkm_primitives::consts::fd::typed_hook_fd;
+
+/// Calls the typed hook registered by the host under `name` with the request `req`, and returns
+/// its response.
+///
+/// ### Examples
+/// ```ignore
+/// let (root, remainder): (u64, u64) = zkm_zkvm::hook::call("isqrt", &n);
+/// assert!(root * root + remainder == n && remainder <= 2 * root);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the response cannot be decoded. The execution fails if no hook is registered under
+/// `name`, or if the hook cannot decode the request.
+pub fn call<Req: Serialize, Resp: DeserializeOwned>(name: &str, req: &Req) -> Resp {
+    let buf = bincode::serialize(req).expect("serialization failed");
+    crate::io::write(typed_hook_fd(name), &buf);
+    crate::io::read()
+}
@@ -1,6 +1,8 @@
 //! Ported from Entrypoint for Ziren zkVM.
 #![feature(asm_experimental_arch)]
+pub mod env;
 pub mod heap;
+pub mod hook;
 pub mod syscalls;
 pub mod io {
     pub use zkm_lib::io::*;
@@ -16,10 +18,10 @@ macro_rules! entrypoint {
     ($path:path) => {
         const ZKVM_ENTRY: fn() = $path;
 
-        use $crate::heap::SimpleAlloc;
+        use $crate::heap::ZkvmAlloc;
 
         #[global_allocator]
-        static HEAP: SimpleAlloc = SimpleAlloc;
+        static HEAP: ZkvmAlloc = ZkvmAlloc;
 
         mod zkvm_generated_main {
 
@@ -59,6 +61,8 @@ mod zkvm {
 
     #[no_mangle]
     fn _main() {
+        crate::heap::init();
+
         unsafe {
             PUBLIC_VALUES_HASHER = Some(Sha256::new());
             #[cfg(feature = "verify")]
@@ -82,7 +86,7 @@ mod zkvm {
     .section .text.main;
     .globl main;
     main:
-        li  $sp, 0xfffc000
+        li  $sp, 0xfffc000 # STACK_TOP
         jal _main;
     "#
     );
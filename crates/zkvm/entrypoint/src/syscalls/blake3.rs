@@ -0,0 +1,28 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Applies the seven rounds of the BLAKE3 compression function to the given state.
+///
+/// The mixed state is written over the input. The caller is responsible for building the state
+/// from the chaining value, IV, counter, block length and flags, and for the final feed-forward.
+///
+/// ### Safety
+///
+/// The caller must ensure that `state` and `msg` are valid pointers to data that is aligned along
+/// a four byte boundary, and that they do not overlap.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_blake3_compress_inner(state: *mut [u32; 16], msg: *const [u32; 16]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "syscall",
+            in("$2") crate::syscalls::BLAKE3_COMPRESS_INNER,
+            in("$4") state,
+            in("$5") msg
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -12,17 +12,104 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+include!(concat!(env!("OUT_DIR"), "/stack_size.rs"));
+
 // Memory addresses must be lower than KoalaBear prime.
 pub const MAX_MEMORY: usize = 0x78000000;
 
-#[allow(clippy::missing_safety_doc)]
+/// The initial stack pointer, set by the entrypoint. The stack grows down from there.
+pub const STACK_TOP: usize = 0xfffc000;
+
+/// The end of the memory below the stack, which starts at the end of the program.
+pub const HEAP_END: usize = STACK_TOP - STACK_SIZE;
+
+/// The start of the memory above the stack, which ends at [MAX_MEMORY].
+pub const UPPER_MEMORY_START: usize = 0x10000000;
+
+/// The end of the memory below the stack, exported for the executor to detect the stack growing
+/// into the heap.
 #[no_mangle]
-pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u8 {
+#[used]
+pub static ZKM_HEAP_END: usize = HEAP_END;
+
+/// Returns the end of the memory below the stack.
+///
+/// The static is read through a volatile load so that the linker keeps the symbol.
+#[inline]
+pub(crate) fn heap_end() -> usize {
+    unsafe { core::ptr::read_volatile(&ZKM_HEAP_END) }
+}
+
+/// Returns the end of the program, where the memory below the stack starts.
+#[inline]
+pub(crate) fn program_end() -> usize {
     extern "C" {
         // https://lld.llvm.org/ELF/linker_script.html#sections-command
         static _end: u8;
     }
 
+    unsafe { (&_end) as *const u8 as usize }
+}
+
+#[allow(clippy::missing_safety_doc)]
+#[no_mangle]
+pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u8 {
+    #[cfg(feature = "embedded")]
+    {
+        let layout = core::alloc::Layout::from_size_align(bytes, align).unwrap();
+        unsafe { core::alloc::GlobalAlloc::alloc(&crate::heap::EmbeddedAlloc, layout) }
+    }
+
+    #[cfg(not(feature = "embedded"))]
+    {
+        unsafe { bump_alloc(bytes, align) }
+    }
+}
+
+/// Allocates a buffer to read a hint into.
+///
+/// The executor requires the memory written by a hint read to be uninitialized, so the buffers are
+/// never reused. With the bump allocator, which never frees memory, they are allocated from the
+/// heap. With the `embedded` feature, they are allocated from the memory below the stack, while
+/// the heap takes the memory above it, and must not be passed to the global allocator's `dealloc`,
+/// which ignores them.
+#[allow(clippy::missing_safety_doc)]
+#[no_mangle]
+pub unsafe extern "C" fn sys_alloc_hint_aligned(bytes: usize, align: usize) -> *mut u8 {
+    #[cfg(feature = "embedded")]
+    {
+        // Pointer to next hint address to use, or 0 if no hint has been read yet.
+        static mut HINT_POS: usize = 0;
+
+        // SAFETY: Single threaded, so nothing else can touch this while we're working.
+        let mut hint_pos = unsafe { HINT_POS };
+        if hint_pos == 0 {
+            hint_pos = program_end();
+        }
+
+        let ptr = bump(&mut hint_pos, bytes, align, heap_end())
+            .unwrap_or_else(|| panic!("Memory limit exceeded: too many hints read"));
+
+        unsafe { HINT_POS = hint_pos };
+        ptr
+    }
+
+    #[cfg(not(feature = "embedded"))]
+    {
+        unsafe { bump_alloc(bytes, align) }
+    }
+}
+
+/// Returns whether the pointer was allocated by [sys_alloc_hint_aligned].
+#[cfg(feature = "embedded")]
+pub(crate) fn is_hint(ptr: *mut u8) -> bool {
+    (ptr as usize) < UPPER_MEMORY_START
+}
+
+/// Allocates from the memory below the stack, then from the memory above it once the former is
+/// exhausted.
+#[cfg(not(feature = "embedded"))]
+unsafe fn bump_alloc(bytes: usize, align: usize) -> *mut u8 {
     // Pointer to next heap address to use, or 0 if the heap has not yet been
     // initialized.
     static mut HEAP_POS: usize = 0;
@@ -31,21 +118,29 @@ pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u
     let mut heap_pos = unsafe { HEAP_POS };
 
     if heap_pos == 0 {
-        heap_pos = unsafe { (&_end) as *const u8 as usize };
+        heap_pos = program_end();
     }
 
-    let offset = heap_pos & (align - 1);
-    if offset != 0 {
-        heap_pos += align - offset;
-    }
-
-    let ptr = heap_pos as *mut u8;
-    let (heap_pos, overflowed) = heap_pos.overflowing_add(bytes);
-
-    if overflowed || MAX_MEMORY < heap_pos {
-        panic!("Memory limit exceeded (0xC000000)");
-    }
+    let ptr = bump(&mut heap_pos, bytes, align, heap_end())
+        .or_else(|| {
+            heap_pos = heap_pos.max(UPPER_MEMORY_START);
+            bump(&mut heap_pos, bytes, align, MAX_MEMORY)
+        })
+        .unwrap_or_else(|| panic!("Memory limit exceeded ({MAX_MEMORY:#x})"));
 
     unsafe { HEAP_POS = heap_pos };
     ptr
 }
+
+/// Bumps `pos` past a buffer of `bytes` aligned to `align`, returning the buffer if it ends before
+/// `end`.
+fn bump(pos: &mut usize, bytes: usize, align: usize, end: usize) -> Option<*mut u8> {
+    let offset = *pos & (align - 1);
+    let start = if offset != 0 { pos.checked_add(align - offset)? } else { *pos };
+    let next = start.checked_add(bytes)?;
+    if next > end {
+        return None;
+    }
+    *pos = next;
+    Some(start as *mut u8)
+}
@@ -1,4 +1,5 @@
 mod bigint;
+mod blake3;
 mod bls12381;
 mod bn254;
 mod ed25519;
@@ -7,6 +8,7 @@ mod halt;
 mod io;
 mod keccak_sponge;
 mod memory;
+mod poseidon2;
 mod secp256k1;
 mod secp256r1;
 mod sha_compress;
@@ -19,6 +21,7 @@ mod unconstrained;
 mod verify;
 
 pub use bigint::*;
+pub use blake3::*;
 pub use bls12381::*;
 pub use bn254::*;
 pub use ed25519::*;
@@ -27,6 +30,7 @@ pub use halt::*;
 pub use io::*;
 pub use keccak_sponge::*;
 pub use memory::*;
+pub use poseidon2::*;
 pub use secp256k1::*;
 pub use secp256r1::*;
 pub use sha_compress::*;
@@ -89,6 +93,12 @@ pub const SECP256R1_DECOMPRESS: u32 = 0x00_00_01_2E;
 /// Executes `U256XU2048_MUL`.
 pub const U256XU2048_MUL: u32 = 0x00_01_01_2F;
 
+/// Executes `POSEIDON2_PERMUTE`.
+pub const POSEIDON2_PERMUTE: u32 = 0x00_01_01_30;
+
+/// Executes `BLAKE3_COMPRESS_INNER`.
+pub const BLAKE3_COMPRESS_INNER: u32 = 0x00_38_01_33;
+
 /// Executes `BN254_ADD`.
 pub const BN254_ADD: u32 = 0x00_01_01_0E;
 
@@ -140,6 +150,9 @@ pub const BLS12381_FP2_SUB: u32 = 0x00_01_01_24;
 /// Executes the `BLS12381_FP2_MUL` precompile.
 pub const BLS12381_FP2_MUL: u32 = 0x00_01_01_25;
 
+/// Executes the `BLS12381_FP6_MUL` precompile.
+pub const BLS12381_FP6_MUL: u32 = 0x00_01_01_32;
+
 /// Executes the `BN254_FP_ADD` precompile.
 pub const BN254_FP_ADD: u32 = 0x00_01_01_26;
 
@@ -157,3 +170,6 @@ pub const BN254_FP2_SUB: u32 = 0x00_01_01_2A;
 
 /// Executes the `BN254_FP2_MUL` precompile.
 pub const BN254_FP2_MUL: u32 = 0x00_01_01_2B;
+
+/// Executes the `BN254_FP6_MUL` precompile.
+pub const BN254_FP6_MUL: u32 = 0x00_01_01_31;
@@ -2,22 +2,28 @@ use std::sync::Mutex;
 
 use lazy_static::lazy_static;
 use rand::{rngs::StdRng, Rng, SeedableRng};
+use zkm_primitives::consts::fd::FD_GETENV;
 
-use crate::syscalls::{syscall_halt, syscall_write};
+use crate::{
+    env::rng_seed,
+    syscalls::{sys_alloc_aligned, syscall_halt, syscall_write},
+};
 
-/// The random number generator seed for the zkVM.
-///
-/// In the future, we can pass in this seed from the host or have the verifier generate it.
+/// The random number generator seed for the zkVM, if the host did not set one.
 const PRNG_SEED: u64 = 0x123456789abcdef0;
 
 lazy_static! {
-    /// A lazy static to generate a global random number generator.
-    static ref RNG: Mutex<StdRng> = Mutex::new(StdRng::seed_from_u64(PRNG_SEED));
+    /// A lazy static to generate a global random number generator, seeded by the host if it set a
+    /// seed.
+    static ref RNG: Mutex<StdRng> = Mutex::new(match rng_seed() {
+        Some(seed) => StdRng::from_seed(seed),
+        None => {
+            eprintln!("WARNING: Using insecure random number generator.");
+            StdRng::seed_from_u64(PRNG_SEED)
+        }
+    });
 }
 
-/// A lazy static to print a warning once for using the `sys_rand` system call.
-static SYS_RAND_WARNING: std::sync::Once = std::sync::Once::new();
-
 /// Generates random bytes.
 ///
 /// # Safety
@@ -25,9 +31,6 @@ static SYS_RAND_WARNING: std::sync::Once = std::sync::Once::new();
 /// Make sure that `buf` has at least `nwords` words.
 #[no_mangle]
 pub unsafe extern "C" fn sys_rand(recv_buf: *mut u8, words: usize) {
-    SYS_RAND_WARNING.call_once(|| {
-        eprintln!("WARNING: Using insecure random number generator.");
-    });
     let mut rng = RNG.lock().unwrap();
     for i in 0..words {
         let element = recv_buf.add(i);
@@ -42,21 +45,42 @@ pub unsafe extern "C" fn sys_panic(msg_ptr: *const u8, len: usize) -> ! {
     syscall_halt(1);
 }
 
-#[allow(unused_variables)]
+/// Reads the environment variable `varname` set by the host, returning the length of its value in
+/// bytes, or `usize::MAX` if it is not set.
+///
+/// At most `words` words of the value are written to `recv_buf`, so `std::env::var` first calls
+/// this without a buffer to get the length of the value.
+///
+/// # Safety
+///
+/// Make sure that `recv_buf` has at least `words` words and `varname` has `varname_len` bytes.
 #[no_mangle]
-pub const fn sys_getenv(
+pub unsafe extern "C" fn sys_getenv(
     recv_buf: *mut u32,
     words: usize,
     varname: *const u8,
     varname_len: usize,
 ) -> usize {
-    0
+    sys_write(FD_GETENV, varname, varname_len);
+    let response = crate::io::read_vec();
+    let Some((&1, value)) = response.split_first() else {
+        return usize::MAX;
+    };
+    let nbytes = value.len().min(words * 4);
+    if nbytes > 0 {
+        core::ptr::copy_nonoverlapping(value.as_ptr(), recv_buf as *mut u8, nbytes);
+    }
+    value.len()
 }
 
-#[allow(unused_variables)]
+/// Allocates a buffer of `nwords` words, which is never freed.
+///
+/// # Safety
+///
+/// The buffer is uninitialized.
 #[no_mangle]
-pub const fn sys_alloc_words(nwords: usize) -> *mut u32 {
-    core::ptr::null_mut()
+pub unsafe extern "C" fn sys_alloc_words(nwords: usize) -> *mut u32 {
+    sys_alloc_aligned(nwords * 4, 4) as *mut u32
 }
 
 #[allow(unused_unsafe)]
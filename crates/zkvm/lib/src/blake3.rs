@@ -0,0 +1,150 @@
+use crate::syscall_blake3_compress_inner;
+
+/// The number of bytes in a BLAKE3 digest.
+pub const OUT_LEN: usize = 32;
+
+/// The number of bytes in a message block.
+pub const BLOCK_LEN: usize = 64;
+
+/// The number of bytes in a chunk.
+pub const CHUNK_LEN: usize = 1024;
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+
+/// The maximum depth of the chunk tree, enough for inputs of up to 2^64 bytes.
+const MAX_DEPTH: usize = 54;
+
+const IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+/// The BLAKE3 compression function.
+///
+/// Returns the full 16 word output, the first 8 words of which are the new chaining value.
+pub fn compress(
+    cv: &[u32; 8],
+    block: &[u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    let mut state = [
+        cv[0],
+        cv[1],
+        cv[2],
+        cv[3],
+        cv[4],
+        cv[5],
+        cv[6],
+        cv[7],
+        IV[0],
+        IV[1],
+        IV[2],
+        IV[3],
+        counter as u32,
+        (counter >> 32) as u32,
+        block_len,
+        flags,
+    ];
+    unsafe {
+        syscall_blake3_compress_inner(&mut state, block);
+    }
+
+    for i in 0..8 {
+        state[i] ^= state[i + 8];
+        state[i + 8] ^= cv[i];
+    }
+    state
+}
+
+/// Hashes `input` with BLAKE3, returning the 32 byte digest.
+pub fn hash(input: &[u8]) -> [u8; OUT_LEN] {
+    let mut cv_stack = [[0u32; 8]; MAX_DEPTH];
+    let mut depth = 0;
+
+    // Every chunk but the last one is full and is merged into the tree as soon as possible.
+    let last_chunk_start = input.len().saturating_sub(1) / CHUNK_LEN * CHUNK_LEN;
+    let (chunks, last_chunk) = input.split_at(last_chunk_start);
+    for (index, chunk) in chunks.chunks_exact(CHUNK_LEN).enumerate() {
+        let mut cv = chunk_output(chunk, index as u64).chaining_value();
+        let mut total_chunks = index as u64 + 1;
+        while total_chunks & 1 == 0 {
+            depth -= 1;
+            cv = parent_output(&cv_stack[depth], &cv).chaining_value();
+            total_chunks >>= 1;
+        }
+        cv_stack[depth] = cv;
+        depth += 1;
+    }
+
+    let mut output = chunk_output(last_chunk, (last_chunk_start / CHUNK_LEN) as u64);
+    while depth > 0 {
+        depth -= 1;
+        output = parent_output(&cv_stack[depth], &output.chaining_value());
+    }
+    output.root_hash()
+}
+
+/// The inputs of a compression whose flags are not final yet, since it may be the root.
+struct Output {
+    cv: [u32; 8],
+    block: [u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Output {
+    fn chaining_value(&self) -> [u32; 8] {
+        let out = compress(&self.cv, &self.block, self.counter, self.block_len, self.flags);
+        out[..8].try_into().unwrap()
+    }
+
+    fn root_hash(&self) -> [u8; OUT_LEN] {
+        let out = compress(&self.cv, &self.block, 0, self.block_len, self.flags | ROOT);
+        let mut hash = [0u8; OUT_LEN];
+        for (bytes, word) in hash.chunks_exact_mut(4).zip(out.iter()) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        hash
+    }
+}
+
+/// Compresses every block of a chunk but the last one.
+fn chunk_output(chunk: &[u8], counter: u64) -> Output {
+    let last_block_start = chunk.len().saturating_sub(1) / BLOCK_LEN * BLOCK_LEN;
+    let (blocks, last_block) = chunk.split_at(last_block_start);
+
+    let mut cv = IV;
+    let mut flags = CHUNK_START;
+    for block in blocks.chunks_exact(BLOCK_LEN) {
+        let out = compress(&cv, &block_words(block), counter, BLOCK_LEN as u32, flags);
+        cv = out[..8].try_into().unwrap();
+        flags = 0;
+    }
+
+    Output {
+        cv,
+        block: block_words(last_block),
+        counter,
+        block_len: last_block.len() as u32,
+        flags: flags | CHUNK_END,
+    }
+}
+
+fn parent_output(left: &[u32; 8], right: &[u32; 8]) -> Output {
+    let mut block = [0u32; 16];
+    block[..8].copy_from_slice(left);
+    block[8..].copy_from_slice(right);
+    Output { cv: IV, block, counter: 0, block_len: BLOCK_LEN as u32, flags: PARENT }
+}
+
+/// Reads a block of at most 64 bytes as little-endian words, padding it with zeros.
+fn block_words(block: &[u8]) -> [u32; 16] {
+    let mut bytes = [0u8; BLOCK_LEN];
+    bytes[..block.len()].copy_from_slice(block);
+    core::array::from_fn(|i| u32::from_le_bytes(bytes[4 * i..4 * i + 4].try_into().unwrap()))
+}
@@ -1,13 +1,20 @@
 use std::io::ErrorKind;
 
 use crate::{
+    pairing::{self, Family, Fp2, Fp6, G1Affine, G2Affine, PairingCurve, Twist},
     syscall_bls12381_add, syscall_bls12381_decompress, syscall_bls12381_double,
+    syscall_bls12381_fp2_addmod, syscall_bls12381_fp2_mulmod, syscall_bls12381_fp2_submod,
+    syscall_bls12381_fp6_mulmod, syscall_bls12381_fp_addmod, syscall_bls12381_fp_mulmod,
+    syscall_bls12381_fp_submod,
     utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint},
 };
 
 /// The number of limbs in [Bls12381AffinePoint].
 pub const N: usize = 24;
 
+/// The number of limbs in a BLS12-381 base field element.
+pub const FP_N: usize = 12;
+
 /// A point on the BLS12-381 curve.
 #[derive(Copy, Clone)]
 #[repr(align(4))]
@@ -96,3 +103,127 @@ pub fn decompress_pubkey(compressed_key: &[u8; 48]) -> Result<[u8; 96], ErrorKin
 
     Ok(decompressed_key)
 }
+
+/// The parameters of the BLS12-381 optimal ate pairing.
+pub struct Bls12381Pairing;
+
+impl PairingCurve<FP_N> for Bls12381Pairing {
+    const MODULUS: [u32; FP_N] = [
+        0xffffaaab, 0xb9feffff, 0xb153ffff, 0x1eabfffe, 0xf6b0f624, 0x6730d2a0, 0xf38512bf,
+        0x64774b84, 0x434bacd7, 0x4b1ba7b6, 0x397fe69a, 0x1a0111ea,
+    ];
+    const ORDER: &'static [u32] = &[
+        0x00000001, 0xffffffff, 0xfffe5bfe, 0x53bda402, 0x09a1d805, 0x3339d808, 0x299d7d48,
+        0x73eda753,
+    ];
+    const B: u32 = 4;
+    const NON_RESIDUE_C0: u32 = 1;
+    const LOOP_COUNT: &'static [u32] = &[0x00010000, 0xd2010000];
+    const LOOP_COUNT_IS_NEGATIVE: bool = true;
+    const FAMILY: Family = Family::Bls12;
+    const X: &'static [u32] = &[0x00010000, 0xd2010000];
+    const X_IS_NEGATIVE: bool = true;
+    const FROBENIUS_COEFFS: [Fp2<FP_N>; 5] = [
+        Fp2 {
+            c0: [
+                0x92235fb8, 0x8d0775ed, 0x63e7813d, 0xf67ea53d, 0x84bab9c4, 0x7b2443d7, 0x3cbd5f4f,
+                0x0fd603fd, 0x202c0d1f, 0xc231beb4, 0x02bb0667, 0x1904d3bf,
+            ],
+            c1: [
+                0x6ddc4af3, 0x2cf78a12, 0x4d6c7ec2, 0x282d5ac1, 0x71f63c5f, 0xec0c8ec9, 0xb6c7b36f,
+                0x54a14787, 0x231f9fb8, 0x88e9e902, 0x36c4e032, 0x00fc3e2b,
+            ],
+        },
+        Fp2 {
+            c0: [
+                0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            ],
+            c1: [
+                0x0000aaac, 0x8bfd0000, 0x4f49fffd, 0x409427eb, 0x0fb85f9b, 0x897d2965, 0x89759ad4,
+                0xaa0d857d, 0x63d4de85, 0xec024086, 0x397fe699, 0x1a0111ea,
+            ],
+        },
+        Fp2 {
+            c0: [
+                0xede3cc09, 0xc81084fb, 0x72ec05f4, 0xee67992f, 0x009241c5, 0x77f76e17, 0xc2d3435e,
+                0x48395dab, 0x6bd17ffe, 0x6831e36d, 0x37ff400b, 0x06af0e04,
+            ],
+            c1: [
+                0xede3cc09, 0xc81084fb, 0x72ec05f4, 0xee67992f, 0x009241c5, 0x77f76e17, 0xc2d3435e,
+                0x48395dab, 0x6bd17ffe, 0x6831e36d, 0x37ff400b, 0x06af0e04,
+            ],
+        },
+        Fp2 {
+            c0: [
+                0x0000aaad, 0x8bfd0000, 0x4f49fffd, 0x409427eb, 0x0fb85f9b, 0x897d2965, 0x89759ad4,
+                0xaa0d857d, 0x63d4de85, 0xec024086, 0x397fe699, 0x1a0111ea,
+            ],
+            c1: [
+                0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            ],
+        },
+        Fp2 {
+            c0: [
+                0x80078116, 0x9b18fae9, 0x257f8732, 0xc63a3e6e, 0x8e9c0566, 0x8beadf4d, 0x0c0b8fee,
+                0xf3981624, 0x48b1e045, 0xdf47fa6b, 0x013a5fd8, 0x05b2cfd9,
+            ],
+            c1: [
+                0x7ff82995, 0x1ee60516, 0x8bd478cd, 0x5871c190, 0x6814f0bd, 0xdb45f353, 0xe77982d0,
+                0x70df3560, 0xfa99cc91, 0x6bd3ad4a, 0x384586c1, 0x144e4211,
+            ],
+        },
+    ];
+    const TWIST: Twist = Twist::M;
+
+    fn fp_add(x: &mut [u32; FP_N], y: &[u32; FP_N]) {
+        unsafe { syscall_bls12381_fp_addmod(x.as_mut_ptr(), y.as_ptr()) }
+    }
+
+    fn fp_sub(x: &mut [u32; FP_N], y: &[u32; FP_N]) {
+        unsafe { syscall_bls12381_fp_submod(x.as_mut_ptr(), y.as_ptr()) }
+    }
+
+    fn fp_mul(x: &mut [u32; FP_N], y: &[u32; FP_N]) {
+        unsafe { syscall_bls12381_fp_mulmod(x.as_mut_ptr(), y.as_ptr()) }
+    }
+
+    fn fp2_add(x: &mut Fp2<FP_N>, y: &Fp2<FP_N>) {
+        unsafe { syscall_bls12381_fp2_addmod(x as *mut _ as *mut u32, y as *const _ as *const u32) }
+    }
+
+    fn fp2_sub(x: &mut Fp2<FP_N>, y: &Fp2<FP_N>) {
+        unsafe { syscall_bls12381_fp2_submod(x as *mut _ as *mut u32, y as *const _ as *const u32) }
+    }
+
+    fn fp2_mul(x: &mut Fp2<FP_N>, y: &Fp2<FP_N>) {
+        unsafe { syscall_bls12381_fp2_mulmod(x as *mut _ as *mut u32, y as *const _ as *const u32) }
+    }
+
+    fn fp6_mul(x: &mut Fp6<FP_N>, y: &Fp6<FP_N>) {
+        unsafe { syscall_bls12381_fp6_mulmod(x as *mut _ as *mut u32, y as *const _ as *const u32) }
+    }
+}
+
+/// Returns true if the product of the pairings `e(p, q)` over all `pairs` is one.
+///
+/// Each G1 point is given as the limbs of `x || y`, and each G2 point, which must lie on the
+/// sextic twist, as the limbs of `x.c0 || x.c1 || y.c0 || y.c1`. A point whose limbs are all zero
+/// is the point at infinity, and pairs containing it are skipped.
+pub fn pairing_check(pairs: &[([u32; 2 * FP_N], [u32; 4 * FP_N])]) -> bool {
+    let fp = |limbs: &[u32]| -> [u32; FP_N] { limbs.try_into().unwrap() };
+    let pairs = pairs
+        .iter()
+        .filter(|(p, q)| p.iter().any(|&limb| limb != 0) && q.iter().any(|&limb| limb != 0))
+        .map(|(p, q)| {
+            let p = G1Affine { x: fp(&p[..FP_N]), y: fp(&p[FP_N..]) };
+            let q = G2Affine {
+                x: Fp2 { c0: fp(&q[..FP_N]), c1: fp(&q[FP_N..2 * FP_N]) },
+                y: Fp2 { c0: fp(&q[2 * FP_N..3 * FP_N]), c1: fp(&q[3 * FP_N..]) },
+            };
+            (p, q)
+        })
+        .collect::<Vec<_>>();
+    pairing::pairing_check::<Bls12381Pairing, FP_N>(&pairs)
+}
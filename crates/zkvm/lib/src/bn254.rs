@@ -1,11 +1,17 @@
 use crate::{
-    syscall_bn254_add, syscall_bn254_double,
+    pairing::{self, Family, Fp2, Fp6, G1Affine, G2Affine, PairingCurve, Twist},
+    syscall_bn254_add, syscall_bn254_double, syscall_bn254_fp2_addmod, syscall_bn254_fp2_mulmod,
+    syscall_bn254_fp2_submod, syscall_bn254_fp6_mulmod, syscall_bn254_fp_addmod,
+    syscall_bn254_fp_mulmod, syscall_bn254_fp_submod,
     utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint},
 };
 
 /// The number of limbs in [Bn254AffinePoint].
 pub const N: usize = 16;
 
+/// The number of limbs in a BN254 base field element.
+pub const FP_N: usize = 8;
+
 /// A point on the Bn254 curve.
 #[derive(Copy, Clone)]
 #[repr(align(4))]
@@ -78,3 +84,130 @@ impl AffinePoint<N> for Bn254Point {
         }
     }
 }
+
+/// The parameters of the BN254 pairing.
+///
+/// The Miller loop runs over `6u^2`, so the pairing is the ate pairing rather than the optimal ate
+/// pairing. Both are non-degenerate and bilinear, so the result of a pairing check is the same.
+pub struct Bn254Pairing;
+
+impl PairingCurve<FP_N> for Bn254Pairing {
+    const MODULUS: [u32; FP_N] = [
+        0xd87cfd47, 0x3c208c16, 0x6871ca8d, 0x97816a91, 0x8181585d, 0xb85045b6, 0xe131a029,
+        0x30644e72,
+    ];
+    const ORDER: &'static [u32] = &[
+        0xf0000001, 0x43e1f593, 0x79b97091, 0x2833e848, 0x8181585d, 0xb85045b6, 0xe131a029,
+        0x30644e72,
+    ];
+    const B: u32 = 3;
+    const NON_RESIDUE_C0: u32 = 9;
+    const LOOP_COUNT: &'static [u32] = &[0xe87cfd46, 0xf83e9682, 0xeeb859fb, 0x6f4d8248];
+    const LOOP_COUNT_IS_NEGATIVE: bool = false;
+    const FAMILY: Family = Family::Bn;
+    const X: &'static [u32] = &[0x4a6909f1, 0x44e992b4];
+    const X_IS_NEGATIVE: bool = false;
+    const FROBENIUS_COEFFS: [Fp2<FP_N>; 5] = [
+        Fp2 {
+            c0: [
+                0xdcc9e470, 0xd60b35da, 0x292f2176, 0x5c521e08, 0x76e68b60, 0xe8b99fdd, 0x2865a7df,
+                0x1284b71c,
+            ],
+            c1: [
+                0x80f362ac, 0xca5cf05f, 0x8eeec7e5, 0x74799277, 0x12150b8e, 0xa6327cfe, 0xb4fae7e6,
+                0x246996f3,
+            ],
+        },
+        Fp2 {
+            c0: [
+                0x176f553d, 0x99e39557, 0xc2c3330c, 0xb78cc310, 0xf559b143, 0x4c0bec3c, 0x4f7911f7,
+                0x2fb34798,
+            ],
+            c1: [
+                0x640fcba2, 0x1665d51c, 0x0b7c9dce, 0x32ae2a1d, 0xd75a0794, 0x4ba4cc8b, 0x61ebae20,
+                0x16c9e550,
+            ],
+        },
+        Fp2 {
+            c0: [
+                0x71a0135a, 0xdc540146, 0xa9c95998, 0xdbaae0ed, 0xb6e2f9b9, 0xdc5ec698, 0x489af5dc,
+                0x063cf305,
+            ],
+            c1: [
+                0x2623b0e3, 0x82d37f63, 0x8fa25bd2, 0x21807dc9, 0xec796f2b, 0x0704b5a7, 0xac41049a,
+                0x07c03cbc,
+            ],
+        },
+        Fp2 {
+            c0: [
+                0x921ea762, 0x848a1f55, 0xbe94ec72, 0xd33365f7, 0x5a181e84, 0x80f3c0b7, 0x64eea801,
+                0x05b54f5e,
+            ],
+            c1: [
+                0xcd2b8126, 0xc13b4711, 0x1bdec763, 0x3685d2ea, 0x3b0b1c92, 0x9f3a80b0, 0xe7fd8aee,
+                0x2c145edb,
+            ],
+        },
+        Fp2 {
+            c0: [
+                0xeab7692f, 0x2ea2c810, 0x55aa1bd3, 0x425c459b, 0xa4353ff4, 0xe93a3661, 0x4f798649,
+                0x0183c1e7,
+            ],
+            c1: [
+                0x6e0c2c4b, 0x24c6b8ee, 0x678e2ac0, 0xb080cb99, 0xc7729f7d, 0xa27fb246, 0x76fd0675,
+                0x12acf2ca,
+            ],
+        },
+    ];
+    const TWIST: Twist = Twist::D;
+
+    fn fp_add(x: &mut [u32; FP_N], y: &[u32; FP_N]) {
+        unsafe { syscall_bn254_fp_addmod(x.as_mut_ptr(), y.as_ptr()) }
+    }
+
+    fn fp_sub(x: &mut [u32; FP_N], y: &[u32; FP_N]) {
+        unsafe { syscall_bn254_fp_submod(x.as_mut_ptr(), y.as_ptr()) }
+    }
+
+    fn fp_mul(x: &mut [u32; FP_N], y: &[u32; FP_N]) {
+        unsafe { syscall_bn254_fp_mulmod(x.as_mut_ptr(), y.as_ptr()) }
+    }
+
+    fn fp2_add(x: &mut Fp2<FP_N>, y: &Fp2<FP_N>) {
+        unsafe { syscall_bn254_fp2_addmod(x as *mut _ as *mut u32, y as *const _ as *const u32) }
+    }
+
+    fn fp2_sub(x: &mut Fp2<FP_N>, y: &Fp2<FP_N>) {
+        unsafe { syscall_bn254_fp2_submod(x as *mut _ as *mut u32, y as *const _ as *const u32) }
+    }
+
+    fn fp2_mul(x: &mut Fp2<FP_N>, y: &Fp2<FP_N>) {
+        unsafe { syscall_bn254_fp2_mulmod(x as *mut _ as *mut u32, y as *const _ as *const u32) }
+    }
+
+    fn fp6_mul(x: &mut Fp6<FP_N>, y: &Fp6<FP_N>) {
+        unsafe { syscall_bn254_fp6_mulmod(x as *mut _ as *mut u32, y as *const _ as *const u32) }
+    }
+}
+
+/// Returns true if the product of the pairings `e(p, q)` over all `pairs` is one.
+///
+/// Each G1 point is given as the limbs of `x || y`, and each G2 point, which must lie on the
+/// sextic twist, as the limbs of `x.c0 || x.c1 || y.c0 || y.c1`. A point whose limbs are all zero
+/// is the point at infinity, and pairs containing it are skipped.
+pub fn pairing_check(pairs: &[([u32; 2 * FP_N], [u32; 4 * FP_N])]) -> bool {
+    let fp = |limbs: &[u32]| -> [u32; FP_N] { limbs.try_into().unwrap() };
+    let pairs = pairs
+        .iter()
+        .filter(|(p, q)| p.iter().any(|&limb| limb != 0) && q.iter().any(|&limb| limb != 0))
+        .map(|(p, q)| {
+            let p = G1Affine { x: fp(&p[..FP_N]), y: fp(&p[FP_N..]) };
+            let q = G2Affine {
+                x: Fp2 { c0: fp(&q[..FP_N]), c1: fp(&q[FP_N..2 * FP_N]) },
+                y: Fp2 { c0: fp(&q[2 * FP_N..3 * FP_N]), c1: fp(&q[3 * FP_N..]) },
+            };
+            (p, q)
+        })
+        .collect::<Vec<_>>();
+    pairing::pairing_check::<Bn254Pairing, FP_N>(&pairs)
+}
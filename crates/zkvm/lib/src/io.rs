@@ -1,5 +1,5 @@
 #![allow(unused_unsafe)]
-use crate::{syscall_hint_len, syscall_hint_read, syscall_write};
+use crate::{sys_alloc_hint_aligned, syscall_hint_len, syscall_hint_read, syscall_write};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     alloc::Layout,
@@ -40,18 +40,18 @@ pub fn read_vec() -> Vec<u8> {
 
     // Allocate a buffer of the required length that is 4 byte aligned
     let layout = Layout::from_size_align(capacity, 4).expect("vec is too large");
-    let ptr = unsafe { std::alloc::alloc(layout) };
+    let ptr = unsafe { sys_alloc_hint_aligned(layout.size(), layout.align()) };
 
     // SAFETY:
-    // 1. `ptr` was allocated using alloc
-    // 2. We assuume that the VM global allocator doesn't dealloc
+    // 1. `ptr` was allocated by `sys_alloc_hint_aligned`, whose buffers are never reused, and
+    //    which the VM global allocator ignores on dealloc
     // 3/6. Size is correct from above
     // 4/5. Length is 0
     // 7. Layout::from_size_align already checks this
     let mut vec = unsafe { Vec::from_raw_parts(ptr, 0, capacity) };
 
     // Read the vec into uninitialized memory. The syscall assumes the memory is uninitialized,
-    // which should be true because the allocator does not dealloc, so a new alloc should be fresh.
+    // which is true because the hint buffers are never reused, so a new alloc is fresh.
     unsafe {
         syscall_hint_read(ptr, len);
         vec.set_len(len);
@@ -3,6 +3,7 @@
 //! Documentation for these syscalls can be found in the zkVM entrypoint
 //! `zkm_zkvm::syscalls` module.
 
+pub mod blake3;
 pub mod bls12381;
 pub mod bn254;
 #[cfg(feature = "ecdsa")]
@@ -11,6 +12,9 @@ pub mod ecdsa;
 pub mod ed25519;
 pub mod io;
 pub mod keccak256;
+pub mod modexp;
+pub mod pairing;
+pub mod poseidon2;
 pub mod secp256k1;
 pub mod secp256r1;
 pub mod sha3;
@@ -84,6 +88,13 @@ extern "C" {
         lo: *mut [u32; 64],
         hi: *mut [u32; 8],
     );
+
+    /// Executes the Poseidon2 permutation on the given KoalaBear state.
+    pub fn syscall_poseidon2_permute(state: *mut [u32; 16]);
+
+    /// Executes the rounds of the BLAKE3 compression function on the given state and message.
+    pub fn syscall_blake3_compress_inner(state: *mut [u32; 16], msg: *const [u32; 16]);
+
     /// Enters unconstrained mode.
     pub fn syscall_enter_unconstrained() -> bool;
 
@@ -102,6 +113,10 @@ extern "C" {
     /// Allocates a buffer aligned to the given alignment.
     pub fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u8;
 
+    /// Allocates a buffer aligned to the given alignment to read a hint into, which is never
+    /// reused.
+    pub fn sys_alloc_hint_aligned(bytes: usize, align: usize) -> *mut u8;
+
     /// Decompresses a BLS12-381 point.
     pub fn syscall_bls12381_decompress(point: &mut [u8; 96], is_odd: bool);
 
@@ -132,6 +147,9 @@ extern "C" {
     /// Executes a BLS12-381 Fp2 multiplication on the given inputs.
     pub fn syscall_bls12381_fp2_mulmod(p: *mut u32, q: *const u32);
 
+    /// Executes a BLS12-381 Fp6 multiplication on the given inputs.
+    pub fn syscall_bls12381_fp6_mulmod(p: *mut u32, q: *const u32);
+
     /// Executes a BN254 field addition on the given inputs.
     pub fn syscall_bn254_fp_addmod(p: *mut u32, q: *const u32);
 
@@ -150,4 +168,7 @@ extern "C" {
     /// Executes a BN254 Fp2 multiplication on the given inputs.
     pub fn syscall_bn254_fp2_mulmod(p: *mut u32, q: *const u32);
 
+    /// Executes a BN254 Fp6 multiplication on the given inputs.
+    pub fn syscall_bn254_fp6_mulmod(p: *mut u32, q: *const u32);
+
 }
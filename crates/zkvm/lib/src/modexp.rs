@@ -0,0 +1,354 @@
+//! Modular exponentiation over arbitrary-width moduli.
+//!
+//! Numbers are little-endian `u32` words. Montgomery multiplication processes the multiplier one
+//! 256-bit digit at a time, computing each digit-by-number product with `syscall_u256x2048_mul`
+//! over 2048-bit segments and the Montgomery quotient digit with `sys_bigint`, so moduli of any
+//! width are supported.
+
+use core::cmp::Ordering;
+
+use crate::{sys_bigint, syscall_u256x2048_mul};
+
+/// The number of words in a digit, the width of the first `u256x2048_mul` operand.
+const DIGIT_WORDS: usize = 8;
+
+/// The number of words in a segment, the width of the second `u256x2048_mul` operand.
+const SEGMENT_WORDS: usize = 64;
+
+/// Montgomery arithmetic modulo an odd modulus `N`, with `R = 2^(256 * digits)`.
+#[derive(Debug, Clone)]
+pub struct Montgomery {
+    /// The modulus, padded to whole segments.
+    modulus: Vec<u32>,
+    /// The number of digits of `R`.
+    digits: usize,
+    /// `-N^(-1) mod 2^256`.
+    n_prime: [u32; DIGIT_WORDS],
+    /// `R mod N`, the Montgomery form of one.
+    one: Vec<u32>,
+    /// `R^2 mod N`.
+    r2: Vec<u32>,
+}
+
+impl Montgomery {
+    /// Creates the Montgomery context for the given little-endian modulus.
+    ///
+    /// Panics if the modulus is even or one.
+    pub fn new(modulus: &[u32]) -> Self {
+        let bits = bit_len(modulus);
+        assert!(bits > 1 && modulus[0] & 1 == 1, "the modulus must be odd and greater than one");
+
+        let digits = bits.div_ceil(DIGIT_WORDS * 32);
+        let words = (digits * DIGIT_WORDS).next_multiple_of(SEGMENT_WORDS);
+        let modulus = resize(modulus, words);
+
+        // Newton's iteration doubles the number of correct low bits of the inverse, starting from
+        // the three bits that are correct for any odd number.
+        let n0: [u32; DIGIT_WORDS] = modulus[..DIGIT_WORDS].try_into().unwrap();
+        let mut inverse = n0;
+        for _ in 0..7 {
+            let mut error = mul_256(&n0, &inverse);
+            negate(&mut error);
+            add_assign(&mut error, &[2]);
+            inverse = mul_256(&inverse, &error);
+        }
+        negate(&mut inverse);
+
+        // `R mod N`, doubling the largest power of two below the modulus up to `R`.
+        let mut one = vec![0u32; words];
+        one[(bits - 1) / 32] = 1 << ((bits - 1) % 32);
+        for _ in bits - 1..digits * DIGIT_WORDS * 32 {
+            double_mod(&mut one, &modulus);
+        }
+
+        let mut context = Self { modulus, digits, n_prime: inverse, r2: Vec::new(), one };
+
+        // `R^2 mod N` is the Montgomery form of `2^(256 * digits)`. Doubling the Montgomery form
+        // of one `digits` times and squaring it eight times raises two to that power.
+        let mut r2 = context.one.clone();
+        for _ in 0..digits {
+            double_mod(&mut r2, &context.modulus);
+        }
+        for _ in 0..8 {
+            r2 = context.mul(&r2, &r2);
+        }
+        context.r2 = r2;
+        context
+    }
+
+    /// Returns the Montgomery product `a * b * R^(-1) mod N`.
+    ///
+    /// `a` must be less than `R` and `b` less than `N`, and the result is less than `N`.
+    pub fn mul(&self, a: &[u32], b: &[u32]) -> Vec<u32> {
+        let words = self.modulus.len();
+        let b = resize(b, words);
+
+        let mut t = vec![0u32; words + DIGIT_WORDS + 1];
+        for i in 0..self.digits {
+            let a_i = digit(a, i);
+            mul_digit_add(&mut t, &a_i, &b);
+
+            // Adding `m * N` clears the low digit, which is then shifted out.
+            let m = mul_256(&t[..DIGIT_WORDS].try_into().unwrap(), &self.n_prime);
+            mul_digit_add(&mut t, &m, &self.modulus);
+            debug_assert!(t[..DIGIT_WORDS].iter().all(|&w| w == 0));
+            t.copy_within(DIGIT_WORDS.., 0);
+            t[words + 1..].fill(0);
+        }
+
+        if t[words] != 0 || compare(&t[..words], &self.modulus) != Ordering::Less {
+            sub_assign(&mut t, &self.modulus);
+        }
+        t.truncate(words);
+        t
+    }
+
+    /// Converts `x`, of any width, to its Montgomery form `x * R mod N`.
+    pub fn to_montgomery(&self, x: &[u32]) -> Vec<u32> {
+        // Horner's method over the base-R digits of `x`, from the most significant one.
+        let chunk_words = self.digits * DIGIT_WORDS;
+        let mut acc = vec![0u32; self.modulus.len()];
+        for chunk in x.chunks(chunk_words).rev() {
+            acc = self.mul(&acc, &self.r2);
+            let chunk = self.mul(chunk, &self.r2);
+            add_mod(&mut acc, &chunk, &self.modulus);
+        }
+        acc
+    }
+
+    /// Converts `x` back from its Montgomery form, returning `x * R^(-1) mod N`.
+    pub fn from_montgomery(&self, x: &[u32]) -> Vec<u32> {
+        self.mul(x, &[1])
+    }
+
+    /// Returns `base^exp mod N`, for a base of any width and a big-endian exponent.
+    pub fn pow(&self, base: &[u32], exp: &[u8]) -> Vec<u32> {
+        let base = self.to_montgomery(base);
+        let mut acc = self.one.clone();
+        for byte in exp {
+            for i in (0..8).rev() {
+                acc = self.mul(&acc, &acc);
+                if (byte >> i) & 1 == 1 {
+                    acc = self.mul(&acc, &base);
+                }
+            }
+        }
+        self.from_montgomery(&acc)
+    }
+}
+
+/// Returns `base^exp mod modulus`, with the semantics of the EIP-198 `MODEXP` precompile.
+///
+/// All values are big-endian bytes, and the result has the length of the modulus. The modulus may
+/// be even, in which case the odd and power of two parts are combined with the CRT.
+pub fn modexp(base: &[u8], exp: &[u8], modulus: &[u8]) -> Vec<u8> {
+    let base = from_be_bytes(base);
+    let modulus_words = from_be_bytes(modulus);
+    if bit_len(&modulus_words) <= 1 {
+        return vec![0; modulus.len()];
+    }
+
+    // Split the modulus into `2^twos * odd`.
+    let twos = trailing_zeros(&modulus_words);
+    let odd = shr(&modulus_words, twos);
+
+    let odd_result =
+        if bit_len(&odd) > 1 { Montgomery::new(&odd).pow(&base, exp) } else { vec![0] };
+    if twos == 0 {
+        return to_be_bytes(&odd_result, modulus.len());
+    }
+
+    // The result modulo `2^twos`, with multiplications truncated to that many bits.
+    let mut pow2_result = truncate(&[1], twos);
+    let base = truncate(&base, twos);
+    for byte in exp {
+        for i in (0..8).rev() {
+            pow2_result = truncate(&mul(&pow2_result, &pow2_result), twos);
+            if (byte >> i) & 1 == 1 {
+                pow2_result = truncate(&mul(&pow2_result, &base), twos);
+            }
+        }
+    }
+
+    // Combine both with `x = odd_result + odd * ((pow2_result - odd_result) / odd mod 2^twos)`.
+    let mut odd_inverse = truncate(&odd, twos);
+    let mut correct_bits = 3;
+    while correct_bits < twos {
+        let mut error = truncate(&mul(&odd, &odd_inverse), twos);
+        negate(&mut error);
+        add_assign(&mut error, &[2]);
+        odd_inverse = truncate(&mul(&odd_inverse, &error), twos);
+        correct_bits *= 2;
+    }
+    let mut difference = resize(&pow2_result, pow2_result.len().max(odd_result.len()));
+    sub_assign(&mut difference, &odd_result);
+    let h = truncate(&mul(&truncate(&difference, twos), &odd_inverse), twos);
+
+    let mut result = mul(&odd, &h);
+    result.push(0);
+    add_assign(&mut result, &odd_result);
+    to_be_bytes(&result, modulus.len())
+}
+
+/// Returns `a * b mod 2^256`.
+fn mul_256(a: &[u32; DIGIT_WORDS], b: &[u32; DIGIT_WORDS]) -> [u32; DIGIT_WORDS] {
+    let mut result = [0u32; DIGIT_WORDS];
+    unsafe {
+        sys_bigint(&mut result, 0, a, b, &[0; DIGIT_WORDS]);
+    }
+    result
+}
+
+/// Adds `a * b` to `acc`, where `b` is made of whole segments and `acc` has at least a digit more
+/// words than `b`.
+fn mul_digit_add(acc: &mut [u32], a: &[u32; DIGIT_WORDS], b: &[u32]) {
+    if a.iter().all(|&w| w == 0) {
+        return;
+    }
+
+    let mut lo = [0u32; SEGMENT_WORDS];
+    let mut hi = [0u32; DIGIT_WORDS];
+    for (j, segment) in b.chunks_exact(SEGMENT_WORDS).enumerate() {
+        let segment: &[u32; SEGMENT_WORDS] = segment.try_into().unwrap();
+        unsafe {
+            syscall_u256x2048_mul(a, segment, &mut lo, &mut hi);
+        }
+        add_assign(&mut acc[j * SEGMENT_WORDS..], &lo);
+        add_assign(&mut acc[(j + 1) * SEGMENT_WORDS..], &hi);
+    }
+}
+
+/// Returns the full product `a * b`.
+fn mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let b = resize(b, b.len().max(1).next_multiple_of(SEGMENT_WORDS));
+    let digits = a.len().div_ceil(DIGIT_WORDS);
+    let mut acc = vec![0u32; digits * DIGIT_WORDS + b.len() + DIGIT_WORDS];
+    for i in 0..digits {
+        mul_digit_add(&mut acc[i * DIGIT_WORDS..], &digit(a, i), &b);
+    }
+    acc
+}
+
+/// Returns the `i`-th 256-bit digit of `x`, padded with zeros.
+fn digit(x: &[u32], i: usize) -> [u32; DIGIT_WORDS] {
+    core::array::from_fn(|j| x.get(i * DIGIT_WORDS + j).copied().unwrap_or(0))
+}
+
+/// Sets `x` to `2 * x mod modulus`, for `x` less than the modulus.
+fn double_mod(x: &mut [u32], modulus: &[u32]) {
+    let mut carry = 0;
+    for word in x.iter_mut() {
+        let next_carry = *word >> 31;
+        *word = (*word << 1) | carry;
+        carry = next_carry;
+    }
+    if carry != 0 || compare(x, modulus) != Ordering::Less {
+        sub_assign(x, modulus);
+    }
+}
+
+/// Sets `x` to `x + y mod modulus`, for `x` and `y` less than the modulus.
+fn add_mod(x: &mut [u32], y: &[u32], modulus: &[u32]) {
+    let carry = add_assign(x, y);
+    if carry != 0 || compare(x, modulus) != Ordering::Less {
+        sub_assign(x, modulus);
+    }
+}
+
+/// Adds `y` to `x` in place, returning the carry out of `x`.
+fn add_assign(x: &mut [u32], y: &[u32]) -> u32 {
+    let mut carry = 0u64;
+    for (i, word) in x.iter_mut().enumerate() {
+        let y_i = y.get(i).copied().unwrap_or(0);
+        if i >= y.len() && carry == 0 {
+            break;
+        }
+        let sum = *word as u64 + y_i as u64 + carry;
+        *word = sum as u32;
+        carry = sum >> 32;
+    }
+    carry as u32
+}
+
+/// Subtracts `y` from `x` in place, wrapping around on underflow.
+fn sub_assign(x: &mut [u32], y: &[u32]) {
+    let mut borrow = 0u64;
+    for (i, word) in x.iter_mut().enumerate() {
+        let y_i = y.get(i).copied().unwrap_or(0);
+        if i >= y.len() && borrow == 0 {
+            break;
+        }
+        let difference = (*word as u64).wrapping_sub(y_i as u64).wrapping_sub(borrow);
+        *word = difference as u32;
+        borrow = (difference >> 63) & 1;
+    }
+}
+
+/// Sets `x` to `-x mod 2^(32 * x.len())`.
+fn negate(x: &mut [u32]) {
+    for word in x.iter_mut() {
+        *word = !*word;
+    }
+    add_assign(x, &[1]);
+}
+
+/// Compares `x` and `y`, which may have different lengths.
+fn compare(x: &[u32], y: &[u32]) -> Ordering {
+    for i in (0..x.len().max(y.len())).rev() {
+        let x_i = x.get(i).copied().unwrap_or(0);
+        let y_i = y.get(i).copied().unwrap_or(0);
+        match x_i.cmp(&y_i) {
+            Ordering::Equal => continue,
+            ordering => return ordering,
+        }
+    }
+    Ordering::Equal
+}
+
+fn bit_len(x: &[u32]) -> usize {
+    x.iter().rposition(|&w| w != 0).map_or(0, |i| i * 32 + 32 - x[i].leading_zeros() as usize)
+}
+
+fn trailing_zeros(x: &[u32]) -> usize {
+    let i = x.iter().position(|&w| w != 0).unwrap();
+    i * 32 + x[i].trailing_zeros() as usize
+}
+
+/// Returns `x >> shift`.
+fn shr(x: &[u32], shift: usize) -> Vec<u32> {
+    let (words, bits) = (shift / 32, shift % 32);
+    (words..x.len())
+        .map(|i| {
+            let high = if bits == 0 { 0 } else { x.get(i + 1).map_or(0, |&w| w << (32 - bits)) };
+            (x[i] >> bits) | high
+        })
+        .collect()
+}
+
+/// Returns `x mod 2^bits`, as exactly as many words as needed for `bits`.
+fn truncate(x: &[u32], bits: usize) -> Vec<u32> {
+    let mut x = resize(x, bits.div_ceil(32));
+    if bits % 32 != 0 {
+        *x.last_mut().unwrap() &= (1 << (bits % 32)) - 1;
+    }
+    x
+}
+
+/// Returns `x` truncated or padded with zeros to `words` words.
+fn resize(x: &[u32], words: usize) -> Vec<u32> {
+    let mut x = x[..x.len().min(words)].to_vec();
+    x.resize(words, 0);
+    x
+}
+
+fn from_be_bytes(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .rchunks(4)
+        .map(|chunk| chunk.iter().fold(0u32, |acc, &byte| (acc << 8) | byte as u32))
+        .collect()
+}
+
+/// Returns the `len` low bytes of `x` in big-endian order.
+fn to_be_bytes(x: &[u32], len: usize) -> Vec<u8> {
+    (0..len).rev().map(|i| x.get(i / 4).map_or(0, |&w| (w >> (8 * (i % 4))) as u8)).collect()
+}
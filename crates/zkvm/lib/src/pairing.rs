@@ -0,0 +1,616 @@
+//! A pairing check over the BN254 and BLS12-381 curves, built on top of the field precompiles.
+//!
+//! The extension tower is `Fp2 = Fp[u] / (u^2 + 1)`, `Fp6 = Fp2[v] / (v^3 - xi)` with
+//! `xi = xi0 + u`, and `Fp12 = Fp6[w] / (w^2 - v)`. Every element is stored as little-endian `u32`
+//! limbs with its coefficients laid out one after another, which is the layout the `fp`, `fp2`
+//! and `fp6` precompiles expect.
+//!
+//! Points on G2 must be given in affine coordinates on the sextic twist. The check fails unless all
+//! points have canonical coordinates, lie on the curve and are in the prime order subgroups. Use
+//! [`crate::bn254::pairing_check`] and [`crate::bls12381::pairing_check`] rather than this module
+//! directly.
+
+use core::marker::PhantomData;
+
+/// An element of the base field.
+pub type Fp<const N: usize> = [u32; N];
+
+/// An element of `Fp2 = Fp[u] / (u^2 + 1)`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Fp2<const N: usize> {
+    pub c0: Fp<N>,
+    pub c1: Fp<N>,
+}
+
+/// An element of `Fp6 = Fp2[v] / (v^3 - xi)`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Fp6<const N: usize> {
+    pub c0: Fp2<N>,
+    pub c1: Fp2<N>,
+    pub c2: Fp2<N>,
+}
+
+/// An element of `Fp12 = Fp6[w] / (w^2 - v)`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Fp12<const N: usize> {
+    pub c0: Fp6<N>,
+    pub c1: Fp6<N>,
+}
+
+/// An affine point on G1.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct G1Affine<const N: usize> {
+    pub x: Fp<N>,
+    pub y: Fp<N>,
+}
+
+/// An affine point on the sextic twist of G2.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct G2Affine<const N: usize> {
+    pub x: Fp2<N>,
+    pub y: Fp2<N>,
+}
+
+/// The type of the sextic twist G2 is defined over.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Twist {
+    /// The twist `y^2 = x^3 + b / xi`.
+    D,
+    /// The twist `y^2 = x^3 + b * xi`.
+    M,
+}
+
+/// The family of a pairing friendly curve, which determines how the hard part of the final
+/// exponentiation is computed from the curve parameter `x`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Family {
+    /// A Barreto-Naehrig curve, with `p = 36x^4 + 36x^3 + 24x^2 + 6x + 1`.
+    Bn,
+    /// A BLS12 curve, with `p = (x - 1)^2 (x^4 - x^2 + 1) / 3 + x`.
+    Bls12,
+}
+
+/// A pairing friendly curve `y^2 = x^3 + b` whose base field has `N` limbs.
+pub trait PairingCurve<const N: usize> {
+    /// The modulus of the base field.
+    const MODULUS: Fp<N>;
+    /// The order `r` of the G1 and G2 subgroups, as little-endian limbs.
+    const ORDER: &'static [u32];
+    /// The coefficient `b` of the curve.
+    const B: u32;
+    /// The constant coefficient `xi0` of the sextic non-residue `xi = xi0 + u`.
+    const NON_RESIDUE_C0: u32;
+    /// The absolute value of the Miller loop parameter, as little-endian limbs.
+    const LOOP_COUNT: &'static [u32];
+    /// Whether the Miller loop parameter is negative.
+    const LOOP_COUNT_IS_NEGATIVE: bool;
+    /// The family of the curve.
+    const FAMILY: Family;
+    /// The absolute value of the curve parameter `x`, as little-endian limbs.
+    const X: &'static [u32];
+    /// Whether the curve parameter `x` is negative.
+    const X_IS_NEGATIVE: bool;
+    /// The coefficients `xi^(k * (p - 1) / 6)` for `k = 1..=5` of the Frobenius map `f -> f^p`.
+    const FROBENIUS_COEFFS: [Fp2<N>; 5];
+    /// The type of the twist.
+    const TWIST: Twist;
+
+    /// Sets `x = x + y` in the base field.
+    fn fp_add(x: &mut Fp<N>, y: &Fp<N>);
+    /// Sets `x = x - y` in the base field.
+    fn fp_sub(x: &mut Fp<N>, y: &Fp<N>);
+    /// Sets `x = x * y` in the base field.
+    fn fp_mul(x: &mut Fp<N>, y: &Fp<N>);
+    /// Sets `x = x + y` in `Fp2`.
+    fn fp2_add(x: &mut Fp2<N>, y: &Fp2<N>);
+    /// Sets `x = x - y` in `Fp2`.
+    fn fp2_sub(x: &mut Fp2<N>, y: &Fp2<N>);
+    /// Sets `x = x * y` in `Fp2`.
+    fn fp2_mul(x: &mut Fp2<N>, y: &Fp2<N>);
+    /// Sets `x = x * y` in `Fp6`.
+    fn fp6_mul(x: &mut Fp6<N>, y: &Fp6<N>);
+}
+
+/// Returns true if the product of the pairings `e(p, q)` over all `pairs` is one.
+///
+/// Returns false if any of the points is not canonical, not on the curve or not in the prime order
+/// subgroup.
+pub fn pairing_check<C: PairingCurve<N>, const N: usize>(
+    pairs: &[(G1Affine<N>, G2Affine<N>)],
+) -> bool {
+    if !pairs.iter().all(|(p, q)| Tower::<C, N>::is_valid_g1(p) && Tower::<C, N>::is_valid_g2(q)) {
+        return false;
+    }
+    let f = Tower::<C, N>::miller_loop(pairs);
+    Tower::<C, N>::final_exponentiation(&f) == Tower::<C, N>::fp12_one()
+}
+
+/// Iterates over the bits of `limbs` from the most significant set bit down.
+fn bits_msb_first(limbs: &[u32]) -> impl Iterator<Item = bool> + '_ {
+    let len = limbs.len() * 32;
+    let leading_zeros = limbs
+        .iter()
+        .rev()
+        .position(|&limb| limb != 0)
+        .map_or(len, |i| i * 32 + limbs[limbs.len() - 1 - i].leading_zeros() as usize);
+    (0..len - leading_zeros).rev().map(move |i| (limbs[i / 32] >> (i % 32)) & 1 == 1)
+}
+
+struct Tower<C, const N: usize>(PhantomData<C>);
+
+impl<C: PairingCurve<N>, const N: usize> Tower<C, N> {
+    fn fp_one() -> Fp<N> {
+        let mut one = [0; N];
+        one[0] = 1;
+        one
+    }
+
+    fn fp_mul(a: &Fp<N>, b: &Fp<N>) -> Fp<N> {
+        let mut c = *a;
+        C::fp_mul(&mut c, b);
+        c
+    }
+
+    fn fp_neg(a: &Fp<N>) -> Fp<N> {
+        let mut c = [0; N];
+        C::fp_sub(&mut c, a);
+        c
+    }
+
+    /// Inverts `a` as `a^(p - 2)`.
+    fn fp_inv(a: &Fp<N>) -> Fp<N> {
+        let mut exp = C::MODULUS;
+        exp[0] -= 2;
+        let mut c = Self::fp_one();
+        for bit in bits_msb_first(&exp) {
+            c = Self::fp_mul(&c, &c);
+            if bit {
+                C::fp_mul(&mut c, a);
+            }
+        }
+        c
+    }
+
+    /// Returns whether `a` is reduced modulo `p`.
+    fn fp_is_canonical(a: &Fp<N>) -> bool {
+        a.iter()
+            .rev()
+            .zip(C::MODULUS.iter().rev())
+            .find(|(a, m)| a != m)
+            .is_some_and(|(a, m)| a < m)
+    }
+
+    fn fp2_zero() -> Fp2<N> {
+        Fp2 { c0: [0; N], c1: [0; N] }
+    }
+
+    fn fp2_one() -> Fp2<N> {
+        Fp2 { c0: Self::fp_one(), c1: [0; N] }
+    }
+
+    /// Embeds the base field element `a` into `Fp2`.
+    fn fp2_from_fp(a: &Fp<N>) -> Fp2<N> {
+        Fp2 { c0: *a, c1: [0; N] }
+    }
+
+    fn fp2_conjugate(a: &Fp2<N>) -> Fp2<N> {
+        Fp2 { c0: a.c0, c1: Self::fp_neg(&a.c1) }
+    }
+
+    fn fp2_add(a: &Fp2<N>, b: &Fp2<N>) -> Fp2<N> {
+        let mut c = *a;
+        C::fp2_add(&mut c, b);
+        c
+    }
+
+    fn fp2_sub(a: &Fp2<N>, b: &Fp2<N>) -> Fp2<N> {
+        let mut c = *a;
+        C::fp2_sub(&mut c, b);
+        c
+    }
+
+    fn fp2_mul(a: &Fp2<N>, b: &Fp2<N>) -> Fp2<N> {
+        let mut c = *a;
+        C::fp2_mul(&mut c, b);
+        c
+    }
+
+    fn fp2_square(a: &Fp2<N>) -> Fp2<N> {
+        Self::fp2_mul(a, a)
+    }
+
+    /// Multiplies `a` by the base field element `b`.
+    fn fp2_mul_by_fp(a: &Fp2<N>, b: &Fp<N>) -> Fp2<N> {
+        Fp2 { c0: Self::fp_mul(&a.c0, b), c1: Self::fp_mul(&a.c1, b) }
+    }
+
+    /// Multiplies `a` by the non-residue `xi = xi0 + u`.
+    fn fp2_mul_by_non_residue(a: &Fp2<N>) -> Fp2<N> {
+        let mut xi0 = [0; N];
+        xi0[0] = C::NON_RESIDUE_C0;
+        let mut c0 = Self::fp_mul(&a.c0, &xi0);
+        C::fp_sub(&mut c0, &a.c1);
+        let mut c1 = Self::fp_mul(&a.c1, &xi0);
+        C::fp_add(&mut c1, &a.c0);
+        Fp2 { c0, c1 }
+    }
+
+    /// Inverts `a` as its conjugate divided by its norm.
+    fn fp2_inv(a: &Fp2<N>) -> Fp2<N> {
+        let mut norm = Self::fp_mul(&a.c0, &a.c0);
+        C::fp_add(&mut norm, &Self::fp_mul(&a.c1, &a.c1));
+        let norm_inv = Self::fp_inv(&norm);
+        Fp2 {
+            c0: Self::fp_mul(&a.c0, &norm_inv),
+            c1: Self::fp_neg(&Self::fp_mul(&a.c1, &norm_inv)),
+        }
+    }
+
+    fn fp6_zero() -> Fp6<N> {
+        Fp6 { c0: Self::fp2_zero(), c1: Self::fp2_zero(), c2: Self::fp2_zero() }
+    }
+
+    fn fp6_add(a: &Fp6<N>, b: &Fp6<N>) -> Fp6<N> {
+        Fp6 {
+            c0: Self::fp2_add(&a.c0, &b.c0),
+            c1: Self::fp2_add(&a.c1, &b.c1),
+            c2: Self::fp2_add(&a.c2, &b.c2),
+        }
+    }
+
+    fn fp6_sub(a: &Fp6<N>, b: &Fp6<N>) -> Fp6<N> {
+        Fp6 {
+            c0: Self::fp2_sub(&a.c0, &b.c0),
+            c1: Self::fp2_sub(&a.c1, &b.c1),
+            c2: Self::fp2_sub(&a.c2, &b.c2),
+        }
+    }
+
+    fn fp6_mul(a: &Fp6<N>, b: &Fp6<N>) -> Fp6<N> {
+        let mut c = *a;
+        C::fp6_mul(&mut c, b);
+        c
+    }
+
+    /// Multiplies `a` by `v`.
+    fn fp6_mul_by_v(a: &Fp6<N>) -> Fp6<N> {
+        Fp6 { c0: Self::fp2_mul_by_non_residue(&a.c2), c1: a.c0, c2: a.c1 }
+    }
+
+    fn fp6_inv(a: &Fp6<N>) -> Fp6<N> {
+        // t0 = a0^2 - xi * a1 * a2
+        let t0 = Self::fp2_sub(
+            &Self::fp2_square(&a.c0),
+            &Self::fp2_mul_by_non_residue(&Self::fp2_mul(&a.c1, &a.c2)),
+        );
+        // t1 = xi * a2^2 - a0 * a1
+        let t1 = Self::fp2_sub(
+            &Self::fp2_mul_by_non_residue(&Self::fp2_square(&a.c2)),
+            &Self::fp2_mul(&a.c0, &a.c1),
+        );
+        // t2 = a1^2 - a0 * a2
+        let t2 = Self::fp2_sub(&Self::fp2_square(&a.c1), &Self::fp2_mul(&a.c0, &a.c2));
+        // d = a0 * t0 + xi * (a2 * t1 + a1 * t2)
+        let d = Self::fp2_add(
+            &Self::fp2_mul(&a.c0, &t0),
+            &Self::fp2_mul_by_non_residue(&Self::fp2_add(
+                &Self::fp2_mul(&a.c2, &t1),
+                &Self::fp2_mul(&a.c1, &t2),
+            )),
+        );
+        let d_inv = Self::fp2_inv(&d);
+        Fp6 {
+            c0: Self::fp2_mul(&t0, &d_inv),
+            c1: Self::fp2_mul(&t1, &d_inv),
+            c2: Self::fp2_mul(&t2, &d_inv),
+        }
+    }
+
+    fn fp12_one() -> Fp12<N> {
+        let mut one = Self::fp6_zero();
+        one.c0.c0 = Self::fp_one();
+        Fp12 { c0: one, c1: Self::fp6_zero() }
+    }
+
+    fn fp12_mul(a: &Fp12<N>, b: &Fp12<N>) -> Fp12<N> {
+        let t0 = Self::fp6_mul(&a.c0, &b.c0);
+        let t1 = Self::fp6_mul(&a.c1, &b.c1);
+        let c1 = Self::fp6_mul(&Self::fp6_add(&a.c0, &a.c1), &Self::fp6_add(&b.c0, &b.c1));
+        Fp12 {
+            c0: Self::fp6_add(&t0, &Self::fp6_mul_by_v(&t1)),
+            c1: Self::fp6_sub(&Self::fp6_sub(&c1, &t0), &t1),
+        }
+    }
+
+    fn fp12_conjugate(a: &Fp12<N>) -> Fp12<N> {
+        Fp12 { c0: a.c0, c1: Self::fp6_sub(&Self::fp6_zero(), &a.c1) }
+    }
+
+    /// Inverts `a = g + h * w` as `(g - h * w) / (g^2 - v * h^2)`.
+    fn fp12_inv(a: &Fp12<N>) -> Fp12<N> {
+        let d = Self::fp6_sub(
+            &Self::fp6_mul(&a.c0, &a.c0),
+            &Self::fp6_mul_by_v(&Self::fp6_mul(&a.c1, &a.c1)),
+        );
+        let d_inv = Self::fp6_inv(&d);
+        Fp12 {
+            c0: Self::fp6_mul(&a.c0, &d_inv),
+            c1: Self::fp6_sub(&Self::fp6_zero(), &Self::fp6_mul(&a.c1, &d_inv)),
+        }
+    }
+
+    /// Raises `a` to the power `p`.
+    ///
+    /// In the basis `1, w, ..., w^5` of `Fp12` over `Fp2`, the map conjugates every coefficient and
+    /// multiplies the one of `w^k` by `w^(k * (p - 1)) = xi^(k * (p - 1) / 6)`.
+    fn fp12_frobenius(a: &Fp12<N>) -> Fp12<N> {
+        let [g1, g2, g3, g4, g5] = &C::FROBENIUS_COEFFS;
+        let frobenius = |c: &Fp2<N>, g: &Fp2<N>| Self::fp2_mul(&Self::fp2_conjugate(c), g);
+        Fp12 {
+            c0: Fp6 {
+                c0: Self::fp2_conjugate(&a.c0.c0),
+                c1: frobenius(&a.c0.c1, g2),
+                c2: frobenius(&a.c0.c2, g4),
+            },
+            c1: Fp6 {
+                c0: frobenius(&a.c1.c0, g1),
+                c1: frobenius(&a.c1.c1, g3),
+                c2: frobenius(&a.c1.c2, g5),
+            },
+        }
+    }
+
+    /// Squares `a` in the cyclotomic subgroup, with the formulas of Granger and Scott.
+    ///
+    /// `a` is seen as three elements `z0 + z1 * s`, `z2 + z3 * s` and `z4 + z5 * s` of
+    /// `Fp4 = Fp2[s] / (s^2 - xi)`, each of which is squared with three multiplications in `Fp2`.
+    fn fp12_cyclotomic_square(a: &Fp12<N>) -> Fp12<N> {
+        let fp4_square = |x: &Fp2<N>, y: &Fp2<N>| {
+            let xy = Self::fp2_mul(x, y);
+            let t0 = Self::fp2_sub(
+                &Self::fp2_sub(
+                    &Self::fp2_mul(
+                        &Self::fp2_add(x, y),
+                        &Self::fp2_add(&Self::fp2_mul_by_non_residue(y), x),
+                    ),
+                    &xy,
+                ),
+                &Self::fp2_mul_by_non_residue(&xy),
+            );
+            (t0, Self::fp2_add(&xy, &xy))
+        };
+        // Returns 3 * t - 2 * z.
+        let sub_twice = |t: &Fp2<N>, z: &Fp2<N>| {
+            let d = Self::fp2_sub(t, z);
+            Self::fp2_add(&Self::fp2_add(&d, &d), t)
+        };
+        // Returns 3 * t + 2 * z.
+        let add_twice = |t: &Fp2<N>, z: &Fp2<N>| {
+            let d = Self::fp2_add(t, z);
+            Self::fp2_add(&Self::fp2_add(&d, &d), t)
+        };
+
+        let (t0, t1) = fp4_square(&a.c0.c0, &a.c1.c1);
+        let (t2, t3) = fp4_square(&a.c1.c0, &a.c0.c2);
+        let (t4, t5) = fp4_square(&a.c0.c1, &a.c1.c2);
+        Fp12 {
+            c0: Fp6 {
+                c0: sub_twice(&t0, &a.c0.c0),
+                c1: sub_twice(&t2, &a.c0.c1),
+                c2: sub_twice(&t4, &a.c0.c2),
+            },
+            c1: Fp6 {
+                c0: add_twice(&Self::fp2_mul_by_non_residue(&t5), &a.c1.c0),
+                c1: add_twice(&t1, &a.c1.c1),
+                c2: add_twice(&t3, &a.c1.c2),
+            },
+        }
+    }
+
+    /// Raises `a` in the cyclotomic subgroup to the power `exp`.
+    fn fp12_cyclotomic_pow(a: &Fp12<N>, exp: &[u32]) -> Fp12<N> {
+        let mut c = Self::fp12_one();
+        for bit in bits_msb_first(exp) {
+            c = Self::fp12_cyclotomic_square(&c);
+            if bit {
+                c = Self::fp12_mul(&c, a);
+            }
+        }
+        c
+    }
+
+    /// Raises `a` in the cyclotomic subgroup to the power of the curve parameter `x`, where the
+    /// inverse is the conjugate.
+    fn fp12_pow_by_x(a: &Fp12<N>) -> Fp12<N> {
+        let c = Self::fp12_cyclotomic_pow(a, C::X);
+        if C::X_IS_NEGATIVE {
+            Self::fp12_conjugate(&c)
+        } else {
+            c
+        }
+    }
+
+    /// Evaluates at `p` the line with slope `lambda` through the point `t` on the twist.
+    fn line(lambda: &Fp2<N>, t: &G2Affine<N>, p: &G1Affine<N>) -> Fp12<N> {
+        let zero = Self::fp2_zero();
+        let c = Self::fp2_sub(&Self::fp2_mul(lambda, &t.x), &t.y);
+        let d = Self::fp2_sub(&zero, &Self::fp2_mul_by_fp(lambda, &p.x));
+        let y = Fp2 { c0: p.y, c1: [0; N] };
+        match C::TWIST {
+            Twist::D => {
+                Fp12 { c0: Fp6 { c0: y, c1: zero, c2: zero }, c1: Fp6 { c0: d, c1: c, c2: zero } }
+            }
+            Twist::M => {
+                Fp12 { c0: Fp6 { c0: c, c1: d, c2: zero }, c1: Fp6 { c0: zero, c1: y, c2: zero } }
+            }
+        }
+    }
+
+    /// Doubles `t` in place and returns the tangent line at `t` evaluated at `p`.
+    fn double_step(t: &mut G2Affine<N>, p: &G1Affine<N>) -> Fp12<N> {
+        // lambda = 3 * x^2 / (2 * y)
+        let x_squared = Self::fp2_square(&t.x);
+        let numerator = Self::fp2_add(&Self::fp2_add(&x_squared, &x_squared), &x_squared);
+        let lambda = Self::fp2_mul(&numerator, &Self::fp2_inv(&Self::fp2_add(&t.y, &t.y)));
+        let line = Self::line(&lambda, t, p);
+
+        let x = Self::fp2_sub(&Self::fp2_square(&lambda), &Self::fp2_add(&t.x, &t.x));
+        let y = Self::fp2_sub(&Self::fp2_mul(&lambda, &Self::fp2_sub(&t.x, &x)), &t.y);
+        *t = G2Affine { x, y };
+        line
+    }
+
+    /// Sets `t = t + q` and returns the line through `t` and `q` evaluated at `p`.
+    fn add_step(t: &mut G2Affine<N>, q: &G2Affine<N>, p: &G1Affine<N>) -> Fp12<N> {
+        // lambda = (y_q - y_t) / (x_q - x_t)
+        let lambda =
+            Self::fp2_mul(&Self::fp2_sub(&q.y, &t.y), &Self::fp2_inv(&Self::fp2_sub(&q.x, &t.x)));
+        let line = Self::line(&lambda, t, p);
+
+        let x = Self::fp2_sub(&Self::fp2_sub(&Self::fp2_square(&lambda), &t.x), &q.x);
+        let y = Self::fp2_sub(&Self::fp2_mul(&lambda, &Self::fp2_sub(&t.x, &x)), &t.y);
+        *t = G2Affine { x, y };
+        line
+    }
+
+    /// Computes the product of the Miller loops of all `pairs`, sharing the squarings.
+    fn miller_loop(pairs: &[(G1Affine<N>, G2Affine<N>)]) -> Fp12<N> {
+        let mut f = Self::fp12_one();
+        let mut ts = pairs.iter().map(|(_, q)| *q).collect::<Vec<_>>();
+        for bit in bits_msb_first(C::LOOP_COUNT).skip(1) {
+            f = Self::fp12_mul(&f, &f);
+            for ((p, q), t) in pairs.iter().zip(ts.iter_mut()) {
+                f = Self::fp12_mul(&f, &Self::double_step(t, p));
+                if bit {
+                    f = Self::fp12_mul(&f, &Self::add_step(t, q, p));
+                }
+            }
+        }
+        if C::LOOP_COUNT_IS_NEGATIVE {
+            f = Self::fp12_conjugate(&f);
+        }
+        f
+    }
+
+    /// Raises `f` to the power `(p^12 - 1) / r`, or to a multiple of it coprime to `r`, which
+    /// leaves the result of a pairing check unchanged.
+    fn final_exponentiation(f: &Fp12<N>) -> Fp12<N> {
+        // f^(p^6 - 1)
+        let f = Self::fp12_mul(&Self::fp12_conjugate(f), &Self::fp12_inv(f));
+        // f^(p^2 + 1)
+        let f = Self::fp12_mul(&Self::fp12_frobenius(&Self::fp12_frobenius(&f)), &f);
+
+        // f is now in the cyclotomic subgroup, where the inverse is the conjugate.
+        let mul = Self::fp12_mul;
+        let conjugate = Self::fp12_conjugate;
+        let frobenius = Self::fp12_frobenius;
+        let pow = Self::fp12_cyclotomic_pow;
+        let pow_by_x = Self::fp12_pow_by_x;
+        match C::FAMILY {
+            Family::Bn => {
+                // (p^4 - p^2 + 1) / r = l0 + l1 * p + l2 * p^2 + p^3, with
+                // l0 = -36x^3 - 30x^2 - 18x - 2, l1 = -36x^3 - 18x^2 - 12x + 1, l2 = 6x^2 + 1.
+                let fx = pow_by_x(&f);
+                let fx2 = pow_by_x(&fx);
+                let fx3 = pow_by_x(&fx2);
+                let fx3_36 = pow(&fx3, &[36]);
+                let l2 = mul(&pow(&fx2, &[6]), &f);
+                let l1 =
+                    mul(&conjugate(&mul(&mul(&fx3_36, &pow(&fx2, &[18])), &pow(&fx, &[12]))), &f);
+                let l0 = conjugate(&mul(
+                    &mul(&mul(&fx3_36, &pow(&fx2, &[30])), &pow(&fx, &[18])),
+                    &Self::fp12_cyclotomic_square(&f),
+                ));
+                let f_p3 = frobenius(&frobenius(&frobenius(&f)));
+                mul(&mul(&f_p3, &frobenius(&frobenius(&l2))), &mul(&frobenius(&l1), &l0))
+            }
+            Family::Bls12 => {
+                // 3 * (p^4 - p^2 + 1) / r = (x - 1)^2 (x + p) (x^2 + p^2 - 1) + 3.
+                let a = mul(&pow_by_x(&f), &conjugate(&f));
+                let a = mul(&pow_by_x(&a), &conjugate(&a));
+                let b = mul(&pow_by_x(&a), &frobenius(&a));
+                let c =
+                    mul(&mul(&pow_by_x(&pow_by_x(&b)), &frobenius(&frobenius(&b))), &conjugate(&b));
+                mul(&c, &mul(&Self::fp12_cyclotomic_square(&f), &f))
+            }
+        }
+    }
+
+    /// Returns whether `p` is a canonical point of G1.
+    fn is_valid_g1(p: &G1Affine<N>) -> bool {
+        let mut b = [0; N];
+        b[0] = C::B;
+        Self::fp_is_canonical(&p.x)
+            && Self::fp_is_canonical(&p.y)
+            && Self::is_in_subgroup(
+                &Self::fp2_from_fp(&p.x),
+                &Self::fp2_from_fp(&p.y),
+                &Self::fp2_from_fp(&b),
+            )
+    }
+
+    /// Returns whether `q` is a canonical point of G2 on the twist.
+    fn is_valid_g2(q: &G2Affine<N>) -> bool {
+        let mut b = [0; N];
+        b[0] = C::B;
+        let b = Self::fp2_from_fp(&b);
+        let b = match C::TWIST {
+            Twist::D => {
+                let mut xi = Self::fp2_one();
+                xi.c0[0] = C::NON_RESIDUE_C0;
+                xi.c1[0] = 1;
+                Self::fp2_mul(&b, &Self::fp2_inv(&xi))
+            }
+            Twist::M => Self::fp2_mul_by_non_residue(&b),
+        };
+        [q.x.c0, q.x.c1, q.y.c0, q.y.c1].iter().all(Self::fp_is_canonical)
+            && Self::is_in_subgroup(&q.x, &q.y, &b)
+    }
+
+    /// Returns whether `(x, y)` lies on the curve `y^2 = x^3 + b` over `Fp2` and has order `r`.
+    ///
+    /// The order is checked by computing `r * (x, y)` in projective coordinates with the complete
+    /// addition formulas of Renes, Costello and Batina, which have no exceptional cases.
+    fn is_in_subgroup(x: &Fp2<N>, y: &Fp2<N>, b: &Fp2<N>) -> bool {
+        let x_cubed = Self::fp2_mul(&Self::fp2_square(x), x);
+        if Self::fp2_square(y) != Self::fp2_add(&x_cubed, b) {
+            return false;
+        }
+
+        let b3 = Self::fp2_add(&Self::fp2_add(b, b), b);
+        let point = [*x, *y, Self::fp2_one()];
+        let mut acc = [Self::fp2_zero(), Self::fp2_one(), Self::fp2_zero()];
+        for bit in bits_msb_first(C::ORDER) {
+            acc = Self::projective_add(&acc, &acc, &b3);
+            if bit {
+                acc = Self::projective_add(&acc, &point, &b3);
+            }
+        }
+        acc[2] == Self::fp2_zero()
+    }
+
+    /// Adds the projective points `p` and `q` on the curve `y^2 = x^3 + b`, given `b3 = 3 * b`.
+    fn projective_add(p: &[Fp2<N>; 3], q: &[Fp2<N>; 3], b3: &Fp2<N>) -> [Fp2<N>; 3] {
+        let (add, sub, mul) = (Self::fp2_add, Self::fp2_sub, Self::fp2_mul);
+        let [x1, y1, z1] = p;
+        let [x2, y2, z2] = q;
+        let t0 = mul(x1, x2);
+        let t1 = mul(y1, y2);
+        let t2 = mul(z1, z2);
+        let t3 = sub(&mul(&add(x1, y1), &add(x2, y2)), &add(&t0, &t1));
+        let t4 = sub(&mul(&add(y1, z1), &add(y2, z2)), &add(&t1, &t2));
+        let y3 = sub(&mul(&add(x1, z1), &add(x2, z2)), &add(&t0, &t2));
+        let t0 = add(&add(&t0, &t0), &t0);
+        let t2 = mul(b3, &t2);
+        let z3 = add(&t1, &t2);
+        let t1 = sub(&t1, &t2);
+        let y3 = mul(b3, &y3);
+        let x3 = sub(&mul(&t3, &t1), &mul(&t4, &y3));
+        let y3 = add(&mul(&t1, &z3), &mul(&y3, &t0));
+        let z3 = add(&mul(&z3, &t4), &mul(&t0, &t3));
+        [x3, y3, z3]
+    }
+}
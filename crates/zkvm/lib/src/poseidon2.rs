@@ -0,0 +1,30 @@
+use crate::syscall_poseidon2_permute;
+
+/// The number of KoalaBear elements in the Poseidon2 state.
+pub const WIDTH: usize = 16;
+
+/// The number of KoalaBear elements in a Poseidon2 digest.
+pub const DIGEST_SIZE: usize = 8;
+
+/// Applies the Poseidon2 permutation to `state` in place.
+///
+/// Every element of the state must be a canonical KoalaBear field element, i.e. less than
+/// `0x7f000001`, otherwise the execution panics.
+pub fn permute(state: &mut [u32; WIDTH]) {
+    unsafe {
+        syscall_poseidon2_permute(state);
+    }
+}
+
+/// Compresses two digests into one, the same way the recursion stack compresses the nodes of its
+/// Merkle trees.
+pub fn compress(left: &[u32; DIGEST_SIZE], right: &[u32; DIGEST_SIZE]) -> [u32; DIGEST_SIZE] {
+    let mut state = [0u32; WIDTH];
+    state[..DIGEST_SIZE].copy_from_slice(left);
+    state[DIGEST_SIZE..].copy_from_slice(right);
+    permute(&mut state);
+
+    let mut digest = [0u32; DIGEST_SIZE];
+    digest.copy_from_slice(&state[..DIGEST_SIZE]);
+    digest
+}
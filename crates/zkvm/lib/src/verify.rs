@@ -9,3 +9,22 @@ pub fn verify_zkm_proof(vk_digest: &[u32; 8], pv_digest: &[u8; 32]) {
         syscall_verify_zkm_proof(vk_digest, pv_digest);
     }
 }
+
+pub use zkm_primitives::types::VkeyMembershipProof;
+
+/// Verifies the next proof in the proof input stream given a verification key digest and public
+/// values digest, accepting any verification key of the vkey registry with root `registry_root`.
+/// If the vkey is not in the registry or the proof is invalid, the function will panic.
+///
+/// The registry root, rather than the vkey, can then be committed to, so that the programs of the
+/// registry can be upgraded without changing this program.
+pub fn verify_zkm_proof_in_registry(
+    registry_root: &[u32; 8],
+    vk_digest: &[u32; 8],
+    membership_proof: &VkeyMembershipProof,
+    pv_digest: &[u8; 32],
+) {
+    let root = membership_proof.root(vk_digest, crate::poseidon2::compress);
+    assert_eq!(&root, registry_root, "vkey is not in the registry");
+    verify_zkm_proof(vk_digest, pv_digest);
+}
@@ -27,7 +27,7 @@ fn main() {
     println!("public values: 0x{}", hex::encode(public_values));
 
     // Get the proof as bytes.
-    let solidity_proof = proof.bytes();
+    let solidity_proof = proof.bytes().unwrap();
     println!("proof: 0x{}", hex::encode(solidity_proof));
     println!("vk: {:?}", vk.bytes32());
 
@@ -29,7 +29,7 @@ fn generate_fibonacci_proof() -> (Vec<u8>, Vec<u8>, String) {
     let (pk, vk) = client.setup(FIBONACCI_ELF);
     println!("vk: {:?}", vk.bytes32());
     let proof = client.prove(&pk, stdin).plonk().run().unwrap();
-    (proof.bytes(), proof.public_values.to_vec(), vk.bytes32())
+    (proof.bytes().unwrap(), proof.public_values.to_vec(), vk.bytes32())
 }
 
 fn main() {